@@ -1,9 +1,12 @@
 use crate::api::{client::grpc_web_call, APICaller};
-use crate::models::authorization::{Authorization, AuthorizationFormData};
+use crate::models::authorization::{ApiKey, Authorization, AuthorizationFormData, IssuedToken};
 use proto_web::mcp::orchestrator::v1::{
-    AuthorizationResponse, CreateAuthorizationRequest, DeleteAuthorizationRequest,
+    ApiKeyResponse, AuthorizationResponse, CreateAuthorizationRequest, DeleteAuthorizationRequest,
     DeleteAuthorizationResponse, GenerateTokenRequest, GenerateTokenResponse,
-    GetAuthorizationRequest, ListAuthorizationsRequest, ListAuthorizationsResponse,
+    GetAuthorizationRequest, IssueApiKeyRequest, IssueApiKeyResponse, IssuedTokenResponse,
+    ListApiKeysRequest, ListApiKeysResponse, ListAuthorizationsRequest, ListAuthorizationsResponse,
+    ListTokensRequest, ListTokensResponse, RevokeApiKeyRequest, RevokeApiKeyResponse,
+    RevokeTokenRequest, RevokeTokenResponse,
 };
 
 impl APICaller {
@@ -84,6 +87,7 @@ impl APICaller {
         let request = DeleteAuthorizationRequest {
             namespace: Some(namespace),
             name,
+            force: false,
         };
 
         let response: DeleteAuthorizationResponse = grpc_web_call(
@@ -105,6 +109,7 @@ impl APICaller {
         namespace: String,
         name: String,
         expire_days: Option<i64>,
+        bound: bool,
     ) -> Result<(String, Option<String>), String> {
         let expire_duration = expire_days.map(|days| prost_wkt_types::Duration {
             seconds: days * 24 * 60 * 60,
@@ -115,6 +120,7 @@ impl APICaller {
             namespace: Some(namespace),
             name,
             expire_duration,
+            bound: Some(bound),
         };
 
         let response: GenerateTokenResponse = grpc_web_call(
@@ -140,6 +146,134 @@ impl APICaller {
 
         Ok((response.token, expire_at))
     }
+
+    pub async fn list_tokens(
+        &self,
+        namespace: String,
+        name: String,
+    ) -> Result<Vec<IssuedToken>, String> {
+        let request = ListTokensRequest {
+            namespace: Some(namespace),
+            name,
+        };
+
+        let response: ListTokensResponse = grpc_web_call(
+            "/mcp.orchestrator.v1.McpOrchestratorService/ListTokens",
+            request,
+            self.access_token.as_deref(),
+        )
+        .await?;
+
+        Ok(response
+            .data
+            .into_iter()
+            .map(from_proto_issued_token)
+            .collect())
+    }
+
+    pub async fn revoke_token(
+        &self,
+        namespace: String,
+        name: String,
+        id: String,
+    ) -> Result<String, String> {
+        let request = RevokeTokenRequest {
+            namespace: Some(namespace),
+            name,
+            id,
+        };
+
+        let response: RevokeTokenResponse = grpc_web_call(
+            "/mcp.orchestrator.v1.McpOrchestratorService/RevokeToken",
+            request,
+            self.access_token.as_deref(),
+        )
+        .await?;
+
+        if response.success {
+            Ok(response.message)
+        } else {
+            Err(response.message)
+        }
+    }
+
+    pub async fn list_api_keys(
+        &self,
+        namespace: String,
+        name: String,
+    ) -> Result<Vec<ApiKey>, String> {
+        let request = ListApiKeysRequest {
+            namespace: Some(namespace),
+            name,
+        };
+
+        let response: ListApiKeysResponse = grpc_web_call(
+            "/mcp.orchestrator.v1.McpOrchestratorService/ListApiKeys",
+            request,
+            self.access_token.as_deref(),
+        )
+        .await?;
+
+        Ok(response.data.into_iter().map(from_proto_api_key).collect())
+    }
+
+    /// Returns the issued key with its secret, which is only available in this response.
+    pub async fn issue_api_key(
+        &self,
+        namespace: String,
+        name: String,
+        key_name: String,
+        expire_days: Option<i64>,
+    ) -> Result<(ApiKey, String), String> {
+        let request = IssueApiKeyRequest {
+            namespace: Some(namespace),
+            name,
+            key_name,
+            expire_duration: expire_days.map(|days| prost_wkt_types::Duration {
+                seconds: days * 24 * 60 * 60,
+                nanos: 0,
+            }),
+        };
+
+        let response: IssueApiKeyResponse = grpc_web_call(
+            "/mcp.orchestrator.v1.McpOrchestratorService/IssueApiKey",
+            request,
+            self.access_token.as_deref(),
+        )
+        .await?;
+
+        let key = response
+            .key
+            .map(from_proto_api_key)
+            .ok_or_else(|| "Issued api key is missing".to_string())?;
+        Ok((key, response.api_key))
+    }
+
+    pub async fn revoke_api_key(
+        &self,
+        namespace: String,
+        name: String,
+        key_name: String,
+    ) -> Result<String, String> {
+        let request = RevokeApiKeyRequest {
+            namespace: Some(namespace),
+            name,
+            key_name,
+        };
+
+        let response: RevokeApiKeyResponse = grpc_web_call(
+            "/mcp.orchestrator.v1.McpOrchestratorService/RevokeApiKey",
+            request,
+            self.access_token.as_deref(),
+        )
+        .await?;
+
+        if response.success {
+            Ok(response.message)
+        } else {
+            Err(response.message)
+        }
+    }
 }
 
 fn format_timestamp(t: prost_wkt_types::Timestamp) -> String {
@@ -167,3 +301,23 @@ fn from_proto_authorization(proto: AuthorizationResponse) -> Authorization {
         deleted_at: proto.deleted_at.map(format_timestamp),
     }
 }
+
+fn from_proto_api_key(proto: ApiKeyResponse) -> ApiKey {
+    ApiKey {
+        name: proto.name,
+        created_at: proto.created_at.map(format_timestamp).unwrap_or_default(),
+        expire_at: proto.expire_at.map(format_timestamp),
+        last_used_at: proto.last_used_at.map(format_timestamp),
+    }
+}
+
+fn from_proto_issued_token(proto: IssuedTokenResponse) -> IssuedToken {
+    IssuedToken {
+        id: proto.id,
+        requester: proto.requester,
+        audience: proto.audience,
+        bound: proto.bound,
+        created_at: proto.created_at.map(format_timestamp).unwrap_or_default(),
+        expire_at: proto.expire_at.map(format_timestamp),
+    }
+}
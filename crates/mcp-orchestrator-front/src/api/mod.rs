@@ -5,6 +5,7 @@ pub mod namespaces;
 pub mod resource_limits;
 pub mod secrets;
 pub mod templates;
+pub mod usage;
 
 #[derive(Clone, PartialEq)]
 pub struct APICaller {
@@ -2,7 +2,7 @@ use crate::api::{client::grpc_web_call, APICaller};
 use crate::models::namespace::Namespace;
 use proto_web::{
     CreateNamespaceRequest, GetNamespaceRequest, ListNamespacesRequest, ListNamespacesResponse,
-    NamespaceResponse,
+    NamespaceResponse, UpdateNamespaceRequest,
 };
 
 impl APICaller {
@@ -51,4 +51,18 @@ impl APICaller {
 
         Ok(Namespace::from(response))
     }
+
+    pub async fn update_namespace(
+        &self,
+        request: UpdateNamespaceRequest,
+    ) -> Result<Namespace, String> {
+        let response: NamespaceResponse = grpc_web_call(
+            "/mcp.orchestrator.v1.McpOrchestratorService/UpdateNamespace",
+            request,
+            self.access_token.as_deref(),
+        )
+        .await?;
+
+        Ok(Namespace::from(response))
+    }
 }
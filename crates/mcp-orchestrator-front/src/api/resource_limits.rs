@@ -1,8 +1,9 @@
 use crate::api::{client::grpc_web_call, APICaller};
 use crate::models::resource_limit::ResourceLimit;
 use proto_web::{
-    CreateResourceLimitRequest, DeleteResourceLimitRequest, DeleteResourceLimitResponse,
-    GetResourceLimitRequest, ListResourceLimitsRequest, ListResourceLimitsResponse,
+    CheckResourceLimitRequest, CheckResourceLimitResponse, CreateResourceLimitRequest,
+    DeleteResourceLimitRequest, DeleteResourceLimitResponse, GetResourceLimitRequest,
+    ListResourceLimitsRequest, ListResourceLimitsResponse, ResourceLimit as ProtoResourceLimit,
     ResourceLimitResponse,
 };
 
@@ -53,6 +54,25 @@ impl APICaller {
         Ok(ResourceLimit::from(response))
     }
 
+    /// Evaluates the stored resource limit, or `limits` when set, against the nodes of the cluster.
+    pub async fn check_resource_limit(
+        &self,
+        name: &str,
+        limits: Option<ProtoResourceLimit>,
+    ) -> Result<CheckResourceLimitResponse, String> {
+        let request = CheckResourceLimitRequest {
+            name: name.to_string(),
+            limits,
+        };
+
+        grpc_web_call(
+            "/mcp.orchestrator.v1.McpOrchestratorService/CheckResourceLimit",
+            request,
+            self.access_token.as_deref(),
+        )
+        .await
+    }
+
     pub async fn delete_resource_limit(
         &self,
         name: &str,
@@ -1,9 +1,12 @@
 use crate::api::{client::grpc_web_call, APICaller};
-use crate::models::secret::Secret;
+use crate::models::secret::{Secret, UserSecret};
 use proto_web::{
-    CreateSecretRequest, DeleteSecretRequest, DeleteSecretResponse, GetSecretRequest,
-    ListSecretsRequest, ListSecretsResponse, SecretResponse, UpdateSecretRequest,
+    CreateSecretRequest, DeleteSecretRequest, DeleteSecretResponse, DeleteUserSecretRequest,
+    GetSecretRequest, ListSecretsRequest, ListSecretsResponse, ListUserSecretsRequest,
+    ListUserSecretsResponse, PutUserSecretRequest, SecretResponse, UpdateSecretRequest,
+    UserSecretResponse,
 };
+use std::collections::HashMap;
 
 impl APICaller {
     pub async fn list_secrets(&self, namespace: &str) -> Result<Vec<Secret>, String> {
@@ -79,4 +82,59 @@ impl APICaller {
         )
         .await
     }
+
+    pub async fn list_user_secrets(&self, namespace: &str) -> Result<Vec<UserSecret>, String> {
+        let request = ListUserSecretsRequest {
+            namespace: Some(namespace.to_string()),
+        };
+
+        let response: ListUserSecretsResponse = grpc_web_call(
+            "/mcp.orchestrator.v1.McpOrchestratorService/ListUserSecrets",
+            request,
+            self.access_token.as_deref(),
+        )
+        .await?;
+
+        Ok(response.data.into_iter().map(UserSecret::from).collect())
+    }
+
+    pub async fn put_user_secret(
+        &self,
+        namespace: &str,
+        name: &str,
+        data: HashMap<String, String>,
+    ) -> Result<UserSecret, String> {
+        let request = PutUserSecretRequest {
+            namespace: Some(namespace.to_string()),
+            name: name.to_string(),
+            data,
+        };
+
+        let response: UserSecretResponse = grpc_web_call(
+            "/mcp.orchestrator.v1.McpOrchestratorService/PutUserSecret",
+            request,
+            self.access_token.as_deref(),
+        )
+        .await?;
+
+        Ok(UserSecret::from(response))
+    }
+
+    pub async fn delete_user_secret(
+        &self,
+        namespace: &str,
+        name: &str,
+    ) -> Result<DeleteSecretResponse, String> {
+        let request = DeleteUserSecretRequest {
+            namespace: Some(namespace.to_string()),
+            name: name.to_string(),
+        };
+
+        grpc_web_call(
+            "/mcp.orchestrator.v1.McpOrchestratorService/DeleteUserSecret",
+            request,
+            self.access_token.as_deref(),
+        )
+        .await
+    }
 }
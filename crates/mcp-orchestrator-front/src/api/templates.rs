@@ -2,7 +2,9 @@ use crate::api::{client::grpc_web_call, APICaller};
 use crate::models::template::Template;
 use proto_web::{
     CreateMcpTemplateRequest, DeleteMcpTemplateRequest, DeleteMcpTemplateResponse,
-    GetMcpTemplateRequest, ListMcpTemplatesRequest, ListMcpTemplatesResponse, McpTemplateResponse,
+    GetMcpTemplateRequest, ImportMcpTemplateRequest, ImportMcpTemplateResponse,
+    ListMcpTemplatesRequest, ListMcpTemplatesResponse, McpTemplateImportFormat,
+    McpTemplateResponse, TemplateValidation, TemplateValidationReport,
 };
 
 impl APICaller {
@@ -54,6 +56,53 @@ impl APICaller {
         Ok(Template::from(response))
     }
 
+    /// Validates the template with a dry-run create, the template is not persisted.
+    pub async fn validate_template(
+        &self,
+        request: CreateMcpTemplateRequest,
+        smoke_test: bool,
+    ) -> Result<TemplateValidationReport, String> {
+        let request = CreateMcpTemplateRequest {
+            validation: Some(TemplateValidation {
+                dry_run: true,
+                smoke_test,
+                ..Default::default()
+            }),
+            ..request
+        };
+        let response: McpTemplateResponse = grpc_web_call(
+            "/mcp.orchestrator.v1.McpOrchestratorService/CreateMcpTemplate",
+            request,
+            self.access_token.as_deref(),
+        )
+        .await?;
+
+        response
+            .validation
+            .ok_or_else(|| "Validation report is missing".to_string())
+    }
+
+    pub async fn import_template(
+        &self,
+        namespace: &str,
+        content: String,
+        server_name: Option<String>,
+    ) -> Result<ImportMcpTemplateResponse, String> {
+        let request = ImportMcpTemplateRequest {
+            namespace: Some(namespace.to_string()),
+            content,
+            format: McpTemplateImportFormat::Auto.into(),
+            server_name,
+        };
+
+        grpc_web_call(
+            "/mcp.orchestrator.v1.McpOrchestratorService/ImportMcpTemplate",
+            request,
+            self.access_token.as_deref(),
+        )
+        .await
+    }
+
     pub async fn delete_template(&self, namespace: &str, name: &str) -> Result<(), String> {
         let request = DeleteMcpTemplateRequest {
             namespace: Some(namespace.to_string()),
@@ -0,0 +1,16 @@
+use crate::api::{client::grpc_web_call, APICaller};
+use proto_web::{GetUsageReportRequest, GetUsageReportResponse};
+
+impl APICaller {
+    pub async fn get_usage_report(
+        &self,
+        request: GetUsageReportRequest,
+    ) -> Result<GetUsageReportResponse, String> {
+        grpc_web_call(
+            "/mcp.orchestrator.v1.McpOrchestratorService/GetUsageReport",
+            request,
+            self.access_token.as_deref(),
+        )
+        .await
+    }
+}
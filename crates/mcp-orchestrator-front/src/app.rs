@@ -5,7 +5,7 @@ use crate::pages::{
     AuthorizationCreate, AuthorizationDetail, AuthorizationList, Home, NamespaceCreate,
     NamespaceDetail, NamespaceList, OAuthCallback, ResourceLimitCreate, ResourceLimitDetail,
     ResourceLimitList, SecretCreate, SecretDetail, SecretList, SecretUpdate, TemplateDetail,
-    TemplateForm, TemplateList,
+    TemplateForm, TemplateList, UserSecretList,
 };
 use crate::routes::Route;
 use yew::prelude::*;
@@ -114,6 +114,7 @@ fn switch(routes: Route) -> Html {
         Route::SecretEdit { namespace, name } => {
             html! { <SecretUpdate namespace={namespace} name={name} /> }
         }
+        Route::UserSecretList => html! { <UserSecretList /> },
         Route::ResourceLimitList => html! { <ResourceLimitList /> },
         Route::ResourceLimitCreate => html! { <ResourceLimitCreate /> },
         Route::ResourceLimitDetail { name } => html! { <ResourceLimitDetail name={name} /> },
@@ -122,6 +123,7 @@ fn switch(routes: Route) -> Html {
         Route::AuthorizationDetail { namespace, name } => {
             html! { <AuthorizationDetail namespace={namespace} name={name} /> }
         }
+        Route::UsageReport => html! { <UsageReport /> },
         Route::OAuthCallback => html! { <OAuthCallback /> },
         Route::NotFound => html! { <div class="container"><h2>{"404 - Page Not Found"}</h2></div> },
     }
@@ -0,0 +1,184 @@
+use proto_web::{TemplateContainer, VolumeMount};
+use yew::prelude::*;
+
+#[derive(Properties, PartialEq)]
+pub struct ContainerListEditorProps {
+    pub label: String,
+    pub help: String,
+    pub containers: Vec<TemplateContainer>,
+    pub on_change: Callback<Vec<TemplateContainer>>,
+    /// Sidecars always need a resource percent, init containers default to 100.
+    #[prop_or_default]
+    pub sidecar: bool,
+}
+
+fn split_words(value: &str) -> Vec<String> {
+    value.split_whitespace().map(|x| x.to_string()).collect()
+}
+
+fn format_envs(container: &TemplateContainer) -> String {
+    let mut envs: Vec<_> = container
+        .envs
+        .iter()
+        .map(|(k, v)| format!("{}={}", k, v))
+        .collect();
+    envs.sort();
+    envs.join(" ")
+}
+
+fn format_mounts(container: &TemplateContainer) -> String {
+    container
+        .volume_mounts
+        .iter()
+        .map(|m| format!("{}:{}", m.pattern, m.mount_path))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[function_component(ContainerListEditor)]
+pub fn container_list_editor(props: &ContainerListEditorProps) -> Html {
+    let update = {
+        let containers = props.containers.clone();
+        let on_change = props.on_change.clone();
+        move |index: usize, f: &dyn Fn(&mut TemplateContainer, String), value: String| {
+            let mut containers = containers.clone();
+            if let Some(container) = containers.get_mut(index) {
+                f(container, value);
+            }
+            on_change.emit(containers);
+        }
+    };
+
+    let field = |index: usize, f: fn(&mut TemplateContainer, String)| {
+        let update = update.clone();
+        Callback::from(move |e: Event| {
+            let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+            update(index, &f, input.value());
+        })
+    };
+
+    let on_add = {
+        let containers = props.containers.clone();
+        let on_change = props.on_change.clone();
+        let sidecar = props.sidecar;
+        Callback::from(move |_| {
+            let mut containers = containers.clone();
+            containers.push(TemplateContainer {
+                resource_percent: if sidecar { Some(10) } else { None },
+                ..Default::default()
+            });
+            on_change.emit(containers);
+        })
+    };
+
+    html! {
+        <div class="form-section">
+            <label class="section-label">{ &props.label }</label>
+            <small class="form-help">{ &props.help }</small>
+
+            { for props.containers.iter().enumerate().map(|(index, container)| {
+                let on_remove = {
+                    let containers = props.containers.clone();
+                    let on_change = props.on_change.clone();
+                    Callback::from(move |_| {
+                        let mut containers = containers.clone();
+                        containers.remove(index);
+                        on_change.emit(containers);
+                    })
+                };
+
+                html! {
+                    <div key={index} class="container-item">
+                        <div class="label-row">
+                            <input
+                                type="text"
+                                value={container.name.clone()}
+                                onchange={field(index, |c, v| c.name = v)}
+                                placeholder="name"
+                                class="label-key"
+                            />
+                            <input
+                                type="text"
+                                value={container.image.clone()}
+                                onchange={field(index, |c, v| c.image = v)}
+                                placeholder="image:tag"
+                                class="label-value"
+                            />
+                            <input
+                                type="number"
+                                min="1"
+                                max="100"
+                                value={container.resource_percent.map(|x| x.to_string()).unwrap_or_default()}
+                                onchange={field(index, |c, v| c.resource_percent = v.parse().ok())}
+                                placeholder={if props.sidecar { "%" } else { "100" }}
+                                class="label-key"
+                            />
+                            <button
+                                type="button"
+                                onclick={on_remove}
+                                class="btn-danger-small"
+                            >
+                                { "×" }
+                            </button>
+                        </div>
+                        <div class="label-row">
+                            <input
+                                type="text"
+                                value={container.command.join(" ")}
+                                onchange={field(index, |c, v| c.command = split_words(&v))}
+                                placeholder="command"
+                                class="label-key"
+                            />
+                            <input
+                                type="text"
+                                value={container.args.join(" ")}
+                                onchange={field(index, |c, v| c.args = split_words(&v))}
+                                placeholder="args"
+                                class="label-value"
+                            />
+                        </div>
+                        <div class="label-row">
+                            <input
+                                type="text"
+                                value={format_envs(container)}
+                                onchange={field(index, |c, v| {
+                                    c.envs = v
+                                        .split_whitespace()
+                                        .filter_map(|x| x.split_once('='))
+                                        .map(|(k, v)| (k.to_string(), v.to_string()))
+                                        .collect();
+                                })}
+                                placeholder="KEY=value KEY2=value"
+                                class="label-key"
+                            />
+                            <input
+                                type="text"
+                                value={format_mounts(container)}
+                                onchange={field(index, |c, v| {
+                                    c.volume_mounts = v
+                                        .split_whitespace()
+                                        .filter_map(|x| x.split_once(':'))
+                                        .map(|(pattern, path)| VolumeMount {
+                                            pattern: pattern.to_string(),
+                                            mount_path: path.to_string(),
+                                        })
+                                        .collect();
+                                })}
+                                placeholder="volume:/path"
+                                class="label-value"
+                            />
+                        </div>
+                    </div>
+                }
+            })}
+
+            <button
+                type="button"
+                onclick={on_add}
+                class="btn-secondary-small"
+            >
+                { if props.sidecar { "+ Add Sidecar" } else { "+ Add Init Container" } }
+            </button>
+        </div>
+    }
+}
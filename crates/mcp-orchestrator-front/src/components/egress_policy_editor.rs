@@ -0,0 +1,177 @@
+use proto_web::{EgressPolicy, EgressPolicyType, EgressPort, EgressRule};
+use yew::prelude::*;
+
+#[derive(Properties, PartialEq)]
+pub struct EgressPolicyEditorProps {
+    pub policy: Option<EgressPolicy>,
+    pub on_change: Callback<Option<EgressPolicy>>,
+}
+
+/// `443 53/UDP`
+pub fn format_egress_ports(ports: &[EgressPort]) -> String {
+    ports
+        .iter()
+        .map(|port| match &port.protocol {
+            Some(protocol) => format!("{}/{}", port.port, protocol),
+            None => port.port.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn parse_egress_ports(value: &str) -> Vec<EgressPort> {
+    value
+        .split_whitespace()
+        .filter_map(|x| {
+            let (port, protocol) = match x.split_once('/') {
+                Some((port, protocol)) => (port, Some(protocol.to_uppercase())),
+                None => (x, None),
+            };
+            Some(EgressPort {
+                port: port.parse().ok()?,
+                protocol,
+            })
+        })
+        .collect()
+}
+
+/// Destination of a rule, `service:namespace/name` for in-cluster services.
+pub fn format_egress_target(rule: &EgressRule) -> String {
+    match (&rule.cidr, &rule.service) {
+        (Some(cidr), _) => cidr.clone(),
+        (None, Some(service)) => format!("service:{}", service),
+        (None, None) => String::new(),
+    }
+}
+
+fn parse_egress_target(rule: &mut EgressRule, value: &str) {
+    let value = value.trim();
+    match value.strip_prefix("service:") {
+        Some(service) => {
+            rule.cidr = None;
+            rule.service = Some(service.to_string());
+        }
+        None => {
+            rule.cidr = Some(value.to_string()).filter(|x| !x.is_empty());
+            rule.service = None;
+        }
+    }
+}
+
+#[function_component(EgressPolicyEditor)]
+pub fn egress_policy_editor(props: &EgressPolicyEditorProps) -> Html {
+    let on_type_change = {
+        let policy = props.policy.clone();
+        let on_change = props.on_change.clone();
+        Callback::from(move |e: Event| {
+            let select: web_sys::HtmlSelectElement = e.target_unchecked_into();
+            let policy = EgressPolicyType::from_str_name(&select.value()).map(|r#type| {
+                let mut policy = policy.clone().unwrap_or_default();
+                policy.set_type(r#type);
+                if r#type != EgressPolicyType::Allowlist {
+                    policy.rules.clear();
+                } else if policy.rules.is_empty() {
+                    policy.rules.push(EgressRule::default());
+                }
+                policy
+            });
+            on_change.emit(policy);
+        })
+    };
+
+    let update_rules = {
+        let policy = props.policy.clone();
+        let on_change = props.on_change.clone();
+        move |f: &dyn Fn(&mut Vec<EgressRule>)| {
+            let mut policy = policy.clone().unwrap_or_default();
+            f(&mut policy.rules);
+            on_change.emit(Some(policy));
+        }
+    };
+
+    let field = |index: usize, f: fn(&mut EgressRule, String)| {
+        let update_rules = update_rules.clone();
+        Callback::from(move |e: Event| {
+            let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+            let value = input.value();
+            update_rules(&|rules| {
+                if let Some(rule) = rules.get_mut(index) {
+                    f(rule, value.clone());
+                }
+            });
+        })
+    };
+
+    let on_add = {
+        let update_rules = update_rules.clone();
+        Callback::from(move |_| update_rules(&|rules| rules.push(EgressRule::default())))
+    };
+
+    let selected = props.policy.as_ref().map(|x| x.r#type());
+    let option = |r#type: EgressPolicyType, label: &str| {
+        html! {
+            <option value={r#type.as_str_name()} selected={selected == Some(r#type)}>{ label.to_string() }</option>
+        }
+    };
+    let rules = props
+        .policy
+        .as_ref()
+        .map(|x| x.rules.clone())
+        .unwrap_or_default();
+
+    html! {
+        <div class="form-section">
+            <label class="section-label">{ "Egress Policy" }</label>
+            <small class="form-help">{ "Materialized as a NetworkPolicy selecting the session pods. Allowlist and DNS only permit DNS lookups to kube-system" }</small>
+            <select onchange={on_type_change}>
+                <option value="" selected={selected.is_none()}>{ "Unrestricted" }</option>
+                { option(EgressPolicyType::DenyAll, "Deny all") }
+                { option(EgressPolicyType::DnsOnly, "DNS only") }
+                { option(EgressPolicyType::Allowlist, "Allowlist") }
+            </select>
+
+            if selected == Some(EgressPolicyType::Allowlist) {
+                { for rules.iter().enumerate().map(|(index, rule)| {
+                    let on_remove = {
+                        let update_rules = update_rules.clone();
+                        Callback::from(move |_| update_rules(&|rules| {
+                            rules.remove(index);
+                        }))
+                    };
+                    html! {
+                        <div key={index} class="label-row">
+                            <input
+                                type="text"
+                                value={format_egress_target(rule)}
+                                onchange={field(index, |r, v| parse_egress_target(r, &v))}
+                                placeholder="10.0.0.0/8 or service:namespace/name"
+                                class="label-key"
+                            />
+                            <input
+                                type="text"
+                                value={format_egress_ports(&rule.ports)}
+                                onchange={field(index, |r, v| r.ports = parse_egress_ports(&v))}
+                                placeholder="443 5432/TCP, every port when empty"
+                                class="label-value"
+                            />
+                            <button
+                                type="button"
+                                onclick={on_remove}
+                                class="btn-danger-small"
+                            >
+                                { "×" }
+                            </button>
+                        </div>
+                    }
+                })}
+                <button
+                    type="button"
+                    onclick={on_add}
+                    class="btn-secondary-small"
+                >
+                    { "+ Add Destination" }
+                </button>
+            }
+        </div>
+    }
+}
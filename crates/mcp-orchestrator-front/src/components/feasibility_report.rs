@@ -0,0 +1,39 @@
+use proto_web::CheckResourceLimitResponse;
+use yew::prelude::*;
+
+#[derive(Properties, PartialEq)]
+pub struct FeasibilityReportProps {
+    pub report: CheckResourceLimitResponse,
+}
+
+#[function_component(FeasibilityReport)]
+pub fn feasibility_report(props: &FeasibilityReportProps) -> Html {
+    let report = &props.report;
+    html! {
+        <div class="validation-report">
+            <p>
+                <strong>{ format!("{} sessions", report.schedulable_sessions) }</strong>
+                { " can be scheduled with the current free capacity" }
+            </p>
+            <small class="form-help">{ "Topology spread and pod anti-affinity are not evaluated" }</small>
+            <table class="data-table">
+                <thead>
+                    <tr>
+                        <th>{ "Node" }</th>
+                        <th>{ "Sessions" }</th>
+                        <th>{ "Eliminated By" }</th>
+                    </tr>
+                </thead>
+                <tbody>
+                    { for report.nodes.iter().map(|node| html! {
+                        <tr key={node.node.clone()}>
+                            <td><code>{ &node.node }</code></td>
+                            <td>{ node.sessions }</td>
+                            <td>{ node.reason.clone().unwrap_or_else(|| "-".to_string()) }</td>
+                        </tr>
+                    }) }
+                </tbody>
+            </table>
+        </div>
+    }
+}
@@ -1,23 +1,37 @@
 pub mod confirm_dialog;
+pub mod container_editor;
 pub mod copy_config_dialog;
+pub mod egress_policy_editor;
 pub mod error_message;
+pub mod feasibility_report;
 pub mod form_field;
 pub mod layout;
 pub mod loading;
+pub mod namespace_guard_editor;
 pub mod namespace_selector;
 pub mod navbar;
+pub mod placement_editor;
 pub mod resource_card;
 pub mod resource_list;
+pub mod security_profile_editor;
+pub mod session_quota_editor;
 pub mod sidebar;
 pub mod user_menu;
 
 pub use confirm_dialog::*;
+pub use container_editor::*;
 pub use copy_config_dialog::*;
+pub use egress_policy_editor::*;
 pub use error_message::*;
+pub use feasibility_report::*;
 pub use form_field::*;
 pub use layout::*;
 pub use loading::*;
+pub use namespace_guard_editor::*;
 pub use namespace_selector::*;
 pub use navbar::*;
+pub use placement_editor::*;
+pub use security_profile_editor::*;
+pub use session_quota_editor::*;
 pub use sidebar::*;
 pub use user_menu::*;
@@ -0,0 +1,119 @@
+use proto_web::NamespaceResourceQuota;
+use yew::prelude::*;
+use yewdux::prelude::*;
+
+use crate::api::APICaller;
+use crate::components::FormField;
+use crate::models::resource_limit::ResourceLimit;
+use crate::models::state::AuthState;
+
+/// ResourceQuota and default LimitRange of a namespace.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct NamespaceGuards {
+    pub resource_quota: NamespaceResourceQuota,
+    pub default_resource_limit: Option<String>,
+}
+
+impl NamespaceGuards {
+    /// None when no hard limit is set, the ResourceQuota is removed then.
+    pub fn resource_quota(&self) -> Option<NamespaceResourceQuota> {
+        Some(self.resource_quota.clone()).filter(|x| *x != NamespaceResourceQuota::default())
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct NamespaceGuardEditorProps {
+    pub guards: NamespaceGuards,
+    pub on_change: Callback<NamespaceGuards>,
+}
+
+fn non_empty(value: String) -> Option<String> {
+    Some(value.trim().to_string()).filter(|x| !x.is_empty())
+}
+
+#[function_component(NamespaceGuardEditor)]
+pub fn namespace_guard_editor(props: &NamespaceGuardEditorProps) -> Html {
+    let resource_limits = use_state(Vec::<ResourceLimit>::new);
+    let (auth_state, _) = use_store::<AuthState>();
+
+    {
+        let resource_limits = resource_limits.clone();
+        use_effect_with((), move |_| {
+            wasm_bindgen_futures::spawn_local(async move {
+                let api = APICaller::new(auth_state.access_token.clone());
+                match api.list_resource_limits().await {
+                    Ok(limits) => resource_limits.set(limits),
+                    Err(e) => web_sys::console::error_1(
+                        &format!("Failed to load resource limits: {}", e).into(),
+                    ),
+                }
+            });
+            || ()
+        });
+    }
+
+    let on_field = |f: fn(&mut NamespaceGuards, String)| {
+        let guards = props.guards.clone();
+        let on_change = props.on_change.clone();
+        Callback::from(move |e: Event| {
+            let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+            let mut guards = guards.clone();
+            f(&mut guards, input.value());
+            on_change.emit(guards);
+        })
+    };
+    let quota = &props.guards.resource_quota;
+
+    html! {
+        <>
+            <small class="form-help">{ "Enforced by Kubernetes for every pod of the namespace, empty fields are unlimited" }</small>
+            <FormField label="Pods" error={None::<String>}>
+                <input
+                    type="number"
+                    min="0"
+                    value={quota.pods.map(|x| x.to_string()).unwrap_or_default()}
+                    onchange={on_field(|g, v| g.resource_quota.pods = v.trim().parse().ok())}
+                />
+            </FormField>
+            <FormField label="Requests CPU" error={None::<String>}>
+                <input
+                    type="text"
+                    value={quota.requests_cpu.clone().unwrap_or_default()}
+                    onchange={on_field(|g, v| g.resource_quota.requests_cpu = non_empty(v))}
+                    placeholder="16"
+                />
+            </FormField>
+            <FormField label="Requests Memory" error={None::<String>}>
+                <input
+                    type="text"
+                    value={quota.requests_memory.clone().unwrap_or_default()}
+                    onchange={on_field(|g, v| g.resource_quota.requests_memory = non_empty(v))}
+                    placeholder="32Gi"
+                />
+            </FormField>
+            <FormField label="Requests Ephemeral Storage" error={None::<String>}>
+                <input
+                    type="text"
+                    value={quota.requests_ephemeral_storage.clone().unwrap_or_default()}
+                    onchange={on_field(|g, v| g.resource_quota.requests_ephemeral_storage = non_empty(v))}
+                    placeholder="100Gi"
+                />
+            </FormField>
+            <FormField label="Default Resource Limit" error={None::<String>}>
+                <select onchange={on_field(|g, v| g.default_resource_limit = non_empty(v))}>
+                    <option value="" selected={props.guards.default_resource_limit.is_none()}>{ "-- No LimitRange --" }</option>
+                    { for resource_limits.iter().map(|limit| html! {
+                        <option
+                            key={limit.name.clone()}
+                            value={limit.name.clone()}
+                            selected={props.guards.default_resource_limit.as_ref() == Some(&limit.name)}
+                        >
+                            { format!("{} (CPU: {}, Memory: {})", limit.name, limit.limits.cpu, limit.limits.memory) }
+                        </option>
+                    }) }
+                </select>
+                <small class="form-help">{ "Requests and limits applied to containers that do not set their own" }</small>
+            </FormField>
+        </>
+    }
+}
@@ -0,0 +1,291 @@
+use proto_web::{PodAntiAffinity, SessionScope, Toleration, TopologySpread};
+use yew::prelude::*;
+
+/// Scheduling fields of a resource limit besides node selector and node affinity.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Placement {
+    pub tolerations: Vec<Toleration>,
+    pub priority_class_name: Option<String>,
+    pub topology_spread: Vec<TopologySpread>,
+    pub anti_affinity: Vec<PodAntiAffinity>,
+}
+
+#[derive(Properties, PartialEq)]
+pub struct PlacementEditorProps {
+    pub placement: Placement,
+    pub on_change: Callback<Placement>,
+}
+
+pub fn session_scope_label(scope: SessionScope) -> &'static str {
+    match scope {
+        SessionScope::Template => "Same template",
+        SessionScope::User => "Same user",
+        SessionScope::All => "All sessions",
+    }
+}
+
+/// `key=value:NoSchedule`, `key:NoExecute (300s)`
+pub fn format_toleration(toleration: &Toleration) -> String {
+    let key = toleration.key.clone().unwrap_or_else(|| "*".to_string());
+    let mut text = match (toleration.operator.as_deref(), &toleration.value) {
+        (Some("Exists"), _) | (_, None) => key,
+        (_, Some(value)) => format!("{}={}", key, value),
+    };
+    if let Some(effect) = &toleration.effect {
+        text = format!("{}:{}", text, effect);
+    }
+    if let Some(seconds) = toleration.toleration_seconds {
+        text = format!("{} ({}s)", text, seconds);
+    }
+    text
+}
+
+fn non_empty(value: String) -> Option<String> {
+    Some(value.trim().to_string()).filter(|x| !x.is_empty())
+}
+
+fn on_field(
+    placement: &Placement,
+    on_change: &Callback<Placement>,
+    f: impl Fn(&mut Placement, String) + 'static,
+) -> Callback<Event> {
+    let placement = placement.clone();
+    let on_change = on_change.clone();
+    // input 과 select 모두 value 로 읽음
+    Callback::from(move |e: Event| {
+        let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+        let mut placement = placement.clone();
+        f(&mut placement, input.value());
+        on_change.emit(placement);
+    })
+}
+
+fn on_toggle(
+    placement: &Placement,
+    on_change: &Callback<Placement>,
+    f: impl Fn(&mut Placement, bool) + 'static,
+) -> Callback<Event> {
+    let placement = placement.clone();
+    let on_change = on_change.clone();
+    Callback::from(move |e: Event| {
+        let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+        let mut placement = placement.clone();
+        f(&mut placement, input.checked());
+        on_change.emit(placement);
+    })
+}
+
+fn on_click(
+    placement: &Placement,
+    on_change: &Callback<Placement>,
+    f: impl Fn(&mut Placement) + 'static,
+) -> Callback<MouseEvent> {
+    let placement = placement.clone();
+    let on_change = on_change.clone();
+    Callback::from(move |_| {
+        let mut placement = placement.clone();
+        f(&mut placement);
+        on_change.emit(placement);
+    })
+}
+
+#[function_component(PlacementEditor)]
+pub fn placement_editor(props: &PlacementEditorProps) -> Html {
+    let placement = &props.placement;
+    let on_change = &props.on_change;
+
+    let scope_options = |selected: SessionScope| {
+        html! {
+            { for [SessionScope::Template, SessionScope::User, SessionScope::All].into_iter().map(|scope| html! {
+                <option value={scope.as_str_name()} selected={scope == selected}>{ session_scope_label(scope) }</option>
+            }) }
+        }
+    };
+
+    html! {
+        <>
+            <label class="section-label">{ "Tolerations" }</label>
+            <small class="form-help">{ "Taints of dedicated node pools the session pods tolerate" }</small>
+            { for placement.tolerations.iter().enumerate().map(|(index, toleration)| html! {
+                <div key={index} class="label-row">
+                    <input
+                        type="text"
+                        value={toleration.key.clone().unwrap_or_default()}
+                        onchange={on_field(placement, on_change, move |p, v| p.tolerations[index].key = non_empty(v))}
+                        placeholder="key, every taint when empty"
+                        class="label-key"
+                    />
+                    <select onchange={on_field(placement, on_change, move |p, v| p.tolerations[index].operator = non_empty(v))}>
+                        <option value="" selected={toleration.operator.is_none()}>{ "Equal" }</option>
+                        <option value="Exists" selected={toleration.operator.as_deref() == Some("Exists")}>{ "Exists" }</option>
+                    </select>
+                    <input
+                        type="text"
+                        value={toleration.value.clone().unwrap_or_default()}
+                        onchange={on_field(placement, on_change, move |p, v| p.tolerations[index].value = non_empty(v))}
+                        placeholder="value"
+                        class="label-value"
+                    />
+                    <select onchange={on_field(placement, on_change, move |p, v| {
+                        let toleration = &mut p.tolerations[index];
+                        toleration.effect = non_empty(v);
+                        if toleration.effect.as_deref() != Some("NoExecute") {
+                            toleration.toleration_seconds = None;
+                        }
+                    })}>
+                        <option value="" selected={toleration.effect.is_none()}>{ "Any effect" }</option>
+                        { for ["NoSchedule", "PreferNoSchedule", "NoExecute"].into_iter().map(|effect| html! {
+                            <option value={effect} selected={toleration.effect.as_deref() == Some(effect)}>{ effect }</option>
+                        }) }
+                    </select>
+                    if toleration.effect.as_deref() == Some("NoExecute") {
+                        <input
+                            type="number"
+                            min="0"
+                            value={toleration.toleration_seconds.map(|x| x.to_string()).unwrap_or_default()}
+                            onchange={on_field(placement, on_change, move |p, v| p.tolerations[index].toleration_seconds = v.parse().ok())}
+                            placeholder="seconds"
+                        />
+                    }
+                    <button
+                        type="button"
+                        onclick={on_click(placement, on_change, move |p| { p.tolerations.remove(index); })}
+                        class="btn-danger-small"
+                    >
+                        { "×" }
+                    </button>
+                </div>
+            }) }
+            <button
+                type="button"
+                onclick={on_click(placement, on_change, |p| p.tolerations.push(Toleration::default()))}
+                class="btn-secondary-small"
+            >
+                { "+ Add Toleration" }
+            </button>
+
+            <label class="section-label">{ "Priority Class" }</label>
+            <input
+                type="text"
+                value={placement.priority_class_name.clone().unwrap_or_default()}
+                onchange={on_field(placement, on_change, |p, v| p.priority_class_name = non_empty(v))}
+                placeholder="mcp-sessions"
+            />
+
+            <label class="section-label">{ "Topology Spread" }</label>
+            <small class="form-help">{ "Spread sessions across zones or nodes, the skew counts the sessions of the scope" }</small>
+            { for placement.topology_spread.iter().enumerate().map(|(index, spread)| html! {
+                <div key={index} class="label-row">
+                    <input
+                        type="text"
+                        value={spread.topology_key.clone()}
+                        onchange={on_field(placement, on_change, move |p, v| p.topology_spread[index].topology_key = v.trim().to_string())}
+                        placeholder="topology.kubernetes.io/zone"
+                        class="label-key"
+                    />
+                    <input
+                        type="number"
+                        min="1"
+                        value={spread.max_skew.to_string()}
+                        onchange={on_field(placement, on_change, move |p, v| p.topology_spread[index].max_skew = v.parse().unwrap_or(1))}
+                        placeholder="max skew"
+                    />
+                    <select onchange={on_field(placement, on_change, move |p, v| {
+                        if let Some(scope) = SessionScope::from_str_name(&v) {
+                            p.topology_spread[index].set_scope(scope);
+                        }
+                    })}>
+                        { scope_options(spread.scope()) }
+                    </select>
+                    <label class="checkbox-label">
+                        <input
+                            type="checkbox"
+                            checked={spread.schedule_anyway}
+                            onchange={on_toggle(placement, on_change, move |p, v| p.topology_spread[index].schedule_anyway = v)}
+                        />
+                        { "Best effort" }
+                    </label>
+                    <button
+                        type="button"
+                        onclick={on_click(placement, on_change, move |p| { p.topology_spread.remove(index); })}
+                        class="btn-danger-small"
+                    >
+                        { "×" }
+                    </button>
+                </div>
+            }) }
+            <button
+                type="button"
+                onclick={on_click(placement, on_change, |p| p.topology_spread.push(TopologySpread {
+                    topology_key: "topology.kubernetes.io/zone".to_string(),
+                    max_skew: 1,
+                    ..Default::default()
+                }))}
+                class="btn-secondary-small"
+            >
+                { "+ Add Spread Constraint" }
+            </button>
+
+            <label class="section-label">{ "Pod Anti-Affinity" }</label>
+            <small class="form-help">{ "Keep sessions of the scope apart, e.g. the sessions of one user on different nodes" }</small>
+            { for placement.anti_affinity.iter().enumerate().map(|(index, anti_affinity)| html! {
+                <div key={index} class="label-row">
+                    <select onchange={on_field(placement, on_change, move |p, v| {
+                        if let Some(scope) = SessionScope::from_str_name(&v) {
+                            p.anti_affinity[index].set_scope(scope);
+                        }
+                    })}>
+                        { scope_options(anti_affinity.scope()) }
+                    </select>
+                    <input
+                        type="text"
+                        value={anti_affinity.topology_key.clone()}
+                        onchange={on_field(placement, on_change, move |p, v| p.anti_affinity[index].topology_key = v.trim().to_string())}
+                        placeholder="kubernetes.io/hostname"
+                        class="label-key"
+                    />
+                    <label class="checkbox-label">
+                        <input
+                            type="checkbox"
+                            checked={anti_affinity.required}
+                            onchange={on_toggle(placement, on_change, move |p, v| {
+                                p.anti_affinity[index].required = v;
+                                if v {
+                                    p.anti_affinity[index].weight = None;
+                                }
+                            })}
+                        />
+                        { "Required" }
+                    </label>
+                    if !anti_affinity.required {
+                        <input
+                            type="number"
+                            min="1"
+                            max="100"
+                            value={anti_affinity.weight.map(|x| x.to_string()).unwrap_or_default()}
+                            onchange={on_field(placement, on_change, move |p, v| p.anti_affinity[index].weight = v.parse().ok())}
+                            placeholder="weight 100"
+                        />
+                    }
+                    <button
+                        type="button"
+                        onclick={on_click(placement, on_change, move |p| { p.anti_affinity.remove(index); })}
+                        class="btn-danger-small"
+                    >
+                        { "×" }
+                    </button>
+                </div>
+            }) }
+            <button
+                type="button"
+                onclick={on_click(placement, on_change, |p| p.anti_affinity.push(PodAntiAffinity {
+                    scope: SessionScope::User as i32,
+                    ..Default::default()
+                }))}
+                class="btn-secondary-small"
+            >
+                { "+ Add Anti-Affinity" }
+            </button>
+        </>
+    }
+}
@@ -0,0 +1,91 @@
+use proto_web::{SecurityProfile, SecurityProfileType};
+use yew::prelude::*;
+
+#[derive(Properties, PartialEq)]
+pub struct SecurityProfileEditorProps {
+    pub profile: Option<SecurityProfile>,
+    pub on_change: Callback<Option<SecurityProfile>>,
+    /// Label of the option that leaves the profile unset.
+    pub unset_label: String,
+}
+
+/// Short description of a profile, used by the detail pages.
+pub fn security_profile_summary(profile: &SecurityProfile) -> String {
+    let name = match profile.r#type() {
+        SecurityProfileType::None => "none",
+        SecurityProfileType::Baseline => "baseline",
+        SecurityProfileType::Restricted => "restricted",
+        SecurityProfileType::Custom => "custom",
+    };
+    match &profile.runtime_class_name {
+        Some(runtime_class_name) => format!("{} (runtime {})", name, runtime_class_name),
+        None => name.to_string(),
+    }
+}
+
+#[function_component(SecurityProfileEditor)]
+pub fn security_profile_editor(props: &SecurityProfileEditorProps) -> Html {
+    let on_type_change = {
+        let profile = props.profile.clone();
+        let on_change = props.on_change.clone();
+        Callback::from(move |e: Event| {
+            let select: web_sys::HtmlSelectElement = e.target_unchecked_into();
+            let profile = SecurityProfileType::from_str_name(&select.value()).map(|r#type| {
+                let mut profile = profile.clone().unwrap_or_default();
+                profile.set_type(r#type);
+                profile
+            });
+            on_change.emit(profile);
+        })
+    };
+
+    let update = |f: fn(&mut SecurityProfile, String)| {
+        let profile = props.profile.clone();
+        let on_change = props.on_change.clone();
+        Callback::from(move |e: Event| {
+            let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+            let mut profile = profile.clone().unwrap_or_default();
+            f(&mut profile, input.value());
+            on_change.emit(Some(profile));
+        })
+    };
+
+    let selected = props.profile.as_ref().map(|x| x.r#type());
+    let option = |r#type: SecurityProfileType, label: &str| {
+        html! {
+            <option value={r#type.as_str_name()} selected={selected == Some(r#type)}>{ label.to_string() }</option>
+        }
+    };
+
+    html! {
+        <div class="form-group">
+            <label>{ "Security Profile" }</label>
+            <select onchange={on_type_change}>
+                <option value="" selected={selected.is_none()}>{ &props.unset_label }</option>
+                { option(SecurityProfileType::None, "None, namespace defaults") }
+                { option(SecurityProfileType::Baseline, "Baseline") }
+                { option(SecurityProfileType::Restricted, "Restricted (non-root, read-only root filesystem)") }
+                { option(SecurityProfileType::Custom, "Custom") }
+            </select>
+            if let Some(profile) = &props.profile {
+                <div class="label-row">
+                    <input
+                        type="number"
+                        value={profile.run_as_user.map(|x| x.to_string()).unwrap_or_default()}
+                        onchange={update(|p, v| p.run_as_user = v.parse().ok())}
+                        placeholder="runAsUser"
+                        class="label-key"
+                    />
+                    <input
+                        type="text"
+                        value={profile.runtime_class_name.clone().unwrap_or_default()}
+                        onchange={update(|p, v| p.runtime_class_name = Some(v).filter(|x| !x.is_empty()))}
+                        placeholder="runtimeClassName, e.g. gvisor"
+                        class="label-value"
+                    />
+                </div>
+                <small class="form-help">{ "Restricted runs as user 65532 unless set, and mounts a writable /tmp. Anonymous sessions do not mount a service account token" }</small>
+            }
+        </div>
+    }
+}
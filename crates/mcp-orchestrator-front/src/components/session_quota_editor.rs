@@ -0,0 +1,235 @@
+use proto_web::{SessionQuota, SessionUsage};
+use yew::prelude::*;
+
+use crate::components::FormField;
+use crate::utils::validation::{validate_cpu, validate_memory};
+
+#[derive(Properties, PartialEq)]
+pub struct SessionQuotaEditorProps {
+    pub quota: SessionQuota,
+    pub on_save: Callback<SessionQuota>,
+    #[prop_or_default]
+    pub saving: bool,
+}
+
+/// `2.5Gi`, `512Mi`
+pub fn format_bytes(bytes: f64) -> String {
+    const UNITS: [&str; 4] = ["Ki", "Mi", "Gi", "Ti"];
+    let mut value = bytes;
+    let mut unit = "";
+    for next in UNITS {
+        if value < 1024.0 {
+            break;
+        }
+        value /= 1024.0;
+        unit = next;
+    }
+    format!("{}{}", (value * 10.0).round() / 10.0, unit)
+}
+
+/// `3 / 10`, `3 / ∞` when the quota is unset.
+pub fn format_usage(used: String, max: Option<String>) -> String {
+    format!("{} / {}", used, max.unwrap_or_else(|| "∞".to_string()))
+}
+
+#[derive(Properties, PartialEq)]
+pub struct SessionUsageTableProps {
+    pub quota: SessionQuota,
+    pub usage: SessionUsage,
+}
+
+#[function_component(SessionUsageTable)]
+pub fn session_usage_table(props: &SessionUsageTableProps) -> Html {
+    let quota = &props.quota;
+    let usage = &props.usage;
+    let rows = [
+        (
+            "Sessions",
+            format_usage(
+                usage.sessions.to_string(),
+                quota.max_sessions.map(|x| x.to_string()),
+            ),
+        ),
+        (
+            "Busiest Template",
+            format_usage(
+                usage
+                    .template_sessions
+                    .values()
+                    .max()
+                    .copied()
+                    .unwrap_or_default()
+                    .to_string(),
+                quota.max_sessions_per_template.map(|x| x.to_string()),
+            ),
+        ),
+        (
+            "CPU",
+            format_usage(
+                ((usage.cpu * 1000.0).round() / 1000.0).to_string(),
+                quota.cpu.clone(),
+            ),
+        ),
+        (
+            "Memory",
+            format_usage(format_bytes(usage.memory), quota.memory.clone()),
+        ),
+    ];
+    html! {
+        <table class="data-table">
+            <thead>
+                <tr>
+                    <th>{ "Quota" }</th>
+                    <th>{ "Used / Limit" }</th>
+                </tr>
+            </thead>
+            <tbody>
+                { for rows.into_iter().map(|(name, value)| html! {
+                    <tr key={name}>
+                        <td>{ name }</td>
+                        <td>{ value }</td>
+                    </tr>
+                }) }
+            </tbody>
+        </table>
+    }
+}
+
+#[derive(Clone, Default, PartialEq)]
+struct QuotaFormData {
+    max_sessions: String,
+    max_sessions_per_template: String,
+    max_sessions_per_user: String,
+    cpu: String,
+    memory: String,
+}
+
+impl QuotaFormData {
+    fn from_quota(quota: &SessionQuota) -> Self {
+        let count = |x: Option<u32>| x.map(|x| x.to_string()).unwrap_or_default();
+        Self {
+            max_sessions: count(quota.max_sessions),
+            max_sessions_per_template: count(quota.max_sessions_per_template),
+            max_sessions_per_user: count(quota.max_sessions_per_user),
+            cpu: quota.cpu.clone().unwrap_or_default(),
+            memory: quota.memory.clone().unwrap_or_default(),
+        }
+    }
+
+    fn to_quota(&self) -> Result<SessionQuota, String> {
+        let count = |name: &str, value: &str| {
+            let value = value.trim();
+            if value.is_empty() {
+                return Ok(None);
+            }
+            value
+                .parse::<u32>()
+                .map(Some)
+                .map_err(|_| format!("{} must be a non-negative number", name))
+        };
+        let quantity = |value: &str| Some(value.trim().to_string()).filter(|x| !x.is_empty());
+        if let Some(error) = validate_cpu(self.cpu.trim()) {
+            return Err(error);
+        }
+        if let Some(error) = validate_memory(self.memory.trim()) {
+            return Err(error);
+        }
+        Ok(SessionQuota {
+            max_sessions: count("Max sessions", &self.max_sessions)?,
+            max_sessions_per_template: count(
+                "Max sessions per template",
+                &self.max_sessions_per_template,
+            )?,
+            max_sessions_per_user: count("Max sessions per user", &self.max_sessions_per_user)?,
+            cpu: quantity(&self.cpu),
+            memory: quantity(&self.memory),
+        })
+    }
+}
+
+#[function_component(SessionQuotaEditor)]
+pub fn session_quota_editor(props: &SessionQuotaEditorProps) -> Html {
+    let form_data = use_state(|| QuotaFormData::from_quota(&props.quota));
+    let error = use_state(|| Option::<String>::None);
+
+    let on_field = |f: fn(&mut QuotaFormData, String)| {
+        let form_data = form_data.clone();
+        Callback::from(move |e: Event| {
+            let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+            let mut data = (*form_data).clone();
+            f(&mut data, input.value());
+            form_data.set(data);
+        })
+    };
+
+    let on_submit = {
+        let form_data = form_data.clone();
+        let error = error.clone();
+        let on_save = props.on_save.clone();
+        Callback::from(move |e: SubmitEvent| {
+            e.prevent_default();
+            match form_data.to_quota() {
+                Ok(quota) => {
+                    error.set(None);
+                    on_save.emit(quota);
+                }
+                Err(e) => error.set(Some(e)),
+            }
+        })
+    };
+
+    html! {
+        <form onsubmit={on_submit} class="form">
+            <small class="form-help">{ "Empty fields are unlimited, sessions over the quota are rejected with 429" }</small>
+            <FormField label="Max Sessions" error={None::<String>}>
+                <input
+                    type="number"
+                    min="0"
+                    value={form_data.max_sessions.clone()}
+                    onchange={on_field(|d, v| d.max_sessions = v)}
+                />
+            </FormField>
+            <FormField label="Max Sessions per Template" error={None::<String>}>
+                <input
+                    type="number"
+                    min="0"
+                    value={form_data.max_sessions_per_template.clone()}
+                    onchange={on_field(|d, v| d.max_sessions_per_template = v)}
+                />
+            </FormField>
+            <FormField label="Max Sessions per User" error={None::<String>}>
+                <input
+                    type="number"
+                    min="0"
+                    value={form_data.max_sessions_per_user.clone()}
+                    onchange={on_field(|d, v| d.max_sessions_per_user = v)}
+                />
+                <small class="form-help">{ "Anonymous sessions are not counted" }</small>
+            </FormField>
+            <FormField label="CPU" error={None::<String>}>
+                <input
+                    type="text"
+                    value={form_data.cpu.clone()}
+                    onchange={on_field(|d, v| d.cpu = v)}
+                    placeholder="8 or 8000m"
+                />
+            </FormField>
+            <FormField label="Memory" error={None::<String>}>
+                <input
+                    type="text"
+                    value={form_data.memory.clone()}
+                    onchange={on_field(|d, v| d.memory = v)}
+                    placeholder="16Gi"
+                />
+            </FormField>
+            if let Some(error) = &*error {
+                <span class="field-error">{ error }</span>
+            }
+            <div class="form-actions">
+                <button type="submit" class="btn-primary" disabled={props.saving}>
+                    { if props.saving { "Saving..." } else { "Save Quota" } }
+                </button>
+            </div>
+        </form>
+    }
+}
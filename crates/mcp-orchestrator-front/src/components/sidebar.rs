@@ -17,6 +17,10 @@ pub fn sidebar() -> Html {
                         <span class="sidebar-link-icon">{"⚙️"}</span>
                         <span class="sidebar-link-text">{"Resource Limits"}</span>
                     </Link<Route>>
+                    <Link<Route> to={Route::UsageReport} classes="sidebar-link">
+                        <span class="sidebar-link-icon">{"📊"}</span>
+                        <span class="sidebar-link-text">{"Usage"}</span>
+                    </Link<Route>>
                 </nav>
             </div>
             <div class="sidebar-section">
@@ -34,6 +38,10 @@ pub fn sidebar() -> Html {
                         <span class="sidebar-link-icon">{"🔐"}</span>
                         <span class="sidebar-link-text">{"Secrets"}</span>
                     </Link<Route>>
+                    <Link<Route> to={Route::UserSecretList} classes="sidebar-link">
+                        <span class="sidebar-link-icon">{"🗝️"}</span>
+                        <span class="sidebar-link-text">{"My Secrets"}</span>
+                    </Link<Route>>
                     <Link<Route> to={Route::AuthorizationList} classes="sidebar-link">
                         <span class="sidebar-link-icon">{"🔑"}</span>
                         <span class="sidebar-link-text">{"Authorizations"}</span>
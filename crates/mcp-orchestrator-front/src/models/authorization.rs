@@ -41,3 +41,21 @@ impl AuthorizationFormData {
         Self::default()
     }
 }
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ApiKey {
+    pub name: String,
+    pub created_at: String,
+    pub expire_at: Option<String>,
+    pub last_used_at: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct IssuedToken {
+    pub id: String,
+    pub requester: Option<String>,
+    pub audience: String,
+    pub bound: bool,
+    pub created_at: String,
+    pub expire_at: Option<String>,
+}
@@ -1,4 +1,4 @@
-use proto_web::NamespaceResponse;
+use proto_web::{NamespaceResourceQuota, NamespaceResponse, SessionQuota, SessionUsage};
 use std::collections::HashMap;
 
 #[derive(Clone, Debug, PartialEq)]
@@ -7,6 +7,11 @@ pub struct Namespace {
     pub labels: HashMap<String, String>,
     pub created_at: String,
     pub deleted_at: Option<String>,
+    pub quota: SessionQuota,
+    pub usage: SessionUsage,
+    pub resource_quota: Option<NamespaceResourceQuota>,
+    pub resource_quota_used: HashMap<String, String>,
+    pub default_resource_limit: Option<String>,
 }
 
 impl From<NamespaceResponse> for Namespace {
@@ -16,6 +21,11 @@ impl From<NamespaceResponse> for Namespace {
             labels: response.labels,
             created_at: response.created_at,
             deleted_at: response.deleted_at,
+            quota: response.quota.unwrap_or_default(),
+            usage: response.usage.unwrap_or_default(),
+            resource_quota: response.resource_quota,
+            resource_quota_used: response.resource_quota_used,
+            default_resource_limit: response.default_resource_limit,
         }
     }
 }
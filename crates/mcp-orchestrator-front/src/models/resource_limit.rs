@@ -1,4 +1,5 @@
-use proto_web::{ResourceLimit as ProtoResourceLimit, ResourceLimitResponse};
+use crate::components::Placement;
+use proto_web::{ResourceLimit as ProtoResourceLimit, ResourceLimitResponse, SecurityProfile};
 use std::collections::HashMap;
 
 #[derive(Clone, Debug, PartialEq)]
@@ -20,6 +21,9 @@ pub struct ResourceLimitSpec {
     pub ephemeral_storage: Option<String>,
     pub node_selector: Option<String>,
     pub node_affinity: Option<String>,
+    pub security: Option<SecurityProfile>,
+    pub image_pull_secrets: Vec<String>,
+    pub placement: Placement,
 }
 
 impl From<ResourceLimitResponse> for ResourceLimit {
@@ -33,6 +37,12 @@ impl From<ResourceLimitResponse> for ResourceLimit {
             volumes: HashMap::new(),
             node_selector: HashMap::new(),
             node_affinity: None,
+            security: None,
+            image_pull_secrets: vec![],
+            tolerations: vec![],
+            priority_class_name: None,
+            topology_spread: vec![],
+            anti_affinity: vec![],
         });
 
         let node_selector_yaml = if !limits.node_selector.is_empty() {
@@ -58,6 +68,14 @@ impl From<ResourceLimitResponse> for ResourceLimit {
                 ephemeral_storage: limits.ephemeral_storage,
                 node_selector: node_selector_yaml,
                 node_affinity: node_affinity_yaml,
+                security: limits.security,
+                image_pull_secrets: limits.image_pull_secrets,
+                placement: Placement {
+                    tolerations: limits.tolerations,
+                    priority_class_name: limits.priority_class_name,
+                    topology_spread: limits.topology_spread,
+                    anti_affinity: limits.anti_affinity,
+                },
             },
             labels: response.labels,
             created_at: response.created_at,
@@ -1,4 +1,4 @@
-use proto_web::SecretResponse;
+use proto_web::{SecretResponse, SecretType, UserSecretResponse};
 use std::collections::HashMap;
 
 #[derive(Clone, Debug, PartialEq)]
@@ -6,6 +6,7 @@ pub struct Secret {
     pub name: String,
     pub namespace: String,
     pub keys: Vec<String>,
+    pub secret_type: SecretType,
     pub labels: HashMap<String, String>,
     pub created_at: String,
     pub deleted_at: Option<String>,
@@ -16,6 +17,7 @@ impl From<SecretResponse> for Secret {
         Self {
             name: response.name,
             namespace: response.namespace,
+            secret_type: response.r#type(),
             keys: response.keys,
             labels: response.labels,
             created_at: response.created_at,
@@ -23,3 +25,23 @@ impl From<SecretResponse> for Secret {
         }
     }
 }
+
+/// Secret owned by the signed-in user, referenced by templates as `user:<name>`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct UserSecret {
+    pub name: String,
+    pub namespace: String,
+    pub keys: Vec<String>,
+    pub created_at: String,
+}
+
+impl From<UserSecretResponse> for UserSecret {
+    fn from(response: UserSecretResponse) -> Self {
+        Self {
+            name: response.name,
+            namespace: response.namespace,
+            keys: response.keys,
+            created_at: response.created_at,
+        }
+    }
+}
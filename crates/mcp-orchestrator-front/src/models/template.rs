@@ -1,4 +1,8 @@
-use proto_web::{CreateMcpTemplateRequest, McpTemplateResponse};
+use proto_web::{
+    ArgEnvSpec, ArgEnvType, CreateMcpTemplateRequest, EgressPolicy, McpTemplateResponse,
+    PackageSource, SecretMount, SecretRotationPolicy, SecurityProfile, TemplateContainer,
+    VolumeMount, WorkspaceConfig,
+};
 use std::collections::HashMap;
 
 #[derive(Clone, Debug, PartialEq)]
@@ -11,9 +15,21 @@ pub struct Template {
     pub args: Vec<String>,
     pub envs: HashMap<String, String>,
     pub arg_envs: HashMap<String, String>,
+    pub arg_env_specs: HashMap<String, ArgEnvSpec>,
     pub secret_envs: Vec<String>,
     pub resource_limit_name: Option<String>,
-    pub authorization_name: Option<String>,
+    pub authorization_names: Vec<String>,
+    pub volume_mounts: Vec<VolumeMount>,
+    pub secret_mounts: Vec<SecretMount>,
+    pub workspace: Option<WorkspaceConfig>,
+    pub claim_envs: HashMap<String, String>,
+    pub package: Option<PackageSource>,
+    pub init_containers: Vec<TemplateContainer>,
+    pub sidecars: Vec<TemplateContainer>,
+    pub security: Option<SecurityProfile>,
+    pub egress: Option<EgressPolicy>,
+    pub image_pull_secrets: Vec<String>,
+    pub secret_rotation: SecretRotationPolicy,
     pub created_at: String,
     pub deleted_at: Option<String>,
 }
@@ -29,17 +45,32 @@ impl From<McpTemplateResponse> for Template {
             args: response.args,
             envs: response.envs,
             arg_envs: response.arg_envs,
+            arg_env_specs: response.arg_env_specs,
             secret_envs: response.secret_envs,
             resource_limit_name: if response.resource_limit_name.is_empty() {
                 None
             } else {
                 Some(response.resource_limit_name)
             },
-            authorization_name: if response.authorization_name.is_empty() {
-                None
-            } else {
+            authorization_names: if response.authorization_names.is_empty() {
                 Some(response.authorization_name)
+                    .filter(|x| !x.is_empty())
+                    .into_iter()
+                    .collect()
+            } else {
+                response.authorization_names
             },
+            volume_mounts: response.volume_mounts,
+            secret_mounts: response.secret_mounts,
+            workspace: response.workspace,
+            claim_envs: response.claim_envs,
+            package: response.package,
+            init_containers: response.init_containers,
+            sidecars: response.sidecars,
+            security: response.security,
+            egress: response.egress,
+            secret_rotation: response.secret_rotation(),
+            image_pull_secrets: response.image_pull_secrets,
             created_at: response.created_at,
             deleted_at: response.deleted_at,
         }
@@ -55,12 +86,63 @@ pub struct TemplateFormData {
     pub args: Vec<String>,
     pub envs: HashMap<String, String>,
     pub arg_envs: HashMap<String, String>,
+    pub arg_env_specs: HashMap<String, ArgEnvSpec>,
     pub secret_envs: Vec<String>,
     pub resource_limit_name: Option<String>,
-    pub authorization_name: Option<String>,
+    /// Empty uses the default anonymous authorization.
+    pub authorization_names: Vec<String>,
+    pub volume_mounts: Vec<VolumeMount>,
+    pub secret_mounts: Vec<SecretMount>,
+    pub workspace: Option<WorkspaceConfig>,
+    pub claim_envs: HashMap<String, String>,
+    pub package: Option<PackageSource>,
+    pub init_containers: Vec<TemplateContainer>,
+    pub sidecars: Vec<TemplateContainer>,
+    pub security: Option<SecurityProfile>,
+    pub egress: Option<EgressPolicy>,
+    pub image_pull_secrets: Vec<String>,
+    pub secret_rotation: SecretRotationPolicy,
     pub labels: HashMap<String, String>,
 }
 
+impl From<CreateMcpTemplateRequest> for TemplateFormData {
+    fn from(request: CreateMcpTemplateRequest) -> Self {
+        Self {
+            namespace: request.namespace.unwrap_or_default(),
+            name: request.name,
+            image: request.image,
+            command: request.command,
+            args: request.args,
+            envs: request.envs,
+            arg_envs: request.arg_envs,
+            arg_env_specs: request.arg_env_specs,
+            secret_envs: request.secret_envs,
+            resource_limit_name: if request.resource_limit_name.is_empty() {
+                None
+            } else {
+                Some(request.resource_limit_name)
+            },
+            authorization_names: request
+                .authorization_name
+                .into_iter()
+                .chain(request.authorization_names)
+                .collect(),
+            volume_mounts: request.volume_mounts,
+            secret_mounts: request.secret_mounts,
+            workspace: request.workspace,
+            claim_envs: request.claim_envs,
+            package: request.package,
+            init_containers: request.init_containers,
+            sidecars: request.sidecars,
+            security: request.security,
+            egress: request.egress,
+            secret_rotation: request.secret_rotation(),
+            image_pull_secrets: request.image_pull_secrets,
+            labels: request.labels,
+        }
+    }
+}
+
 impl TemplateFormData {
     pub fn into_create_request(self) -> CreateMcpTemplateRequest {
         let filtered_secret_envs: Vec<String> = self
@@ -82,11 +164,70 @@ impl TemplateFormData {
             args: self.args,
             envs: self.envs,
             arg_envs: self.arg_envs,
+            arg_env_specs: self.arg_env_specs,
             secret_envs: filtered_secret_envs,
             resource_limit_name: self.resource_limit_name.unwrap_or_default(),
-            authorization_name: self.authorization_name,
-            volume_mounts: Vec::new(),
-            secret_mounts: Vec::new(),
+            authorization_name: None,
+            authorization_names: self.authorization_names,
+            volume_mounts: self
+                .volume_mounts
+                .into_iter()
+                .filter(|m| !m.pattern.is_empty() && !m.mount_path.is_empty())
+                .collect(),
+            secret_mounts: self
+                .secret_mounts
+                .into_iter()
+                .filter(|m| !m.name.is_empty() && !m.mount_path.is_empty())
+                .collect(),
+            workspace: self.workspace,
+            claim_envs: self
+                .claim_envs
+                .into_iter()
+                .filter(|(k, v)| !k.is_empty() && !v.is_empty())
+                .collect(),
+            package: self.package,
+            init_containers: self
+                .init_containers
+                .into_iter()
+                .filter(|c| !c.name.is_empty())
+                .collect(),
+            sidecars: self
+                .sidecars
+                .into_iter()
+                .filter(|c| !c.name.is_empty())
+                .collect(),
+            validation: None,
+            security: self.security,
+            egress: self.egress.map(|mut egress| {
+                egress
+                    .rules
+                    .retain(|rule| rule.cidr.is_some() || rule.service.is_some());
+                egress
+            }),
+            image_pull_secrets: self.image_pull_secrets,
+            secret_rotation: self.secret_rotation.into(),
+        }
+    }
+}
+
+/// Example header value of an argument, used by the copyable MCP client config.
+pub fn arg_env_example(spec: &ArgEnvSpec) -> String {
+    let value = if spec.secret {
+        "<secret>".to_string()
+    } else if let Some(default_value) = &spec.default_value {
+        default_value.clone()
+    } else {
+        match spec.r#type() {
+            ArgEnvType::String => "value".to_string(),
+            ArgEnvType::Int => "0".to_string(),
+            ArgEnvType::Bool => "true".to_string(),
+            ArgEnvType::Enum => spec.allowed_values.join(" | "),
+            ArgEnvType::Url => "https://example.com".to_string(),
         }
+    };
+    if spec.optional || spec.default_value.is_some() {
+        format!("\"{}\" or null", value)
+    } else {
+        format!("\"{}\"", value)
     }
 }
@@ -0,0 +1,279 @@
+use crate::api::APICaller;
+use crate::components::{ConfirmDialog, ErrorMessage, Loading};
+use crate::models::authorization::ApiKey;
+use crate::models::state::AuthState;
+use crate::utils::validation::validate_name;
+use yew::prelude::*;
+use yewdux::prelude::*;
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub namespace: String,
+    pub name: String,
+}
+
+#[derive(Debug)]
+enum LoadState {
+    Loading,
+    Loaded(Vec<ApiKey>),
+    Error(String),
+}
+
+/// Keys of an API_KEY authorization, the issued key is shown only once.
+#[function_component(ApiKeySection)]
+pub fn api_key_section(props: &Props) -> Html {
+    let load_state = use_state(|| LoadState::Loading);
+    let reload = use_state(|| 0u32);
+    let key_name = use_state(String::new);
+    let expire_days = use_state(|| String::from("90"));
+    let form_error = use_state(|| Option::<String>::None);
+    let is_issuing = use_state(|| false);
+    let issued_key = use_state(|| Option::<(ApiKey, String)>::None);
+    let revoke_target = use_state(|| Option::<String>::None);
+    let (auth_state, _) = use_store::<AuthState>();
+
+    let namespace = props.namespace.clone();
+    let name = props.name.clone();
+
+    {
+        let load_state = load_state.clone();
+        let api = APICaller::new(auth_state.access_token.clone());
+        use_effect_with(
+            (namespace.clone(), name.clone(), *reload),
+            move |(namespace, name, _)| {
+                let namespace = namespace.clone();
+                let name = name.clone();
+                wasm_bindgen_futures::spawn_local(async move {
+                    match api.list_api_keys(namespace, name).await {
+                        Ok(keys) => load_state.set(LoadState::Loaded(keys)),
+                        Err(e) => load_state.set(LoadState::Error(e)),
+                    }
+                });
+                || ()
+            },
+        );
+    }
+
+    let on_key_name_change = {
+        let key_name = key_name.clone();
+        Callback::from(move |e: Event| {
+            let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+            key_name.set(input.value());
+        })
+    };
+
+    let on_expire_days_change = {
+        let expire_days = expire_days.clone();
+        Callback::from(move |e: Event| {
+            let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+            expire_days.set(input.value());
+        })
+    };
+
+    let on_issue = {
+        let key_name = key_name.clone();
+        let expire_days = expire_days.clone();
+        let form_error = form_error.clone();
+        let is_issuing = is_issuing.clone();
+        let issued_key = issued_key.clone();
+        let reload = reload.clone();
+        let namespace = namespace.clone();
+        let name = name.clone();
+        let auth_state = auth_state.clone();
+        Callback::from(move |e: SubmitEvent| {
+            e.prevent_default();
+            if let Some(error) = validate_name(&key_name) {
+                form_error.set(Some(error));
+                return;
+            }
+            // 비워두면 만료 없는 키 (서버에서 허용하는 경우에만)
+            let days = if expire_days.trim().is_empty() {
+                None
+            } else {
+                match expire_days.trim().parse::<i64>() {
+                    Ok(days) if (1..=365).contains(&days) => Some(days),
+                    _ => {
+                        form_error.set(Some("Expire days must be between 1 and 365".to_string()));
+                        return;
+                    }
+                }
+            };
+
+            is_issuing.set(true);
+            form_error.set(None);
+            let key = (*key_name).clone();
+            let key_name = key_name.clone();
+            let form_error = form_error.clone();
+            let is_issuing = is_issuing.clone();
+            let issued_key = issued_key.clone();
+            let reload = reload.clone();
+            let namespace = namespace.clone();
+            let name = name.clone();
+            let api = APICaller::new(auth_state.access_token.clone());
+            wasm_bindgen_futures::spawn_local(async move {
+                match api.issue_api_key(namespace, name, key, days).await {
+                    Ok(result) => {
+                        issued_key.set(Some(result));
+                        key_name.set(String::new());
+                        reload.set(*reload + 1);
+                    }
+                    Err(e) => form_error.set(Some(e)),
+                }
+                is_issuing.set(false);
+            });
+        })
+    };
+
+    let on_copy_key = {
+        let issued_key = issued_key.clone();
+        Callback::from(move |_| {
+            if let Some((_, key)) = &*issued_key {
+                let window = web_sys::window().unwrap();
+                let _ = window.navigator().clipboard().write_text(key);
+            }
+        })
+    };
+
+    let on_dismiss_key = {
+        let issued_key = issued_key.clone();
+        Callback::from(move |_| issued_key.set(None))
+    };
+
+    let on_revoke_confirm = {
+        let revoke_target = revoke_target.clone();
+        let load_state = load_state.clone();
+        let reload = reload.clone();
+        let namespace = namespace.clone();
+        let name = name.clone();
+        let auth_state = auth_state.clone();
+        Callback::from(move |_| {
+            let Some(key_name) = (*revoke_target).clone() else {
+                return;
+            };
+            revoke_target.set(None);
+            let load_state = load_state.clone();
+            let reload = reload.clone();
+            let namespace = namespace.clone();
+            let name = name.clone();
+            let api = APICaller::new(auth_state.access_token.clone());
+            wasm_bindgen_futures::spawn_local(async move {
+                match api.revoke_api_key(namespace, name, key_name).await {
+                    Ok(_) => reload.set(*reload + 1),
+                    Err(e) => load_state.set(LoadState::Error(e)),
+                }
+            });
+        })
+    };
+
+    let on_revoke_cancel = {
+        let revoke_target = revoke_target.clone();
+        Callback::from(move |_| revoke_target.set(None))
+    };
+
+    html! {
+        <div class="detail-section">
+            <h2>{ "API Keys" }</h2>
+            <p class="form-help">
+                { "Clients send the key as " }
+                <code>{ "Authorization: Bearer mcpk_..." }</code>
+            </p>
+
+            { if let Some((key, secret)) = &*issued_key {
+                html! {
+                    <div class="token-result">
+                        <h3>{ format!("Key '{}' Issued", key.name) }</h3>
+                        <div style="display: flex; gap: 0.5rem; align-items: center;">
+                            <pre class="code-block" style="flex: 1; margin: 0; overflow-x: auto;">{ secret }</pre>
+                            <button class="btn-secondary-small" onclick={on_copy_key}>{ "Copy" }</button>
+                            <button class="btn-secondary-small" onclick={on_dismiss_key}>{ "×" }</button>
+                        </div>
+                        <p class="form-help">{ "⚠️ Save this key securely. It will not be shown again." }</p>
+                    </div>
+                }
+            } else { html! {} }}
+
+            { match &*load_state {
+                LoadState::Loading => html! { <Loading /> },
+                LoadState::Error(e) => html! { <ErrorMessage message={e.clone()} /> },
+                LoadState::Loaded(keys) if keys.is_empty() => html! {
+                    <div class="empty-state">
+                        <p>{ "No api keys issued yet." }</p>
+                    </div>
+                },
+                LoadState::Loaded(keys) => html! {
+                    <table class="data-table">
+                        <thead>
+                            <tr>
+                                <th>{ "Name" }</th>
+                                <th>{ "Created" }</th>
+                                <th>{ "Expires" }</th>
+                                <th>{ "Last Used" }</th>
+                                <th></th>
+                            </tr>
+                        </thead>
+                        <tbody>
+                            { for keys.iter().map(|key| {
+                                let on_revoke = {
+                                    let revoke_target = revoke_target.clone();
+                                    let key_name = key.name.clone();
+                                    Callback::from(move |_| revoke_target.set(Some(key_name.clone())))
+                                };
+                                html! {
+                                    <tr key={key.name.clone()}>
+                                        <td>{ &key.name }</td>
+                                        <td>{ &key.created_at }</td>
+                                        <td>{ key.expire_at.clone().unwrap_or_else(|| "Never".to_string()) }</td>
+                                        <td>{ key.last_used_at.clone().unwrap_or_else(|| "-".to_string()) }</td>
+                                        <td>
+                                            <button class="btn-danger-small" onclick={on_revoke}>{ "Revoke" }</button>
+                                        </td>
+                                    </tr>
+                                }
+                            })}
+                        </tbody>
+                    </table>
+                },
+            }}
+
+            <form onsubmit={on_issue} class="form">
+                { if let Some(error) = &*form_error {
+                    html! { <ErrorMessage message={error.clone()} /> }
+                } else { html! {} }}
+                <div class="field">
+                    <label>{ "Key Name *" }</label>
+                    <input
+                        type="text"
+                        value={(*key_name).clone()}
+                        onchange={on_key_name_change}
+                        placeholder="ci-pipeline"
+                        required={true}
+                    />
+                </div>
+                <div class="field">
+                    <label>{ "Expiration (days)" }</label>
+                    <input
+                        type="number"
+                        value={(*expire_days).clone()}
+                        onchange={on_expire_days_change}
+                        min="1"
+                        max="365"
+                    />
+                    <small class="form-help">{ "Leave empty for a key without expiration, if the server allows it" }</small>
+                </div>
+                <div class="form-actions">
+                    <button type="submit" class="btn-primary" disabled={*is_issuing}>
+                        { if *is_issuing { "Issuing..." } else { "Issue Key" } }
+                    </button>
+                </div>
+            </form>
+
+            <ConfirmDialog
+                title="Revoke API Key"
+                message={format!("Are you sure you want to revoke api key '{}'? Clients using it will be rejected immediately.", (*revoke_target).clone().unwrap_or_default())}
+                on_confirm={on_revoke_confirm}
+                on_cancel={on_revoke_cancel}
+                show={revoke_target.is_some()}
+            />
+        </div>
+    }
+}
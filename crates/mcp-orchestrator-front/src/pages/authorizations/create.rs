@@ -49,6 +49,8 @@ pub fn authorization_create() -> Html {
             let select: web_sys::HtmlSelectElement = e.target_unchecked_into();
             let value = match select.value().as_str() {
                 "kubernetes-service-account" => 1,
+                "oidc" => 2,
+                "api-key" => 3,
                 _ => 1,
             };
             let mut data = (*form_data).clone();
@@ -159,6 +161,8 @@ pub fn authorization_create() -> Html {
                     <label>{ "Type *" }</label>
                     <select onchange={on_type_change} value="kubernetes-service-account">
                         <option value="kubernetes-service-account">{ "Kubernetes Service Account" }</option>
+                        <option value="oidc">{ "OIDC" }</option>
+                        <option value="api-key">{ "API Key" }</option>
                     </select>
                     <small class="form-help">{ "Authorization type for MCP server access (Anonymous type cannot be created)" }</small>
                 </div>
@@ -168,10 +172,20 @@ pub fn authorization_create() -> Html {
                     <textarea
                         value={form_data.data.clone().unwrap_or_default()}
                         onchange={on_data_change}
-                        placeholder="{}"
+                        placeholder={if form_data.auth_type == 2 {
+                            r#"{"audiences": [], "issuers": [], "claims": [{"path": "groups", "values": ["dev"]}]}"#
+                        } else {
+                            "{}"
+                        }}
                         rows="4"
                     />
-                    <small class="form-help">{ "Additional configuration data in JSON format" }</small>
+                    <small class="form-help">{ if form_data.auth_type == 2 {
+                        "Required audiences, issuers and claim conditions of OIDC login tokens, empty lists use the provider defaults"
+                    } else if form_data.auth_type == 3 {
+                        "Keys are issued from the authorization detail page after creation"
+                    } else {
+                        "Additional configuration data in JSON format"
+                    }}</small>
                 </div>
 
                 <div class="form-actions">
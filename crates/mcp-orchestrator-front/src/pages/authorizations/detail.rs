@@ -1,3 +1,5 @@
+use super::api_keys::ApiKeySection;
+use super::tokens::IssuedTokenSection;
 use crate::api::APICaller;
 use crate::components::{ConfirmDialog, ErrorMessage, Loading};
 use crate::models::authorization::Authorization;
@@ -34,6 +36,8 @@ pub fn authorization_detail(props: &Props) -> Html {
     let token_result = use_state(|| Option::<(String, Option<String>)>::None);
     let token_error = use_state(|| Option::<String>::None);
     let expire_days = use_state(|| String::from("7"));
+    let bound_token = use_state(|| true);
+    let token_reload = use_state(|| 0u32);
 
     let namespace = props.namespace.clone();
     let name = props.name.clone();
@@ -123,11 +127,21 @@ pub fn authorization_detail(props: &Props) -> Html {
         })
     };
 
+    let on_bound_token_change = {
+        let bound_token = bound_token.clone();
+        Callback::from(move |e: Event| {
+            let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+            bound_token.set(input.checked());
+        })
+    };
+
     let on_generate_token_submit = {
         let is_generating_token = is_generating_token.clone();
         let token_result = token_result.clone();
         let token_error = token_error.clone();
         let expire_days = expire_days.clone();
+        let bound_token = bound_token.clone();
+        let token_reload = token_reload.clone();
         let namespace = namespace.clone();
         let name = name.clone();
         let auth_state = auth_state.clone();
@@ -146,15 +160,18 @@ pub fn authorization_detail(props: &Props) -> Html {
             let is_generating_token = is_generating_token.clone();
             let token_result = token_result.clone();
             let token_error = token_error.clone();
+            let token_reload = token_reload.clone();
+            let bound = *bound_token;
             let namespace = namespace.clone();
             let name = name.clone();
             let auth_state = auth_state.clone();
 
             wasm_bindgen_futures::spawn_local(async move {
                 let api = APICaller::new(auth_state.access_token.clone());
-                match api.generate_token(namespace, name, days).await {
+                match api.generate_token(namespace, name, days, bound).await {
                     Ok(result) => {
                         token_result.set(Some(result));
+                        token_reload.set(*token_reload + 1);
                         is_generating_token.set(false);
                     }
                     Err(e) => {
@@ -225,6 +242,8 @@ pub fn authorization_detail(props: &Props) -> Html {
                     let type_name = match authorization.auth_type {
                         0 => "Anonymous",
                         1 => "Kubernetes Service Account",
+                        2 => "OIDC",
+                        3 => "API Key",
                         _ => "Unknown",
                     };
                     html! {
@@ -262,6 +281,29 @@ pub fn authorization_detail(props: &Props) -> Html {
                                 html! {}
                             }}
 
+                            { if authorization.auth_type == 1 {
+                                html! {
+                                    <IssuedTokenSection
+                                        namespace={authorization.namespace.clone()}
+                                        name={authorization.name.clone()}
+                                        reload={*token_reload}
+                                    />
+                                }
+                            } else {
+                                html! {}
+                            }}
+
+                            { if authorization.auth_type == 3 {
+                                html! {
+                                    <ApiKeySection
+                                        namespace={authorization.namespace.clone()}
+                                        name={authorization.name.clone()}
+                                    />
+                                }
+                            } else {
+                                html! {}
+                            }}
+
                             { if !authorization.labels.is_empty() {
                                 html! {
                                     <div class="detail-section">
@@ -347,6 +389,18 @@ pub fn authorization_detail(props: &Props) -> Html {
                                             <small class="form-help">{ "Token will expire after this many days (1-365)" }</small>
                                         </div>
 
+                                        <div class="field">
+                                            <label class="checkbox-label">
+                                                <input
+                                                    type="checkbox"
+                                                    checked={*bound_token}
+                                                    onchange={on_bound_token_change}
+                                                />
+                                                { " Bind to a revocable record" }
+                                            </label>
+                                            <small class="form-help">{ "Bound tokens can be revoked from the issued tokens list before they expire" }</small>
+                                        </div>
+
                                         <div class="form-actions">
                                             <button
                                                 type="submit"
@@ -76,6 +76,8 @@ pub fn authorization_list() -> Html {
                                         let type_name = match auth.auth_type {
                                             0 => "Anonymous",
                                             1 => "Kubernetes Service Account",
+                                            2 => "OIDC",
+                                            3 => "API Key",
                                             _ => "Unknown",
                                         };
                                         html! {
@@ -1,6 +1,8 @@
+mod api_keys;
 mod create;
 mod detail;
 mod list;
+mod tokens;
 
 pub use create::AuthorizationCreate;
 pub use detail::AuthorizationDetail;
@@ -0,0 +1,150 @@
+use crate::api::APICaller;
+use crate::components::{ConfirmDialog, ErrorMessage, Loading};
+use crate::models::authorization::IssuedToken;
+use crate::models::state::AuthState;
+use yew::prelude::*;
+use yewdux::prelude::*;
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub namespace: String,
+    pub name: String,
+    /// Bumped by the parent after a token was generated.
+    #[prop_or_default]
+    pub reload: u32,
+}
+
+#[derive(Debug)]
+enum LoadState {
+    Loading,
+    Loaded(Vec<IssuedToken>),
+    Error(String),
+}
+
+/// Tokens generated for a KUBERNETES_SERVICE_ACCOUNT authorization that have not expired yet.
+#[function_component(IssuedTokenSection)]
+pub fn issued_token_section(props: &Props) -> Html {
+    let load_state = use_state(|| LoadState::Loading);
+    let reload = use_state(|| 0u32);
+    let revoke_target = use_state(|| Option::<String>::None);
+    let (auth_state, _) = use_store::<AuthState>();
+
+    let namespace = props.namespace.clone();
+    let name = props.name.clone();
+
+    {
+        let load_state = load_state.clone();
+        let api = APICaller::new(auth_state.access_token.clone());
+        use_effect_with(
+            (namespace.clone(), name.clone(), props.reload, *reload),
+            move |(namespace, name, _, _)| {
+                let namespace = namespace.clone();
+                let name = name.clone();
+                wasm_bindgen_futures::spawn_local(async move {
+                    match api.list_tokens(namespace, name).await {
+                        Ok(tokens) => load_state.set(LoadState::Loaded(tokens)),
+                        Err(e) => load_state.set(LoadState::Error(e)),
+                    }
+                });
+                || ()
+            },
+        );
+    }
+
+    let on_revoke_confirm = {
+        let revoke_target = revoke_target.clone();
+        let load_state = load_state.clone();
+        let reload = reload.clone();
+        let namespace = namespace.clone();
+        let name = name.clone();
+        let auth_state = auth_state.clone();
+        Callback::from(move |_| {
+            let Some(id) = (*revoke_target).clone() else {
+                return;
+            };
+            revoke_target.set(None);
+            let load_state = load_state.clone();
+            let reload = reload.clone();
+            let namespace = namespace.clone();
+            let name = name.clone();
+            let api = APICaller::new(auth_state.access_token.clone());
+            wasm_bindgen_futures::spawn_local(async move {
+                match api.revoke_token(namespace, name, id).await {
+                    Ok(_) => reload.set(*reload + 1),
+                    Err(e) => load_state.set(LoadState::Error(e)),
+                }
+            });
+        })
+    };
+
+    let on_revoke_cancel = {
+        let revoke_target = revoke_target.clone();
+        Callback::from(move |_| revoke_target.set(None))
+    };
+
+    html! {
+        <div class="detail-section">
+            <h2>{ "Issued Tokens" }</h2>
+            <p class="form-help">
+                { "Only bound tokens can be revoked, unbound tokens stay valid until they expire." }
+            </p>
+
+            { match &*load_state {
+                LoadState::Loading => html! { <Loading /> },
+                LoadState::Error(e) => html! { <ErrorMessage message={e.clone()} /> },
+                LoadState::Loaded(tokens) if tokens.is_empty() => html! {
+                    <div class="empty-state">
+                        <p>{ "No active tokens." }</p>
+                    </div>
+                },
+                LoadState::Loaded(tokens) => html! {
+                    <table class="data-table">
+                        <thead>
+                            <tr>
+                                <th>{ "ID" }</th>
+                                <th>{ "Requester" }</th>
+                                <th>{ "Audience" }</th>
+                                <th>{ "Created" }</th>
+                                <th>{ "Expires" }</th>
+                                <th></th>
+                            </tr>
+                        </thead>
+                        <tbody>
+                            { for tokens.iter().map(|token| {
+                                let on_revoke = {
+                                    let revoke_target = revoke_target.clone();
+                                    let id = token.id.clone();
+                                    Callback::from(move |_| revoke_target.set(Some(id.clone())))
+                                };
+                                html! {
+                                    <tr key={token.id.clone()}>
+                                        <td><code>{ &token.id }</code></td>
+                                        <td>{ token.requester.clone().unwrap_or_else(|| "-".to_string()) }</td>
+                                        <td>{ &token.audience }</td>
+                                        <td>{ &token.created_at }</td>
+                                        <td>{ token.expire_at.clone().unwrap_or_else(|| "Never".to_string()) }</td>
+                                        <td>
+                                            { if token.bound {
+                                                html! { <button class="btn-danger-small" onclick={on_revoke}>{ "Revoke" }</button> }
+                                            } else {
+                                                html! { <span class="form-help">{ "Unbound" }</span> }
+                                            }}
+                                        </td>
+                                    </tr>
+                                }
+                            })}
+                        </tbody>
+                    </table>
+                },
+            }}
+
+            <ConfirmDialog
+                title="Revoke Token"
+                message={format!("Are you sure you want to revoke token '{}'? Clients using it will be rejected immediately.", (*revoke_target).clone().unwrap_or_default())}
+                on_confirm={on_revoke_confirm}
+                on_cancel={on_revoke_cancel}
+                show={revoke_target.is_some()}
+            />
+        </div>
+    }
+}
@@ -5,6 +5,7 @@ pub mod oauth_callback;
 pub mod resource_limits;
 pub mod secrets;
 pub mod templates;
+pub mod usage;
 
 pub use authorizations::*;
 pub use home::*;
@@ -13,3 +14,4 @@ pub use oauth_callback::*;
 pub use resource_limits::*;
 pub use secrets::*;
 pub use templates::*;
+pub use usage::*;
@@ -1,5 +1,5 @@
 use crate::api::APICaller;
-use crate::components::{ErrorMessage, FormField};
+use crate::components::{ErrorMessage, FormField, NamespaceGuardEditor, NamespaceGuards};
 use crate::models::state::AuthState;
 use crate::routes::Route;
 use crate::utils::validation::validate_name;
@@ -13,6 +13,7 @@ use yewdux::prelude::*;
 struct NamespaceFormData {
     name: String,
     labels: Vec<(String, String)>,
+    guards: NamespaceGuards,
 }
 
 #[function_component(NamespaceCreate)]
@@ -84,6 +85,15 @@ pub fn namespace_create() -> Html {
         }
     };
 
+    let on_guards_change = {
+        let form_data = form_data.clone();
+        Callback::from(move |guards: NamespaceGuards| {
+            let mut data = (*form_data).clone();
+            data.guards = guards;
+            form_data.set(data);
+        })
+    };
+
     let on_submit = {
         let form_data = form_data.clone();
         let errors = errors.clone();
@@ -128,6 +138,9 @@ pub fn namespace_create() -> Html {
                 let request = CreateNamespaceRequest {
                     name: data.name.clone(),
                     labels: labels_map,
+                    quota: None,
+                    resource_quota: data.guards.resource_quota(),
+                    default_resource_limit: data.guards.default_resource_limit.clone(),
                 };
 
                 let api = APICaller::new(auth_state.access_token.clone());
@@ -235,6 +248,11 @@ pub fn namespace_create() -> Html {
                     </button>
                 </div>
 
+                <div class="form-section">
+                    <h3>{ "Kubernetes Quota (optional)" }</h3>
+                    <NamespaceGuardEditor guards={form_data.guards.clone()} on_change={on_guards_change} />
+                </div>
+
                 <div class="form-actions">
                     <button
                         type="submit"
@@ -1,8 +1,12 @@
 use crate::api::APICaller;
-use crate::components::{ErrorMessage, Loading};
+use crate::components::{
+    ErrorMessage, Loading, NamespaceGuardEditor, NamespaceGuards, SessionQuotaEditor,
+    SessionUsageTable,
+};
 use crate::models::namespace::Namespace;
 use crate::models::state::AuthState;
 use crate::routes::Route;
+use proto_web::{SessionQuota, UpdateNamespaceRequest};
 use yew::prelude::*;
 use yew_router::prelude::*;
 use yewdux::prelude::*;
@@ -24,16 +28,28 @@ pub fn namespace_detail(props: &Props) -> Html {
     let load_state = use_state(|| LoadState::Loading);
     let name = props.name.clone();
     let (auth_state, _) = use_store::<AuthState>();
+    let is_saving_quota = use_state(|| false);
+    let quota_error = use_state(|| Option::<String>::None);
+    let guards = use_state(NamespaceGuards::default);
+    let is_saving_guards = use_state(|| false);
+    let guards_error = use_state(|| Option::<String>::None);
 
     {
         let load_state = load_state.clone();
+        let guards = guards.clone();
         let name = name.clone();
         let auth_state = auth_state.clone();
         use_effect_with(name.clone(), move |_| {
             wasm_bindgen_futures::spawn_local(async move {
                 let api = APICaller::new(auth_state.access_token.clone());
                 match api.get_namespace(&name).await {
-                    Ok(namespace) => load_state.set(LoadState::Loaded(namespace)),
+                    Ok(namespace) => {
+                        guards.set(NamespaceGuards {
+                            resource_quota: namespace.resource_quota.clone().unwrap_or_default(),
+                            default_resource_limit: namespace.default_resource_limit.clone(),
+                        });
+                        load_state.set(LoadState::Loaded(namespace));
+                    }
                     Err(e) => load_state.set(LoadState::Error(e)),
                 }
             });
@@ -41,6 +57,83 @@ pub fn namespace_detail(props: &Props) -> Html {
         });
     }
 
+    let on_save_quota = {
+        let load_state = load_state.clone();
+        let is_saving_quota = is_saving_quota.clone();
+        let quota_error = quota_error.clone();
+        let name = name.clone();
+        let auth_state = auth_state.clone();
+        Callback::from(move |quota: SessionQuota| {
+            let LoadState::Loaded(namespace) = &*load_state else {
+                return;
+            };
+            // 저장된 guard 는 그대로 두고 quota 만 교체
+            let request = UpdateNamespaceRequest {
+                name: name.clone(),
+                resource_quota: namespace.resource_quota.clone(),
+                default_resource_limit: namespace.default_resource_limit.clone(),
+                quota: Some(quota),
+            };
+            let load_state = load_state.clone();
+            let is_saving_quota = is_saving_quota.clone();
+            let quota_error = quota_error.clone();
+            let auth_state = auth_state.clone();
+            is_saving_quota.set(true);
+            wasm_bindgen_futures::spawn_local(async move {
+                let api = APICaller::new(auth_state.access_token.clone());
+                match api.update_namespace(request).await {
+                    Ok(namespace) => {
+                        quota_error.set(None);
+                        load_state.set(LoadState::Loaded(namespace));
+                    }
+                    Err(e) => quota_error.set(Some(e)),
+                }
+                is_saving_quota.set(false);
+            });
+        })
+    };
+
+    let on_guards_change = {
+        let guards = guards.clone();
+        Callback::from(move |value: NamespaceGuards| guards.set(value))
+    };
+
+    let on_save_guards = {
+        let load_state = load_state.clone();
+        let guards = guards.clone();
+        let is_saving_guards = is_saving_guards.clone();
+        let guards_error = guards_error.clone();
+        let name = name.clone();
+        let auth_state = auth_state.clone();
+        Callback::from(move |_| {
+            let LoadState::Loaded(namespace) = &*load_state else {
+                return;
+            };
+            let request = UpdateNamespaceRequest {
+                name: name.clone(),
+                resource_quota: guards.resource_quota(),
+                default_resource_limit: guards.default_resource_limit.clone(),
+                quota: Some(namespace.quota.clone()),
+            };
+            let load_state = load_state.clone();
+            let is_saving_guards = is_saving_guards.clone();
+            let guards_error = guards_error.clone();
+            let auth_state = auth_state.clone();
+            is_saving_guards.set(true);
+            wasm_bindgen_futures::spawn_local(async move {
+                let api = APICaller::new(auth_state.access_token.clone());
+                match api.update_namespace(request).await {
+                    Ok(namespace) => {
+                        guards_error.set(None);
+                        load_state.set(LoadState::Loaded(namespace));
+                    }
+                    Err(e) => guards_error.set(Some(e)),
+                }
+                is_saving_guards.set(false);
+            });
+        })
+    };
+
     html! {
         <div class="container">
             <div class="header">
@@ -105,6 +198,61 @@ pub fn namespace_detail(props: &Props) -> Html {
                         } else {
                             html! {}
                         }}
+
+                        <div class="detail-section">
+                            <h2>{ "Session Quota" }</h2>
+                            <SessionUsageTable quota={namespace.quota.clone()} usage={namespace.usage.clone()} />
+                            if let Some(error) = &*quota_error {
+                                <ErrorMessage message={error.clone()} />
+                            }
+                            <SessionQuotaEditor
+                                quota={namespace.quota.clone()}
+                                on_save={on_save_quota.clone()}
+                                saving={*is_saving_quota}
+                            />
+                        </div>
+
+                        <div class="detail-section">
+                            <h2>{ "Kubernetes Quota" }</h2>
+                            if let Some(resource_quota) = &namespace.resource_quota {
+                                <table class="data-table">
+                                    <thead>
+                                        <tr>
+                                            <th>{ "Resource" }</th>
+                                            <th>{ "Used / Hard" }</th>
+                                        </tr>
+                                    </thead>
+                                    <tbody>
+                                        { for [
+                                            ("pods", resource_quota.pods.map(|x| x.to_string())),
+                                            ("requests.cpu", resource_quota.requests_cpu.clone()),
+                                            ("requests.memory", resource_quota.requests_memory.clone()),
+                                            ("requests.ephemeral-storage", resource_quota.requests_ephemeral_storage.clone()),
+                                        ].into_iter().filter_map(|(key, hard)| Some((key, hard?))).map(|(key, hard)| html! {
+                                            <tr key={key}>
+                                                <td><code>{ key }</code></td>
+                                                <td>{ format!("{} / {}", namespace.resource_quota_used.get(key).map(String::as_str).unwrap_or("0"), hard) }</td>
+                                            </tr>
+                                        }) }
+                                    </tbody>
+                                </table>
+                            } else {
+                                <p class="form-help">{ "No ResourceQuota" }</p>
+                            }
+                            <div class="detail-field">
+                                <label>{ "Default Resource Limit:" }</label>
+                                <span>{ namespace.default_resource_limit.clone().unwrap_or_else(|| "-".to_string()) }</span>
+                            </div>
+                            if let Some(error) = &*guards_error {
+                                <ErrorMessage message={error.clone()} />
+                            }
+                            <NamespaceGuardEditor guards={(*guards).clone()} on_change={on_guards_change.clone()} />
+                            <div class="form-actions">
+                                <button class="btn-primary" onclick={on_save_guards.clone()} disabled={*is_saving_guards}>
+                                    { if *is_saving_guards { "Saving..." } else { "Save Kubernetes Quota" } }
+                                </button>
+                            </div>
+                        </div>
                     </div>
                 }
             }}
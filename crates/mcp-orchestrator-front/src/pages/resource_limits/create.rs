@@ -1,9 +1,11 @@
 use crate::api::APICaller;
-use crate::components::{ErrorMessage, FormField};
+use crate::components::{
+    ErrorMessage, FeasibilityReport, FormField, Placement, PlacementEditor, SecurityProfileEditor,
+};
 use crate::models::state::AuthState;
 use crate::routes::Route;
 use crate::utils::validation::{validate_cpu, validate_memory, validate_name};
-use proto_web::{CreateResourceLimitRequest, ResourceLimit as ProtoResourceLimit};
+use proto_web::{CheckResourceLimitResponse, CreateResourceLimitRequest, ResourceLimit as ProtoResourceLimit, SecurityProfile};
 use std::collections::HashMap;
 use yew::prelude::*;
 use yew_router::prelude::*;
@@ -19,6 +21,60 @@ struct ResourceLimitFormData {
     memory_limit: String,
     node_selector_yaml: String,
     node_affinity_yaml: String,
+    security: Option<SecurityProfile>,
+    image_pull_secrets: String,
+    placement: Placement,
+}
+
+impl ResourceLimitFormData {
+    fn to_proto(&self) -> Result<ProtoResourceLimit, String> {
+        let node_selector = if !self.node_selector_yaml.trim().is_empty() {
+            serde_yaml::from_str::<HashMap<String, String>>(&self.node_selector_yaml)
+                .map_err(|e| format!("Invalid node_selector YAML: {}", e))?
+        } else {
+            HashMap::new()
+        };
+
+        let node_affinity = if !self.node_affinity_yaml.trim().is_empty() {
+            let yaml_value = serde_yaml::from_str::<serde_json::Value>(&self.node_affinity_yaml)
+                .map_err(|e| format!("Invalid node_affinity YAML: {}", e))?;
+            let json_str = serde_json::to_string(&yaml_value)
+                .map_err(|e| format!("Failed to serialize node_affinity: {}", e))?;
+            Some(json_str)
+        } else {
+            None
+        };
+
+        Ok(ProtoResourceLimit {
+            cpu: self.cpu.clone(),
+            memory: self.memory.clone(),
+            cpu_limit: if self.cpu_limit.is_empty() {
+                None
+            } else {
+                Some(self.cpu_limit.clone())
+            },
+            memory_limit: if self.memory_limit.is_empty() {
+                None
+            } else {
+                Some(self.memory_limit.clone())
+            },
+            ephemeral_storage: None,
+            volumes: HashMap::new(),
+            node_selector,
+            node_affinity,
+            security: self.security.clone(),
+            image_pull_secrets: self
+                .image_pull_secrets
+                .split(',')
+                .map(|x| x.trim().to_string())
+                .filter(|x| !x.is_empty())
+                .collect(),
+            tolerations: self.placement.tolerations.clone(),
+            priority_class_name: self.placement.priority_class_name.clone(),
+            topology_spread: self.placement.topology_spread.clone(),
+            anti_affinity: self.placement.anti_affinity.clone(),
+        })
+    }
 }
 
 #[function_component(ResourceLimitCreate)]
@@ -27,6 +83,8 @@ pub fn resource_limit_create() -> Html {
     let errors = use_state(HashMap::<String, String>::new);
     let is_submitting = use_state(|| false);
     let submit_error = use_state(|| Option::<String>::None);
+    let feasibility = use_state(|| Option::<CheckResourceLimitResponse>::None);
+    let is_checking = use_state(|| false);
     let navigator = use_navigator().unwrap();
     let (auth_state, _) = use_store::<AuthState>();
 
@@ -216,6 +274,71 @@ pub fn resource_limit_create() -> Html {
         })
     };
 
+    let on_security_change = {
+        let form_data = form_data.clone();
+        Callback::from(move |security: Option<SecurityProfile>| {
+            let mut data = (*form_data).clone();
+            data.security = security;
+            form_data.set(data);
+        })
+    };
+
+    let on_placement_change = {
+        let form_data = form_data.clone();
+        Callback::from(move |placement: Placement| {
+            let mut data = (*form_data).clone();
+            data.placement = placement;
+            form_data.set(data);
+        })
+    };
+
+    let on_image_pull_secrets_change = {
+        let form_data = form_data.clone();
+        Callback::from(move |e: Event| {
+            let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+            let mut data = (*form_data).clone();
+            data.image_pull_secrets = input.value();
+            form_data.set(data);
+        })
+    };
+
+    let on_check = {
+        let form_data = form_data.clone();
+        let feasibility = feasibility.clone();
+        let is_checking = is_checking.clone();
+        let submit_error = submit_error.clone();
+        let auth_state = auth_state.clone();
+        Callback::from(move |_| {
+            let limits = match form_data.to_proto() {
+                Ok(limits) => limits,
+                Err(e) => {
+                    submit_error.set(Some(e));
+                    return;
+                }
+            };
+            let name = form_data.name.clone();
+            let feasibility = feasibility.clone();
+            let is_checking = is_checking.clone();
+            let submit_error = submit_error.clone();
+            let auth_state = auth_state.clone();
+            is_checking.set(true);
+            wasm_bindgen_futures::spawn_local(async move {
+                let api = APICaller::new(auth_state.access_token.clone());
+                match api.check_resource_limit(&name, Some(limits)).await {
+                    Ok(report) => {
+                        feasibility.set(Some(report));
+                        submit_error.set(None);
+                    }
+                    Err(e) => {
+                        feasibility.set(None);
+                        submit_error.set(Some(e));
+                    }
+                }
+                is_checking.set(false);
+            });
+        })
+    };
+
     let on_submit = {
         let form_data = form_data.clone();
         let errors = errors.clone();
@@ -256,62 +379,19 @@ pub fn resource_limit_create() -> Html {
             let auth_state = auth_state.clone();
 
             wasm_bindgen_futures::spawn_local(async move {
-                let node_selector = if !data.node_selector_yaml.trim().is_empty() {
-                    match serde_yaml::from_str::<HashMap<String, String>>(&data.node_selector_yaml)
-                    {
-                        Ok(map) => map,
-                        Err(e) => {
-                            submit_error.set(Some(format!("Invalid node_selector YAML: {}", e)));
-                            is_submitting.set(false);
-                            return;
-                        }
-                    }
-                } else {
-                    HashMap::new()
-                };
-
-                let node_affinity = if !data.node_affinity_yaml.trim().is_empty() {
-                    match serde_yaml::from_str::<serde_json::Value>(&data.node_affinity_yaml) {
-                        Ok(yaml_value) => match serde_json::to_string(&yaml_value) {
-                            Ok(json_str) => Some(json_str),
-                            Err(e) => {
-                                submit_error
-                                    .set(Some(format!("Failed to serialize node_affinity: {}", e)));
-                                is_submitting.set(false);
-                                return;
-                            }
-                        },
-                        Err(e) => {
-                            submit_error.set(Some(format!("Invalid node_affinity YAML: {}", e)));
-                            is_submitting.set(false);
-                            return;
-                        }
+                let limits = match data.to_proto() {
+                    Ok(limits) => limits,
+                    Err(e) => {
+                        submit_error.set(Some(e));
+                        is_submitting.set(false);
+                        return;
                     }
-                } else {
-                    None
                 };
 
                 let request = CreateResourceLimitRequest {
                     name: data.name.clone(),
                     description: data.description.clone(),
-                    limits: Some(ProtoResourceLimit {
-                        cpu: data.cpu.clone(),
-                        memory: data.memory.clone(),
-                        cpu_limit: if data.cpu_limit.is_empty() {
-                            None
-                        } else {
-                            Some(data.cpu_limit.clone())
-                        },
-                        memory_limit: if data.memory_limit.is_empty() {
-                            None
-                        } else {
-                            Some(data.memory_limit.clone())
-                        },
-                        ephemeral_storage: None,
-                        volumes: HashMap::new(),
-                        node_selector,
-                        node_affinity,
-                    }),
+                    limits: Some(limits),
                     labels: HashMap::new(),
                 };
 
@@ -460,6 +540,46 @@ pub fn resource_limit_create() -> Html {
                             style="font-family: monospace; font-size: 12px;"
                         />
                     </FormField>
+
+                    <PlacementEditor
+                        placement={form_data.placement.clone()}
+                        on_change={on_placement_change}
+                    />
+                </div>
+
+                <div class="form-section">
+                    <h3>{ "Pod Security (Optional)" }</h3>
+                    <p class="form-help">{ "Default security profile of session pods using this limit. Templates can set their own profile." }</p>
+                    <SecurityProfileEditor
+                        profile={form_data.security.clone()}
+                        on_change={on_security_change}
+                        unset_label="Not set"
+                    />
+                    <FormField label="Image Pull Secrets" error={None::<String>}>
+                        <input
+                            type="text"
+                            value={form_data.image_pull_secrets.clone()}
+                            onchange={on_image_pull_secrets_change}
+                            placeholder="ghcr-credential, harbor-credential"
+                        />
+                        <small class="form-help">{ "Comma separated Docker registry secrets, resolved in the namespace of each session" }</small>
+                    </FormField>
+                </div>
+
+                <div class="form-section">
+                    <h3>{ "Feasibility" }</h3>
+                    <p class="form-help">{ "Evaluates node selector, affinity, taints and the free capacity of every node, nothing is created" }</p>
+                    <button
+                        type="button"
+                        class="btn-secondary-small"
+                        onclick={on_check}
+                        disabled={*is_checking}
+                    >
+                        { if *is_checking { "Checking..." } else { "Check Feasibility" } }
+                    </button>
+                    { if let Some(report) = &*feasibility {
+                        html! { <FeasibilityReport report={report.clone()} /> }
+                    } else { html! {} }}
                 </div>
 
                 <div class="form-actions">
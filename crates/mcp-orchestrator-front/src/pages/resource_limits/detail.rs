@@ -1,5 +1,6 @@
 use crate::api::APICaller;
-use crate::components::{ConfirmDialog, ErrorMessage, Loading};
+use crate::components::{format_toleration, security_profile_summary, session_scope_label, ConfirmDialog, ErrorMessage, FeasibilityReport, Loading, Placement};
+use proto_web::CheckResourceLimitResponse;
 use crate::models::resource_limit::ResourceLimit;
 use crate::models::state::AuthState;
 use crate::routes::Route;
@@ -25,6 +26,8 @@ pub fn resource_limit_detail(props: &Props) -> Html {
     let show_delete_confirm = use_state(|| false);
     let is_deleting = use_state(|| false);
     let delete_error = use_state(|| Option::<String>::None);
+    let feasibility = use_state(|| Option::<Result<CheckResourceLimitResponse, String>>::None);
+    let is_checking = use_state(|| false);
     let navigator = use_navigator().unwrap();
     let (auth_state, _) = use_store::<AuthState>();
 
@@ -46,6 +49,25 @@ pub fn resource_limit_detail(props: &Props) -> Html {
         });
     }
 
+    let on_check = {
+        let feasibility = feasibility.clone();
+        let is_checking = is_checking.clone();
+        let name = name.clone();
+        let auth_state = auth_state.clone();
+        Callback::from(move |_| {
+            let feasibility = feasibility.clone();
+            let is_checking = is_checking.clone();
+            let name = name.clone();
+            let auth_state = auth_state.clone();
+            is_checking.set(true);
+            wasm_bindgen_futures::spawn_local(async move {
+                let api = APICaller::new(auth_state.access_token.clone());
+                feasibility.set(Some(api.check_resource_limit(&name, None).await));
+                is_checking.set(false);
+            });
+        })
+    };
+
     let on_delete_click = {
         let show_delete_confirm = show_delete_confirm.clone();
         Callback::from(move |_| {
@@ -198,6 +220,35 @@ pub fn resource_limit_detail(props: &Props) -> Html {
                             html! {}
                         }}
 
+                        { if let Some(security) = &limit.limits.security {
+                            html! {
+                                <div class="detail-section">
+                                    <h2>{ "Pod Security" }</h2>
+                                    <div class="detail-field">
+                                        <label>{ "Security Profile:" }</label>
+                                        <span>{ security_profile_summary(security) }</span>
+                                    </div>
+                                </div>
+                            }
+                        } else {
+                            html! {}
+                        }}
+
+                        { if !limit.limits.image_pull_secrets.is_empty() {
+                            html! {
+                                <div class="detail-section">
+                                    <h2>{ "Image Pull Secrets" }</h2>
+                                    <div class="tags">
+                                        { for limit.limits.image_pull_secrets.iter().map(|name| html! {
+                                            <span class="tag" key={name.clone()}>{ name }</span>
+                                        }) }
+                                    </div>
+                                </div>
+                            }
+                        } else {
+                            html! {}
+                        }}
+
                         { if limit.limits.node_selector.is_some() || limit.limits.node_affinity.is_some() {
                             html! {
                                 <div class="detail-section">
@@ -232,6 +283,80 @@ pub fn resource_limit_detail(props: &Props) -> Html {
                             html! {}
                         }}
 
+                        { if limit.limits.placement != Placement::default() {
+                            let placement = &limit.limits.placement;
+                            html! {
+                                <div class="detail-section">
+                                    <h2>{ "Placement" }</h2>
+                                    { if let Some(priority_class_name) = &placement.priority_class_name {
+                                        html! {
+                                            <div class="detail-field">
+                                                <label>{ "Priority Class:" }</label>
+                                                <span>{ priority_class_name }</span>
+                                            </div>
+                                        }
+                                    } else {
+                                        html! {}
+                                    }}
+                                    { if !placement.tolerations.is_empty() {
+                                        html! {
+                                            <div class="detail-field">
+                                                <label>{ "Tolerations:" }</label>
+                                                <div class="tags">
+                                                    { for placement.tolerations.iter().map(|toleration| html! {
+                                                        <span class="tag">{ format_toleration(toleration) }</span>
+                                                    }) }
+                                                </div>
+                                            </div>
+                                        }
+                                    } else {
+                                        html! {}
+                                    }}
+                                    { for placement.topology_spread.iter().map(|spread| html! {
+                                        <div class="detail-field">
+                                            <label>{ "Topology Spread:" }</label>
+                                            <span>{ format!(
+                                                "{} by {}, max skew {}{}",
+                                                session_scope_label(spread.scope()),
+                                                spread.topology_key,
+                                                spread.max_skew,
+                                                if spread.schedule_anyway { " (best effort)" } else { "" }
+                                            ) }</span>
+                                        </div>
+                                    }) }
+                                    { for placement.anti_affinity.iter().map(|anti_affinity| html! {
+                                        <div class="detail-field">
+                                            <label>{ "Anti-Affinity:" }</label>
+                                            <span>{ format!(
+                                                "{} apart by {}, {}",
+                                                session_scope_label(anti_affinity.scope()),
+                                                if anti_affinity.topology_key.is_empty() { "kubernetes.io/hostname" } else { anti_affinity.topology_key.as_str() },
+                                                if anti_affinity.required { "required".to_string() } else { format!("preferred, weight {}", anti_affinity.weight.unwrap_or(100)) }
+                                            ) }</span>
+                                        </div>
+                                    }) }
+                                </div>
+                            }
+                        } else {
+                            html! {}
+                        }}
+
+                        <div class="detail-section">
+                            <h2>{ "Feasibility" }</h2>
+                            <button
+                                class="btn-secondary-small"
+                                onclick={on_check.clone()}
+                                disabled={*is_checking}
+                            >
+                                { if *is_checking { "Checking..." } else { "Check Feasibility" } }
+                            </button>
+                            { match &*feasibility {
+                                Some(Ok(report)) => html! { <FeasibilityReport report={report.clone()} /> },
+                                Some(Err(e)) => html! { <ErrorMessage message={e.clone()} /> },
+                                None => html! {},
+                            }}
+                        </div>
+
                         { if !limit.labels.is_empty() {
                             html! {
                                 <div class="detail-section">
@@ -4,7 +4,7 @@ use crate::models::state::AuthState;
 use crate::models::SessionState;
 use crate::routes::Route;
 use crate::utils::validation::validate_name;
-use proto_web::CreateSecretRequest;
+use proto_web::{CreateSecretRequest, RegistryCredential, SecretType};
 use std::collections::HashMap;
 use yew::prelude::*;
 use yew_router::prelude::*;
@@ -13,6 +13,8 @@ use yewdux::prelude::*;
 #[derive(Default, Clone, PartialEq)]
 struct SecretFormData {
     name: String,
+    secret_type: SecretType,
+    registry: RegistryCredential,
     data: Vec<(String, String)>,
     labels: Vec<(String, String)>,
 }
@@ -90,6 +92,31 @@ pub fn secret_create() -> Html {
         }
     };
 
+    let on_type_change = {
+        let form_data = form_data.clone();
+        let errors = errors.clone();
+        Callback::from(move |e: Event| {
+            let select: web_sys::HtmlSelectElement = e.target_unchecked_into();
+            let mut data = (*form_data).clone();
+            data.secret_type = SecretType::from_str_name(&select.value()).unwrap_or_default();
+            form_data.set(data);
+
+            let mut new_errors = (*errors).clone();
+            new_errors.remove("data");
+            errors.set(new_errors);
+        })
+    };
+
+    let registry_field = |f: fn(&mut RegistryCredential, String)| {
+        let form_data = form_data.clone();
+        Callback::from(move |e: Event| {
+            let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+            let mut data = (*form_data).clone();
+            f(&mut data.registry, input.value());
+            form_data.set(data);
+        })
+    };
+
     let on_submit = {
         let form_data = form_data.clone();
         let errors = errors.clone();
@@ -112,7 +139,18 @@ pub fn secret_create() -> Html {
             if let Some(error) = validate_name(&data.name) {
                 validation_errors.insert("name".to_string(), error);
             }
-            if data.data.is_empty() {
+            let is_registry = data.secret_type == SecretType::DockerConfigJson;
+            if is_registry {
+                if data.registry.server.is_empty()
+                    || data.registry.username.is_empty()
+                    || data.registry.password.is_empty()
+                {
+                    validation_errors.insert(
+                        "data".to_string(),
+                        "Registry server, username and password are required".to_string(),
+                    );
+                }
+            } else if data.data.is_empty() {
                 validation_errors.insert(
                     "data".to_string(),
                     "At least one key-value pair is required".to_string(),
@@ -132,6 +170,10 @@ pub fn secret_create() -> Html {
             let auth_state = auth_state.clone();
 
             wasm_bindgen_futures::spawn_local(async move {
+                let registry = is_registry.then(|| RegistryCredential {
+                    email: data.registry.email.clone().filter(|x| !x.is_empty()),
+                    ..data.registry.clone()
+                });
                 let data_map: HashMap<String, String> = data
                     .data
                     .into_iter()
@@ -148,7 +190,9 @@ pub fn secret_create() -> Html {
                     namespace: Some(namespace_value.clone()),
                     name: data.name.clone(),
                     labels: labels_map,
-                    data: data_map,
+                    data: if is_registry { HashMap::new() } else { data_map },
+                    r#type: data.secret_type as i32,
+                    registry,
                 };
 
                 let api = APICaller::new(auth_state.access_token.clone());
@@ -203,6 +247,54 @@ pub fn secret_create() -> Html {
                     <small class="form-help">{ "Lowercase alphanumeric and hyphens only" }</small>
                 </FormField>
 
+                <FormField label="Type" error={None::<String>}>
+                    <select onchange={on_type_change}>
+                        <option value={SecretType::Opaque.as_str_name()} selected={form_data.secret_type == SecretType::Opaque}>{ "Opaque" }</option>
+                        <option value={SecretType::DockerConfigJson.as_str_name()} selected={form_data.secret_type == SecretType::DockerConfigJson}>{ "Docker registry" }</option>
+                        <option value={SecretType::Tls.as_str_name()} selected={form_data.secret_type == SecretType::Tls}>{ "TLS" }</option>
+                        <option value={SecretType::BasicAuth.as_str_name()} selected={form_data.secret_type == SecretType::BasicAuth}>{ "Basic auth" }</option>
+                    </select>
+                    <small class="form-help">{ match form_data.secret_type {
+                        SecretType::Opaque => "Arbitrary key-value pairs",
+                        SecretType::DockerConfigJson => "Registry credential, usable as an image pull secret of templates",
+                        SecretType::Tls => "Requires PEM encoded tls.crt and tls.key",
+                        SecretType::BasicAuth => "Requires username or password",
+                    }}</small>
+                </FormField>
+
+                if form_data.secret_type == SecretType::DockerConfigJson {
+                <div class="form-section">
+                    <label class="section-label">{ "Registry Credential *" }</label>
+                    <input
+                        type="text"
+                        value={form_data.registry.server.clone()}
+                        onchange={registry_field(|r, v| r.server = v)}
+                        placeholder="ghcr.io"
+                    />
+                    <input
+                        type="text"
+                        value={form_data.registry.username.clone()}
+                        onchange={registry_field(|r, v| r.username = v)}
+                        placeholder="username"
+                    />
+                    <input
+                        type="password"
+                        value={form_data.registry.password.clone()}
+                        onchange={registry_field(|r, v| r.password = v)}
+                        placeholder="password or token (hidden)"
+                    />
+                    <input
+                        type="text"
+                        value={form_data.registry.email.clone().unwrap_or_default()}
+                        onchange={registry_field(|r, v| r.email = Some(v))}
+                        placeholder="email (optional)"
+                    />
+
+                    { if let Some(error) = errors.get("data") {
+                        html! { <p class="error-text">{ error }</p> }
+                    } else { html! {} }}
+                </div>
+                } else {
                 <div class="form-section">
                     <label class="section-label">{ "Secret Data * (values will be encrypted)" }</label>
                     <small class="form-help">{ "Add key-value pairs for sensitive data" }</small>
@@ -267,6 +359,7 @@ pub fn secret_create() -> Html {
                         html! { <p class="error-text">{ error }</p> }
                     } else { html! {} }}
                 </div>
+                }
 
                 <div class="form-actions">
                     <button
@@ -3,6 +3,7 @@ use crate::components::{ConfirmDialog, ErrorMessage, Loading};
 use crate::models::secret::Secret;
 use crate::models::state::AuthState;
 use crate::routes::Route;
+use proto_web::SecretType;
 use yew::prelude::*;
 use yew_router::prelude::*;
 use yewdux::prelude::*;
@@ -139,6 +140,15 @@ pub fn secret_detail(props: &Props) -> Html {
                                     <label>{ "Namespace:" }</label>
                                     <span>{ &secret.namespace }</span>
                                 </div>
+                                <div class="detail-field">
+                                    <label>{ "Type:" }</label>
+                                    <span>{ match secret.secret_type {
+                                        SecretType::Opaque => "Opaque",
+                                        SecretType::DockerConfigJson => "Docker registry",
+                                        SecretType::Tls => "TLS",
+                                        SecretType::BasicAuth => "Basic auth",
+                                    }}</span>
+                                </div>
                                 <div class="detail-field">
                                     <label>{ "Created At:" }</label>
                                     <span>{ &secret.created_at }</span>
@@ -2,8 +2,10 @@ mod create;
 mod detail;
 mod list;
 mod update;
+mod user;
 
 pub use create::SecretCreate;
 pub use detail::SecretDetail;
 pub use list::SecretList;
 pub use update::SecretUpdate;
+pub use user::UserSecretList;
@@ -0,0 +1,328 @@
+use crate::api::APICaller;
+use crate::components::{ConfirmDialog, ErrorMessage, FormField, Loading, NamespaceSelector};
+use crate::models::secret::UserSecret;
+use crate::models::state::AuthState;
+use crate::models::SessionState;
+use crate::utils::validation::validate_name;
+use std::collections::HashMap;
+use yew::prelude::*;
+use yewdux::prelude::*;
+
+#[derive(Debug)]
+enum LoadState {
+    Loading,
+    Loaded(Vec<UserSecret>),
+    Error(String),
+}
+
+#[derive(Default, Clone, PartialEq)]
+struct UserSecretFormData {
+    name: String,
+    data: Vec<(String, String)>,
+}
+
+#[function_component(UserSecretList)]
+pub fn user_secret_list() -> Html {
+    let load_state = use_state(|| LoadState::Loading);
+    let reload = use_state(|| 0u32);
+    let form_data = use_state(UserSecretFormData::default);
+    let form_error = use_state(|| Option::<String>::None);
+    let is_submitting = use_state(|| false);
+    let delete_target = use_state(|| Option::<String>::None);
+    let (session_state, _) = use_store::<SessionState>();
+    let (auth_state, _) = use_store::<AuthState>();
+    let namespace = session_state.selected_namespace.clone();
+
+    {
+        let load_state = load_state.clone();
+        let api = APICaller::new(auth_state.access_token.clone());
+        use_effect_with((namespace.clone(), *reload), move |(ns, _)| {
+            if let Some(namespace) = ns {
+                let load_state = load_state.clone();
+                let namespace = namespace.clone();
+                wasm_bindgen_futures::spawn_local(async move {
+                    match api.list_user_secrets(&namespace).await {
+                        Ok(secrets) => load_state.set(LoadState::Loaded(secrets)),
+                        Err(e) => load_state.set(LoadState::Error(e)),
+                    }
+                });
+            } else {
+                load_state.set(LoadState::Error(
+                    "Please select a namespace first".to_string(),
+                ));
+            }
+            || ()
+        });
+    }
+
+    let on_name_change = {
+        let form_data = form_data.clone();
+        Callback::from(move |e: Event| {
+            let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+            let mut data = (*form_data).clone();
+            data.name = input.value();
+            form_data.set(data);
+        })
+    };
+
+    let on_add_data = {
+        let form_data = form_data.clone();
+        Callback::from(move |_| {
+            let mut data = (*form_data).clone();
+            data.data.push(("".to_string(), "".to_string()));
+            form_data.set(data);
+        })
+    };
+
+    let on_data_change = {
+        let form_data = form_data.clone();
+        move |index: usize, is_key: bool, value: String| {
+            let mut data = (*form_data).clone();
+            if let Some(item) = data.data.get_mut(index) {
+                if is_key {
+                    item.0 = value;
+                } else {
+                    item.1 = value;
+                }
+            }
+            form_data.set(data);
+        }
+    };
+
+    let on_remove_data = {
+        let form_data = form_data.clone();
+        move |index: usize| {
+            let mut data = (*form_data).clone();
+            data.data.remove(index);
+            form_data.set(data);
+        }
+    };
+
+    let on_submit = {
+        let form_data = form_data.clone();
+        let form_error = form_error.clone();
+        let is_submitting = is_submitting.clone();
+        let reload = reload.clone();
+        let namespace = namespace.clone();
+        let auth_state = auth_state.clone();
+        Callback::from(move |e: SubmitEvent| {
+            e.prevent_default();
+            let Some(namespace) = namespace.clone() else {
+                return;
+            };
+            let data = (*form_data).clone();
+            if let Some(error) = validate_name(&data.name) {
+                form_error.set(Some(error));
+                return;
+            }
+            let data_map: HashMap<String, String> = data
+                .data
+                .into_iter()
+                .filter(|(k, v)| !k.is_empty() && !v.is_empty())
+                .collect();
+            if data_map.is_empty() {
+                form_error.set(Some("At least one key-value pair is required".to_string()));
+                return;
+            }
+
+            is_submitting.set(true);
+            let form_data = form_data.clone();
+            let form_error = form_error.clone();
+            let is_submitting = is_submitting.clone();
+            let reload = reload.clone();
+            let api = APICaller::new(auth_state.access_token.clone());
+            wasm_bindgen_futures::spawn_local(async move {
+                match api.put_user_secret(&namespace, &data.name, data_map).await {
+                    Ok(_) => {
+                        form_data.set(UserSecretFormData::default());
+                        form_error.set(None);
+                        reload.set(*reload + 1);
+                    }
+                    Err(e) => form_error.set(Some(e)),
+                }
+                is_submitting.set(false);
+            });
+        })
+    };
+
+    let on_delete_confirm = {
+        let delete_target = delete_target.clone();
+        let load_state = load_state.clone();
+        let reload = reload.clone();
+        let namespace = namespace.clone();
+        let auth_state = auth_state.clone();
+        Callback::from(move |_| {
+            let (Some(name), Some(namespace)) = ((*delete_target).clone(), namespace.clone())
+            else {
+                return;
+            };
+            delete_target.set(None);
+            let load_state = load_state.clone();
+            let reload = reload.clone();
+            let api = APICaller::new(auth_state.access_token.clone());
+            wasm_bindgen_futures::spawn_local(async move {
+                match api.delete_user_secret(&namespace, &name).await {
+                    Ok(_) => reload.set(*reload + 1),
+                    Err(e) => load_state.set(LoadState::Error(e)),
+                }
+            });
+        })
+    };
+
+    let on_delete_cancel = {
+        let delete_target = delete_target.clone();
+        Callback::from(move |_| delete_target.set(None))
+    };
+
+    html! {
+        <div class="container">
+            <NamespaceSelector />
+
+            <div class="header">
+                <h1>{ "My Secrets" }</h1>
+            </div>
+            <p class="form-help">
+                { "Secrets only visible to your account. Templates reference them as " }
+                <code>{ "user:<name>" }</code>
+                { " and sessions you open read the values from here." }
+            </p>
+
+            { match &*load_state {
+                LoadState::Loading => html! { <Loading /> },
+                LoadState::Error(e) => html! { <ErrorMessage message={e.clone()} /> },
+                LoadState::Loaded(secrets) => html! {
+                    <div class="secret-list">
+                        { if secrets.is_empty() {
+                            html! {
+                                <div class="empty-state">
+                                    <p>{ "No personal secrets in this namespace yet." }</p>
+                                </div>
+                            }
+                        } else {
+                            html! {
+                                <div class="grid">
+                                    { for secrets.iter().map(|secret| {
+                                        let on_delete = {
+                                            let delete_target = delete_target.clone();
+                                            let name = secret.name.clone();
+                                            Callback::from(move |_| delete_target.set(Some(name.clone())))
+                                        };
+                                        html! {
+                                            <div class="card" key={secret.name.clone()}>
+                                                <div class="card-header">
+                                                    <h3>{ &secret.name }</h3>
+                                                    <code>{ format!("user:{}", secret.name) }</code>
+                                                </div>
+                                                <div class="card-body">
+                                                    <div class="field">
+                                                        <label>{ "Keys:" }</label>
+                                                        <div class="tags">
+                                                            { for secret.keys.iter().map(|key| {
+                                                                html! { <span class="tag" key={key.clone()}>{ key }</span> }
+                                                            })}
+                                                        </div>
+                                                    </div>
+                                                    <div class="field">
+                                                        <label>{ "Created:" }</label>
+                                                        <span>{ &secret.created_at }</span>
+                                                    </div>
+                                                </div>
+                                                <div class="card-footer">
+                                                    <button class="btn-danger" onclick={on_delete}>{ "Delete" }</button>
+                                                </div>
+                                            </div>
+                                        }
+                                    }) }
+                                </div>
+                            }
+                        }}
+                    </div>
+                }
+            }}
+
+            if namespace.is_some() {
+            <form onsubmit={on_submit} class="form">
+                <h2>{ "Set Secret" }</h2>
+                { if let Some(error) = &*form_error {
+                    html! { <ErrorMessage message={error.clone()} /> }
+                } else { html! {} }}
+
+                <FormField label="Secret Name *" error={None::<String>}>
+                    <input
+                        type="text"
+                        value={form_data.name.clone()}
+                        onchange={on_name_change}
+                        required={true}
+                        placeholder="github"
+                    />
+                    <small class="form-help">{ "An existing secret with the same name is replaced" }</small>
+                </FormField>
+
+                <div class="form-section">
+                    <label class="section-label">{ "Secret Data *" }</label>
+                    { for form_data.data.iter().enumerate().map(|(index, (key, value))| {
+                        let on_key_change = {
+                            let on_data_change = on_data_change.clone();
+                            Callback::from(move |e: Event| {
+                                let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+                                on_data_change(index, true, input.value());
+                            })
+                        };
+                        let on_value_change = {
+                            let on_data_change = on_data_change.clone();
+                            Callback::from(move |e: Event| {
+                                let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+                                on_data_change(index, false, input.value());
+                            })
+                        };
+                        let on_remove = {
+                            let on_remove_data = on_remove_data.clone();
+                            Callback::from(move |_| on_remove_data(index))
+                        };
+
+                        html! {
+                            <div class="label-row" key={index}>
+                                <input
+                                    type="text"
+                                    value={key.clone()}
+                                    onchange={on_key_change}
+                                    placeholder="key"
+                                    class="label-key"
+                                />
+                                <span>{ "=" }</span>
+                                <input
+                                    type="password"
+                                    value={value.clone()}
+                                    onchange={on_value_change}
+                                    placeholder="value (hidden)"
+                                    class="label-value"
+                                />
+                                <button type="button" onclick={on_remove} class="btn-danger-small">
+                                    { "×" }
+                                </button>
+                            </div>
+                        }
+                    })}
+                    <button type="button" onclick={on_add_data} class="btn-secondary-small">
+                        { "+ Add Key-Value Pair" }
+                    </button>
+                </div>
+
+                <div class="form-actions">
+                    <button type="submit" class="btn-primary" disabled={*is_submitting}>
+                        { if *is_submitting { "Saving..." } else { "Save Secret" } }
+                    </button>
+                </div>
+            </form>
+            }
+
+            <ConfirmDialog
+                title="Delete Secret"
+                message={format!("Are you sure you want to delete your secret '{}'? Sessions of templates using it will fail to start.", (*delete_target).clone().unwrap_or_default())}
+                on_confirm={on_delete_confirm}
+                on_cancel={on_delete_cancel}
+                show={delete_target.is_some()}
+            />
+        </div>
+    }
+}
@@ -1,9 +1,10 @@
 use crate::api::APICaller;
-use crate::components::{ConfirmDialog, CopyConfigDialog, ErrorMessage, Loading};
+use crate::components::{format_egress_ports, format_egress_target, security_profile_summary, ConfirmDialog, CopyConfigDialog, ErrorMessage, Loading};
 use crate::models::authorization::Authorization;
 use crate::models::state::AuthState;
-use crate::models::template::Template;
+use crate::models::template::{arg_env_example, Template};
 use crate::routes::Route;
+use proto_web::{ArgEnvType, EgressPolicyType, PackageRegistry, SecretRotationPolicy};
 use yew::prelude::*;
 use yew_router::prelude::*;
 use yewdux::prelude::*;
@@ -24,7 +25,7 @@ enum LoadState {
 #[function_component(TemplateDetail)]
 pub fn template_detail(props: &TemplateDetailProps) -> Html {
     let load_state = use_state(|| LoadState::Loading);
-    let authorization_state = use_state(|| Option::<Authorization>::None);
+    let authorization_state = use_state(Vec::<Authorization>::new);
     let show_delete_dialog = use_state(|| false);
     let show_copy_dialog = use_state(|| false);
     let copy_config = use_state(|| String::new());
@@ -43,18 +44,17 @@ pub fn template_detail(props: &TemplateDetailProps) -> Html {
                 let api = APICaller::new(auth_state.access_token.clone());
                 match api.get_template(&namespace, &name).await {
                     Ok(template) => {
-                        // Load authorization if specified
-                        if let Some(auth_name) = &template.authorization_name {
-                            if !auth_name.is_empty() {
-                                match api.get_authorization(template.namespace.clone(), auth_name.clone()).await {
-                                    Ok(auth) => authorization_state.set(Some(auth)),
-                                    Err(e) => {
-                                        web_sys::console::error_1(&format!("Failed to load authorization: {}", e).into());
-                                        authorization_state.set(None);
-                                    }
+                        // Load authorizations if specified
+                        let mut authorizations = Vec::new();
+                        for auth_name in &template.authorization_names {
+                            match api.get_authorization(template.namespace.clone(), auth_name.clone()).await {
+                                Ok(auth) => authorizations.push(auth),
+                                Err(e) => {
+                                    web_sys::console::error_1(&format!("Failed to load authorization: {}", e).into());
                                 }
                             }
                         }
+                        authorization_state.set(authorizations);
                         load_state.set(LoadState::Loaded(Box::new(template)));
                     }
                     Err(e) => load_state.set(LoadState::Error(e)),
@@ -102,35 +102,28 @@ pub fn template_detail(props: &TemplateDetailProps) -> Html {
         })
     };
 
-    let generate_mcp_config = |template: &Template, authorization: &Option<Authorization>| -> String {
+    let generate_mcp_config = |template: &Template, authorizations: &[Authorization]| -> String {
         let current_origin = web_sys::window()
             .and_then(|w| w.location().origin().ok())
             .unwrap_or_else(|| "http://localhost:8080".to_string());
         
         let url = format!("{}/mcp/{}/{}", current_origin, template.namespace, template.name);
         
-        let has_arg_envs = !template.arg_envs.is_empty();
-        let has_k8s_sa_auth = authorization
-            .as_ref()
-            .map(|auth| auth.auth_type == 1) // KUBERNETES_SERVICE_ACCOUNT = 1
-            .unwrap_or(false);
+        let has_arg_envs = !template.arg_env_specs.is_empty();
+        // Every type except ANONYMOUS = 0 expects a bearer token
+        let has_token_auth = authorizations.iter().any(|auth| auth.auth_type != 0);
         
         let mut headers = Vec::new();
         
         // Add arg-env headers
         if has_arg_envs {
-            for (key, value) in &template.arg_envs {
-                let type_example = if value.contains("?") || value.ends_with("?") {
-                    "\"value\" or null"
-                } else {
-                    "\"value\""
-                };
-                headers.push(format!("    \"arg-{}\": {}", key, type_example));
+            for (key, spec) in &template.arg_env_specs {
+                headers.push(format!("    \"arg-{}\": {}", key, arg_env_example(spec)));
             }
         }
         
-        // Add Authorization header if K8s SA
-        if has_k8s_sa_auth {
+        // Add Authorization header if a token is required
+        if has_token_auth {
             headers.push(format!("    \"Authorization\": \"Bearer <token>\""));
         }
         
@@ -172,7 +165,7 @@ r#"{{
         let copy_config = copy_config.clone();
         let authorization_state = authorization_state.clone();
         move |template: &Template| {
-            let config = generate_mcp_config(template, &*authorization_state);
+            let config = generate_mcp_config(template, &authorization_state);
             copy_config.set(config);
             show_copy_dialog.set(true);
         }
@@ -240,6 +233,17 @@ r#"{{
                                         <label>{ "Image:" }</label>
                                         <code>{ &template.image }</code>
                                     </div>
+                                    { if let Some(package) = &template.package {
+                                        html! {
+                                            <div class="field">
+                                                <label>{ "Package:" }</label>
+                                                <code>{ format!("{} {}@{}", if package.registry() == PackageRegistry::Npm { "npm" } else { "pypi" }, package.name, package.version) }</code>
+                                                { if let Some(cache_volume) = &package.cache_volume {
+                                                    html! { <span>{ format!(" (cache: {})", cache_volume) }</span> }
+                                                } else { html! {} }}
+                                            </div>
+                                        }
+                                    } else { html! {} }}
                                     <div class="field">
                                         <label>{ "Created At:" }</label>
                                         <span>{ &template.created_at }</span>
@@ -297,7 +301,7 @@ r#"{{
                                 }
                             } else { html! {} }}
 
-                            { if !template.arg_envs.is_empty() {
+                            { if !template.arg_env_specs.is_empty() {
                                 html! {
                                     <section class="detail-section">
                                         <h2>{ "Argument Environment Variables" }</h2>
@@ -305,15 +309,38 @@ r#"{{
                                             <thead>
                                                 <tr>
                                                     <th>{ "Key" }</th>
-                                                    <th>{ "Value" }</th>
+                                                    <th>{ "Env Name" }</th>
+                                                    <th>{ "Type" }</th>
+                                                    <th>{ "Required" }</th>
+                                                    <th>{ "Default" }</th>
+                                                    <th>{ "Description" }</th>
                                                 </tr>
                                             </thead>
                                             <tbody>
-                                                { for template.arg_envs.iter().map(|(key, value)| {
+                                                { for template.arg_env_specs.iter().map(|(key, spec)| {
+                                                    let arg_type = match spec.r#type() {
+                                                        ArgEnvType::Enum => format!("enum [{}]", spec.allowed_values.join(", ")),
+                                                        ArgEnvType::String => match &spec.pattern {
+                                                            Some(pattern) => format!("string /{}/", pattern),
+                                                            None => "string".to_string(),
+                                                        },
+                                                        ArgEnvType::Int => "int".to_string(),
+                                                        ArgEnvType::Bool => "bool".to_string(),
+                                                        ArgEnvType::Url => "url".to_string(),
+                                                    };
+                                                    let default_value = match &spec.default_value {
+                                                        Some(_) if spec.secret => "••••••".to_string(),
+                                                        Some(value) => value.clone(),
+                                                        None => "-".to_string(),
+                                                    };
                                                     html! {
                                                         <tr key={key.clone()}>
                                                             <td><code>{ key }</code></td>
-                                                            <td><code>{ value }</code></td>
+                                                            <td><code>{ spec.env_name.clone().unwrap_or_else(|| key.clone()) }</code></td>
+                                                            <td>{ arg_type }</td>
+                                                            <td>{ if spec.optional { "No" } else { "Yes" } }</td>
+                                                            <td><code>{ default_value }</code></td>
+                                                            <td>{ spec.description.clone().unwrap_or_default() }</td>
                                                         </tr>
                                                     }
                                                 }) }
@@ -338,6 +365,209 @@ r#"{{
                                 }
                             } else { html! {} }}
 
+                            { if !template.volume_mounts.is_empty() || !template.secret_mounts.is_empty() {
+                                html! {
+                                    <section class="detail-section">
+                                        <h2>{ "Mounts" }</h2>
+                                        <table class="data-table">
+                                            <thead>
+                                                <tr>
+                                                    <th>{ "Type" }</th>
+                                                    <th>{ "Source" }</th>
+                                                    <th>{ "Mount Path" }</th>
+                                                </tr>
+                                            </thead>
+                                            <tbody>
+                                                { for template.volume_mounts.iter().map(|mount| {
+                                                    html! {
+                                                        <tr key={format!("volume-{}", mount.mount_path)}>
+                                                            <td>{ "Volume" }</td>
+                                                            <td><code>{ &mount.pattern }</code></td>
+                                                            <td><code>{ &mount.mount_path }</code></td>
+                                                        </tr>
+                                                    }
+                                                }) }
+                                                { for template.secret_mounts.iter().map(|mount| {
+                                                    html! {
+                                                        <tr key={format!("secret-{}", mount.mount_path)}>
+                                                            <td>{ "Secret" }</td>
+                                                            <td><code>{ &mount.name }</code></td>
+                                                            <td><code>{ &mount.mount_path }</code></td>
+                                                        </tr>
+                                                    }
+                                                }) }
+                                            </tbody>
+                                        </table>
+                                    </section>
+                                }
+                            } else { html! {} }}
+
+                            { if !template.init_containers.is_empty() || !template.sidecars.is_empty() {
+                                html! {
+                                    <section class="detail-section">
+                                        <h2>{ "Containers" }</h2>
+                                        <table class="data-table">
+                                            <thead>
+                                                <tr>
+                                                    <th>{ "Type" }</th>
+                                                    <th>{ "Name" }</th>
+                                                    <th>{ "Image" }</th>
+                                                    <th>{ "Command" }</th>
+                                                    <th>{ "Resources" }</th>
+                                                </tr>
+                                            </thead>
+                                            <tbody>
+                                                { for template.init_containers.iter().map(|c| ("Init", c))
+                                                    .chain(template.sidecars.iter().map(|c| ("Sidecar", c)))
+                                                    .map(|(kind, container)| {
+                                                    html! {
+                                                        <tr key={container.name.clone()}>
+                                                            <td>{ kind }</td>
+                                                            <td><code>{ &container.name }</code></td>
+                                                            <td><code>{ &container.image }</code></td>
+                                                            <td><code>{ container.command.iter().chain(container.args.iter()).cloned().collect::<Vec<_>>().join(" ") }</code></td>
+                                                            <td>{ format!("{}%", container.resource_percent.unwrap_or(100)) }</td>
+                                                        </tr>
+                                                    }
+                                                }) }
+                                            </tbody>
+                                        </table>
+                                    </section>
+                                }
+                            } else { html! {} }}
+
+                            { if let Some(security) = &template.security {
+                                html! {
+                                    <section class="detail-section">
+                                        <h2>{ "Pod Security" }</h2>
+                                        <div class="detail-field">
+                                            <label>{ "Security Profile:" }</label>
+                                            <span>{ security_profile_summary(security) }</span>
+                                        </div>
+                                    </section>
+                                }
+                            } else { html! {} }}
+
+                            { if let Some(egress) = template.egress.as_ref().filter(|x| x.r#type() != EgressPolicyType::Unrestricted) {
+                                html! {
+                                    <section class="detail-section">
+                                        <h2>{ "Egress Policy" }</h2>
+                                        <div class="detail-field">
+                                            <label>{ "Policy:" }</label>
+                                            <span>{ match egress.r#type() {
+                                                EgressPolicyType::DenyAll => "Deny all",
+                                                EgressPolicyType::DnsOnly => "DNS only",
+                                                EgressPolicyType::Allowlist => "DNS and the destinations below",
+                                                EgressPolicyType::Unrestricted => "Unrestricted",
+                                            } }</span>
+                                        </div>
+                                        { if !egress.rules.is_empty() {
+                                            html! {
+                                                <table class="data-table">
+                                                    <thead>
+                                                        <tr>
+                                                            <th>{ "Destination" }</th>
+                                                            <th>{ "Ports" }</th>
+                                                        </tr>
+                                                    </thead>
+                                                    <tbody>
+                                                        { for egress.rules.iter().map(|rule| html! {
+                                                            <tr>
+                                                                <td><code>{ format_egress_target(rule) }</code></td>
+                                                                <td>{ if rule.ports.is_empty() { "all".to_string() } else { format_egress_ports(&rule.ports) } }</td>
+                                                            </tr>
+                                                        }) }
+                                                    </tbody>
+                                                </table>
+                                            }
+                                        } else { html! {} }}
+                                    </section>
+                                }
+                            } else { html! {} }}
+
+                            { if !template.image_pull_secrets.is_empty() {
+                                html! {
+                                    <section class="detail-section">
+                                        <h2>{ "Image Pull Secrets" }</h2>
+                                        <div class="tags">
+                                            { for template.image_pull_secrets.iter().map(|name| html! {
+                                                <span class="tag" key={name.clone()}>{ name }</span>
+                                            }) }
+                                        </div>
+                                    </section>
+                                }
+                            } else { html! {} }}
+
+                            { if template.secret_rotation != SecretRotationPolicy::None {
+                                html! {
+                                    <section class="detail-section">
+                                        <h2>{ "Secret Rotation" }</h2>
+                                        <div class="detail-field">
+                                            <label>{ "Policy:" }</label>
+                                            <span>{ match template.secret_rotation {
+                                                SecretRotationPolicy::Recycle => "Recycle sessions after their in-flight calls",
+                                                SecretRotationPolicy::MarkStale => "Mark sessions stale and notify clients",
+                                                SecretRotationPolicy::None => "Leave running sessions alone",
+                                            } }</span>
+                                        </div>
+                                    </section>
+                                }
+                            } else { html! {} }}
+
+                            { if !template.claim_envs.is_empty() {
+                                html! {
+                                    <section class="detail-section">
+                                        <h2>{ "Identity Claim Environment Variables" }</h2>
+                                        <table class="data-table">
+                                            <thead>
+                                                <tr>
+                                                    <th>{ "Env Name" }</th>
+                                                    <th>{ "Claim" }</th>
+                                                </tr>
+                                            </thead>
+                                            <tbody>
+                                                { for template.claim_envs.iter().map(|(env_name, path)| {
+                                                    html! {
+                                                        <tr key={env_name.clone()}>
+                                                            <td><code>{ env_name }</code></td>
+                                                            <td><code>{ path }</code></td>
+                                                        </tr>
+                                                    }
+                                                }) }
+                                            </tbody>
+                                        </table>
+                                    </section>
+                                }
+                            } else { html! {} }}
+
+                            { if let Some(workspace) = &template.workspace {
+                                html! {
+                                    <section class="detail-section">
+                                        <h2>{ "Persistent Workspace" }</h2>
+                                        <div class="field">
+                                            <label>{ "Volume:" }</label>
+                                            <code>{ &workspace.volume }</code>
+                                        </div>
+                                        <div class="field">
+                                            <label>{ "Mount Path:" }</label>
+                                            <code>{ &workspace.mount_path }</code>
+                                        </div>
+                                        <div class="field">
+                                            <label>{ "Retention:" }</label>
+                                            <span>{ workspace.retention().as_str_name() }</span>
+                                        </div>
+                                        <div class="field">
+                                            <label>{ "Access Mode:" }</label>
+                                            <span>{ workspace.access_mode().as_str_name() }</span>
+                                        </div>
+                                        <div class="field">
+                                            <label>{ "Idle TTL:" }</label>
+                                            <span>{ workspace.idle_ttl.as_ref().map(|ttl| format!("{} days", ttl.seconds / (24 * 60 * 60))).unwrap_or_else(|| "Never".to_string()) }</span>
+                                        </div>
+                                    </section>
+                                }
+                            } else { html! {} }}
+
                             { if let Some(resource_limit) = &template.resource_limit_name {
                                 html! {
                                     <section class="detail-section">
@@ -350,6 +580,20 @@ r#"{{
                                 }
                             } else { html! {} }}
 
+                            { if !template.authorization_names.is_empty() {
+                                html! {
+                                    <section class="detail-section">
+                                        <h2>{ "Authorizations" }</h2>
+                                        <p class="form-help">{ "Any one of them authorizes a session" }</p>
+                                        <div class="tags">
+                                            { for template.authorization_names.iter().map(|name| {
+                                                html! { <span class="tag" key={name.clone()}>{ name }</span> }
+                                            })}
+                                        </div>
+                                    </section>
+                                }
+                            } else { html! {} }}
+
                             { if !template.labels.is_empty() {
                                 html! {
                                     <section class="detail-section">
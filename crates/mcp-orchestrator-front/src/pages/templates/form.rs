@@ -1,13 +1,14 @@
 use crate::api::APICaller;
-use crate::components::{ErrorMessage, FormField, NamespaceSelector};
+use crate::components::{ContainerListEditor, EgressPolicyEditor, ErrorMessage, FormField, NamespaceSelector, SecurityProfileEditor};
 use crate::models::authorization::Authorization;
 use crate::models::resource_limit::ResourceLimit;
 use crate::models::secret::Secret;
 use crate::models::state::AuthState;
 use crate::models::template::TemplateFormData;
+use proto_web::{ArgEnvSpec, ArgEnvType, EgressPolicy, SecretType, PackageRegistry, PackageSource, SecretMount, SecretRotationPolicy, SecurityProfile, TemplateContainer, TemplateValidation, TemplateValidationReport, VolumeMount, WorkspaceAccessMode, WorkspaceConfig, WorkspaceRetention};
 use crate::models::SessionState;
 use crate::routes::Route;
-use crate::utils::validation::{validate_docker_image, validate_name, validate_arg_env_key, validate_arg_env_name, validate_arg_env_value};
+use crate::utils::validation::{validate_docker_image, validate_name, validate_arg_env_key, validate_arg_env_name, validate_arg_env_spec, validate_claim_env_name, validate_mount_path};
 use std::collections::HashMap;
 use yew::prelude::*;
 use yew_router::prelude::*;
@@ -29,6 +30,9 @@ pub fn template_form() -> Html {
     let errors = use_state(HashMap::<String, String>::new);
     let is_submitting = use_state(|| false);
     let submit_error = use_state(|| Option::<String>::None);
+    let smoke_test = use_state(|| false);
+    let validation_report = use_state(|| Option::<TemplateValidationReport>::None);
+    let is_validating = use_state(|| false);
     let navigator = use_navigator().unwrap();
 
     let resource_limits = use_state(Vec::<ResourceLimit>::new);
@@ -163,10 +167,13 @@ pub fn template_form() -> Html {
             let value = input.value();
             let mut data = (*form_data).clone();
             data.image = value.clone();
+            let is_package = data.package.is_some();
             form_data.set(data);
 
             let mut new_errors = (*errors).clone();
-            if let Some(error) = validate_docker_image(&value) {
+            if is_package && value.is_empty() {
+                new_errors.remove("image");
+            } else if let Some(error) = validate_docker_image(&value) {
                 new_errors.insert("image".to_string(), error);
             } else {
                 new_errors.remove("image");
@@ -175,6 +182,66 @@ pub fn template_form() -> Html {
         })
     };
 
+    let on_source_change = {
+        let form_data = form_data.clone();
+        let errors = errors.clone();
+        Callback::from(move |e: Event| {
+            let select: web_sys::HtmlSelectElement = e.target_unchecked_into();
+            let mut data = (*form_data).clone();
+            data.package = PackageRegistry::from_str_name(&select.value()).map(|registry| {
+                let mut package = data.package.clone().unwrap_or_default();
+                package.set_registry(registry);
+                package
+            });
+            if data.package.is_some() {
+                data.command = Vec::new();
+            }
+            form_data.set(data);
+            let mut new_errors = (*errors).clone();
+            new_errors.remove("image");
+            new_errors.remove("package");
+            errors.set(new_errors);
+        })
+    };
+
+    let update_package = {
+        let form_data = form_data.clone();
+        move |update: &dyn Fn(&mut PackageSource)| {
+            let mut data = (*form_data).clone();
+            if let Some(package) = data.package.as_mut() {
+                update(package);
+            }
+            form_data.set(data);
+        }
+    };
+
+    let on_package_name_change = {
+        let update_package = update_package.clone();
+        Callback::from(move |e: Event| {
+            let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+            let value = input.value().trim().to_string();
+            update_package(&|package| package.name = value.clone());
+        })
+    };
+
+    let on_package_version_change = {
+        let update_package = update_package.clone();
+        Callback::from(move |e: Event| {
+            let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+            let value = input.value().trim().to_string();
+            update_package(&|package| package.version = value.clone());
+        })
+    };
+
+    let on_package_cache_change = {
+        let update_package = update_package.clone();
+        Callback::from(move |e: Event| {
+            let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+            let value = Some(input.value().trim().to_string()).filter(|x| !x.is_empty());
+            update_package(&|package| package.cache_volume = value.clone());
+        })
+    };
+
     let on_command_change = {
         let form_data = form_data.clone();
         Callback::from(move |e: Event| {
@@ -267,8 +334,8 @@ pub fn template_form() -> Html {
         });
     }
 
-    // arg_env_items: (id, key, env_name, type)
-    let arg_env_items = use_state(Vec::<(usize, String, String, String)>::new);
+    // arg_env_items: (id, key, spec)
+    let arg_env_items = use_state(Vec::<(usize, String, ArgEnvSpec)>::new);
     let arg_env_counter = use_state(|| 0usize);
 
     let on_add_arg_env = {
@@ -277,7 +344,7 @@ pub fn template_form() -> Html {
         Callback::from(move |_| {
             let mut items = (*arg_env_items).clone();
             let id = *arg_env_counter;
-            items.push((id, String::new(), String::new(), "string".to_string()));
+            items.push((id, String::new(), ArgEnvSpec::default()));
             arg_env_items.set(items);
             arg_env_counter.set(id + 1);
         })
@@ -285,10 +352,16 @@ pub fn template_form() -> Html {
 
     let on_remove_arg_env = {
         let arg_env_items = arg_env_items.clone();
+        let errors = errors.clone();
         move |id: usize| {
             let mut items = (*arg_env_items).clone();
-            items.retain(|(item_id, _, _, _)| *item_id != id);
+            items.retain(|(item_id, _, _)| *item_id != id);
             arg_env_items.set(items);
+            let mut new_errors = (*errors).clone();
+            for prefix in ["arg_env_key", "arg_env_name", "arg_env_spec"] {
+                new_errors.remove(&format!("{}_{}", prefix, id));
+            }
+            errors.set(new_errors);
         }
     };
 
@@ -297,7 +370,7 @@ pub fn template_form() -> Html {
         let errors = errors.clone();
         move |id: usize, new_key: String| {
             let mut items = (*arg_env_items).clone();
-            if let Some(item) = items.iter_mut().find(|(item_id, _, _, _)| *item_id == id) {
+            if let Some(item) = items.iter_mut().find(|(item_id, _, _)| *item_id == id) {
                 item.1 = new_key.clone();
             }
             arg_env_items.set(items);
@@ -322,8 +395,8 @@ pub fn template_form() -> Html {
         let errors = errors.clone();
         move |id: usize, new_env_name: String| {
             let mut items = (*arg_env_items).clone();
-            if let Some(item) = items.iter_mut().find(|(item_id, _, _, _)| *item_id == id) {
-                item.2 = new_env_name.clone();
+            if let Some(item) = items.iter_mut().find(|(item_id, _, _)| *item_id == id) {
+                item.2.env_name = Some(new_env_name.clone()).filter(|x| !x.is_empty());
             }
             arg_env_items.set(items);
 
@@ -342,34 +415,106 @@ pub fn template_form() -> Html {
         }
     };
 
-    let on_arg_env_type_change = {
+    let update_arg_env = {
         let arg_env_items = arg_env_items.clone();
-        move |id: usize, new_type: String| {
+        move |id: usize, update: &dyn Fn(&mut ArgEnvSpec)| {
             let mut items = (*arg_env_items).clone();
-            if let Some(item) = items.iter_mut().find(|(item_id, _, _, _)| *item_id == id) {
-                item.3 = new_type;
+            if let Some(item) = items.iter_mut().find(|(item_id, _, _)| *item_id == id) {
+                update(&mut item.2);
             }
             arg_env_items.set(items);
         }
     };
 
-    // Sync arg_env_items to form_data.arg_envs
+    // Sync arg_env_items to form_data.arg_env_specs
     {
         let form_data = form_data.clone();
         let arg_env_items = arg_env_items.clone();
         use_effect_with(arg_env_items.clone(), move |items| {
             let mut data = (*form_data).clone();
-            data.arg_envs = items
+            data.arg_env_specs = items
                 .iter()
-                .filter(|(_, k, _, _)| !k.is_empty())
-                .map(|(_, k, env_name, type_str)| {
-                    let value = if env_name.is_empty() {
-                        type_str.clone()
-                    } else {
-                        format!("{}: {}", env_name, type_str)
-                    };
-                    (k.clone(), value)
-                })
+                .filter(|(_, k, _)| !k.is_empty())
+                .map(|(_, k, spec)| (k.clone(), spec.clone()))
+                .collect();
+            form_data.set(data);
+            || ()
+        });
+    }
+
+    // claim_env_items: (id, env_name, claim path)
+    let claim_env_items = use_state(Vec::<(usize, String, String)>::new);
+    let claim_env_counter = use_state(|| 0usize);
+
+    let on_add_claim_env = {
+        let claim_env_items = claim_env_items.clone();
+        let claim_env_counter = claim_env_counter.clone();
+        Callback::from(move |_| {
+            let mut items = (*claim_env_items).clone();
+            let id = *claim_env_counter;
+            items.push((id, String::new(), String::new()));
+            claim_env_items.set(items);
+            claim_env_counter.set(id + 1);
+        })
+    };
+
+    let on_remove_claim_env = {
+        let claim_env_items = claim_env_items.clone();
+        let errors = errors.clone();
+        move |id: usize| {
+            let mut items = (*claim_env_items).clone();
+            items.retain(|(item_id, _, _)| *item_id != id);
+            claim_env_items.set(items);
+            let mut new_errors = (*errors).clone();
+            new_errors.remove(&format!("claim_env_{}", id));
+            errors.set(new_errors);
+        }
+    };
+
+    let on_claim_env_name_change = {
+        let claim_env_items = claim_env_items.clone();
+        let errors = errors.clone();
+        move |id: usize, new_env_name: String| {
+            let mut items = (*claim_env_items).clone();
+            if let Some(item) = items.iter_mut().find(|(item_id, _, _)| *item_id == id) {
+                item.1 = new_env_name.clone();
+            }
+            claim_env_items.set(items);
+
+            let mut new_errors = (*errors).clone();
+            match validate_claim_env_name(&new_env_name) {
+                Some(error) if !new_env_name.is_empty() => {
+                    new_errors.insert(format!("claim_env_{}", id), error);
+                }
+                _ => {
+                    new_errors.remove(&format!("claim_env_{}", id));
+                }
+            }
+            errors.set(new_errors);
+        }
+    };
+
+    let on_claim_env_path_change = {
+        let claim_env_items = claim_env_items.clone();
+        move |id: usize, new_path: String| {
+            let mut items = (*claim_env_items).clone();
+            if let Some(item) = items.iter_mut().find(|(item_id, _, _)| *item_id == id) {
+                item.2 = new_path;
+            }
+            claim_env_items.set(items);
+        }
+    };
+
+    // Sync claim_env_items to form_data.claim_envs
+    {
+        let form_data = form_data.clone();
+        let claim_env_items = claim_env_items.clone();
+        use_effect_with(claim_env_items.clone(), move |items| {
+            let mut data = (*form_data).clone();
+            data.claim_envs = items
+                .iter()
+                .filter(|(_, k, v)| !k.is_empty() || !v.is_empty())
+                .map(|(_, k, v)| (k.clone(), v.clone()))
                 .collect();
             form_data.set(data);
             || ()
@@ -401,8 +546,8 @@ pub fn template_form() -> Html {
     let on_secret_env_change = {
         let form_data = form_data.clone();
         move |index: usize, e: Event| {
-            let select: web_sys::HtmlSelectElement = e.target_unchecked_into();
-            let value = select.value();
+            let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+            let value = input.value().trim().to_string();
             let mut data = (*form_data).clone();
             if let Some(item) = data.secret_envs.get_mut(index) {
                 *item = value;
@@ -411,6 +556,266 @@ pub fn template_form() -> Html {
         }
     };
 
+    let on_add_volume_mount = {
+        let form_data = form_data.clone();
+        Callback::from(move |_| {
+            let mut data = (*form_data).clone();
+            data.volume_mounts.push(VolumeMount::default());
+            form_data.set(data);
+        })
+    };
+
+    let on_remove_volume_mount = {
+        let form_data = form_data.clone();
+        let errors = errors.clone();
+        move |index: usize| {
+            let mut new_errors = (*errors).clone();
+            new_errors.retain(|k, _| !k.starts_with("volume_mount_"));
+            errors.set(new_errors);
+
+            let mut data = (*form_data).clone();
+            data.volume_mounts.remove(index);
+            form_data.set(data);
+        }
+    };
+
+    let on_volume_mount_pattern_change = {
+        let form_data = form_data.clone();
+        move |index: usize, value: String| {
+            let mut data = (*form_data).clone();
+            if let Some(item) = data.volume_mounts.get_mut(index) {
+                item.pattern = value;
+            }
+            form_data.set(data);
+        }
+    };
+
+    let on_volume_mount_path_change = {
+        let form_data = form_data.clone();
+        let errors = errors.clone();
+        move |index: usize, value: String| {
+            let mut new_errors = (*errors).clone();
+            if let Some(error) = validate_mount_path(&value) {
+                new_errors.insert(format!("volume_mount_{}", index), error);
+            } else {
+                new_errors.remove(&format!("volume_mount_{}", index));
+            }
+            errors.set(new_errors);
+
+            let mut data = (*form_data).clone();
+            if let Some(item) = data.volume_mounts.get_mut(index) {
+                item.mount_path = value;
+            }
+            form_data.set(data);
+        }
+    };
+
+    let on_init_containers_change = {
+        let form_data = form_data.clone();
+        Callback::from(move |containers: Vec<TemplateContainer>| {
+            let mut data = (*form_data).clone();
+            data.init_containers = containers;
+            form_data.set(data);
+        })
+    };
+
+    let on_sidecars_change = {
+        let form_data = form_data.clone();
+        Callback::from(move |containers: Vec<TemplateContainer>| {
+            let mut data = (*form_data).clone();
+            data.sidecars = containers;
+            form_data.set(data);
+        })
+    };
+
+    let on_security_change = {
+        let form_data = form_data.clone();
+        Callback::from(move |security: Option<SecurityProfile>| {
+            let mut data = (*form_data).clone();
+            data.security = security;
+            form_data.set(data);
+        })
+    };
+
+    let on_image_pull_secret_toggle = {
+        let form_data = form_data.clone();
+        move |name: String| {
+            let mut data = (*form_data).clone();
+            if data.image_pull_secrets.contains(&name) {
+                data.image_pull_secrets.retain(|x| x != &name);
+            } else {
+                data.image_pull_secrets.push(name);
+            }
+            form_data.set(data);
+        }
+    };
+
+    let on_secret_rotation_change = {
+        let form_data = form_data.clone();
+        Callback::from(move |e: Event| {
+            let select: web_sys::HtmlSelectElement = e.target_unchecked_into();
+            let mut data = (*form_data).clone();
+            data.secret_rotation = SecretRotationPolicy::from_str_name(&select.value())
+                .unwrap_or(SecretRotationPolicy::None);
+            form_data.set(data);
+        })
+    };
+
+    let on_egress_change = {
+        let form_data = form_data.clone();
+        Callback::from(move |egress: Option<EgressPolicy>| {
+            let mut data = (*form_data).clone();
+            data.egress = egress;
+            form_data.set(data);
+        })
+    };
+
+    let on_add_secret_mount = {
+        let form_data = form_data.clone();
+        let is_loading_secrets = is_loading_secrets.clone();
+        Callback::from(move |_| {
+            if *is_loading_secrets {
+                return;
+            }
+            let mut data = (*form_data).clone();
+            data.secret_mounts.push(SecretMount::default());
+            form_data.set(data);
+        })
+    };
+
+    let on_remove_secret_mount = {
+        let form_data = form_data.clone();
+        let errors = errors.clone();
+        move |index: usize| {
+            let mut new_errors = (*errors).clone();
+            new_errors.retain(|k, _| !k.starts_with("secret_mount_"));
+            errors.set(new_errors);
+
+            let mut data = (*form_data).clone();
+            data.secret_mounts.remove(index);
+            form_data.set(data);
+        }
+    };
+
+    let on_secret_mount_name_change = {
+        let form_data = form_data.clone();
+        move |index: usize, value: String| {
+            let mut data = (*form_data).clone();
+            if let Some(item) = data.secret_mounts.get_mut(index) {
+                item.name = value;
+            }
+            form_data.set(data);
+        }
+    };
+
+    let on_secret_mount_path_change = {
+        let form_data = form_data.clone();
+        let errors = errors.clone();
+        move |index: usize, value: String| {
+            let mut new_errors = (*errors).clone();
+            if let Some(error) = validate_mount_path(&value) {
+                new_errors.insert(format!("secret_mount_{}", index), error);
+            } else {
+                new_errors.remove(&format!("secret_mount_{}", index));
+            }
+            errors.set(new_errors);
+
+            let mut data = (*form_data).clone();
+            if let Some(item) = data.secret_mounts.get_mut(index) {
+                item.mount_path = value;
+            }
+            form_data.set(data);
+        }
+    };
+
+    let update_workspace = {
+        let form_data = form_data.clone();
+        move |update: &dyn Fn(&mut WorkspaceConfig)| {
+            let mut data = (*form_data).clone();
+            if let Some(workspace) = data.workspace.as_mut() {
+                update(workspace);
+            }
+            form_data.set(data);
+        }
+    };
+
+    let on_workspace_toggle = {
+        let form_data = form_data.clone();
+        Callback::from(move |e: Event| {
+            let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+            let mut data = (*form_data).clone();
+            data.workspace = if input.checked() {
+                Some(WorkspaceConfig {
+                    mount_path: "/workspace".to_string(),
+                    ..Default::default()
+                })
+            } else {
+                None
+            };
+            form_data.set(data);
+        })
+    };
+
+    let on_workspace_volume_change = {
+        let update_workspace = update_workspace.clone();
+        Callback::from(move |e: Event| {
+            let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+            let value = input.value();
+            update_workspace(&|workspace| workspace.volume = value.clone());
+        })
+    };
+
+    let on_workspace_path_change = {
+        let update_workspace = update_workspace.clone();
+        let errors = errors.clone();
+        Callback::from(move |e: Event| {
+            let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+            let value = input.value();
+            let mut new_errors = (*errors).clone();
+            if let Some(error) = validate_mount_path(&value) {
+                new_errors.insert("workspace_mount_path".to_string(), error);
+            } else {
+                new_errors.remove("workspace_mount_path");
+            }
+            errors.set(new_errors);
+            update_workspace(&|workspace| workspace.mount_path = value.clone());
+        })
+    };
+
+    let on_workspace_retention_change = {
+        let update_workspace = update_workspace.clone();
+        Callback::from(move |e: Event| {
+            let select: web_sys::HtmlSelectElement = e.target_unchecked_into();
+            let retention = WorkspaceRetention::from_str_name(&select.value())
+                .unwrap_or(WorkspaceRetention::Retain);
+            update_workspace(&|workspace| workspace.set_retention(retention));
+        })
+    };
+
+    let on_workspace_access_mode_change = {
+        let update_workspace = update_workspace.clone();
+        Callback::from(move |e: Event| {
+            let select: web_sys::HtmlSelectElement = e.target_unchecked_into();
+            let access_mode = WorkspaceAccessMode::from_str_name(&select.value())
+                .unwrap_or(WorkspaceAccessMode::ReadWriteOnce);
+            update_workspace(&|workspace| workspace.set_access_mode(access_mode));
+        })
+    };
+
+    let on_workspace_ttl_change = {
+        let update_workspace = update_workspace.clone();
+        Callback::from(move |e: Event| {
+            let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+            let days = input.value().parse::<i64>().ok().filter(|days| *days > 0);
+            update_workspace(&|workspace| {
+                workspace.idle_ttl = days.map(|days| prost_wkt_types::Duration {
+                    seconds: days * 24 * 60 * 60,
+                    nanos: 0,
+                });
+            });
+        })
+    };
+
     let on_resource_limit_change = {
         let form_data = form_data.clone();
         let errors = errors.clone();
@@ -438,26 +843,27 @@ pub fn template_form() -> Html {
         })
     };
 
-    let on_authorization_change = {
+    let on_authorization_toggle = {
         let form_data = form_data.clone();
-        Callback::from(move |e: Event| {
-            let select: web_sys::HtmlSelectElement = e.target_unchecked_into();
-            let value = select.value();
+        move |name: String| {
             let mut data = (*form_data).clone();
-            data.authorization_name = if value.is_empty() {
-                None
+            if data.authorization_names.contains(&name) {
+                data.authorization_names.retain(|x| x != &name);
             } else {
-                Some(value)
-            };
+                data.authorization_names.push(name);
+            }
             form_data.set(data);
-        })
+        }
     };
 
     let on_submit = {
         let form_data = form_data.clone();
+        let arg_env_items = arg_env_items.clone();
+        let claim_env_items = claim_env_items.clone();
         let errors = errors.clone();
         let is_submitting = is_submitting.clone();
         let submit_error = submit_error.clone();
+        let smoke_test = smoke_test.clone();
         let navigator = navigator.clone();
         let auth_state = auth_state.clone();
 
@@ -474,8 +880,26 @@ pub fn template_form() -> Html {
             if let Some(error) = validate_name(&data.name) {
                 validation_errors.insert("name".to_string(), error);
             }
-            if let Some(error) = validate_docker_image(&data.image) {
-                validation_errors.insert("image".to_string(), error);
+            match &data.package {
+                Some(package) => {
+                    if package.name.is_empty() || package.version.is_empty() {
+                        validation_errors.insert(
+                            "package".to_string(),
+                            "Package name and pinned version are required".to_string(),
+                        );
+                    }
+                    if let Some(error) = Some(&data.image)
+                        .filter(|image| !image.is_empty())
+                        .and_then(|image| validate_docker_image(image))
+                    {
+                        validation_errors.insert("image".to_string(), error);
+                    }
+                }
+                None => {
+                    if let Some(error) = validate_docker_image(&data.image) {
+                        validation_errors.insert("image".to_string(), error);
+                    }
+                }
             }
             if data.resource_limit_name.is_none() {
                 validation_errors.insert(
@@ -484,13 +908,21 @@ pub fn template_form() -> Html {
                 );
             }
             
-            // Validate arg_envs
-            for (key, value) in &data.arg_envs {
+            // Validate arg_env_specs
+            for (id, env_name, _) in claim_env_items.iter() {
+                if let Some(error) = validate_claim_env_name(env_name) {
+                    validation_errors.insert(format!("claim_env_{}", id), error);
+                }
+            }
+            for (id, key, spec) in arg_env_items.iter() {
+                if key.is_empty() {
+                    continue;
+                }
                 if let Some(error) = validate_arg_env_key(key) {
-                    validation_errors.insert(format!("arg_env_key_{}", key), error);
+                    validation_errors.insert(format!("arg_env_key_{}", id), error);
                 }
-                if let Some(error) = validate_arg_env_value(value) {
-                    validation_errors.insert(format!("arg_env_value_{}", key), error);
+                if let Some(error) = validate_arg_env_spec(spec) {
+                    validation_errors.insert(format!("arg_env_spec_{}", id), error);
                 }
             }
 
@@ -505,9 +937,16 @@ pub fn template_form() -> Html {
             let submit_error = submit_error.clone();
             let navigator = navigator.clone();
             let auth_state = auth_state.clone();
+            let smoke_test = *smoke_test;
 
             wasm_bindgen_futures::spawn_local(async move {
-                let request = data.into_create_request();
+                let mut request = data.into_create_request();
+                if smoke_test {
+                    request.validation = Some(TemplateValidation {
+                        smoke_test: true,
+                        ..Default::default()
+                    });
+                }
                 let api = APICaller::new(auth_state.access_token.clone());
                 match api.create_template(request).await {
                     Ok(template) => {
@@ -525,6 +964,146 @@ pub fn template_form() -> Html {
         })
     };
 
+    let on_smoke_test_change = {
+        let smoke_test = smoke_test.clone();
+        Callback::from(move |e: Event| {
+            let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+            smoke_test.set(input.checked());
+        })
+    };
+
+    let on_validate = {
+        let form_data = form_data.clone();
+        let smoke_test = smoke_test.clone();
+        let validation_report = validation_report.clone();
+        let is_validating = is_validating.clone();
+        let submit_error = submit_error.clone();
+        let auth_state = auth_state.clone();
+        Callback::from(move |_| {
+            let request = (*form_data).clone().into_create_request();
+            let smoke_test = *smoke_test;
+            let validation_report = validation_report.clone();
+            let is_validating = is_validating.clone();
+            let submit_error = submit_error.clone();
+            let auth_state = auth_state.clone();
+            is_validating.set(true);
+            wasm_bindgen_futures::spawn_local(async move {
+                let api = APICaller::new(auth_state.access_token.clone());
+                match api.validate_template(request, smoke_test).await {
+                    Ok(report) => {
+                        validation_report.set(Some(report));
+                        submit_error.set(None);
+                    }
+                    Err(e) => {
+                        validation_report.set(None);
+                        submit_error.set(Some(e));
+                    }
+                }
+                is_validating.set(false);
+            });
+        })
+    };
+
+    // Import a draft from a server.json or mcpServers config
+    let import_content = use_state(String::new);
+    let import_server_name = use_state(String::new);
+    let import_warnings = use_state(Vec::<String>::new);
+    let import_error = use_state(|| Option::<String>::None);
+    let is_importing = use_state(|| false);
+
+    let on_import_content_change = {
+        let import_content = import_content.clone();
+        Callback::from(move |e: Event| {
+            let input: web_sys::HtmlTextAreaElement = e.target_unchecked_into();
+            import_content.set(input.value());
+        })
+    };
+
+    let on_import_server_name_change = {
+        let import_server_name = import_server_name.clone();
+        Callback::from(move |e: Event| {
+            let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+            import_server_name.set(input.value());
+        })
+    };
+
+    let on_import = {
+        let form_data = form_data.clone();
+        let env_items = env_items.clone();
+        let env_counter = env_counter.clone();
+        let arg_env_items = arg_env_items.clone();
+        let arg_env_counter = arg_env_counter.clone();
+        let errors = errors.clone();
+        let import_content = import_content.clone();
+        let import_server_name = import_server_name.clone();
+        let import_warnings = import_warnings.clone();
+        let import_error = import_error.clone();
+        let is_importing = is_importing.clone();
+        let auth_state = auth_state.clone();
+        Callback::from(move |_| {
+            let form_data = form_data.clone();
+            let env_items = env_items.clone();
+            let env_counter = env_counter.clone();
+            let arg_env_items = arg_env_items.clone();
+            let arg_env_counter = arg_env_counter.clone();
+            let errors = errors.clone();
+            let import_warnings = import_warnings.clone();
+            let import_error = import_error.clone();
+            let is_importing = is_importing.clone();
+            let auth_state = auth_state.clone();
+            let content = (*import_content).clone();
+            let server_name = Some((*import_server_name).clone()).filter(|x| !x.is_empty());
+            is_importing.set(true);
+            wasm_bindgen_futures::spawn_local(async move {
+                let api = APICaller::new(auth_state.access_token.clone());
+                match api
+                    .import_template(&form_data.namespace, content, server_name)
+                    .await
+                {
+                    Ok(response) => {
+                        let current = (*form_data).clone();
+                        let mut data = TemplateFormData::from(response.draft.unwrap_or_default());
+                        data.namespace = current.namespace;
+                        data.resource_limit_name = data.resource_limit_name.or(current.resource_limit_name);
+                        if data.authorization_names.is_empty() {
+                            data.authorization_names = current.authorization_names;
+                        }
+
+                        let mut envs: Vec<_> = data.envs.clone().into_iter().collect();
+                        envs.sort();
+                        let mut arg_envs: Vec<_> = data.arg_env_specs.clone().into_iter().collect();
+                        arg_envs.sort_by(|a, b| a.0.cmp(&b.0));
+                        let env_start = *env_counter;
+                        let arg_env_start = *arg_env_counter;
+                        env_items.set(
+                            envs.into_iter()
+                                .enumerate()
+                                .map(|(i, (k, v))| (env_start + i, k, v))
+                                .collect(),
+                        );
+                        env_counter.set(env_start + data.envs.len());
+                        arg_env_items.set(
+                            arg_envs.into_iter()
+                                .enumerate()
+                                .map(|(i, (k, spec))| (arg_env_start + i, k, spec))
+                                .collect(),
+                        );
+                        arg_env_counter.set(arg_env_start + data.arg_env_specs.len());
+                        form_data.set(data);
+                        errors.set(HashMap::new());
+                        import_warnings.set(response.warnings);
+                        import_error.set(None);
+                    }
+                    Err(e) => {
+                        import_warnings.set(vec![]);
+                        import_error.set(Some(e));
+                    }
+                }
+                is_importing.set(false);
+            });
+        })
+    };
+
     html! {
         <div class="container">
             <NamespaceSelector />
@@ -540,6 +1119,41 @@ pub fn template_form() -> Html {
                 html! { <ErrorMessage message={error.clone()} /> }
             } else { html! {} }}
 
+            <div class="form form-section">
+                <label class="section-label">{ "Import" }</label>
+                <small class="form-help">{ "Paste an MCP registry server.json or an mcpServers client config to prefill the form, nothing is created until you save" }</small>
+                <textarea
+                    value={(*import_content).clone()}
+                    onchange={on_import_content_change}
+                    placeholder={r#"{ "mcpServers": { "github": { "command": "npx", "args": ["-y", "@modelcontextprotocol/server-github"] } } }"#}
+                    rows="6"
+                />
+                <input
+                    type="text"
+                    value={(*import_server_name).clone()}
+                    onchange={on_import_server_name_change}
+                    placeholder="Server name, when the config declares several"
+                />
+                <button
+                    type="button"
+                    class="btn-secondary"
+                    onclick={on_import}
+                    disabled={*is_importing || import_content.is_empty()}
+                >
+                    { if *is_importing { "Importing..." } else { "Import Draft" } }
+                </button>
+                { if let Some(error) = &*import_error {
+                    html! { <ErrorMessage message={error.clone()} /> }
+                } else { html! {} }}
+                { if !import_warnings.is_empty() {
+                    html! {
+                        <ul class="form-help">
+                            { for import_warnings.iter().map(|warning| html! { <li>{ warning }</li> }) }
+                        </ul>
+                    }
+                } else { html! {} }}
+            </div>
+
             <form onsubmit={on_submit} class="form">
                 <div class="field">
                     <label>{ "Namespace:" }</label>
@@ -560,32 +1174,91 @@ pub fn template_form() -> Html {
                     <small class="form-help">{ "Lowercase alphanumeric and hyphens only, max 63 characters" }</small>
                 </FormField>
 
+                <div class="field">
+                    <label>{ "Source" }</label>
+                    <select onchange={on_source_change}>
+                        <option value="IMAGE" selected={form_data.package.is_none()}>{ "Container image" }</option>
+                        <option
+                            value={PackageRegistry::Npm.as_str_name()}
+                            selected={form_data.package.as_ref().is_some_and(|x| x.registry() == PackageRegistry::Npm)}
+                        >
+                            { "npm package (npx)" }
+                        </option>
+                        <option
+                            value={PackageRegistry::Pypi.as_str_name()}
+                            selected={form_data.package.as_ref().is_some_and(|x| x.registry() == PackageRegistry::Pypi)}
+                        >
+                            { "PyPI package (uvx)" }
+                        </option>
+                    </select>
+                </div>
+
+                if let Some(package) = &form_data.package {
+                    <FormField
+                        label="Package *"
+                        error={errors.get("package").cloned()}
+                    >
+                        <div class="label-row">
+                            <input
+                                type="text"
+                                value={package.name.clone()}
+                                onchange={on_package_name_change}
+                                placeholder={if package.registry() == PackageRegistry::Npm { "@scope/server" } else { "mcp-server-fetch" }}
+                                class="label-key"
+                            />
+                            <span>{ "@" }</span>
+                            <input
+                                type="text"
+                                value={package.version.clone()}
+                                onchange={on_package_version_change}
+                                placeholder="1.0.0"
+                                class="label-value"
+                            />
+                        </div>
+                        <input
+                            type="text"
+                            value={package.cache_volume.clone().unwrap_or_default()}
+                            onchange={on_package_cache_change}
+                            placeholder="Cache volume (optional)"
+                        />
+                        <small class="form-help">{ "Version must be pinned. The command is generated, arguments below are passed to the package. The cache volume must match one volume of the selected resource limit and is shared by the namespace" }</small>
+                    </FormField>
+                }
+
                 <FormField
-                    label="Docker Image *"
+                    label={if form_data.package.is_some() { "Runner Image" } else { "Docker Image *" }}
                     error={errors.get("image").cloned()}
                 >
                     <input
                         type="text"
                         value={form_data.image.clone()}
                         onchange={on_image_change}
-                        required={true}
+                        required={form_data.package.is_none()}
                         placeholder="mcp/server:latest"
                     />
-                    <small class="form-help">{ "Full image name with tag (e.g., myregistry/myimage:v1.0)" }</small>
+                    <small class="form-help">
+                        { if form_data.package.is_some() {
+                            "Leave empty to use the runner image configured on the server"
+                        } else {
+                            "Full image name with tag (e.g., myregistry/myimage:v1.0)"
+                        } }
+                    </small>
                 </FormField>
 
-                <FormField
-                    label="Command"
-                    error={Option::<String>::None}
-                >
-                    <input
-                        type="text"
-                        value={form_data.command.join(" ")}
-                        onchange={on_command_change}
-                        placeholder="/bin/sh -c"
-                    />
-                    <small class="form-help">{ "Optional command override (space-separated)" }</small>
-                </FormField>
+                if form_data.package.is_none() {
+                    <FormField
+                        label="Command"
+                        error={Option::<String>::None}
+                    >
+                        <input
+                            type="text"
+                            value={form_data.command.join(" ")}
+                            onchange={on_command_change}
+                            placeholder="/bin/sh -c"
+                        />
+                        <small class="form-help">{ "Optional command override (space-separated)" }</small>
+                    </FormField>
+                }
 
                 <FormField
                     label="Arguments"
@@ -669,10 +1342,13 @@ pub fn template_form() -> Html {
                     <label class="section-label">{ "Argument Environment Variables" }</label>
                     <small class="form-help">{ "Define arguments that will be passed via HTTP headers (arg-{key}) and injected as environment variables in the Pod" }</small>
 
-                    { for arg_env_items.iter().map(|(id, key, env_name, type_str)| {
+                    { for arg_env_items.iter().map(|(id, key, spec)| {
                         let item_id = *id;
                         let key_error = errors.get(&format!("arg_env_key_{}", item_id)).cloned();
                         let name_error = errors.get(&format!("arg_env_name_{}", item_id)).cloned();
+                        let spec_error = errors.get(&format!("arg_env_spec_{}", item_id)).cloned();
+                        let env_name = spec.env_name.clone().unwrap_or_default();
+                        let arg_type = spec.r#type();
 
                         let on_key_change = {
                             let on_arg_env_key_change = on_arg_env_key_change.clone();
@@ -691,10 +1367,79 @@ pub fn template_form() -> Html {
                         };
 
                         let on_type_change = {
-                            let on_arg_env_type_change = on_arg_env_type_change.clone();
+                            let update_arg_env = update_arg_env.clone();
                             Callback::from(move |e: Event| {
                                 let select: web_sys::HtmlSelectElement = e.target_unchecked_into();
-                                on_arg_env_type_change(item_id, select.value());
+                                let arg_type = ArgEnvType::from_str_name(&select.value())
+                                    .unwrap_or(ArgEnvType::String);
+                                update_arg_env(item_id, &|spec| {
+                                    spec.set_type(arg_type);
+                                    if arg_type != ArgEnvType::Enum {
+                                        spec.allowed_values.clear();
+                                    }
+                                    if arg_type != ArgEnvType::String {
+                                        spec.pattern = None;
+                                    }
+                                });
+                            })
+                        };
+
+                        let on_optional_change = {
+                            let update_arg_env = update_arg_env.clone();
+                            Callback::from(move |e: Event| {
+                                let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+                                let checked = input.checked();
+                                update_arg_env(item_id, &|spec| spec.optional = checked);
+                            })
+                        };
+
+                        let on_secret_change = {
+                            let update_arg_env = update_arg_env.clone();
+                            Callback::from(move |e: Event| {
+                                let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+                                let checked = input.checked();
+                                update_arg_env(item_id, &|spec| spec.secret = checked);
+                            })
+                        };
+
+                        let on_default_change = {
+                            let update_arg_env = update_arg_env.clone();
+                            Callback::from(move |e: Event| {
+                                let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+                                let value = Some(input.value()).filter(|x| !x.is_empty());
+                                update_arg_env(item_id, &|spec| spec.default_value = value.clone());
+                            })
+                        };
+
+                        let on_allowed_values_change = {
+                            let update_arg_env = update_arg_env.clone();
+                            Callback::from(move |e: Event| {
+                                let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+                                let values: Vec<String> = input
+                                    .value()
+                                    .split(',')
+                                    .map(|x| x.trim().to_string())
+                                    .filter(|x| !x.is_empty())
+                                    .collect();
+                                update_arg_env(item_id, &|spec| spec.allowed_values = values.clone());
+                            })
+                        };
+
+                        let on_pattern_change = {
+                            let update_arg_env = update_arg_env.clone();
+                            Callback::from(move |e: Event| {
+                                let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+                                let value = Some(input.value()).filter(|x| !x.is_empty());
+                                update_arg_env(item_id, &|spec| spec.pattern = value.clone());
+                            })
+                        };
+
+                        let on_description_change = {
+                            let update_arg_env = update_arg_env.clone();
+                            Callback::from(move |e: Event| {
+                                let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+                                let value = Some(input.value()).filter(|x| !x.is_empty());
+                                update_arg_env(item_id, &|spec| spec.description = value.clone());
                             })
                         };
 
@@ -722,12 +1467,16 @@ pub fn template_form() -> Html {
                                         class="label-value"
                                     />
                                     <span>{ ":" }</span>
-                                    <select
-                                        onchange={on_type_change}
-                                        value={type_str.clone()}
-                                    >
-                                        <option value="string" selected={type_str == "string"}>{ "string" }</option>
-                                        <option value="string?" selected={type_str == "string?"}>{ "string?" }</option>
+                                    <select onchange={on_type_change}>
+                                        { for [
+                                            (ArgEnvType::String, "string"),
+                                            (ArgEnvType::Int, "int"),
+                                            (ArgEnvType::Bool, "bool"),
+                                            (ArgEnvType::Enum, "enum"),
+                                            (ArgEnvType::Url, "url"),
+                                        ].into_iter().map(|(value, label)| html! {
+                                            <option value={value.as_str_name()} selected={arg_type == value}>{ label }</option>
+                                        })}
                                     </select>
                                     <button
                                         type="button"
@@ -737,7 +1486,60 @@ pub fn template_form() -> Html {
                                         { "×" }
                                     </button>
                                 </div>
-                                { if key_error.is_some() || name_error.is_some() {
+                                <div class="label-row">
+                                    <label>
+                                        <input
+                                            type="checkbox"
+                                            checked={spec.optional}
+                                            onchange={on_optional_change}
+                                        />
+                                        { " Optional" }
+                                    </label>
+                                    <label>
+                                        <input
+                                            type="checkbox"
+                                            checked={spec.secret}
+                                            onchange={on_secret_change}
+                                        />
+                                        { " Secret" }
+                                    </label>
+                                    <input
+                                        type={if spec.secret { "password" } else { "text" }}
+                                        value={spec.default_value.clone().unwrap_or_default()}
+                                        onchange={on_default_change}
+                                        placeholder="default value (optional)"
+                                    />
+                                    { if arg_type == ArgEnvType::Enum {
+                                        html! {
+                                            <input
+                                                type="text"
+                                                value={spec.allowed_values.join(", ")}
+                                                onchange={on_allowed_values_change}
+                                                placeholder="allowed values (comma separated)"
+                                            />
+                                        }
+                                    } else if arg_type == ArgEnvType::String {
+                                        html! {
+                                            <input
+                                                type="text"
+                                                value={spec.pattern.clone().unwrap_or_default()}
+                                                onchange={on_pattern_change}
+                                                placeholder="regex pattern (optional)"
+                                            />
+                                        }
+                                    } else {
+                                        html! {}
+                                    }}
+                                </div>
+                                <div class="label-row">
+                                    <input
+                                        type="text"
+                                        value={spec.description.clone().unwrap_or_default()}
+                                        onchange={on_description_change}
+                                        placeholder="description (optional)"
+                                    />
+                                </div>
+                                { if key_error.is_some() || name_error.is_some() || spec_error.is_some() {
                                     html! {
                                         <div style="margin-top: 0.25rem;">
                                             { if let Some(error) = key_error {
@@ -750,6 +1552,11 @@ pub fn template_form() -> Html {
                                             } else {
                                                 html! {}
                                             }}
+                                            { if let Some(error) = spec_error {
+                                                html! { <small class="error-text" style="display: block;">{ error }</small> }
+                                            } else {
+                                                html! {}
+                                            }}
                                         </div>
                                     }
                                 } else {
@@ -757,24 +1564,18 @@ pub fn template_form() -> Html {
                                 }}
                                 <small class="form-help" style="display: block; margin-top: 0.25rem;">
                                     {{
-                                        let type_example = if type_str.as_str() == "string?" { 
-                                            "\"value\" or null" 
-                                        } else { 
-                                            "\"value\"" 
-                                        };
-                                        let final_env_name = if env_name.is_empty() { 
-                                            key.as_str() 
-                                        } else { 
-                                            env_name.as_str() 
+                                        let type_example = if spec.optional || spec.default_value.is_some() {
+                                            "\"value\" or null"
+                                        } else {
+                                            "\"value\""
                                         };
-                                        
-                                        if env_name.is_empty() {
-                                            format!("HTTP Header = \"arg-{}: {}\" ⇒ Pod Env = \"{}={}\"", 
-                                                key, type_example, key, type_example)
+                                        let final_env_name = if env_name.is_empty() {
+                                            key.as_str()
                                         } else {
-                                            format!("HTTP Header = \"arg-{}: {}\" ⇒ Pod Env = \"{}={}\"", 
-                                                key, type_example, final_env_name, type_example)
-                                        }
+                                            env_name.as_str()
+                                        };
+                                        format!("HTTP Header = \"arg-{}: {}\" ⇒ Pod Env = \"{}={}\"",
+                                            key, type_example, final_env_name, type_example)
                                     }}
                                 </small>
                             </div>
@@ -790,21 +1591,96 @@ pub fn template_form() -> Html {
                     </button>
                 </div>
 
+                <div class="form-section">
+                    <label class="section-label">{ "Identity Claim Environment Variables" }</label>
+                    <small class="form-help">{ "Inject claims of the authenticated user into the Pod, e.g. USER_EMAIL ← email, TENANT ← claims.tenant. Claims are also written as Pod annotations. Requires a non-anonymous authorization" }</small>
+
+                    { for claim_env_items.iter().map(|(id, env_name, path)| {
+                        let item_id = *id;
+                        let claim_error = errors.get(&format!("claim_env_{}", item_id)).cloned();
+
+                        let on_name_change = {
+                            let on_claim_env_name_change = on_claim_env_name_change.clone();
+                            Callback::from(move |e: Event| {
+                                let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+                                on_claim_env_name_change(item_id, input.value());
+                            })
+                        };
+
+                        let on_path_change = {
+                            let on_claim_env_path_change = on_claim_env_path_change.clone();
+                            Callback::from(move |e: Event| {
+                                let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+                                on_claim_env_path_change(item_id, input.value());
+                            })
+                        };
+
+                        let on_remove = {
+                            let on_remove_claim_env = on_remove_claim_env.clone();
+                            Callback::from(move |_| on_remove_claim_env(item_id))
+                        };
+
+                        html! {
+                            <div key={item_id}>
+                                <div class="label-row">
+                                    <input
+                                        type="text"
+                                        value={env_name.clone()}
+                                        onchange={on_name_change}
+                                        placeholder="ENV_NAME"
+                                        class="label-key"
+                                    />
+                                    <span>{ "←" }</span>
+                                    <input
+                                        type="text"
+                                        value={path.clone()}
+                                        onchange={on_path_change}
+                                        placeholder="email or claims.tenant"
+                                        class="label-value"
+                                    />
+                                    <button
+                                        type="button"
+                                        onclick={on_remove}
+                                        class="btn-danger-small"
+                                    >
+                                        { "×" }
+                                    </button>
+                                </div>
+                                if let Some(error) = claim_error {
+                                    <small class="error-text">{ error }</small>
+                                }
+                            </div>
+                        }
+                    })}
+
+                    <button
+                        type="button"
+                        onclick={on_add_claim_env}
+                        class="btn-secondary-small"
+                    >
+                        { "+ Add Claim Environment Variable" }
+                    </button>
+                </div>
+
                 <div class="form-section">
                     <label class="section-label">{ "Secret References" }</label>
-                    <small class="form-help">{ "Reference secrets from the same namespace" }</small>
+                    <small class="form-help">
+                        { "Secrets of the same namespace, or backend references such as vault:team/github and file:github. " }
+                        { "user:github resolves the secret of the user opening the session from My Secrets. " }
+                        <Link<Route> to={Route::SecretCreate}>
+                            { "Create a secret" }
+                        </Link<Route>>
+                    </small>
+                    <datalist id="secret-names">
+                        { for secrets.iter().map(|secret| html! {
+                            <option key={secret.name.clone()} value={secret.name.clone()}>
+                                { format!("{} keys", secret.keys.len()) }
+                            </option>
+                        }) }
+                    </datalist>
 
                     { if *is_loading_secrets {
                         html! { <p>{ "Loading secrets..." }</p> }
-                    } else if secrets.is_empty() {
-                        html! {
-                            <p class="form-help">
-                                { "No secrets available in this namespace. " }
-                                <Link<Route> to={Route::SecretCreate}>
-                                    { "Create a secret first" }
-                                </Link<Route>>
-                            </p>
-                        }
                     } else {
                         html! {
                             <>
@@ -823,20 +1699,14 @@ pub fn template_form() -> Html {
 
                                     html! {
                                         <div class="label-row" key={index}>
-                                            <select
+                                            <input
+                                                type="text"
+                                                list="secret-names"
+                                                value={secret_ref.clone()}
                                                 onchange={on_change}
+                                                placeholder="github or vault:team/github"
                                                 style="flex: 1;"
-                                            >
-                                                <option value="" selected={secret_ref.is_empty()}>{"-- Select a secret --"}</option>
-                                                { for secrets.iter().map(|secret| {
-                                                    let is_selected = secret_ref == &secret.name;
-                                                    html! {
-                                                        <option key={secret.name.clone()} value={secret.name.clone()} selected={is_selected}>
-                                                            {format!("{} ({} keys)", secret.name, secret.keys.len())}
-                                                        </option>
-                                                    }
-                                                })}
-                                            </select>
+                                            />
                                             <button
                                                 type="button"
                                                 onclick={on_remove}
@@ -861,6 +1731,309 @@ pub fn template_form() -> Html {
                     }}
                 </div>
 
+                <div class="form-section">
+                    <label class="section-label">{ "Image Pull Secrets" }</label>
+                    <small class="form-help">{ "Registry credentials of private images, added to the pull secrets of the resource limit" }</small>
+
+                    { for secrets.iter().filter(|secret| secret.secret_type == SecretType::DockerConfigJson).map(|secret| {
+                        let on_toggle = {
+                            let on_image_pull_secret_toggle = on_image_pull_secret_toggle.clone();
+                            let name = secret.name.clone();
+                            Callback::from(move |_| on_image_pull_secret_toggle(name.clone()))
+                        };
+                        html! {
+                            <label key={secret.name.clone()} class="checkbox-label">
+                                <input
+                                    type="checkbox"
+                                    checked={form_data.image_pull_secrets.contains(&secret.name)}
+                                    onchange={on_toggle}
+                                />
+                                { &secret.name }
+                            </label>
+                        }
+                    })}
+                    { if !*is_loading_secrets && !secrets.iter().any(|secret| secret.secret_type == SecretType::DockerConfigJson) {
+                        html! { <p class="form-help">{ "No registry credential secrets in this namespace" }</p> }
+                    } else { html! {} }}
+                </div>
+
+                <div class="form-section">
+                    <label class="section-label">{ "Secret Rotation" }</label>
+                    <small class="form-help">{ "What happens to running sessions when a secret they use is updated" }</small>
+                    <select onchange={on_secret_rotation_change}>
+                        { for [
+                            (SecretRotationPolicy::None, "Leave running sessions alone"),
+                            (SecretRotationPolicy::Recycle, "Recycle sessions after their in-flight calls"),
+                            (SecretRotationPolicy::MarkStale, "Mark sessions stale and notify clients"),
+                        ].into_iter().map(|(policy, label)| html! {
+                            <option
+                                value={policy.as_str_name()}
+                                selected={form_data.secret_rotation == policy}
+                            >
+                                { label }
+                            </option>
+                        }) }
+                    </select>
+                </div>
+
+                <div class="form-section">
+                    <label class="section-label">{ "Volume Mounts" }</label>
+                    <small class="form-help">{ "Mount volumes declared by the selected resource limit. Pattern may use * and ? but must match exactly one volume" }</small>
+
+                    { for form_data.volume_mounts.iter().enumerate().map(|(index, mount)| {
+                        let on_pattern_change = {
+                            let on_volume_mount_pattern_change = on_volume_mount_pattern_change.clone();
+                            Callback::from(move |e: Event| {
+                                let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+                                on_volume_mount_pattern_change(index, input.value());
+                            })
+                        };
+
+                        let on_path_change = {
+                            let on_volume_mount_path_change = on_volume_mount_path_change.clone();
+                            Callback::from(move |e: Event| {
+                                let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+                                on_volume_mount_path_change(index, input.value());
+                            })
+                        };
+
+                        let on_remove = {
+                            let on_remove_volume_mount = on_remove_volume_mount.clone();
+                            Callback::from(move |_| on_remove_volume_mount(index))
+                        };
+
+                        let path_error = errors.get(&format!("volume_mount_{}", index)).cloned();
+
+                        html! {
+                            <div key={index}>
+                                <div class="label-row">
+                                    <input
+                                        type="text"
+                                        value={mount.pattern.clone()}
+                                        onchange={on_pattern_change}
+                                        placeholder="volume-name"
+                                        class="label-key"
+                                    />
+                                    <span>{ "→" }</span>
+                                    <input
+                                        type="text"
+                                        value={mount.mount_path.clone()}
+                                        onchange={on_path_change}
+                                        placeholder="/data"
+                                        class="label-value"
+                                    />
+                                    <button
+                                        type="button"
+                                        onclick={on_remove}
+                                        class="btn-danger-small"
+                                    >
+                                        { "×" }
+                                    </button>
+                                </div>
+                                if let Some(error) = path_error {
+                                    <small class="error-text" style="display: block;">{ error }</small>
+                                }
+                            </div>
+                        }
+                    })}
+
+                    <button
+                        type="button"
+                        onclick={on_add_volume_mount}
+                        class="btn-secondary-small"
+                    >
+                        { "+ Add Volume Mount" }
+                    </button>
+                </div>
+
+                <ContainerListEditor
+                    label="Init Containers"
+                    help="Run in order before the MCP server starts. Volumes must be mounted by the template above. Resource percent of the resource limit defaults to 100"
+                    containers={form_data.init_containers.clone()}
+                    on_change={on_init_containers_change}
+                />
+
+                <ContainerListEditor
+                    label="Sidecars"
+                    help="Run next to the MCP server in the same pod. Each sidecar takes its percent of the resource limit and the MCP server gets the rest"
+                    containers={form_data.sidecars.clone()}
+                    on_change={on_sidecars_change}
+                    sidecar=true
+                />
+
+                <div class="form-section">
+                    <label class="section-label">{ "Pod Security" }</label>
+                    <small class="form-help">{ "Replaces the security profile of the resource limit, it must be at least as strict" }</small>
+                    <SecurityProfileEditor
+                        profile={form_data.security.clone()}
+                        on_change={on_security_change}
+                        unset_label="Inherit from resource limit"
+                    />
+                </div>
+
+                <EgressPolicyEditor
+                    policy={form_data.egress.clone()}
+                    on_change={on_egress_change}
+                />
+
+                <div class="form-section">
+                    <label class="section-label">{ "Secret Mounts" }</label>
+                    <small class="form-help">{ "Mount secrets as read-only files, one file per key" }</small>
+
+                    { if *is_loading_secrets {
+                        html! { <p>{ "Loading secrets..." }</p> }
+                    } else {
+                        html! {
+                            <>
+                                { for form_data.secret_mounts.iter().enumerate().map(|(index, mount)| {
+                                    let on_name_change = {
+                                        let on_secret_mount_name_change = on_secret_mount_name_change.clone();
+                                        Callback::from(move |e: Event| {
+                                            let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+                                            on_secret_mount_name_change(index, input.value().trim().to_string());
+                                        })
+                                    };
+
+                                    let on_path_change = {
+                                        let on_secret_mount_path_change = on_secret_mount_path_change.clone();
+                                        Callback::from(move |e: Event| {
+                                            let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+                                            on_secret_mount_path_change(index, input.value());
+                                        })
+                                    };
+
+                                    let on_remove = {
+                                        let on_remove_secret_mount = on_remove_secret_mount.clone();
+                                        Callback::from(move |_| on_remove_secret_mount(index))
+                                    };
+
+                                    let path_error = errors.get(&format!("secret_mount_{}", index)).cloned();
+
+                                    html! {
+                                        <div key={index}>
+                                            <div class="label-row">
+                                                <input
+                                                    type="text"
+                                                    list="secret-names"
+                                                    value={mount.name.clone()}
+                                                    onchange={on_name_change}
+                                                    placeholder="github or vault:team/github"
+                                                    style="flex: 1;"
+                                                />
+                                                <span>{ "→" }</span>
+                                                <input
+                                                    type="text"
+                                                    value={mount.mount_path.clone()}
+                                                    onchange={on_path_change}
+                                                    placeholder="/etc/secret"
+                                                    class="label-value"
+                                                />
+                                                <button
+                                                    type="button"
+                                                    onclick={on_remove}
+                                                    class="btn-danger-small"
+                                                >
+                                                    { "×" }
+                                                </button>
+                                            </div>
+                                            if let Some(error) = path_error {
+                                                <small class="error-text" style="display: block;">{ error }</small>
+                                            }
+                                        </div>
+                                    }
+                                })}
+
+                                <button
+                                    type="button"
+                                    onclick={on_add_secret_mount}
+                                    class="btn-secondary-small"
+                                    disabled={*is_loading_secrets}
+                                >
+                                    { if *is_loading_secrets { "+ Loading..." } else { "+ Add Secret Mount" } }
+                                </button>
+                            </>
+                        }
+                    }}
+                </div>
+
+                <div class="form-section">
+                    <label class="section-label">{ "Persistent Workspace" }</label>
+                    <small class="form-help">{ "Give each authenticated user a persistent volume which survives session restarts. Requires a non-anonymous authorization" }</small>
+
+                    <label>
+                        <input
+                            type="checkbox"
+                            checked={form_data.workspace.is_some()}
+                            onchange={on_workspace_toggle}
+                        />
+                        { " Enable per-user workspace" }
+                    </label>
+
+                    if let Some(workspace) = &form_data.workspace {
+                        <div class="label-row">
+                            <input
+                                type="text"
+                                value={workspace.volume.clone()}
+                                onchange={on_workspace_volume_change}
+                                placeholder="volume-name"
+                                class="label-key"
+                            />
+                            <span>{ "→" }</span>
+                            <input
+                                type="text"
+                                value={workspace.mount_path.clone()}
+                                onchange={on_workspace_path_change}
+                                placeholder="/workspace"
+                                class="label-value"
+                            />
+                        </div>
+                        if let Some(error) = errors.get("workspace_mount_path").cloned() {
+                            <small class="error-text" style="display: block;">{ error }</small>
+                        }
+                        <div class="label-row">
+                            <select onchange={on_workspace_retention_change} style="flex: 1;">
+                                <option
+                                    value={WorkspaceRetention::Retain.as_str_name()}
+                                    selected={workspace.retention() == WorkspaceRetention::Retain}
+                                >
+                                    { "Retain until deleted" }
+                                </option>
+                                <option
+                                    value={WorkspaceRetention::DeleteWithTemplate.as_str_name()}
+                                    selected={workspace.retention() == WorkspaceRetention::DeleteWithTemplate}
+                                >
+                                    { "Delete with template" }
+                                </option>
+                            </select>
+                            <input
+                                type="number"
+                                value={workspace.idle_ttl.as_ref().map(|ttl| (ttl.seconds / (24 * 60 * 60)).to_string()).unwrap_or_default()}
+                                onchange={on_workspace_ttl_change}
+                                placeholder="Idle TTL (days)"
+                                min="1"
+                                class="label-value"
+                            />
+                        </div>
+                        <div class="label-row">
+                            <select onchange={on_workspace_access_mode_change} style="flex: 1;">
+                                <option
+                                    value={WorkspaceAccessMode::ReadWriteOnce.as_str_name()}
+                                    selected={workspace.access_mode() == WorkspaceAccessMode::ReadWriteOnce}
+                                >
+                                    { "One session at a time (ReadWriteOnce)" }
+                                </option>
+                                <option
+                                    value={WorkspaceAccessMode::ReadWriteMany.as_str_name()}
+                                    selected={workspace.access_mode() == WorkspaceAccessMode::ReadWriteMany}
+                                >
+                                    { "Concurrent sessions (ReadWriteMany)" }
+                                </option>
+                            </select>
+                        </div>
+                        <small class="form-help">{ "Volume must match one volume of the selected resource limit. Leave idle TTL empty to keep idle workspaces. ReadWriteMany requires a storage class that supports it" }</small>
+                    }
+                </div>
+
                 <FormField
                     label="Resource Limit *"
                     error={errors.get("resource_limit_name").cloned()}
@@ -906,37 +2079,87 @@ pub fn template_form() -> Html {
                     }
                 </FormField>
 
-                <FormField
-                    label="Authorization"
-                    error={Option::<String>::None}
-                >
+                <div class="form-section">
+                    <label class="section-label">{ "Authorizations" }</label>
+                    <small class="form-help">{ "Optional: A request is accepted when any selected authorization accepts it, the first one provides the pod service account. If none is selected, anonymous access is used." }</small>
                     if *is_loading_authorizations {
-                        <select disabled={true}>
-                            <option>{"Loading authorizations..."}</option>
-                        </select>
+                        <p class="form-help">{ "Loading authorizations..." }</p>
                     } else {
-                        <>
-                            <select
-                                onchange={on_authorization_change}
-                            >
-                                <option value="" selected={form_data.authorization_name.is_none()}>{"-- Use default (anonymous) --"}</option>
-                                { for authorizations.iter().map(|auth| {
-                                    let is_selected = form_data.authorization_name.as_ref() == Some(&auth.name);
-                                    let type_name = match auth.auth_type {
-                                        1 => "Service Account",
-                                        _ => "Unknown",
-                                    };
-                                    html! {
-                                        <option key={auth.name.clone()} value={auth.name.clone()} selected={is_selected}>
-                                            {format!("{} ({})", auth.name, type_name)}
-                                        </option>
-                                    }
-                                })}
-                            </select>
-                            <small class="form-help">{ "Optional: Select an authorization for accessing this MCP server. If not selected, anonymous access is used." }</small>
-                        </>
+                        { for authorizations.iter().map(|auth| {
+                            let on_toggle = {
+                                let on_authorization_toggle = on_authorization_toggle.clone();
+                                let name = auth.name.clone();
+                                Callback::from(move |_| on_authorization_toggle(name.clone()))
+                            };
+                            let type_name = match auth.auth_type {
+                                0 => "Anonymous",
+                                1 => "Service Account",
+                                2 => "OIDC",
+                                3 => "API Key",
+                                _ => "Unknown",
+                            };
+                            html! {
+                                <label key={auth.name.clone()} class="checkbox-label">
+                                    <input
+                                        type="checkbox"
+                                        checked={form_data.authorization_names.contains(&auth.name)}
+                                        onchange={on_toggle}
+                                    />
+                                    { format!("{} ({})", auth.name, type_name) }
+                                </label>
+                            }
+                        })}
                     }
-                </FormField>
+                </div>
+
+                <div class="form-section">
+                    <label class="section-label">{ "Validation" }</label>
+                    <label>
+                        <input
+                            type="checkbox"
+                            checked={*smoke_test}
+                            onchange={on_smoke_test_change}
+                        />
+                        { " Smoke test: start a temporary session and list its tools before creating" }
+                    </label>
+                    <small class="form-help">{ "Validate renders the session pod with a server-side dry-run and checks that a node can schedule it, nothing is created" }</small>
+                    <button
+                        type="button"
+                        class="btn-secondary-small"
+                        onclick={on_validate}
+                        disabled={*is_validating}
+                    >
+                        { if *is_validating { "Validating..." } else { "Validate" } }
+                    </button>
+                    { if let Some(report) = &*validation_report {
+                        html! {
+                            <div class="validation-report">
+                                <p>
+                                    <strong>{ if report.passed { "Passed" } else { "Failed" } }</strong>
+                                    { format!(" in {} ms", report.duration_ms) }
+                                </p>
+                                <ul>
+                                    <li>{ format!("Pod accepted: {}", if report.pod_accepted { "yes" } else { "no" }) }</li>
+                                    <li>{ format!("Schedulable: {}", match report.schedulable { Some(true) => "yes", Some(false) => "no", None => "unknown" }) }</li>
+                                    { if let Some(handshake_ok) = report.handshake_ok {
+                                        html! {
+                                            <li>
+                                                { format!(
+                                                    "Handshake: {}, protocol {}, server {}, {} tools",
+                                                    if handshake_ok { "ok" } else { "failed" },
+                                                    report.protocol_version.as_deref().unwrap_or("-"),
+                                                    report.server_name.as_deref().unwrap_or("-"),
+                                                    report.tool_count.unwrap_or_default(),
+                                                ) }
+                                            </li>
+                                        }
+                                    } else { html! {} }}
+                                    { for report.messages.iter().map(|message| html! { <li>{ message }</li> }) }
+                                </ul>
+                            </div>
+                        }
+                    } else { html! {} }}
+                </div>
 
                 <div class="form-actions">
                     <button
@@ -2,7 +2,7 @@ use crate::api::APICaller;
 use crate::components::{CopyConfigDialog, ErrorMessage, Loading, NamespaceSelector};
 use crate::models::authorization::Authorization;
 use crate::models::state::AuthState;
-use crate::models::template::Template;
+use crate::models::template::{arg_env_example, Template};
 use crate::models::SessionState;
 use crate::routes::Route;
 use yew::prelude::*;
@@ -44,35 +44,28 @@ pub fn template_list() -> Html {
         });
     }
 
-    let generate_mcp_config = |template: &Template, authorization: &Option<Authorization>| -> String {
+    let generate_mcp_config = |template: &Template, authorizations: &[Authorization]| -> String {
         let current_origin = web_sys::window()
             .and_then(|w| w.location().origin().ok())
             .unwrap_or_else(|| "http://localhost:8080".to_string());
         
         let url = format!("{}/mcp/{}/{}", current_origin, template.namespace, template.name);
         
-        let has_arg_envs = !template.arg_envs.is_empty();
-        let has_k8s_sa_auth = authorization
-            .as_ref()
-            .map(|auth| auth.auth_type == 1) // KUBERNETES_SERVICE_ACCOUNT = 1
-            .unwrap_or(false);
+        let has_arg_envs = !template.arg_env_specs.is_empty();
+        // Every type except ANONYMOUS = 0 expects a bearer token
+        let has_token_auth = authorizations.iter().any(|auth| auth.auth_type != 0);
         
         let mut headers = Vec::new();
         
         // Add arg-env headers
         if has_arg_envs {
-            for (key, value) in &template.arg_envs {
-                let type_example = if value.contains("?") || value.ends_with("?") {
-                    "\"value\" or null"
-                } else {
-                    "\"value\""
-                };
-                headers.push(format!("    \"arg-{}\": {}", key, type_example));
+            for (key, spec) in &template.arg_env_specs {
+                headers.push(format!("    \"arg-{}\": {}", key, arg_env_example(spec)));
             }
         }
         
-        // Add Authorization header if K8s SA
-        if has_k8s_sa_auth {
+        // Add Authorization header if a token is required
+        if has_token_auth {
             headers.push(format!("    \"Authorization\": \"Bearer <token>\""));
         }
         
@@ -121,24 +114,18 @@ r#"{{
             wasm_bindgen_futures::spawn_local(async move {
                 let api = APICaller::new(auth_state.access_token.clone());
                 
-                // Load authorization if specified
-                let authorization = if let Some(auth_name) = &template.authorization_name {
-                    if !auth_name.is_empty() {
-                        match api.get_authorization(template.namespace.clone(), auth_name.clone()).await {
-                            Ok(auth) => Some(auth),
-                            Err(e) => {
-                                web_sys::console::error_1(&format!("Failed to load authorization: {}", e).into());
-                                None
-                            }
+                // Load authorizations if specified
+                let mut authorizations = Vec::new();
+                for auth_name in &template.authorization_names {
+                    match api.get_authorization(template.namespace.clone(), auth_name.clone()).await {
+                        Ok(auth) => authorizations.push(auth),
+                        Err(e) => {
+                            web_sys::console::error_1(&format!("Failed to load authorization: {}", e).into());
                         }
-                    } else {
-                        None
                     }
-                } else {
-                    None
-                };
+                }
                 
-                let config = generate_mcp_config(&template, &authorization);
+                let config = generate_mcp_config(&template, &authorizations);
                 copy_config.set(config);
                 show_copy_dialog.set(true);
             });
@@ -0,0 +1,197 @@
+use crate::api::APICaller;
+use crate::components::session_quota_editor::format_bytes;
+use crate::components::{ErrorMessage, FormField, Loading, NamespaceSelector};
+use crate::models::state::AuthState;
+use crate::models::SessionState;
+use proto_web::{GetUsageReportRequest, GetUsageReportResponse, UsageGroupBy};
+use yew::prelude::*;
+use yewdux::prelude::*;
+
+#[derive(Debug)]
+enum LoadState {
+    Loading,
+    Loaded(GetUsageReportResponse),
+    Error(String),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct ReportFilter {
+    group_by: UsageGroupBy,
+    /// `YYYY-MM-DD`, empty for the default range of the last 30 days.
+    start: String,
+    end: String,
+}
+
+impl ReportFilter {
+    fn to_request(&self, namespace: Option<String>) -> GetUsageReportRequest {
+        let date = |value: &str, time: &str| {
+            Some(value.trim())
+                .filter(|x| !x.is_empty())
+                .map(|x| format!("{}T{}Z", x, time))
+        };
+        GetUsageReportRequest {
+            namespace,
+            group_by: self.group_by as i32,
+            start: date(&self.start, "00:00:00"),
+            end: date(&self.end, "23:59:59"),
+            csv: true,
+        }
+    }
+}
+
+/// `1.5h`, `3.2d` of a duration in seconds.
+fn format_seconds(seconds: f64) -> String {
+    let (value, unit) = if seconds >= 86400.0 {
+        (seconds / 86400.0, "d")
+    } else if seconds >= 3600.0 {
+        (seconds / 3600.0, "h")
+    } else {
+        (seconds / 60.0, "m")
+    };
+    format!("{}{}", (value * 10.0).round() / 10.0, unit)
+}
+
+fn format_core_hours(cpu_seconds: f64) -> String {
+    format!("{}", (cpu_seconds / 3600.0 * 100.0).round() / 100.0)
+}
+
+fn format_byte_hours(byte_seconds: f64) -> String {
+    format!("{}·h", format_bytes(byte_seconds / 3600.0))
+}
+
+#[function_component(UsageReport)]
+pub fn usage_report() -> Html {
+    let load_state = use_state(|| LoadState::Loading);
+    let filter = use_state(|| ReportFilter {
+        group_by: UsageGroupBy::Namespace,
+        start: String::new(),
+        end: String::new(),
+    });
+    let (session_state, _) = use_store::<SessionState>();
+    let (auth_state, _) = use_store::<AuthState>();
+    let namespace = session_state.selected_namespace.clone();
+
+    {
+        let load_state = load_state.clone();
+        let api = APICaller::new(auth_state.access_token.clone());
+        use_effect_with(
+            ((*filter).clone(), namespace.clone()),
+            move |(filter, namespace)| {
+                let request = filter.to_request(namespace.clone());
+                load_state.set(LoadState::Loading);
+                wasm_bindgen_futures::spawn_local(async move {
+                    match api.get_usage_report(request).await {
+                        Ok(report) => load_state.set(LoadState::Loaded(report)),
+                        Err(e) => load_state.set(LoadState::Error(e)),
+                    }
+                });
+                || ()
+            },
+        );
+    }
+
+    let on_group_by = {
+        let filter = filter.clone();
+        Callback::from(move |e: Event| {
+            let select: web_sys::HtmlSelectElement = e.target_unchecked_into();
+            let mut data = (*filter).clone();
+            data.group_by =
+                UsageGroupBy::from_str_name(&select.value()).unwrap_or(UsageGroupBy::Namespace);
+            filter.set(data);
+        })
+    };
+    let on_date = |f: fn(&mut ReportFilter, String)| {
+        let filter = filter.clone();
+        Callback::from(move |e: Event| {
+            let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+            let mut data = (*filter).clone();
+            f(&mut data, input.value());
+            filter.set(data);
+        })
+    };
+    let key_title = match filter.group_by {
+        UsageGroupBy::Namespace => "Namespace",
+        UsageGroupBy::Template => "Template",
+        UsageGroupBy::Subject => "User",
+    };
+
+    html! {
+        <div class="container">
+            <NamespaceSelector />
+
+            <div class="header">
+                <h1>{ "Usage Report" }</h1>
+                if let LoadState::Loaded(GetUsageReportResponse { csv: Some(csv), .. }) = &*load_state {
+                    <a
+                        href={format!("data:text/csv;charset=utf-8,{}", String::from(js_sys::encode_uri_component(csv)))}
+                        download="usage-report.csv"
+                    >
+                        <button class="btn-secondary">{ "Download CSV" }</button>
+                    </a>
+                }
+            </div>
+
+            <div class="form">
+                <FormField label="Group By" error={None::<String>}>
+                    <select onchange={on_group_by}>
+                        { for [
+                            (UsageGroupBy::Namespace, "Namespace"),
+                            (UsageGroupBy::Template, "Template"),
+                            (UsageGroupBy::Subject, "User"),
+                        ].into_iter().map(|(value, label)| html! {
+                            <option value={value.as_str_name()} selected={filter.group_by == value}>{ label }</option>
+                        }) }
+                    </select>
+                </FormField>
+                <FormField label="From" error={None::<String>}>
+                    <input type="date" value={filter.start.clone()} onchange={on_date(|f, v| f.start = v)} />
+                </FormField>
+                <FormField label="To" error={None::<String>}>
+                    <input type="date" value={filter.end.clone()} onchange={on_date(|f, v| f.end = v)} />
+                    <small class="form-help">{ "Defaults to the last 30 days, dates are in UTC" }</small>
+                </FormField>
+            </div>
+
+            { match &*load_state {
+                LoadState::Loading => html! { <Loading /> },
+                LoadState::Error(e) => html! { <ErrorMessage message={e.clone()} /> },
+                LoadState::Loaded(report) if report.rows.is_empty() => html! {
+                    <div class="empty-state">
+                        <p>{ "No sessions in this range." }</p>
+                    </div>
+                },
+                LoadState::Loaded(report) => html! {
+                    <>
+                        <small class="form-help">{ format!("{} ~ {}", report.start, report.end) }</small>
+                        <table class="data-table">
+                            <thead>
+                                <tr>
+                                    <th>{ key_title }</th>
+                                    <th>{ "Sessions" }</th>
+                                    <th>{ "Session Time" }</th>
+                                    <th>{ "Requested CPU (core·h)" }</th>
+                                    <th>{ "Requested Memory" }</th>
+                                    <th>{ "Used CPU (core·h)" }</th>
+                                    <th>{ "Used Memory" }</th>
+                                </tr>
+                            </thead>
+                            <tbody>
+                                { for report.rows.iter().map(|row| html! {
+                                    <tr key={row.key.clone()}>
+                                        <td>{ if row.key.is_empty() { "(anonymous)" } else { row.key.as_str() } }</td>
+                                        <td>{ row.sessions }</td>
+                                        <td>{ format_seconds(row.session_seconds) }</td>
+                                        <td>{ format_core_hours(row.cpu_seconds) }</td>
+                                        <td>{ format_byte_hours(row.memory_byte_seconds) }</td>
+                                        <td>{ row.used_cpu_seconds.map(format_core_hours).unwrap_or_else(|| "-".to_string()) }</td>
+                                        <td>{ row.used_memory_byte_seconds.map(format_byte_hours).unwrap_or_else(|| "-".to_string()) }</td>
+                                    </tr>
+                                }) }
+                            </tbody>
+                        </table>
+                    </>
+                },
+            }}
+        </div>
+    }
+}
@@ -32,6 +32,8 @@ pub enum Route {
     SecretDetail { namespace: String, name: String },
     #[at("/secrets/:namespace/:name/edit")]
     SecretEdit { namespace: String, name: String },
+    #[at("/my-secrets")]
+    UserSecretList,
 
     #[at("/resource-limits")]
     ResourceLimitList,
@@ -47,6 +49,9 @@ pub enum Route {
     #[at("/authorizations/:namespace/:name")]
     AuthorizationDetail { namespace: String, name: String },
 
+    #[at("/usage")]
+    UsageReport,
+
     #[at("/callback")]
     OAuthCallback,
 
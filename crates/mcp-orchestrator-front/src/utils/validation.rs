@@ -1,3 +1,4 @@
+use proto_web::{ArgEnvSpec, ArgEnvType};
 use std::collections::HashMap;
 
 #[allow(dead_code)]
@@ -92,73 +93,47 @@ pub fn validate_arg_env_key(key: &str) -> Option<String> {
     None
 }
 
-pub fn validate_arg_env_value(value: &str) -> Option<String> {
-    if value.is_empty() {
-        return Some("Value is required".to_string());
+pub fn validate_arg_env_spec(spec: &ArgEnvSpec) -> Option<String> {
+    if let Some(error) = spec.env_name.as_deref().and_then(validate_arg_env_name) {
+        return Some(error);
     }
-    
-    // ^((?<ENV_NAME>[A-Za-z0-9_-]+)\s*:\s*)?(?<TYPENAME>[a-z][a-z0-9-]*\??)$
-    
-    // {ENV_NAME}: {TYPE} 형태인지 확인
-    if let Some((env_name, type_part)) = value.split_once(':') {
-        let env_name = env_name.trim();
-        let type_part = type_part.trim();
-        
-        // 환경변수명 검증: [A-Za-z0-9_-]+
-        if env_name.is_empty() {
-            return Some("Environment variable name is required before ':'".to_string());
-        }
-        
-        if !env_name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-') {
-            return Some("Environment variable name can only contain letters (A-Z, a-z), digits (0-9), underscores (_), and hyphens (-)".to_string());
-        }
-        
-        // 타입 검증: ^[a-z][a-z0-9-]*\??$ (끝에 선택적으로 ? 허용)
-        if type_part.is_empty() {
-            return Some("Type is required after ':'".to_string());
+
+    match spec.r#type() {
+        ArgEnvType::Enum if spec.allowed_values.is_empty() => {
+            return Some("Enum requires at least one allowed value".to_string());
         }
-        
-        let first_char = type_part.chars().next().unwrap();
-        if !first_char.is_ascii_lowercase() {
-            return Some("Type must start with a lowercase letter".to_string());
+        ArgEnvType::Enum => {}
+        _ if !spec.allowed_values.is_empty() => {
+            return Some("Allowed values can only be set for enum".to_string());
         }
-        
-        if !type_part.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '?') {
-            return Some("Type can only contain lowercase letters, digits, hyphens, and optional '?' at the end".to_string());
+        _ => {}
+    }
+
+    if spec.pattern.is_some() && spec.r#type() != ArgEnvType::String {
+        return Some("Pattern can only be set for string".to_string());
+    }
+
+    // 기본값 형식 검증, 패턴과 URL 은 서버에서 검증
+    let Some(default_value) = spec.default_value.as_deref() else {
+        return None;
+    };
+    match spec.r#type() {
+        ArgEnvType::Int if default_value.trim().parse::<i64>().is_err() => {
+            Some("Default value must be an integer".to_string())
         }
-        
-        // ? 는 끝에만 올 수 있고 최대 1개만 허용
-        let question_count = type_part.chars().filter(|&c| c == '?').count();
-        if question_count > 1 {
-            return Some("Type can only have one '?' at the end".to_string());
+        ArgEnvType::Bool
+            if !matches!(
+                default_value.trim().to_ascii_lowercase().as_str(),
+                "true" | "false" | "1" | "0" | "yes" | "no" | "on" | "off"
+            ) =>
+        {
+            Some("Default value must be a boolean".to_string())
         }
-        if question_count == 1 && !type_part.ends_with('?') {
-            return Some("'?' can only appear at the end of the type".to_string());
+        ArgEnvType::Enum if !spec.allowed_values.iter().any(|x| x == default_value) => {
+            Some("Default value must be one of the allowed values".to_string())
         }
-        
-        return None;
-    }
-    
-    // {TYPE} 형태만 있는 경우: ^[a-z][a-z0-9-]*\??$ (끝에 선택적으로 ? 허용)
-    let first_char = value.chars().next().unwrap();
-    if !first_char.is_ascii_lowercase() {
-        return Some("Type must start with a lowercase letter".to_string());
-    }
-    
-    if !value.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '?') {
-        return Some("Type can only contain lowercase letters, digits, hyphens, and optional '?' at the end".to_string());
-    }
-    
-    // ? 는 끝에만 올 수 있고 최대 1개만 허용
-    let question_count = value.chars().filter(|&c| c == '?').count();
-    if question_count > 1 {
-        return Some("Type can only have one '?' at the end".to_string());
+        _ => None,
     }
-    if question_count == 1 && !value.ends_with('?') {
-        return Some("'?' can only appear at the end of the type".to_string());
-    }
-    
-    None
 }
 
 pub fn validate_arg_env_name(env_name: &str) -> Option<String> {
@@ -173,3 +148,37 @@ pub fn validate_arg_env_name(env_name: &str) -> Option<String> {
     
     None
 }
+
+pub fn validate_claim_env_name(env_name: &str) -> Option<String> {
+    if env_name.is_empty() {
+        return Some("Environment variable name is required".to_string());
+    }
+
+    // [A-Za-z_][A-Za-z0-9_]*, annotation 이름 길이 제한으로 최대 57자
+    if env_name.len() > 57 {
+        return Some("Environment variable name must be at most 57 characters".to_string());
+    }
+    if env_name.starts_with(|c: char| c.is_ascii_digit())
+        || !env_name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+    {
+        return Some("Environment variable name can only contain letters (A-Z, a-z), digits (0-9) and underscores (_), and must not start with a digit".to_string());
+    }
+
+    None
+}
+
+pub fn validate_mount_path(path: &str) -> Option<String> {
+    if path.is_empty() {
+        return Some("Mount path is required".to_string());
+    }
+
+    if !path.starts_with('/') {
+        return Some("Mount path must be an absolute path".to_string());
+    }
+
+    if path.contains(':') || path.split('/').any(|seg| seg == "..") {
+        return Some("Mount path must not contain ':' or '..'".to_string());
+    }
+
+    None
+}
@@ -5,7 +5,7 @@ use figment::{
 };
 use oidc_auth::OpenIdConfig;
 use serde::{Deserialize, Serialize};
-use std::{path::PathBuf, time::Duration};
+use std::{collections::HashMap, path::PathBuf, time::Duration};
 
 #[derive(Debug, Clone, Parser)]
 #[command(name = "mcp-orchestrator")]
@@ -166,6 +166,10 @@ pub struct McpConfig {
 
     #[serde(with = "humantime_serde", default = "default_session_max_idle_time")]
     pub session_max_idle_time: Duration,
+
+    /// How long a session scheduled for recycling waits for its in-flight calls.
+    #[serde(with = "humantime_serde", default = "default_recycle_drain_timeout")]
+    pub recycle_drain_timeout: Duration,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -180,6 +184,114 @@ pub struct AuthConfig {
     pub openid: Option<OpenIdConfig>,
 }
 
+/// Runner images of package based templates.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PackageConfig {
+    #[serde(default = "default_npm_image")]
+    pub npm_image: String,
+
+    #[serde(default = "default_pypi_image")]
+    pub pypi_image: String,
+}
+
+/// Images admitted for templates, an empty allowlist admits every image.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ImageAdmissionPolicy {
+    /// Globs of `registry/repository`, e.g. `ghcr.io/acme/*` or `docker.io/library/node`.
+    #[serde(default)]
+    pub allowed: Vec<String>,
+
+    /// Rejects images which are not referenced by digest.
+    #[serde(default)]
+    pub require_digest: bool,
+
+    /// Resolves tags to digests at template creation and stores the digest reference.
+    #[serde(default)]
+    pub resolve_digest: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ImageAdmissionConfig {
+    #[serde(flatten)]
+    pub default: ImageAdmissionPolicy,
+
+    /// Replaces the cluster-wide policy for the namespace.
+    #[serde(default)]
+    pub namespaces: HashMap<String, ImageAdmissionPolicy>,
+}
+
+impl ImageAdmissionConfig {
+    pub fn policy(&self, namespace: &str) -> &ImageAdmissionPolicy {
+        self.namespaces.get(namespace).unwrap_or(&self.default)
+    }
+}
+
+/// HashiCorp Vault KV v2 engine, referenced as `vault:<path>`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VaultBackendConfig {
+    /// e.g. `https://vault.example.com:8200`
+    pub address: String,
+
+    /// Mount path of the KV v2 engine.
+    #[serde(default = "default_vault_mount")]
+    pub mount: String,
+
+    #[serde(default)]
+    pub token: Option<String>,
+
+    /// Read on every request so a token renewed by Vault Agent is picked up.
+    #[serde(default)]
+    pub token_file: Option<PathBuf>,
+
+    /// Enterprise namespace sent as `X-Vault-Namespace`.
+    #[serde(default)]
+    pub namespace: Option<String>,
+
+    /// Prefixes every path with the orchestrator namespace of the template,
+    /// disable only when every namespace may read every secret.
+    #[serde(default = "default_secret_backend_namespaced")]
+    pub namespaced: bool,
+
+    #[serde(with = "humantime_serde", default = "default_vault_timeout")]
+    pub timeout: Duration,
+}
+
+/// Directories of key files for local development, referenced as `file:<path>`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileBackendConfig {
+    pub root: PathBuf,
+
+    /// Reads `<root>/<namespace>/<path>`, the orchestrator namespace of the template.
+    #[serde(default = "default_secret_backend_namespaced")]
+    pub namespaced: bool,
+}
+
+/// Secret backends in addition to Kubernetes Secrets, which are always available.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct SecretBackendConfig {
+    #[serde(default)]
+    pub vault: Option<VaultBackendConfig>,
+
+    #[serde(default)]
+    pub file: Option<FileBackendConfig>,
+}
+
+/// Session usage metering for chargeback reports.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MeteringConfig {
+    /// How often the actual usage is sampled, records are opened and closed with the session.
+    #[serde(with = "humantime_serde", default = "default_metering_interval")]
+    pub interval: Duration,
+
+    /// Ended sessions older than this are removed from the usage records.
+    #[serde(with = "humantime_serde", default = "default_metering_retention")]
+    pub retention: Duration,
+
+    /// Samples the actual usage from metrics.k8s.io when metrics-server is installed.
+    #[serde(default = "default_metering_sample_metrics")]
+    pub sample_metrics: bool,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct AppConfig {
     #[serde(default)]
@@ -193,6 +305,18 @@ pub struct AppConfig {
 
     #[serde(default)]
     pub auth: AuthConfig,
+
+    #[serde(default)]
+    pub package: PackageConfig,
+
+    #[serde(default)]
+    pub image_admission: ImageAdmissionConfig,
+
+    #[serde(default)]
+    pub metering: MeteringConfig,
+
+    #[serde(default)]
+    pub secret_backends: SecretBackendConfig,
 }
 
 fn default_keep_alive() -> Option<Duration> {
@@ -203,6 +327,10 @@ fn default_session_max_idle_time() -> Duration {
     std::time::Duration::from_mins(30)
 }
 
+fn default_recycle_drain_timeout() -> Duration {
+    std::time::Duration::from_mins(5)
+}
+
 fn default_url() -> String {
     "http://localhost:3000".to_string()
 }
@@ -231,6 +359,38 @@ fn default_allow_expireless_token() -> bool {
     false
 }
 
+fn default_npm_image() -> String {
+    "node:22-alpine".to_string()
+}
+
+fn default_pypi_image() -> String {
+    "ghcr.io/astral-sh/uv:python3.12-alpine".to_string()
+}
+
+fn default_vault_mount() -> String {
+    "secret".to_string()
+}
+
+fn default_vault_timeout() -> Duration {
+    std::time::Duration::from_secs(10)
+}
+
+fn default_secret_backend_namespaced() -> bool {
+    true
+}
+
+fn default_metering_interval() -> Duration {
+    std::time::Duration::from_mins(1)
+}
+
+fn default_metering_retention() -> Duration {
+    std::time::Duration::from_hours(24 * 90)
+}
+
+fn default_metering_sample_metrics() -> bool {
+    true
+}
+
 impl Default for ServerConfig {
     fn default() -> Self {
         Self {
@@ -257,6 +417,7 @@ impl Default for McpConfig {
         Self {
             keep_alive: default_keep_alive(),
             session_max_idle_time: default_session_max_idle_time(),
+            recycle_drain_timeout: default_recycle_drain_timeout(),
         }
     }
 }
@@ -271,6 +432,25 @@ impl Default for AuthConfig {
     }
 }
 
+impl Default for PackageConfig {
+    fn default() -> Self {
+        Self {
+            npm_image: default_npm_image(),
+            pypi_image: default_pypi_image(),
+        }
+    }
+}
+
+impl Default for MeteringConfig {
+    fn default() -> Self {
+        Self {
+            interval: default_metering_interval(),
+            retention: default_metering_retention(),
+            sample_metrics: default_metering_sample_metrics(),
+        }
+    }
+}
+
 impl AppConfig {
     #[allow(clippy::result_large_err)]
     pub fn load() -> Result<Self, figment::Error> {
@@ -32,6 +32,9 @@ pub enum AppError {
 
     #[error("Protected namespace: {0}")]
     ProtectedNamespace(String),
+
+    #[error("Quota exceeded: {0}")]
+    QuotaExceeded(String),
 }
 
 impl IntoResponse for AppError {
@@ -42,6 +45,9 @@ impl IntoResponse for AppError {
             AppError::InvalidInput(msg) => (axum::http::StatusCode::BAD_REQUEST, msg.clone()),
             AppError::ProtectedNamespace(msg) => (axum::http::StatusCode::FORBIDDEN, msg.clone()),
             AppError::InvalidArgEnv(msg) => (axum::http::StatusCode::BAD_REQUEST, msg.clone()),
+            AppError::QuotaExceeded(msg) => {
+                (axum::http::StatusCode::TOO_MANY_REQUESTS, msg.clone())
+            }
             _ => (
                 axum::http::StatusCode::INTERNAL_SERVER_ERROR,
                 self.to_string(),
@@ -0,0 +1,51 @@
+use proto::mcp::orchestrator::v1::*;
+use tonic::{Request, Response, Status};
+
+use crate::error::AppError;
+use crate::state::AppState;
+use crate::storage::bundle::{Bundle, export_bundle as export, import_bundle as import};
+
+pub async fn export_bundle(
+    state: &AppState,
+    request: Request<ExportBundleRequest>,
+) -> Result<Response<ExportBundleResponse>, Status> {
+    let req = request.into_inner();
+
+    let yaml = export(&state.kube_store, req.namespace, &req.template_names)
+        .await
+        .and_then(|bundle| bundle.to_yaml())
+        .map_err(|e| match e {
+            AppError::NotFound(msg) => Status::not_found(msg),
+            _ => Status::internal(format!("Failed to export bundle: {}", e)),
+        })?;
+
+    Ok(Response::new(ExportBundleResponse { yaml }))
+}
+
+pub async fn import_bundle(
+    state: &AppState,
+    request: Request<ImportBundleRequest>,
+) -> Result<Response<ImportBundleResponse>, Status> {
+    let req = request.into_inner();
+    let bundle =
+        Bundle::from_yaml(&req.yaml).map_err(|e| Status::invalid_argument(e.to_string()))?;
+    tracing::info!(
+        "Importing bundle into {:?}, dry_run={}",
+        req.namespace,
+        req.dry_run
+    );
+
+    let items = import(
+        &state.kube_store,
+        req.namespace.clone(),
+        &bundle,
+        req.conflict_policy(),
+        req.dry_run,
+    )
+    .await;
+
+    Ok(Response::new(ImportBundleResponse {
+        dry_run: req.dry_run,
+        items,
+    }))
+}
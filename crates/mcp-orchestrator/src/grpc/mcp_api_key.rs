@@ -0,0 +1,102 @@
+use chrono::Duration;
+use proto::mcp::orchestrator::v1::{
+    ApiKeyResponse, IssueApiKeyRequest, IssueApiKeyResponse, ListApiKeysRequest,
+    ListApiKeysResponse, RevokeApiKeyRequest, RevokeApiKeyResponse,
+};
+use tonic::{Request, Response, Status};
+
+use crate::{
+    error::AppError, grpc::utils::ProtoWktTime, state::AppState,
+    storage::authorization_api_key::ApiKeyEntry,
+};
+
+fn from(entry: ApiKeyEntry) -> ApiKeyResponse {
+    ApiKeyResponse {
+        name: entry.name,
+        created_at: Some(entry.created_at.to_wkt_time()),
+        expire_at: entry.expire_at.map(|x| x.to_wkt_time()),
+        last_used_at: entry.last_used_at.map(|x| x.to_wkt_time()),
+    }
+}
+
+fn map_err(action: &'static str) -> impl Fn(AppError) -> Status {
+    move |e| match e {
+        AppError::InvalidInput(msg) => Status::invalid_argument(msg),
+        AppError::NotFound(msg) => Status::not_found(msg),
+        _ => Status::internal(format!("Failed to {} api key: {}", action, e)),
+    }
+}
+
+pub async fn issue_api_key(
+    state: &AppState,
+    request: Request<IssueApiKeyRequest>,
+) -> Result<Response<IssueApiKeyResponse>, Status> {
+    let req = request.into_inner();
+    let store = state.kube_store.authorization(req.namespace.clone());
+    //
+    if req.expire_duration.is_none() && !state.config.auth.allow_expireless_token {
+        return Err(Status::invalid_argument(
+            "expire_duration is not allowed, server requires expire_duration to be set",
+        ));
+    }
+    let expire_duration = req
+        .expire_duration
+        .map(|dur| {
+            let dur = Duration::new(dur.seconds, dur.nanos as u32)
+                .filter(|x| *x > Duration::zero())
+                .ok_or_else(|| Status::invalid_argument("expire_duration must be positive"))?;
+            if dur > Duration::days(365) {
+                return Err(Status::invalid_argument(
+                    "expire_duration cannot be more than 365 days",
+                ));
+            }
+            Ok(dur)
+        })
+        .transpose()?;
+    //
+    let (entry, api_key) = store
+        .issue_api_key(&req.name, &req.key_name, expire_duration)
+        .await
+        .map_err(map_err("issue"))?;
+
+    Ok(Response::new(IssueApiKeyResponse {
+        key: Some(from(entry)),
+        api_key,
+    }))
+}
+
+pub async fn list_api_keys(
+    state: &AppState,
+    request: Request<ListApiKeysRequest>,
+) -> Result<Response<ListApiKeysResponse>, Status> {
+    let req = request.into_inner();
+    let store = state.kube_store.authorization(req.namespace.clone());
+
+    let authorization = store
+        .get(&req.name)
+        .await
+        .map_err(map_err("list"))?
+        .ok_or_else(|| Status::not_found(format!("Authorization {} not found", req.name)))?;
+
+    Ok(Response::new(ListApiKeysResponse {
+        data: authorization.api_keys.into_iter().map(from).collect(),
+    }))
+}
+
+pub async fn revoke_api_key(
+    state: &AppState,
+    request: Request<RevokeApiKeyRequest>,
+) -> Result<Response<RevokeApiKeyResponse>, Status> {
+    let req = request.into_inner();
+    let store = state.kube_store.authorization(req.namespace.clone());
+
+    store
+        .revoke_api_key(&req.name, &req.key_name)
+        .await
+        .map_err(map_err("revoke"))?;
+
+    Ok(Response::new(RevokeApiKeyResponse {
+        success: true,
+        message: format!("Api key {} revoked", req.key_name),
+    }))
+}
@@ -1,6 +1,7 @@
 use proto::mcp::orchestrator::v1::*;
 use tonic::{Request, Response, Status};
 
+use crate::error::AppError;
 use crate::grpc::utils::convert_label_query;
 use crate::state::AppState;
 use crate::storage::store_authorization::AuthorizationData;
@@ -39,7 +40,10 @@ pub async fn create_authorization(
     let auth = store
         .create(&req.name, req.labels.into_iter(), r#type, &data)
         .await
-        .map_err(|e| Status::internal(format!("Failed to create resource limit: {}", e)))?;
+        .map_err(|e| match e {
+            AppError::InvalidInput(msg) => Status::invalid_argument(msg),
+            _ => Status::internal(format!("Failed to create authorization: {}", e)),
+        })?;
 
     Ok(Response::new(from(auth)))
 }
@@ -105,7 +109,17 @@ pub async fn delete_authorization(
 ) -> Result<Response<DeleteAuthorizationResponse>, Status> {
     let req = request.into_inner();
     let store = state.kube_store.authorization(req.namespace.clone());
-    //
+
+    let is_deletable = store
+        .is_deletable(&req.name)
+        .await
+        .map_err(|e| Status::internal(format!("Failed to check deletability: {}", e)))?;
+
+    if !is_deletable && !req.force {
+        return Err(Status::failed_precondition(
+            "Authorization is in use by MCP templates. Use force=true to delete anyway.",
+        ));
+    }
 
     let result = store
         .delete(
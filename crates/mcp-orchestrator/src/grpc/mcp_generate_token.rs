@@ -1,15 +1,41 @@
 use chrono::Duration;
 use proto::mcp::orchestrator::v1::{
-    AuthorizationType, GenerateTokenRequest, GenerateTokenResponse,
+    AuthorizationType, GenerateTokenRequest, GenerateTokenResponse, IssuedTokenResponse,
+    ListTokensRequest, ListTokensResponse, RevokeTokenRequest, RevokeTokenResponse,
 };
 use tonic::{Request, Response, Status};
 
-use crate::{grpc::utils::ProtoWktTime, state::AppState};
+use crate::{
+    error::AppError,
+    grpc::utils::{ProtoWktTime, authenticated_subject},
+    state::AppState,
+    storage::authorization_token::IssuedTokenData,
+};
+
+fn from(token: IssuedTokenData) -> IssuedTokenResponse {
+    IssuedTokenResponse {
+        id: token.id,
+        requester: token.record.requester,
+        audience: token.record.audience,
+        bound: token.record.bound,
+        created_at: Some(token.created_at.to_wkt_time()),
+        expire_at: token.record.expire_at.map(|x| x.to_wkt_time()),
+    }
+}
+
+fn map_err(action: &'static str) -> impl Fn(AppError) -> Status {
+    move |e| match e {
+        AppError::InvalidInput(msg) => Status::invalid_argument(msg),
+        AppError::NotFound(msg) => Status::not_found(msg),
+        _ => Status::internal(format!("Failed to {} token: {}", action, e)),
+    }
+}
 
 pub async fn generate_token(
     state: &AppState,
     request: Request<GenerateTokenRequest>,
 ) -> Result<Response<GenerateTokenResponse>, Status> {
+    let requester = authenticated_subject(&request).ok();
     let req = request.into_inner();
     let store = state.kube_store.authorization(req.namespace.clone());
     //
@@ -44,13 +70,60 @@ pub async fn generate_token(
             req.name
         )));
     }
-    let (token, expire_at) = store
-        .generate_token(&req.name, &state.config.auth.audience, expire_duration)
+    let (issued, token) = store
+        .generate_token(
+            &req.name,
+            &state.config.auth.audience,
+            expire_duration,
+            requester.as_deref(),
+            req.bound.unwrap_or(true),
+        )
         .await
-        .map_err(|e| Status::internal(format!("Failed to generate token: {}", e)))?;
+        .map_err(map_err("generate"))?;
 
     Ok(Response::new(GenerateTokenResponse {
         token,
-        expire_at: Some(expire_at.to_wkt_time()),
+        expire_at: issued.record.expire_at.map(|x| x.to_wkt_time()),
+        id: issued.id,
+    }))
+}
+
+pub async fn list_tokens(
+    state: &AppState,
+    request: Request<ListTokensRequest>,
+) -> Result<Response<ListTokensResponse>, Status> {
+    let req = request.into_inner();
+    let store = state.kube_store.authorization(req.namespace.clone());
+
+    store
+        .get(&req.name)
+        .await
+        .map_err(map_err("list"))?
+        .ok_or_else(|| Status::not_found(format!("Authorization {} not found", req.name)))?;
+    let tokens = store
+        .list_tokens(&req.name)
+        .await
+        .map_err(map_err("list"))?;
+
+    Ok(Response::new(ListTokensResponse {
+        data: tokens.into_iter().map(from).collect(),
+    }))
+}
+
+pub async fn revoke_token(
+    state: &AppState,
+    request: Request<RevokeTokenRequest>,
+) -> Result<Response<RevokeTokenResponse>, Status> {
+    let req = request.into_inner();
+    let store = state.kube_store.authorization(req.namespace.clone());
+
+    store
+        .revoke_token(&req.name, &req.id)
+        .await
+        .map_err(map_err("revoke"))?;
+
+    Ok(Response::new(RevokeTokenResponse {
+        success: true,
+        message: format!("Token {} revoked", req.id),
     }))
 }
@@ -7,6 +7,8 @@ use crate::{
     state::AppState,
     storage::{
         McpTemplateCreate, McpTemplateData,
+        template_import::import_template,
+        template_package::runner_image,
         util_delete::{DeleteOption, DeleteResult},
         util_list::ListOption,
     },
@@ -22,49 +24,126 @@ fn from(rl: McpTemplateData) -> McpTemplateResponse {
         args: rl.args,
         envs: rl.envs,
         arg_envs: rl.arg_envs,
+        arg_env_specs: rl.arg_env_specs,
         secret_envs: rl.secret_envs,
         resource_limit_name: rl.resource_limit_name,
-        authorization_name: rl.authorization_name,
+        authorization_name: rl.authorization_names.first().cloned().unwrap_or_default(),
+        authorization_names: rl.authorization_names,
         volume_mounts: rl.volume_mounts,
         secret_mounts: rl.secret_mounts,
+        workspace: rl.workspace,
+        claim_envs: rl.claim_envs,
+        package: rl.package,
+        init_containers: rl.init_containers,
+        sidecars: rl.sidecars,
+        validation: None,
+        security: rl.security,
+        egress: Some(rl.egress.unwrap_or_default()),
+        image_pull_secrets: rl.image_pull_secrets,
+        secret_rotation: rl.secret_rotation.into(),
         created_at: rl.created_at.to_rfc3339(),
         deleted_at: rl.deleted_at.map(|dt| dt.to_rfc3339()),
     }
 }
 
+/// Merges the deprecated single authorization into the list, anonymous when both are empty.
+fn authorization_names(name: Option<String>, names: Vec<String>) -> Vec<String> {
+    let mut result: Vec<String> = name.into_iter().filter(|x| !names.contains(x)).collect();
+    result.extend(names);
+    if result.is_empty() {
+        result.push("anonymous".to_string());
+    }
+    result
+}
+
 pub async fn create_mcp_template(
     state: &AppState,
     request: Request<CreateMcpTemplateRequest>,
 ) -> Result<Response<McpTemplateResponse>, Status> {
     let req: CreateMcpTemplateRequest = request.into_inner();
+    let secret_rotation = req.secret_rotation();
     let store = state.kube_store.mcp_templates(req.namespace.clone());
     tracing::info!("Creating MCP template: {:?}", req.name);
     tracing::debug!("MCP template request: {:?}", req);
+    let image = match &req.package {
+        Some(package) if req.image.is_empty() => runner_image(package, &state.config.package),
+        _ => req.image,
+    };
 
-    let mt = store
-        .create(
-            &req.name,
-            req.labels.into_iter(),
-            McpTemplateCreate {
-                image: req.image,
-                command: req.command,
-                args: req.args,
-                envs: req.envs,
-                arg_envs: req.arg_envs,
-                secret_envs: req.secret_envs,
-                resource_limit_name: req.resource_limit_name,
-                authorization_name: req.authorization_name.unwrap_or("anonymous".to_string()),
-                volume_mounts: req.volume_mounts,
-                secret_mounts: req.secret_mounts,
-            },
-        )
+    let map_create_err = |e: AppError| {
+        tracing::error!("Failed to create MCP template: {}", e);
+        match e {
+            AppError::InvalidInput(msg) => Status::invalid_argument(msg),
+            AppError::NotFound(msg) => Status::not_found(msg),
+            _ => Status::internal(format!("Failed to create MCP template: {}", e)),
+        }
+    };
+    let create = McpTemplateCreate {
+        image,
+        command: req.command,
+        args: req.args,
+        envs: req.envs,
+        arg_envs: req.arg_envs,
+        arg_env_specs: req.arg_env_specs,
+        secret_envs: req.secret_envs,
+        resource_limit_name: req.resource_limit_name,
+        authorization_names: authorization_names(req.authorization_name, req.authorization_names),
+        volume_mounts: req.volume_mounts,
+        secret_mounts: req.secret_mounts,
+        workspace: req.workspace,
+        claim_envs: req.claim_envs,
+        package: req.package,
+        init_containers: req.init_containers,
+        sidecars: req.sidecars,
+        security: req.security,
+        egress: req.egress,
+        image_pull_secrets: req.image_pull_secrets,
+        secret_rotation,
+    };
+
+    let Some(validation) = req.validation else {
+        let mt = store
+            .create(&req.name, req.labels.into_iter(), create)
+            .await
+            .map_err(map_create_err)?;
+        return Ok(Response::new(from(mt)));
+    };
+
+    let draft = store
+        .dry_run_create(&req.name, req.labels.clone().into_iter(), create.clone())
         .await
-        .map_err(|e| {
-            tracing::error!("Failed to create MCP template: {}", e);
-            Status::internal(format!("Failed to create MCP template: {}", e))
-        })?;
+        .map_err(map_create_err)?;
+    let report = state
+        .podmcp
+        .validate_template(&draft, &validation)
+        .await
+        .map_err(|e| Status::internal(format!("Failed to validate MCP template: {}", e)))?;
+    tracing::info!(
+        "Validated MCP template {}: passed {}",
+        req.name,
+        report.passed
+    );
+    if validation.dry_run {
+        return Ok(Response::new(McpTemplateResponse {
+            validation: Some(report),
+            ..from(draft)
+        }));
+    }
+    if !report.passed {
+        return Err(Status::failed_precondition(format!(
+            "MCP template validation failed: {}",
+            report.messages.join("; ")
+        )));
+    }
 
-    Ok(Response::new(from(mt)))
+    let mt = store
+        .create(&req.name, req.labels.into_iter(), create)
+        .await
+        .map_err(map_create_err)?;
+    Ok(Response::new(McpTemplateResponse {
+        validation: Some(report),
+        ..from(mt)
+    }))
 }
 
 pub async fn get_mcp_template(
@@ -141,3 +220,55 @@ pub async fn delete_mcp_template(
         message,
     }))
 }
+
+pub async fn import_mcp_template(
+    state: &AppState,
+    request: Request<ImportMcpTemplateRequest>,
+) -> Result<Response<ImportMcpTemplateResponse>, Status> {
+    let req = request.into_inner();
+
+    let import = import_template(
+        &req.content,
+        req.format(),
+        req.server_name.as_deref(),
+        &state.config.package,
+    )
+    .map_err(|e| match e {
+        AppError::InvalidInput(msg) => Status::invalid_argument(msg),
+        _ => Status::internal(format!("Failed to import MCP template: {}", e)),
+    })?;
+
+    Ok(Response::new(ImportMcpTemplateResponse {
+        draft: Some(CreateMcpTemplateRequest {
+            namespace: req.namespace,
+            ..import.draft
+        }),
+        format: import.format.into(),
+        warnings: import.warnings,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_authorization_names() {
+        assert_eq!(authorization_names(None, vec![]), vec!["anonymous"]);
+        assert_eq!(
+            authorization_names(Some("oidc".to_string()), vec![]),
+            vec!["oidc"]
+        );
+        assert_eq!(
+            authorization_names(
+                Some("oidc".to_string()),
+                vec!["ci".to_string(), "oidc".to_string()]
+            ),
+            vec!["ci", "oidc"]
+        );
+        assert_eq!(
+            authorization_names(Some("sa".to_string()), vec!["ci".to_string()]),
+            vec!["sa", "ci"]
+        );
+    }
+}
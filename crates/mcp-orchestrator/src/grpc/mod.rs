@@ -1,3 +1,5 @@
+mod bundle;
+mod mcp_api_key;
 mod mcp_authorization;
 mod mcp_generate_token;
 mod mcp_server;
@@ -5,6 +7,9 @@ mod mcp_template;
 mod namespace;
 mod resource_limit;
 mod secret;
+mod usage;
+mod user_secret;
+mod workspace;
 pub mod utils;
 
 use proto::mcp::orchestrator::v1::{mcp_orchestrator_service_server::McpOrchestratorService, *};
@@ -52,6 +57,13 @@ impl McpOrchestratorService for GrpcService {
         mcp_template::delete_mcp_template(&self.state, request).await
     }
 
+    async fn import_mcp_template(
+        &self,
+        request: Request<ImportMcpTemplateRequest>,
+    ) -> Result<Response<ImportMcpTemplateResponse>, Status> {
+        mcp_template::import_mcp_template(&self.state, request).await
+    }
+
     async fn get_mcp(
         &self,
         _request: Request<McpRequest>,
@@ -96,6 +108,13 @@ impl McpOrchestratorService for GrpcService {
         namespace::delete_namespace(&self.state, request).await
     }
 
+    async fn update_namespace(
+        &self,
+        request: Request<UpdateNamespaceRequest>,
+    ) -> Result<Response<NamespaceResponse>, Status> {
+        namespace::update_namespace(&self.state, request).await
+    }
+
     async fn create_secret(
         &self,
         request: Request<CreateSecretRequest>,
@@ -131,6 +150,34 @@ impl McpOrchestratorService for GrpcService {
         secret::delete_secret(&self.state, request).await
     }
 
+    async fn put_user_secret(
+        &self,
+        request: Request<PutUserSecretRequest>,
+    ) -> Result<Response<UserSecretResponse>, Status> {
+        user_secret::put_user_secret(&self.state, request).await
+    }
+
+    async fn get_user_secret(
+        &self,
+        request: Request<GetUserSecretRequest>,
+    ) -> Result<Response<UserSecretResponse>, Status> {
+        user_secret::get_user_secret(&self.state, request).await
+    }
+
+    async fn list_user_secrets(
+        &self,
+        request: Request<ListUserSecretsRequest>,
+    ) -> Result<Response<ListUserSecretsResponse>, Status> {
+        user_secret::list_user_secrets(&self.state, request).await
+    }
+
+    async fn delete_user_secret(
+        &self,
+        request: Request<DeleteUserSecretRequest>,
+    ) -> Result<Response<DeleteSecretResponse>, Status> {
+        user_secret::delete_user_secret(&self.state, request).await
+    }
+
     async fn create_resource_limit(
         &self,
         request: Request<CreateResourceLimitRequest>,
@@ -159,6 +206,13 @@ impl McpOrchestratorService for GrpcService {
         resource_limit::delete_resource_limit(&self.state, request).await
     }
 
+    async fn check_resource_limit(
+        &self,
+        request: Request<CheckResourceLimitRequest>,
+    ) -> Result<Response<CheckResourceLimitResponse>, Status> {
+        resource_limit::check_resource_limit(&self.state, request).await
+    }
+
     async fn create_authorization(
         &self,
         request: Request<CreateAuthorizationRequest>,
@@ -193,4 +247,74 @@ impl McpOrchestratorService for GrpcService {
     ) -> Result<Response<GenerateTokenResponse>, Status> {
         mcp_generate_token::generate_token(&self.state, request).await
     }
+
+    async fn list_tokens(
+        &self,
+        request: Request<ListTokensRequest>,
+    ) -> Result<Response<ListTokensResponse>, Status> {
+        mcp_generate_token::list_tokens(&self.state, request).await
+    }
+
+    async fn revoke_token(
+        &self,
+        request: Request<RevokeTokenRequest>,
+    ) -> Result<Response<RevokeTokenResponse>, Status> {
+        mcp_generate_token::revoke_token(&self.state, request).await
+    }
+
+    async fn issue_api_key(
+        &self,
+        request: Request<IssueApiKeyRequest>,
+    ) -> Result<Response<IssueApiKeyResponse>, Status> {
+        mcp_api_key::issue_api_key(&self.state, request).await
+    }
+
+    async fn list_api_keys(
+        &self,
+        request: Request<ListApiKeysRequest>,
+    ) -> Result<Response<ListApiKeysResponse>, Status> {
+        mcp_api_key::list_api_keys(&self.state, request).await
+    }
+
+    async fn revoke_api_key(
+        &self,
+        request: Request<RevokeApiKeyRequest>,
+    ) -> Result<Response<RevokeApiKeyResponse>, Status> {
+        mcp_api_key::revoke_api_key(&self.state, request).await
+    }
+
+    async fn list_workspaces(
+        &self,
+        request: Request<ListWorkspacesRequest>,
+    ) -> Result<Response<ListWorkspacesResponse>, Status> {
+        workspace::list_workspaces(&self.state, request).await
+    }
+
+    async fn delete_workspace(
+        &self,
+        request: Request<DeleteWorkspaceRequest>,
+    ) -> Result<Response<DeleteWorkspaceResponse>, Status> {
+        workspace::delete_workspace(&self.state, request).await
+    }
+
+    async fn export_bundle(
+        &self,
+        request: Request<ExportBundleRequest>,
+    ) -> Result<Response<ExportBundleResponse>, Status> {
+        bundle::export_bundle(&self.state, request).await
+    }
+
+    async fn import_bundle(
+        &self,
+        request: Request<ImportBundleRequest>,
+    ) -> Result<Response<ImportBundleResponse>, Status> {
+        bundle::import_bundle(&self.state, request).await
+    }
+
+    async fn get_usage_report(
+        &self,
+        request: Request<GetUsageReportRequest>,
+    ) -> Result<Response<GetUsageReportResponse>, Status> {
+        usage::get_usage_report(&self.state, request).await
+    }
 }
@@ -5,18 +5,44 @@ use crate::error::AppError;
 use crate::grpc::utils::convert_label_query;
 use crate::state::AppState;
 use crate::storage::NamespaceData;
+use crate::storage::namespace_guard::{
+    NamespaceGuardSpec, ResourceQuotaStatus, list_resource_quotas, load_resource_quota,
+};
 use crate::storage::util_delete::DeleteOption;
 use crate::storage::util_list::ListOption;
 
-fn from(ns: NamespaceData) -> NamespaceResponse {
+fn from(
+    ns: NamespaceData,
+    usage: SessionUsage,
+    resource_quota: Option<ResourceQuotaStatus>,
+) -> NamespaceResponse {
+    let (resource_quota, resource_quota_used) = resource_quota.unzip();
     NamespaceResponse {
         name: ns.name,
         labels: ns.labels,
         created_at: ns.created_at.to_rfc3339(),
         deleted_at: ns.deleted_at.map(|dt| dt.to_rfc3339()),
+        quota: Some(ns.quota),
+        usage: Some(usage),
+        resource_quota,
+        resource_quota_used: resource_quota_used.unwrap_or_default(),
+        default_resource_limit: ns.default_resource_limit,
     }
 }
 
+async fn with_usage(state: &AppState, ns: NamespaceData) -> Result<NamespaceResponse, Status> {
+    let usage = state
+        .kube_store
+        .session_quotas()
+        .usage(&ns.name)
+        .await
+        .map_err(|e| Status::internal(format!("Failed to get session usage: {}", e)))?;
+    let resource_quota = load_resource_quota(state.kube_store.to_client(), &ns.name)
+        .await
+        .map_err(|e| Status::internal(format!("Failed to get resource quota: {}", e)))?;
+    Ok(from(ns, usage, resource_quota))
+}
+
 pub async fn create_namespace(
     state: &AppState,
     request: Request<CreateNamespaceRequest>,
@@ -25,11 +51,22 @@ pub async fn create_namespace(
     let req = request.into_inner();
 
     let ns = store
-        .create(&req.name, req.labels.into_iter())
+        .create(
+            &req.name,
+            req.labels.into_iter(),
+            req.quota.as_ref(),
+            &NamespaceGuardSpec {
+                resource_quota: req.resource_quota,
+                default_resource_limit: req.default_resource_limit,
+            },
+        )
         .await
-        .map_err(|e| Status::internal(format!("Failed to create namespace: {}", e)))?;
+        .map_err(|e| match e {
+            AppError::InvalidInput(msg) => Status::invalid_argument(msg),
+            _ => Status::internal(format!("Failed to create namespace: {}", e)),
+        })?;
 
-    Ok(Response::new(from(ns)))
+    Ok(Response::new(with_usage(state, ns).await?))
 }
 
 pub async fn get_namespace(
@@ -45,7 +82,7 @@ pub async fn get_namespace(
         .map_err(|e| Status::internal(format!("Failed to get namespace: {}", e)))?
         .ok_or_else(|| Status::not_found(format!("Namespace {} not found", req.name)))?;
 
-    Ok(Response::new(from(ns)))
+    Ok(Response::new(with_usage(state, ns).await?))
 }
 
 pub async fn list_namespaces(
@@ -68,7 +105,23 @@ pub async fn list_namespaces(
         .await
         .map_err(|e| Status::internal(format!("Failed to list namespaces: {}", e)))?;
 
-    let data = namespaces.into_iter().map(from).collect::<Vec<_>>();
+    let mut usages = state
+        .kube_store
+        .session_quotas()
+        .usage_all()
+        .await
+        .map_err(|e| Status::internal(format!("Failed to get session usage: {}", e)))?;
+    let mut resource_quotas = list_resource_quotas(state.kube_store.to_client())
+        .await
+        .map_err(|e| Status::internal(format!("Failed to list resource quotas: {}", e)))?;
+    let data = namespaces
+        .into_iter()
+        .map(|ns| {
+            let usage = usages.remove(&ns.name).unwrap_or_default();
+            let resource_quota = resource_quotas.remove(&ns.name);
+            from(ns, usage, resource_quota)
+        })
+        .collect::<Vec<_>>();
 
     Ok(Response::new(ListNamespacesResponse {
         data,
@@ -97,3 +150,29 @@ pub async fn delete_namespace(
         message: format!("Namespace {} deleted successfully", req.name),
     }))
 }
+
+pub async fn update_namespace(
+    state: &AppState,
+    request: Request<UpdateNamespaceRequest>,
+) -> Result<Response<NamespaceResponse>, Status> {
+    let store = state.kube_store.namespaces();
+    let req = request.into_inner();
+
+    let ns = store
+        .update(
+            &req.name,
+            req.quota.as_ref(),
+            &NamespaceGuardSpec {
+                resource_quota: req.resource_quota,
+                default_resource_limit: req.default_resource_limit,
+            },
+        )
+        .await
+        .map_err(|e| match e {
+            AppError::InvalidInput(msg) => Status::invalid_argument(msg),
+            AppError::NotFound(msg) => Status::not_found(msg),
+            _ => Status::internal(format!("Failed to update namespace: {}", e)),
+        })?;
+
+    Ok(Response::new(with_usage(state, ns).await?))
+}
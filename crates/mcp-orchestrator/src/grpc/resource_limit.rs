@@ -4,54 +4,29 @@ use tonic::{Request, Response, Status};
 use crate::grpc::utils::convert_label_query;
 use crate::state::AppState;
 use crate::storage::ResourceLimitData;
-use crate::storage::scheduling_validation::{validate_node_affinity, validate_node_selector};
+use crate::storage::scheduling_validation::{
+    validate_node_affinity, validate_node_selector, validate_pod_anti_affinity,
+    validate_priority_class_name, validate_toleration, validate_topology_spread,
+};
+use crate::storage::template_security::validate_security_profile;
 use crate::storage::util_delete::{DeleteOption, DeleteResult};
 use crate::storage::util_list::ListOption;
 use k8s_openapi::api::core::v1::Affinity;
 use std::collections::BTreeMap;
 
 fn from(rl: ResourceLimitData) -> ResourceLimitResponse {
-    let node_selector = rl
-        .node_selector
-        .unwrap_or_default()
-        .iter()
-        .map(|(k, v)| (k.clone(), v.clone()))
-        .collect();
-
-    let node_affinity = rl
-        .node_affinity
-        .and_then(|na| serde_json::to_string(&na).ok());
-
     ResourceLimitResponse {
+        limits: Some(rl.to_resource_limit()),
         name: rl.name,
         description: rl.description,
-        limits: Some(ResourceLimit {
-            cpu: rl.cpu,
-            memory: rl.memory,
-            cpu_limit: rl.cpu_limit,
-            memory_limit: rl.memory_limit,
-            ephemeral_storage: rl.ephemeral_storage,
-            volumes: rl.volumes.into_iter().collect(),
-            node_selector,
-            node_affinity,
-        }),
         labels: rl.labels,
         created_at: rl.created_at.to_rfc3339(),
         deleted_at: rl.deleted_at.map(|dt| dt.to_rfc3339()),
+        warnings: Vec::new(),
     }
 }
 
-pub async fn create_resource_limit(
-    state: &AppState,
-    request: Request<CreateResourceLimitRequest>,
-) -> Result<Response<ResourceLimitResponse>, Status> {
-    let store = state.kube_store.resource_limits();
-    let req = request.into_inner();
-
-    let limits = req
-        .limits
-        .ok_or_else(|| Status::invalid_argument("limits field is required"))?;
-
+fn validate_limits(limits: &ResourceLimit) -> Result<(), Status> {
     // Validate node_selector
     if !limits.node_selector.is_empty() {
         let btree: BTreeMap<String, String> = limits
@@ -73,12 +48,101 @@ pub async fn create_resource_limit(
             .map_err(|e| Status::invalid_argument(format!("Invalid node_affinity: {}", e)))?;
     }
 
+    for toleration in &limits.tolerations {
+        validate_toleration(toleration)
+            .map_err(|e| Status::invalid_argument(format!("Invalid toleration: {}", e)))?;
+    }
+
+    if let Some(priority_class_name) = &limits.priority_class_name {
+        validate_priority_class_name(priority_class_name)
+            .map_err(|e| Status::invalid_argument(format!("Invalid priority_class_name: {}", e)))?;
+    }
+
+    for spread in &limits.topology_spread {
+        validate_topology_spread(spread)
+            .map_err(|e| Status::invalid_argument(format!("Invalid topology_spread: {}", e)))?;
+    }
+
+    for anti_affinity in &limits.anti_affinity {
+        validate_pod_anti_affinity(anti_affinity)
+            .map_err(|e| Status::invalid_argument(format!("Invalid anti_affinity: {}", e)))?;
+    }
+
+    if let Some(security) = &limits.security {
+        validate_security_profile(security)
+            .map_err(|e| Status::invalid_argument(format!("Invalid security profile: {}", e)))?;
+    }
+
+    Ok(())
+}
+
+pub async fn create_resource_limit(
+    state: &AppState,
+    request: Request<CreateResourceLimitRequest>,
+) -> Result<Response<ResourceLimitResponse>, Status> {
+    let store = state.kube_store.resource_limits();
+    let req = request.into_inner();
+
+    let limits = req
+        .limits
+        .ok_or_else(|| Status::invalid_argument("limits field is required"))?;
+    validate_limits(&limits)?;
+
     let cm = store
         .create(&req.name, req.labels.into_iter(), &req.description, &limits)
         .await
         .map_err(|e| Status::internal(format!("Failed to create resource limit: {}", e)))?;
 
-    Ok(Response::new(from(cm)))
+    // 생성은 막지 않음, 노드가 늘어나면 스케줄링될 수 있음
+    let warnings = match store.check(&cm).await {
+        Ok(check) if check.schedulable_sessions == 0 => std::iter::once(
+            "No node can currently schedule a session of this resource limit".to_string(),
+        )
+        .chain(check.nodes.into_iter().filter_map(|node| {
+            node.reason
+                .map(|reason| format!("{}: {}", node.node, reason))
+        }))
+        .collect(),
+        Ok(_) => Vec::new(),
+        Err(e) => {
+            tracing::warn!("Failed to check resource limit {}: {}", req.name, e);
+            vec![format!("Scheduling feasibility unknown: {}", e)]
+        }
+    };
+
+    Ok(Response::new(ResourceLimitResponse {
+        warnings,
+        ..from(cm)
+    }))
+}
+
+pub async fn check_resource_limit(
+    state: &AppState,
+    request: Request<CheckResourceLimitRequest>,
+) -> Result<Response<CheckResourceLimitResponse>, Status> {
+    let store = state.kube_store.resource_limits();
+    let req = request.into_inner();
+
+    let rl = match &req.limits {
+        Some(limits) => {
+            validate_limits(limits)?;
+            store
+                .render(&req.name, limits)
+                .map_err(|e| Status::invalid_argument(format!("Invalid resource limit: {}", e)))?
+        }
+        None => store
+            .get(&req.name)
+            .await
+            .map_err(|e| Status::internal(format!("Failed to get resource limit: {}", e)))?
+            .ok_or_else(|| Status::not_found(format!("ResourceLimit {} not found", req.name)))?,
+    };
+
+    let response = store
+        .check(&rl)
+        .await
+        .map_err(|e| Status::internal(format!("Failed to check resource limit: {}", e)))?;
+
+    Ok(Response::new(response))
 }
 
 pub async fn get_resource_limit(
@@ -140,7 +204,7 @@ pub async fn delete_resource_limit(
 
     if !is_deletable && !req.force {
         return Err(Status::failed_precondition(
-            "ResourceLimit is in use by MCP templates. Use force=true to delete anyway.",
+            "ResourceLimit is in use by MCP templates or namespace LimitRanges. Use force=true to delete anyway.",
         ));
     }
 
@@ -5,11 +5,13 @@ use crate::error::AppError;
 use crate::grpc::utils::convert_label_query;
 use crate::state::AppState;
 use crate::storage::SecretData;
+use crate::storage::secret_type::{build_secret_data, secret_type_from_name, secret_type_name};
 use crate::storage::util_delete::{DeleteOption, DeleteResult};
 use crate::storage::util_list::ListOption;
 
 fn from(secret: SecretData) -> SecretResponse {
     SecretResponse {
+        r#type: secret_type_from_name(secret.raw.type_.as_deref()).into(),
         namespace: secret.namespace,
         name: secret.name,
         labels: secret.labels,
@@ -31,13 +33,16 @@ pub async fn create_secret(
 ) -> Result<Response<SecretResponse>, Status> {
     let req = request.into_inner();
     let store = state.kube_store.secrets(req.namespace.clone());
+    let secret_type = req.r#type();
+    let data = build_secret_data(secret_type, req.registry.as_ref(), req.data)
+        .map_err(|e| Status::invalid_argument(format!("Invalid secret: {}", e)))?;
 
     let secret = store
         .create(
             &req.name,
-            None,
+            secret_type_name(secret_type).map(str::to_string),
             req.labels.into_iter(),
-            req.data.into_iter(),
+            data.into_iter(),
         )
         .await
         .map_err(|e| Status::internal(format!("Failed to create secret: {}", e)))?;
@@ -91,10 +96,45 @@ pub async fn list_secrets(
 }
 
 pub async fn update_secret(
-    _state: &AppState,
-    _request: Request<UpdateSecretRequest>,
+    state: &AppState,
+    request: Request<UpdateSecretRequest>,
 ) -> Result<Response<SecretResponse>, Status> {
-    Err(Status::unimplemented("not yet implemented"))
+    let req = request.into_inner();
+    let store = state.kube_store.secrets(req.namespace.clone());
+    let strategy = req.strategy();
+    let map_update_err = |e: AppError| match e {
+        AppError::InvalidInput(msg) => Status::invalid_argument(msg),
+        AppError::NotFound(msg) => Status::not_found(msg),
+        _ => Status::internal(format!("Failed to update secret: {}", e)),
+    };
+
+    let (secret, changed) = store
+        .update(&req.name, strategy, req.data)
+        .await
+        .map_err(map_update_err)?;
+
+    if !changed.is_empty() {
+        tracing::info!(
+            "Secret {}/{} updated, changed keys: {}",
+            secret.namespace,
+            secret.name,
+            changed.join(", ")
+        );
+        let podmcp = state.podmcp.clone();
+        let recorder = state.kube_recorder.clone();
+        let namespace = secret.namespace.clone();
+        let name = secret.name.clone();
+        tokio::spawn(async move {
+            if let Err(e) = podmcp
+                .rotate_secret(&recorder, &namespace, &name, &changed)
+                .await
+            {
+                tracing::error!("Failed to rotate secret {}/{}: {}", namespace, name, e);
+            }
+        });
+    }
+
+    Ok(Response::new(from(secret)))
 }
 
 pub async fn delete_secret(
@@ -0,0 +1,46 @@
+use chrono::{DateTime, Duration, Utc};
+use proto::mcp::orchestrator::v1::*;
+use tonic::{Request, Response, Status};
+
+use crate::state::AppState;
+use crate::storage::usage_report::{build_report, render_csv};
+
+fn parse_time(name: &str, value: Option<&str>) -> Result<Option<DateTime<Utc>>, Status> {
+    value
+        .filter(|x| !x.is_empty())
+        .map(|x| {
+            DateTime::parse_from_rfc3339(x)
+                .map(|x| x.with_timezone(&Utc))
+                .map_err(|e| Status::invalid_argument(format!("Invalid {}: {}", name, e)))
+        })
+        .transpose()
+}
+
+pub async fn get_usage_report(
+    state: &AppState,
+    request: Request<GetUsageReportRequest>,
+) -> Result<Response<GetUsageReportResponse>, Status> {
+    let req = request.into_inner();
+    let group_by = req.group_by();
+    let end = parse_time("end", req.end.as_deref())?.unwrap_or_else(Utc::now);
+    let start = parse_time("start", req.start.as_deref())?.unwrap_or(end - Duration::days(30));
+    if start >= end {
+        return Err(Status::invalid_argument("start must be before end"));
+    }
+
+    let records = state
+        .kube_store
+        .usage()
+        .report_records(req.namespace.as_deref(), start, end)
+        .await
+        .map_err(|e| Status::internal(format!("Failed to load usage records: {}", e)))?;
+    let rows = build_report(&records, group_by, start, end);
+    let csv = req.csv.then(|| render_csv(group_by, &rows));
+
+    Ok(Response::new(GetUsageReportResponse {
+        start: start.to_rfc3339(),
+        end: end.to_rfc3339(),
+        rows,
+        csv,
+    }))
+}
@@ -0,0 +1,98 @@
+use proto::mcp::orchestrator::v1::*;
+use tonic::{Request, Response, Status};
+
+use crate::error::AppError;
+use crate::grpc::utils::authenticated_subject;
+use crate::state::AppState;
+use crate::storage::store_user_secret::UserSecretData;
+use crate::storage::util_delete::DeleteResult;
+
+fn from(secret: UserSecretData) -> UserSecretResponse {
+    UserSecretResponse {
+        namespace: secret.namespace,
+        name: secret.name,
+        keys: secret.keys,
+        created_at: secret.created_at.to_rfc3339(),
+    }
+}
+
+fn map_err(action: &'static str) -> impl Fn(AppError) -> Status {
+    move |e| match e {
+        AppError::InvalidInput(msg) => Status::invalid_argument(msg),
+        AppError::NotFound(msg) => Status::not_found(msg),
+        _ => Status::internal(format!("Failed to {} user secret: {}", action, e)),
+    }
+}
+
+pub async fn put_user_secret(
+    state: &AppState,
+    request: Request<PutUserSecretRequest>,
+) -> Result<Response<UserSecretResponse>, Status> {
+    let subject = authenticated_subject(&request)?;
+    let req = request.into_inner();
+    let store = state.kube_store.user_secrets(req.namespace);
+
+    let secret = store
+        .put(&subject, &req.name, req.data)
+        .await
+        .map_err(map_err("put"))?;
+
+    Ok(Response::new(from(secret)))
+}
+
+pub async fn get_user_secret(
+    state: &AppState,
+    request: Request<GetUserSecretRequest>,
+) -> Result<Response<UserSecretResponse>, Status> {
+    let subject = authenticated_subject(&request)?;
+    let req = request.into_inner();
+    let store = state.kube_store.user_secrets(req.namespace);
+
+    let secret = store
+        .get(&subject, &req.name)
+        .await
+        .map_err(map_err("get"))?
+        .ok_or_else(|| Status::not_found("User secret not found".to_string()))?;
+
+    Ok(Response::new(from(secret)))
+}
+
+pub async fn list_user_secrets(
+    state: &AppState,
+    request: Request<ListUserSecretsRequest>,
+) -> Result<Response<ListUserSecretsResponse>, Status> {
+    let subject = authenticated_subject(&request)?;
+    let req = request.into_inner();
+    let store = state.kube_store.user_secrets(req.namespace);
+
+    let data = store
+        .list(&subject)
+        .await
+        .map_err(map_err("list"))?
+        .into_iter()
+        .map(from)
+        .collect();
+
+    Ok(Response::new(ListUserSecretsResponse { data }))
+}
+
+pub async fn delete_user_secret(
+    state: &AppState,
+    request: Request<DeleteUserSecretRequest>,
+) -> Result<Response<DeleteSecretResponse>, Status> {
+    let subject = authenticated_subject(&request)?;
+    let req = request.into_inner();
+    let store = state.kube_store.user_secrets(req.namespace);
+
+    let result = store
+        .delete(&subject, &req.name)
+        .await
+        .map_err(map_err("delete"))?;
+
+    let (success, message) = match result {
+        DeleteResult::Deleted => (true, "User secret deleted successfully".to_string()),
+        DeleteResult::Deleting => (false, "User secret is being deleted".to_string()),
+    };
+
+    Ok(Response::new(DeleteSecretResponse { success, message }))
+}
@@ -3,7 +3,7 @@ use crate::storage::label_query::LabelQuery;
 use prost_wkt_types::Any;
 use proto::mcp::orchestrator::v1::{self, LabelKeyValue, LabelKeyValues};
 use serde::{Serialize, de::DeserializeOwned};
-use tonic::Status;
+use tonic::{Request, Status};
 
 pub fn convert_label_query(label: v1::LabelQuery) -> Vec<LabelQuery> {
     let mut queries = Vec::new();
@@ -28,6 +28,16 @@ pub fn convert_label_query(label: v1::LabelQuery) -> Vec<LabelQuery> {
     queries
 }
 
+/// `sub` claim of the OIDC token the auth layer attached to the request.
+pub fn authenticated_subject<T>(request: &Request<T>) -> Result<String, Status> {
+    request
+        .extensions()
+        .get::<oidc_auth::OptionalAuthenticatedUser>()
+        .and_then(|x| x.0.as_ref())
+        .map(|claims| claims.sub.clone())
+        .ok_or_else(|| Status::unauthenticated("Authenticated user is required"))
+}
+
 #[allow(dead_code)]
 pub fn convert_from_any<D: DeserializeOwned>(value: &Any) -> Result<D, Status> {
     let s = serde_json::from_slice(&value.value)
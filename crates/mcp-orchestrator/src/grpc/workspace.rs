@@ -0,0 +1,73 @@
+use proto::mcp::orchestrator::v1::*;
+use tonic::{Request, Response, Status};
+
+use crate::error::AppError;
+use crate::state::AppState;
+use crate::storage::store_workspace::WorkspaceData;
+use crate::storage::util_delete::DeleteResult;
+use crate::storage::util_list::ListOption;
+
+fn from(workspace: WorkspaceData) -> WorkspaceResponse {
+    WorkspaceResponse {
+        size: workspace.size(),
+        storage_class: workspace.storage_class(),
+        phase: workspace.phase(),
+        namespace: workspace.namespace,
+        name: workspace.name,
+        template_name: workspace.template_name,
+        subject: workspace.subject,
+        retention: workspace.retention.into(),
+        last_used_at: workspace.last_used_at.map(|dt| dt.to_rfc3339()),
+        created_at: workspace.created_at.to_rfc3339(),
+        deleted_at: workspace.deleted_at.map(|dt| dt.to_rfc3339()),
+    }
+}
+
+pub async fn list_workspaces(
+    state: &AppState,
+    request: Request<ListWorkspacesRequest>,
+) -> Result<Response<ListWorkspacesResponse>, Status> {
+    let req = request.into_inner();
+    let store = state.kube_store.workspaces(req.namespace.clone());
+
+    let (workspaces, continue_token, has_more) = store
+        .list(
+            req.template_name.as_deref(),
+            req.subject.as_deref(),
+            ListOption {
+                after: req.after,
+                first: req.first,
+            },
+        )
+        .await
+        .map_err(|e| Status::internal(format!("Failed to list workspaces: {}", e)))?;
+
+    Ok(Response::new(ListWorkspacesResponse {
+        data: workspaces.into_iter().map(from).collect(),
+        end_cursor: continue_token,
+        has_next_page: has_more,
+    }))
+}
+
+pub async fn delete_workspace(
+    state: &AppState,
+    request: Request<DeleteWorkspaceRequest>,
+) -> Result<Response<DeleteWorkspaceResponse>, Status> {
+    let req = request.into_inner();
+    let store = state.kube_store.workspaces(req.namespace.clone());
+
+    let result = store.delete(&req.name).await.map_err(|e| match e {
+        AppError::NotFound(msg) => Status::not_found(msg),
+        _ => Status::internal(format!("Failed to delete workspace: {}", e)),
+    })?;
+
+    let (success, message) = match result {
+        DeleteResult::Deleted => (true, "Workspace deleted successfully".to_string()),
+        DeleteResult::Deleting => (
+            false,
+            "Workspace is being deleted, it is removed once no session uses it".to_string(),
+        ),
+    };
+
+    Ok(Response::new(DeleteWorkspaceResponse { success, message }))
+}
@@ -0,0 +1,91 @@
+use axum::{
+    Json, Router,
+    extract::{Path, State},
+    http::header,
+    response::IntoResponse,
+    routing::get,
+};
+use axum_qs::Qs;
+use oidc_auth::RequiredAuthLayer;
+use proto::mcp::orchestrator::v1::{BundleConflictPolicy, ImportBundleResponse};
+use serde::Deserialize;
+
+use crate::{
+    error::AppError,
+    state::AppState,
+    storage::bundle::{Bundle, export_bundle, import_bundle},
+};
+
+pub fn router(_state: &AppState) -> Router<AppState> {
+    Router::new()
+        .route("/{namespace}", get(handler_export).post(handler_import))
+        .layer(RequiredAuthLayer)
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct ExportQuery {
+    template: Vec<String>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+enum ConflictPolicyQuery {
+    #[default]
+    Skip,
+    Overwrite,
+    Rename,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct ImportQuery {
+    conflict_policy: ConflictPolicyQuery,
+    dry_run: bool,
+}
+
+async fn handler_export(
+    State(state): State<AppState>,
+    Path(namespace): Path<String>,
+    Qs(query): Qs<ExportQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let yaml = export_bundle(&state.kube_store, Some(namespace.clone()), &query.template)
+        .await?
+        .to_yaml()?;
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/yaml".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}.bundle.yaml\"", namespace),
+            ),
+        ],
+        yaml,
+    ))
+}
+
+async fn handler_import(
+    State(state): State<AppState>,
+    Path(namespace): Path<String>,
+    Qs(query): Qs<ImportQuery>,
+    body: String,
+) -> Result<Json<ImportBundleResponse>, AppError> {
+    let bundle = Bundle::from_yaml(&body)?;
+    let policy = match query.conflict_policy {
+        ConflictPolicyQuery::Skip => BundleConflictPolicy::Skip,
+        ConflictPolicyQuery::Overwrite => BundleConflictPolicy::Overwrite,
+        ConflictPolicyQuery::Rename => BundleConflictPolicy::Rename,
+    };
+    let items = import_bundle(
+        &state.kube_store,
+        Some(namespace),
+        &bundle,
+        policy,
+        query.dry_run,
+    )
+    .await;
+    Ok(Json(ImportBundleResponse {
+        dry_run: query.dry_run,
+        items,
+    }))
+}
@@ -15,7 +15,7 @@ use crate::{
     podmcp::PodMcpRequest,
 };
 use crate::{
-    http::mcp::utils::{accepted_response, internal_error_response},
+    http::mcp::utils::{accepted_response, session_error_response},
     state::AppState,
 };
 
@@ -50,6 +50,6 @@ pub async fn handler(
     session_manager
         .close_session(&session_id, req)
         .await
-        .map_err(internal_error_response("close session"))?;
+        .map_err(session_error_response(&state, "close session"))?;
     Ok(accepted_response())
 }
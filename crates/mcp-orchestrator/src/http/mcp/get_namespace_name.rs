@@ -18,7 +18,7 @@ use crate::{
     podmcp::PodMcpRequest,
 };
 use crate::{
-    http::mcp::utils::{get_session_manager, internal_error_response},
+    http::mcp::utils::{get_session_manager, internal_error_response, session_error_response},
     state::AppState,
 };
 
@@ -70,7 +70,7 @@ pub async fn handler(
     let has_session = session_manager
         .has_session(&session_id, req)
         .await
-        .map_err(internal_error_response("check session"))?;
+        .map_err(session_error_response(&state, "check session"))?;
     if !has_session {
         // unauthorized
         return Ok(Response::builder()
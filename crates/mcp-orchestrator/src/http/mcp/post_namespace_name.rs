@@ -24,8 +24,8 @@ use crate::{
 };
 use crate::{
     http::mcp::utils::{
-        accepted_response, expect_json, internal_error_response, sse_stream_response,
-        unexpected_message_response,
+        accepted_response, create_session_error_response, expect_json, internal_error_response,
+        session_error_response, sse_stream_response, unexpected_message_response,
     },
     podmcp::PodMcpRequest,
 };
@@ -95,7 +95,7 @@ pub async fn handler(
         let has_session = session_manager
             .has_session(&session_id, req)
             .await
-            .map_err(internal_error_response("check session"))?;
+            .map_err(session_error_response(&state, "check session"))?;
         if !has_session {
             // unauthorized
             return Ok(Response::builder()
@@ -142,9 +142,7 @@ pub async fn handler(
             .iter()
             .filter(|(name, _)| name.as_str().starts_with("arg-"))
             .filter_map(|(name, value)| {
-                let Some(key) = name.as_str().strip_prefix("arg-") else {
-                    return None;
-                };
+                let key = name.as_str().strip_prefix("arg-")?;
                 let value = match value.to_str() {
                     Ok(value) => value.to_string(),
                     Err(err) => {
@@ -163,7 +161,7 @@ pub async fn handler(
         let session_id = session_manager
             .create_session(req, args)
             .await
-            .map_err(internal_error_response("create session"))?;
+            .map_err(create_session_error_response(&state))?;
         if let ClientJsonRpcMessage::Request(req) = &mut message {
             if !matches!(req.request, ClientRequest::InitializeRequest(_)) {
                 return Err(unexpected_message_response("initialize request"));
@@ -13,7 +13,11 @@ use rmcp::{
     transport::common::{http_header::EVENT_STREAM_MIME_TYPE, server_side_http::ServerSseMessage},
 };
 
-use crate::{podmcp::PodMcpSessionManager, state::AppState};
+use crate::{
+    error::AppError,
+    podmcp::{McpPodError, PodMcpSessionManager},
+    state::AppState,
+};
 
 pub type SessionId = Arc<str>;
 
@@ -103,6 +107,51 @@ pub(crate) const fn internal_error_response<E: Display>(
     }
 }
 
+/// 401 with the resource metadata challenge so OIDC clients can start the login flow.
+pub(crate) fn unauthorized_response(state: &AppState, error: McpPodError) -> BoxResponse {
+    tracing::warn!("Rejected session request: {error}");
+    let mut response = Response::builder()
+        .status(http::StatusCode::UNAUTHORIZED)
+        .body(Full::new(Bytes::from(error.to_string())).boxed())
+        .expect("valid response");
+    if let Some(oidc) = &state.oidc_manager {
+        oidc.www_authenticate(response.headers_mut());
+    }
+    response
+}
+
+pub(crate) fn session_error_response<'a>(
+    state: &'a AppState,
+    context: &'a str,
+) -> impl FnOnce(McpPodError) -> BoxResponse + 'a {
+    move |error| match error {
+        McpPodError::AuthorizationFailed { .. } => unauthorized_response(state, error),
+        _ => internal_error_response(context)(error),
+    }
+}
+
+pub(crate) fn create_session_error_response(
+    state: &AppState,
+) -> impl FnOnce(McpPodError) -> BoxResponse + '_ {
+    move |error| {
+        let status = match &error {
+            McpPodError::AppError(AppError::InvalidArgEnv(_) | AppError::InvalidInput(_)) => {
+                http::StatusCode::BAD_REQUEST
+            }
+            McpPodError::AuthorizationFailed { .. } => return unauthorized_response(state, error),
+            McpPodError::AppError(AppError::QuotaExceeded(_)) => {
+                http::StatusCode::TOO_MANY_REQUESTS
+            }
+            _ => return internal_error_response("create session")(error),
+        };
+        tracing::warn!("Rejected session creation: {error}");
+        Response::builder()
+            .status(status)
+            .body(Full::new(Bytes::from(error.to_string())).boxed())
+            .expect("valid response")
+    }
+}
+
 pub(crate) fn unexpected_message_response(expect: &str) -> Response<BoxBody<Bytes, Infallible>> {
     Response::builder()
         .status(http::StatusCode::UNPROCESSABLE_ENTITY)
@@ -1,3 +1,4 @@
+pub mod bundle;
 pub mod mcp;
 
 pub mod well_known;
@@ -15,6 +16,7 @@ pub fn router(state: &AppState) -> Router<AppState> {
         .route("/", get(index::handler))
         .route("/health", get(health_handler))
         .nest("/mcp", mcp::router(state))
+        .nest("/bundles", bundle::router(state))
         .nest("/oauth", oauth::router(state))
         .nest("/static", statics::router(state))
         .nest("/.well-known", well_known::router(state))
@@ -80,20 +80,22 @@ async fn main() -> anyhow::Result<()> {
         None => None,
     };
 
+    let kube_store = KubeStore::new(kube_client.clone(), &config.kubernetes.namespace)
+        .with_image_admission(config.image_admission.clone())
+        .with_secret_backends(&config.secret_backends)
+        .context("Failed to initialize secret backends")?;
+
     let state = AppState {
-        kube_store: KubeStore::new(kube_client.clone(), &config.kubernetes.namespace),
+        kube_store: kube_store.clone(),
         kube_client: kube_client.clone(),
-        _kube_recorder: Recorder::new(
+        kube_recorder: Recorder::new(
             kube_client.clone(),
             Reporter {
                 controller: "mcp-orchestrator".to_string(),
                 instance: config.kubernetes.pod.as_ref().map(|p| p.name.clone()),
             },
         ),
-        podmcp: PodMcp::new(KubeStore::new(
-            kube_client.clone(),
-            &config.kubernetes.namespace,
-        )),
+        podmcp: PodMcp::new(kube_store, oidc_manager.clone()),
         config: Arc::new(config.clone()),
         oidc_manager,
     };
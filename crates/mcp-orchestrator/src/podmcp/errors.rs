@@ -23,7 +23,7 @@ pub enum McpPodError {
     EventIdParseError(#[from] EventIdParseError),
 
     #[error(transparent)]
-    ClientInitializeError(#[from] ClientInitializeError),
+    ClientInitializeError(Box<ClientInitializeError>),
 
     #[error("Pod not found: {session_id}")]
     PodNotFound { session_id: String },
@@ -43,6 +43,15 @@ pub enum McpPodError {
     #[error("Failed to send message to pod")]
     SendTransportError,
 
+    #[error("MCP handshake failed: {reason}")]
+    HandshakeFailed { reason: String },
+
     #[error("Authorization failed: {reason}")]
     AuthorizationFailed { reason: String },
 }
+
+impl From<ClientInitializeError> for McpPodError {
+    fn from(e: ClientInitializeError) -> Self {
+        McpPodError::ClientInitializeError(Box::new(e))
+    }
+}
@@ -1,5 +1,6 @@
 use std::{collections::HashMap, sync::Arc};
 
+use chrono::{Duration, Utc};
 use futures::Stream;
 use k8s_openapi::api::{
     authentication::v1::{TokenReview, TokenReviewSpec},
@@ -9,6 +10,7 @@ use kube::{
     Api,
     api::{DeleteParams, PostParams},
 };
+use oidc_auth::AuthManager;
 use proto::mcp::orchestrator::v1::AuthorizationType;
 use rmcp::{
     model::{ClientJsonRpcMessage, ServerJsonRpcMessage},
@@ -17,29 +19,51 @@ use rmcp::{
         streamable_http_server::SessionId,
     },
 };
-use tokio::sync::RwLock;
+use serde_json::json;
+use tokio::sync::{RwLock, Semaphore};
 
 use crate::{
+    error::AppError,
     podmcp::{McpPodError, PodMcpTransport},
     storage::{
-        McpTemplateData, resource_type::RESOURCE_TYPE_PREFIX_AUTHORIZATION_SA, store::KubeStore,
-        store_authorization::AuthorizationData, util_name::encode_k8sname,
+        McpTemplateData,
+        annotations::ANNOTATION_SESSION_AUTHORIZATION,
+        authorization_api_key::{ApiKeyCache, parse_api_key, verify_api_key},
+        authorization_oidc::OidcAuthorization,
+        resource_type::RESOURCE_TYPE_PREFIX_AUTHORIZATION_SA,
+        session_quota::SessionEntry,
+        session_secret::{adopt_session_secrets, create_session_secrets, delete_session_secrets},
+        store::KubeStore,
+        store_authorization::AuthorizationData,
+        store_workspace::subject_hash,
+        template_claim::SessionIdentity,
+        util_name::encode_k8sname,
     },
 };
 
+/// Verified api keys are trusted for this long without another argon2 verification.
+const API_KEY_CACHE_TTL: std::time::Duration = std::time::Duration::from_secs(60);
+const MAX_CONCURRENT_API_KEY_VERIFICATIONS: usize = 4;
+
 #[derive(Clone)]
 pub struct PodMcp(Arc<PodMcpInner>);
 
 pub struct PodMcpInner {
     client: KubeStore,
+    oidc: Option<AuthManager>,
     transports: RwLock<HashMap<SessionId, PodMcpTransport>>,
+    api_key_cache: ApiKeyCache,
+    api_key_permits: Semaphore,
 }
 
 impl PodMcp {
-    pub fn new(client: KubeStore) -> Self {
+    pub fn new(client: KubeStore, oidc: Option<AuthManager>) -> Self {
         Self(Arc::new(PodMcpInner {
             client,
+            oidc,
             transports: RwLock::new(HashMap::new()),
+            api_key_cache: ApiKeyCache::new(API_KEY_CACHE_TTL),
+            api_key_permits: Semaphore::new(MAX_CONCURRENT_API_KEY_VERIFICATIONS),
         }))
     }
     pub async fn session_manager(&self, template: McpTemplateData) -> PodMcpSessionManager {
@@ -52,10 +76,27 @@ impl PodMcp {
         )
     }
 
+    pub(crate) fn client(&self) -> &KubeStore {
+        &self.0.client
+    }
+
     pub(crate) async fn remove_transport(&self, session_id: &SessionId) {
         let mut transports = self.0.transports.write().await;
         transports.remove(session_id);
     }
+
+    /// Unanswered requests of the session, None when it is not connected to this replica.
+    pub(crate) async fn in_flight(&self, session_id: &str) -> Option<usize> {
+        let transports = self.0.transports.read().await;
+        transports.get(session_id).map(|x| x.in_flight())
+    }
+
+    pub(crate) async fn notify(&self, session_id: &str, message: ServerJsonRpcMessage) -> bool {
+        let transports = self.0.transports.read().await;
+        transports
+            .get(session_id)
+            .is_some_and(|x| x.notify(message))
+    }
 }
 
 #[derive(Clone)]
@@ -107,6 +148,77 @@ pub struct PodMcpRequest {
     pub token: Option<String>,
 }
 
+/// Shape of the bearer token, so it is only sent to the authorizations that can accept it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum TokenShape {
+    Missing,
+    /// `mcpk_<id>_<secret>` of an API_KEY authorization.
+    ApiKey,
+    /// JWT whose `iss` is the orchestrator OIDC provider.
+    ProviderJwt,
+    /// Anything else, e.g. a Kubernetes service account token.
+    Other,
+}
+
+fn token_shape(token: Option<&str>, oidc: Option<&AuthManager>) -> TokenShape {
+    match token {
+        None => TokenShape::Missing,
+        Some(token) if parse_api_key(token).is_some() => TokenShape::ApiKey,
+        Some(token) if oidc.is_some_and(|x| x.is_provider_token(token)) => TokenShape::ProviderJwt,
+        Some(_) => TokenShape::Other,
+    }
+}
+
+/// Api keys only go to API_KEY and provider JWTs only to OIDC, never to the TokenReview API.
+fn dispatches_to(r#type: AuthorizationType, shape: TokenShape) -> bool {
+    match (r#type, shape) {
+        (AuthorizationType::Anonymous, _) | (_, TokenShape::Missing) => true,
+        (AuthorizationType::ApiKey, shape) => shape == TokenShape::ApiKey,
+        // 다른 발급자의 JWT 는 authorization 의 issuers 조건으로 허용될 수 있음
+        (AuthorizationType::Oidc, shape) => shape != TokenShape::ApiKey,
+        (_, shape) => shape == TokenShape::Other,
+    }
+}
+
+/// Results of checking the authorizations of a template, any one of them is enough.
+#[derive(Default)]
+struct AuthAttempts {
+    reasons: Vec<String>,
+    error: Option<McpPodError>,
+}
+
+impl AuthAttempts {
+    /// Returns the value of an accepted authorization, remembers why the others failed.
+    fn record<T>(&mut self, name: &str, result: Result<T, McpPodError>) -> Option<T> {
+        match result {
+            Ok(value) => return Some(value),
+            Err(McpPodError::AuthorizationFailed { reason }) => {
+                self.reasons.push(format!("{}: {}", name, reason));
+            }
+            Err(e) => {
+                tracing::warn!("Checking authorization {} failed: {}", name, e);
+                self.error.get_or_insert(e);
+            }
+        }
+        None
+    }
+
+    /// Authorization fails only when every authorization rejected the request,
+    /// an error checking one of them is returned as it is.
+    fn into_error(self) -> McpPodError {
+        if let Some(e) = self.error {
+            return e;
+        }
+        tracing::debug!(
+            "Authorizing pod session failed: {}",
+            self.reasons.join("; ")
+        );
+        McpPodError::AuthorizationFailed {
+            reason: self.reasons.join("; "),
+        }
+    }
+}
+
 impl PodMcpSessionManager {
     pub async fn create_session(
         &self,
@@ -114,11 +226,37 @@ impl PodMcpSessionManager {
         args: HashMap<String, String>,
     ) -> Result<SessionId, McpPodError> {
         let id = session_id();
-        let (pod, auth) = self.0.template.to_pod(&id, &self.1.client, args).await?;
-        //
-        self.assert_auth_check(&auth, &req).await?;
-        //
-        self.0.api.create(&PostParams::default(), &pod).await?;
+        let auths = self.0.template.get_authorizations(&self.1.client).await?;
+        let (identity, matched) = self.assert_auth_check(&auths, &req).await?;
+        // 워크스페이스, 캐시 PVC 를 만들기 전에 쿼터부터 확보
+        let namespace = &self.0.template.namespace;
+        let requests = self.0.template.session_requests(&self.1.client).await?;
+        let quotas = self.1.client.session_quotas();
+        quotas
+            .admit(
+                namespace,
+                SessionEntry {
+                    session_id: id.to_string(),
+                    template: self.0.template.name.clone(),
+                    subject_hash: identity.as_ref().map(|x| subject_hash(&x.subject)),
+                    cpu: requests.get("cpu").copied().unwrap_or_default(),
+                    memory: requests.get("memory").copied().unwrap_or_default(),
+                },
+            )
+            .await?;
+        let pod = match self.create_pod(&id, args, identity.as_ref(), matched).await {
+            Ok(pod) => pod,
+            Err(e) => {
+                if let Err(err) = quotas.release(namespace, &id).await {
+                    tracing::warn!("Failed to release quota reservation of {}: {}", id, err);
+                }
+                return Err(e);
+            }
+        };
+        // 짧은 세션도 과금되도록 측정 주기를 기다리지 않고 바로 기록
+        if let Err(e) = self.1.client.usage().open(&pod).await {
+            tracing::error!("Failed to open usage record of session {}: {}", id, e);
+        }
         let transport = PodMcpTransport::connect(
             self.1.client.clone(),
             &self.0.template.namespace,
@@ -136,6 +274,41 @@ impl PodMcpSessionManager {
         Ok(id)
     }
 
+    /// Renders and creates the session pod along with the Secrets it owns.
+    async fn create_pod(
+        &self,
+        id: &SessionId,
+        args: HashMap<String, String>,
+        identity: Option<&SessionIdentity>,
+        matched: String,
+    ) -> Result<Pod, McpPodError> {
+        let (mut pod, secrets) = self
+            .0
+            .template
+            .to_pod(id, &self.1.client, args, identity, false)
+            .await?;
+        // 감사용으로 세션을 허용한 authorization 을 기록
+        pod.metadata
+            .annotations
+            .get_or_insert_default()
+            .insert(ANNOTATION_SESSION_AUTHORIZATION.to_string(), matched);
+        let namespace = &self.0.template.namespace;
+        let client = self.1.client.to_client();
+        // Secret 이 먼저 있어야 컨테이너가 바로 시작되므로 생성 후 Pod 에 소유권을 넘김
+        create_session_secrets(client.clone(), namespace, &secrets).await?;
+        let pod = match self.0.api.create(&PostParams::default(), &pod).await {
+            Ok(pod) => pod,
+            Err(e) => {
+                delete_session_secrets(client, namespace, &secrets).await;
+                return Err(e.into());
+            }
+        };
+        if let Err(e) = adopt_session_secrets(client, &pod, &secrets).await {
+            tracing::error!("Failed to hand session secrets over to pod {}: {}", id, e);
+        }
+        Ok(pod)
+    }
+
     pub async fn initialize_session(
         &self,
         id: &SessionId,
@@ -154,8 +327,8 @@ impl PodMcpSessionManager {
     ) -> Result<(), McpPodError> {
         tracing::info!("Deleting pod for session {}", id);
         //
-        let auth = self.0.template.get_authorization(&self.1.client).await?;
-        self.assert_auth_check(&auth, &req).await?;
+        let auths = self.0.template.get_authorizations(&self.1.client).await?;
+        self.assert_auth_check(&auths, &req).await?;
         //
         self.1.transports.write().await.remove(id);
 
@@ -163,6 +336,9 @@ impl PodMcpSessionManager {
             .api
             .delete(id.to_string().as_str(), &DeleteParams::default())
             .await?;
+        if let Err(e) = self.1.client.usage().close(id).await {
+            tracing::error!("Failed to close usage record of session {}: {}", id, e);
+        }
 
         Ok(())
     }
@@ -175,8 +351,8 @@ impl PodMcpSessionManager {
         tracing::debug!("Checking existence of session {}", id);
         let pod = self.0.api.get_opt(id.to_string().as_str()).await?;
         //
-        let auth = self.0.template.get_authorization(&self.1.client).await?;
-        self.assert_auth_check(&auth, &req).await?;
+        let auths = self.0.template.get_authorizations(&self.1.client).await?;
+        self.assert_auth_check(&auths, &req).await?;
         //
         Ok(pod.is_some())
     }
@@ -228,26 +404,139 @@ impl PodMcpSessionManager {
         Ok(())
     }
 
+    /// Checks the request against the template authorizations, any one of them is enough.
+    /// Returns the authenticated user and the name of the authorization that matched.
     async fn assert_auth_check(
+        &self,
+        auths: &[AuthorizationData],
+        req: &PodMcpRequest,
+    ) -> Result<(Option<SessionIdentity>, String), McpPodError> {
+        let shape = token_shape(req.token.as_deref(), self.1.oidc.as_ref());
+        let mut attempts = AuthAttempts::default();
+        for auth in auths {
+            let result = if dispatches_to(auth.r#type, shape) {
+                self.check_authorization(auth, req).await
+            } else {
+                Err(McpPodError::AuthorizationFailed {
+                    reason: format!("Token is not a {} credential", auth.r#type.as_str_name()),
+                })
+            };
+            if let Some(identity) = attempts.record(&auth.name, result) {
+                return Ok((identity, auth.name.clone()));
+            }
+        }
+        Err(attempts.into_error())
+    }
+
+    async fn check_authorization(
         &self,
         auth: &AuthorizationData,
         req: &PodMcpRequest,
-    ) -> Result<(), McpPodError> {
+    ) -> Result<Option<SessionIdentity>, McpPodError> {
         if auth.r#type == AuthorizationType::Anonymous {
-            return Ok(());
+            return Ok(None);
         }
         let Some(token) = &req.token else {
-            tracing::debug!("Authorizing pod session failed: token is missing");
             return Err(McpPodError::AuthorizationFailed {
                 reason: "Authorization token is missing".to_string(),
             });
         };
+        match auth.r#type {
+            AuthorizationType::Oidc => self.check_oidc(auth, token).map(Some),
+            AuthorizationType::ApiKey => self.check_api_key(auth, token).await.map(Some),
+            _ => self.check_service_account(auth, token, req).await.map(Some),
+        }
+    }
+
+    /// Validates a JWT of the orchestrator OIDC provider against the authorization conditions.
+    fn check_oidc(
+        &self,
+        auth: &AuthorizationData,
+        token: &str,
+    ) -> Result<SessionIdentity, McpPodError> {
+        let Some(oidc) = &self.1.oidc else {
+            return Err(McpPodError::AuthorizationFailed {
+                reason: format!(
+                    "Authorization {} requires OIDC but it is not configured",
+                    auth.name
+                ),
+            });
+        };
+        let condition = OidcAuthorization::from_data(&auth.data)?;
+        let claims = oidc
+            .decode_claims(token, &condition.audiences, &condition.issuers)
+            .map_err(|e| McpPodError::AuthorizationFailed {
+                reason: format!("OIDC token rejected: {}", e),
+            })?;
+        let identity = SessionIdentity::from_claims(claims);
+        condition
+            .check(&identity)
+            .map_err(|reason| McpPodError::AuthorizationFailed { reason })?;
+        Ok(identity)
+    }
+
+    async fn check_api_key(
+        &self,
+        auth: &AuthorizationData,
+        token: &str,
+    ) -> Result<SessionIdentity, McpPodError> {
+        let now = Utc::now();
+        let scope = format!("{}/{}", auth.namespace, auth.name);
+        let entry = match self.1.api_key_cache.get(&scope, &auth.api_keys, token, now) {
+            Some(entry) => entry.clone(),
+            None => {
+                // 검증 요청이 몰려도 blocking 스레드를 모두 점유하지 않도록 동시 검증 수를 제한
+                let _permit = self.1.api_key_permits.acquire().await.map_err(|e| {
+                    AppError::Internal(format!("Api key verification closed: {}", e))
+                })?;
+                let api_keys = auth.api_keys.clone();
+                let key = token.to_string();
+                // argon2 검증은 CPU 를 오래 쓰므로 blocking 스레드에서 수행
+                let entry = tokio::task::spawn_blocking(move || {
+                    verify_api_key(&api_keys, &key, now).cloned()
+                })
+                .await
+                .map_err(|e| AppError::Internal(format!("Api key verification panicked: {}", e)))?
+                .map_err(|reason| McpPodError::AuthorizationFailed { reason })?;
+                self.1.api_key_cache.insert(&scope, token, &entry);
+                entry
+            }
+        };
+        // 마지막 사용 시각은 분 단위로만 기록해 요청마다 Secret 을 쓰지 않도록 함
+        if entry
+            .last_used_at
+            .is_none_or(|x| now - x >= Duration::minutes(1))
+        {
+            let store = self.1.client.authorization(Some(auth.namespace.clone()));
+            let (name, id) = (auth.name.clone(), entry.id.clone());
+            tokio::spawn(async move {
+                if let Err(e) = store.touch_api_key(&name, &id).await {
+                    tracing::warn!("Failed to record api key use of {}: {}", name, e);
+                }
+            });
+        }
+        let claims = json!({
+            "sub": format!("apikey:{}/{}:{}", auth.namespace, auth.name, entry.name),
+            "authorization": auth.name,
+            "api_key": entry.name,
+        });
+        Ok(SessionIdentity::from_claims(
+            claims.as_object().cloned().unwrap_or_default(),
+        ))
+    }
+
+    async fn check_service_account(
+        &self,
+        auth: &AuthorizationData,
+        token: &str,
+        req: &PodMcpRequest,
+    ) -> Result<SessionIdentity, McpPodError> {
         let review = Api::<TokenReview>::all(self.1.client.to_client())
             .create(
                 &PostParams::default(),
                 &TokenReview {
                     spec: TokenReviewSpec {
-                        token: Some(token.clone()),
+                        token: Some(token.to_string()),
                         audiences: Some(vec![req.audience.clone()]),
                     },
                     ..Default::default()
@@ -255,32 +544,26 @@ impl PodMcpSessionManager {
             )
             .await?;
         let Some(review_status) = review.status else {
-            return Ok(());
+            return Err(McpPodError::AuthorizationFailed {
+                reason: "Token review returned no status".to_string(),
+            });
         };
         if !review_status.authenticated.unwrap_or(false) {
             return Err(McpPodError::AuthorizationFailed {
                 reason: "Token is not authenticated".to_string(),
             });
         }
-        let username = review_status
-            .user
-            .map(|x| x.username.unwrap_or_default())
-            .unwrap_or_default();
+        let identity = SessionIdentity::from_user_info(review_status.user.unwrap_or_default());
+        let username = identity.subject.clone();
         let expected_username = format!(
             "system:serviceaccount:{}:{}",
             self.0.template.namespace,
-            encode_k8sname(
-                RESOURCE_TYPE_PREFIX_AUTHORIZATION_SA,
-                &self.0.template.authorization_name
-            )
+            encode_k8sname(RESOURCE_TYPE_PREFIX_AUTHORIZATION_SA, &auth.name)
         );
         if username != expected_username {
             tracing::info!(
                 "Authorization failed: expected service account {}, got {}",
-                format!(
-                    "system:serviceaccount:{}:{}",
-                    self.0.template.namespace, self.0.template.authorization_name
-                ),
+                expected_username,
                 username
             );
             return Err(McpPodError::AuthorizationFailed {
@@ -290,6 +573,74 @@ impl PodMcpSessionManager {
                 ),
             });
         }
-        Ok(())
+        Ok(identity)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_token_shape() {
+        assert_eq!(token_shape(None, None), TokenShape::Missing);
+        let key = format!("mcpk_{}_{}", "a".repeat(12), "b".repeat(40));
+        assert_eq!(token_shape(Some(&key), None), TokenShape::ApiKey);
+        assert_eq!(
+            token_shape(Some("eyJhbGciOi.e30.sig"), None),
+            TokenShape::Other
+        );
+    }
+
+    #[test]
+    fn test_dispatches_to() {
+        use AuthorizationType::*;
+        assert!(dispatches_to(Anonymous, TokenShape::ApiKey));
+        assert!(dispatches_to(ApiKey, TokenShape::ApiKey));
+        assert!(!dispatches_to(Oidc, TokenShape::ApiKey));
+        assert!(!dispatches_to(KubernetesServiceAccount, TokenShape::ApiKey));
+        assert!(dispatches_to(Oidc, TokenShape::ProviderJwt));
+        assert!(!dispatches_to(ApiKey, TokenShape::ProviderJwt));
+        assert!(!dispatches_to(
+            KubernetesServiceAccount,
+            TokenShape::ProviderJwt
+        ));
+        assert!(dispatches_to(KubernetesServiceAccount, TokenShape::Other));
+        assert!(dispatches_to(Oidc, TokenShape::Other));
+        assert!(!dispatches_to(ApiKey, TokenShape::Other));
+        // 토큰이 없다는 사유를 각 authorization 이 보고
+        assert!(dispatches_to(ApiKey, TokenShape::Missing));
+    }
+
+    fn rejected(reason: &str) -> Result<(), McpPodError> {
+        Err(McpPodError::AuthorizationFailed {
+            reason: reason.to_string(),
+        })
+    }
+
+    #[test]
+    fn test_auth_attempts() {
+        let mut attempts = AuthAttempts::default();
+        assert_eq!(attempts.record("oidc", rejected("expired")), None);
+        assert_eq!(attempts.record("ci", Ok(())), Some(()));
+
+        let mut attempts = AuthAttempts::default();
+        attempts.record("oidc", rejected("expired"));
+        attempts.record("ci", rejected("not an api key"));
+        match attempts.into_error() {
+            McpPodError::AuthorizationFailed { reason } => {
+                assert_eq!(reason, "oidc: expired; ci: not an api key")
+            }
+            e => panic!("unexpected error {}", e),
+        }
+
+        // 확인 자체의 오류가 거부보다 우선
+        let mut attempts = AuthAttempts::default();
+        attempts.record("oidc", rejected("expired"));
+        attempts.record::<()>(
+            "sa",
+            Err(AppError::Internal("token review unavailable".to_string()).into()),
+        );
+        assert!(matches!(attempts.into_error(), McpPodError::AppError(_)));
     }
 }
@@ -1,6 +1,8 @@
 mod errors;
 mod manager;
+mod rotation;
 mod transport;
+mod validation;
 
 pub use errors::*;
 pub use manager::*;
@@ -0,0 +1,322 @@
+use chrono::{DateTime, Duration, Utc};
+use k8s_openapi::api::core::v1::{ConfigMap, ObjectReference, Pod};
+use kube::{
+    Api, Resource, ResourceExt,
+    api::{DeleteParams, ListParams, Patch, PatchParams},
+    runtime::events::{Event, EventType, Recorder},
+};
+use proto::mcp::orchestrator::v1::SecretRotationPolicy;
+use rmcp::model::{
+    LoggingLevel, LoggingMessageNotificationParam, Notification, ServerJsonRpcMessage,
+    ServerNotification,
+};
+use serde_json::json;
+
+use crate::{
+    error::AppError,
+    podmcp::PodMcp,
+    storage::{
+        McpTemplateData,
+        annotations::{
+            ANNOTATION_LAST_ACCESS_AT, ANNOTATION_ROTATED_SECRET, ANNOTATION_SESSION_RECYCLE_AT,
+            ANNOTATION_SESSION_STALE_AT,
+        },
+        label_query::build_label_query,
+        labels::label_dependency_query,
+        resource_type::{
+            RESOURCE_TYPE_MCP_SERVER, RESOURCE_TYPE_MCP_TEMPLATE, RESOURCE_TYPE_PREFIX_SECRET,
+            RESOURCE_TYPE_SECRET,
+        },
+        util_name::encode_k8sname,
+    },
+};
+
+/// Whether a session scheduled for recycling at `requested_at` can be deleted.
+/// `in_flight` is None when the session is not connected to this replica,
+/// it is then only recycled when it was not accessed since the request.
+pub fn should_recycle(
+    requested_at: DateTime<Utc>,
+    now: DateTime<Utc>,
+    drain_timeout: Duration,
+    in_flight: Option<usize>,
+    last_access_at: Option<DateTime<Utc>>,
+) -> bool {
+    if now - requested_at >= drain_timeout {
+        return true;
+    }
+    match in_flight {
+        Some(in_flight) => in_flight == 0,
+        None => last_access_at.is_none_or(|x| x < requested_at),
+    }
+}
+
+fn parse_time(pod: &Pod, annotation: &str) -> Option<DateTime<Utc>> {
+    pod.annotations()
+        .get(annotation)
+        .and_then(|x| x.parse().ok())
+}
+
+async fn record(
+    recorder: &Recorder,
+    pod: &Pod,
+    secondary: Option<ObjectReference>,
+    type_: EventType,
+    reason: &str,
+    note: String,
+) {
+    tracing::info!("Session {}: {} - {}", pod.name_any(), reason, note);
+    let event = Event {
+        type_,
+        reason: reason.to_string(),
+        note: Some(note),
+        action: "SecretRotation".to_string(),
+        secondary,
+    };
+    if let Err(e) = recorder.publish(&event, &pod.object_ref(&())).await {
+        tracing::error!("Failed to record {} of {}: {}", reason, pod.name_any(), e);
+    }
+}
+
+impl PodMcp {
+    /// Applies the rotation policy of every template using the secret to its running sessions.
+    pub async fn rotate_secret(
+        &self,
+        recorder: &Recorder,
+        namespace: &str,
+        secret: &str,
+        keys: &[String],
+    ) -> Result<(), AppError> {
+        let client = self.client().to_client();
+        let label = build_label_query(
+            RESOURCE_TYPE_MCP_TEMPLATE,
+            &[label_dependency_query(RESOURCE_TYPE_SECRET, secret)],
+        )?
+        .to_string();
+        let templates = Api::<ConfigMap>::namespaced(client.clone(), namespace)
+            .list(&ListParams::default().labels(&label))
+            .await?
+            .items
+            .into_iter()
+            .map(McpTemplateData::try_from_config_map)
+            .collect::<Result<Vec<_>, _>>()?;
+        let secondary = ObjectReference {
+            api_version: Some("v1".to_string()),
+            kind: Some("Secret".to_string()),
+            namespace: Some(namespace.to_string()),
+            name: Some(encode_k8sname(RESOURCE_TYPE_PREFIX_SECRET, secret)),
+            ..Default::default()
+        };
+        let api = Api::<Pod>::namespaced(client, namespace);
+        let now = Utc::now();
+        for template in templates {
+            let label = build_label_query(
+                RESOURCE_TYPE_MCP_SERVER,
+                &[label_dependency_query(
+                    RESOURCE_TYPE_MCP_TEMPLATE,
+                    &template.name,
+                )],
+            )?
+            .to_string();
+            let pods = api.list(&ListParams::default().labels(&label)).await?;
+            for pod in pods
+                .items
+                .iter()
+                .filter(|x| x.meta().deletion_timestamp.is_none())
+            {
+                let session_id = pod.name_any();
+                let summary = format!(
+                    "Secret {} of template {} changed ({})",
+                    secret,
+                    template.name,
+                    keys.join(", ")
+                );
+                let annotation = match template.secret_rotation {
+                    SecretRotationPolicy::None => {
+                        record(
+                            recorder,
+                            pod,
+                            Some(secondary.clone()),
+                            EventType::Normal,
+                            "SecretRotationIgnored",
+                            format!("{}, the session keeps the old values", summary),
+                        )
+                        .await;
+                        continue;
+                    }
+                    SecretRotationPolicy::Recycle => ANNOTATION_SESSION_RECYCLE_AT,
+                    SecretRotationPolicy::MarkStale => ANNOTATION_SESSION_STALE_AT,
+                };
+                let patch = Patch::Merge(json!({
+                    "metadata": {
+                        "annotations": {
+                            annotation: &now,
+                            ANNOTATION_ROTATED_SECRET: secret,
+                        }
+                    }
+                }));
+                if let Err(e) = api
+                    .patch(&session_id, &PatchParams::default(), &patch)
+                    .await
+                {
+                    tracing::error!("Failed to annotate session {}: {}", session_id, e);
+                    continue;
+                }
+                if template.secret_rotation == SecretRotationPolicy::Recycle {
+                    record(
+                        recorder,
+                        pod,
+                        Some(secondary.clone()),
+                        EventType::Normal,
+                        "RecycleScheduled",
+                        format!(
+                            "{}, the session is recycled after its in-flight calls",
+                            summary
+                        ),
+                    )
+                    .await;
+                    continue;
+                }
+                let notification = ServerNotification::LoggingMessageNotification(
+                    Notification::new(LoggingMessageNotificationParam {
+                        level: LoggingLevel::Warning,
+                        logger: Some("mcp-orchestrator".to_string()),
+                        data: json!({
+                            "message": format!("{}, reconnect to use the new values", summary),
+                            "secret": secret,
+                        }),
+                    }),
+                );
+                let notified = self
+                    .notify(
+                        &session_id,
+                        ServerJsonRpcMessage::notification(notification),
+                    )
+                    .await;
+                record(
+                    recorder,
+                    pod,
+                    Some(secondary.clone()),
+                    EventType::Warning,
+                    "SessionStale",
+                    format!(
+                        "{}, the session uses stale values{}",
+                        summary,
+                        if notified {
+                            ", clients were notified"
+                        } else {
+                            ""
+                        }
+                    ),
+                )
+                .await;
+            }
+        }
+        Ok(())
+    }
+
+    /// Deletes the sessions scheduled for recycling once their in-flight calls finished.
+    pub async fn recycle_sessions(
+        &self,
+        recorder: &Recorder,
+        drain_timeout: Duration,
+    ) -> Result<(), AppError> {
+        let label = build_label_query(RESOURCE_TYPE_MCP_SERVER, &[])?.to_string();
+        let pods = Api::<Pod>::all(self.client().to_client())
+            .list(&ListParams::default().labels(&label))
+            .await?;
+        let now = Utc::now();
+        for pod in pods
+            .items
+            .iter()
+            .filter(|x| x.meta().deletion_timestamp.is_none())
+        {
+            let Some(requested_at) = parse_time(pod, ANNOTATION_SESSION_RECYCLE_AT) else {
+                continue;
+            };
+            let session_id = pod.name_any();
+            let in_flight = self.in_flight(&session_id).await;
+            let last_access_at = parse_time(pod, ANNOTATION_LAST_ACCESS_AT);
+            if !should_recycle(requested_at, now, drain_timeout, in_flight, last_access_at) {
+                tracing::debug!(
+                    "Session {} is draining, {:?} calls in flight",
+                    session_id,
+                    in_flight
+                );
+                continue;
+            }
+            let namespace = pod.namespace().unwrap_or_else(|| "default".to_string());
+            self.remove_transport(&session_id.as_str().into()).await;
+            let api = Api::<Pod>::namespaced(self.client().to_client(), &namespace);
+            if let Err(e) = api.delete(&session_id, &DeleteParams::default()).await {
+                tracing::error!("Failed to recycle session {}: {}", session_id, e);
+                continue;
+            }
+            if let Err(e) = self.client().usage().close(&session_id).await {
+                tracing::error!(
+                    "Failed to close usage record of session {}: {}",
+                    session_id,
+                    e
+                );
+            }
+            let drained = now - requested_at < drain_timeout;
+            record(
+                recorder,
+                pod,
+                None,
+                if drained {
+                    EventType::Normal
+                } else {
+                    EventType::Warning
+                },
+                "Recycled",
+                if drained {
+                    "Session deleted after its in-flight calls finished".to_string()
+                } else {
+                    format!(
+                        "Session deleted after the drain timeout of {}s",
+                        drain_timeout.num_seconds()
+                    )
+                },
+            )
+            .await;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_should_recycle() {
+        let requested_at = Utc::now();
+        let timeout = Duration::minutes(5);
+        let later = requested_at + Duration::seconds(30);
+
+        assert!(should_recycle(requested_at, later, timeout, Some(0), None));
+        assert!(!should_recycle(requested_at, later, timeout, Some(2), None));
+        assert!(should_recycle(
+            requested_at,
+            requested_at + timeout,
+            timeout,
+            Some(2),
+            None
+        ));
+        // 다른 레플리카에 연결된 세션은 요청 이후 접근이 없을 때만
+        assert!(should_recycle(
+            requested_at,
+            later,
+            timeout,
+            None,
+            Some(requested_at - Duration::minutes(1))
+        ));
+        assert!(!should_recycle(
+            requested_at,
+            later,
+            timeout,
+            None,
+            Some(requested_at + Duration::seconds(10))
+        ));
+    }
+}
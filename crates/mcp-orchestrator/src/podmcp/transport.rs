@@ -7,7 +7,7 @@ use kube::{
 };
 use rmcp::{
     RoleServer,
-    model::{ClientJsonRpcMessage, ServerJsonRpcMessage},
+    model::{ClientJsonRpcMessage, JsonRpcMessage, ServerJsonRpcMessage},
     service::RxJsonRpcMessage,
     transport::{
         IntoTransport, Transport, async_rw::AsyncRwTransport,
@@ -15,7 +15,10 @@ use rmcp::{
     },
 };
 use serde_json::json;
-use std::sync::Arc;
+use std::sync::{
+    Arc,
+    atomic::{AtomicUsize, Ordering},
+};
 use tokio::sync::{
     Mutex,
     broadcast::{self, error::RecvError},
@@ -25,7 +28,10 @@ use tokio_stream::wrappers::BroadcastStream;
 
 use crate::{
     podmcp::{McpPodError, PodMcp},
-    storage::{annotations::ANNOTATION_LAST_ACCESS_AT, store::KubeStore},
+    storage::{
+        annotations::ANNOTATION_LAST_ACCESS_AT, store::KubeStore,
+        template_container::MAIN_CONTAINER_NAME,
+    },
 };
 
 #[derive(Clone)]
@@ -37,22 +43,40 @@ pub struct PodMcpTransport {
     api: Api<Pod>,
     upstream_tx: mpsc::Sender<ClientJsonRpcMessage>,
     downstream_tx: broadcast::Sender<ServerJsonRpcMessage>,
+    /// Requests sent to the pod that are not answered yet.
+    in_flight: Arc<AtomicUsize>,
 }
 
+/// How long `connect` waits for the session pod to be running and attachable.
+const CONNECT_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(15);
+
 impl PodMcpTransport {
     pub async fn connect(
         client: KubeStore,
         namespace: &str,
         session_id: &SessionId,
         podmcp: PodMcp,
+    ) -> Result<Self, McpPodError> {
+        let deadline = tokio::time::Instant::now() + CONNECT_TIMEOUT;
+        Self::connect_until(client, namespace, session_id, podmcp, deadline).await
+    }
+
+    /// Retries attaching to the session pod until `deadline`.
+    pub async fn connect_until(
+        client: KubeStore,
+        namespace: &str,
+        session_id: &SessionId,
+        podmcp: PodMcp,
+        deadline: tokio::time::Instant,
     ) -> Result<Self, McpPodError> {
         let api = Api::<Pod>::namespaced(client.to_client(), namespace);
         let (upstream_tx, mut upstream_rx) = mpsc::channel::<ClientJsonRpcMessage>(16);
         let (downstream_tx, _) = broadcast::channel::<ServerJsonRpcMessage>(16);
+        let in_flight = Arc::new(AtomicUsize::new(0));
         tokio::spawn({
             let api = api.clone();
             let mut attach = Option::<AttachedProcess>::None;
-            for _ in 0..50 {
+            while tokio::time::Instant::now() < deadline {
                 tokio::time::sleep(Duration::milliseconds(300).to_std().unwrap()).await;
                 tracing::debug!("Looking for pod with session ID {}", session_id);
                 let Some(pod) =
@@ -85,7 +109,7 @@ impl PodMcpTransport {
                     .stdin(true)
                     .stdout(true)
                     .stderr(false)
-                    .container("main")
+                    .container(MAIN_CONTAINER_NAME)
                     .tty(false);
 
                 // TODO: Lease 활용해 단일 스트림만 허용하도록 제한
@@ -125,6 +149,7 @@ impl PodMcpTransport {
             let transport = AsyncRwTransport::new_client(stdout, stdin);
             let mut transport = transport.into_transport();
             let downstream_tx = downstream_tx.clone();
+            let in_flight = in_flight.clone();
 
             async move {
                 let mut last_activity_at = DateTime::<Utc>::MIN_UTC;
@@ -166,6 +191,9 @@ impl PodMcpTransport {
                             match result {
                                 Some(msg) => {
                                     tracing::trace!("Received message from pod for session {}: {:?}", session_id, msg);
+                                    if matches!(msg, JsonRpcMessage::Response(_) | JsonRpcMessage::Error(_)) {
+                                        let _ = in_flight.fetch_update(Ordering::SeqCst, Ordering::SeqCst, |x| x.checked_sub(1));
+                                    }
                                     if let Err(err) = downstream_tx.send(msg) {
                                         tracing::warn!("no active receivers for session {}: {}", session_id, err);
                                         continue;
@@ -179,6 +207,9 @@ impl PodMcpTransport {
                         }
                         Some(msg) = upstream_rx.recv() => {
                             tracing::trace!("Sending message to pod for session {}: {:?}", session_id, msg);
+                            if matches!(msg, JsonRpcMessage::Request(_)) {
+                                in_flight.fetch_add(1, Ordering::SeqCst);
+                            }
                             if let Err(err) = transport.send(msg).await {
                                 tracing::error!("Failed to send message to pod for session {}: {}", session_id, err);
                                 break;
@@ -215,9 +246,19 @@ impl PodMcpTransport {
             last_event_time: Arc::new(Mutex::new(Utc::now())),
             upstream_tx,
             downstream_tx,
+            in_flight,
         })
     }
 
+    pub fn in_flight(&self) -> usize {
+        self.in_flight.load(Ordering::SeqCst)
+    }
+
+    /// Sends a message to the clients streaming the session, false when none is listening.
+    pub(crate) fn notify(&self, message: ServerJsonRpcMessage) -> bool {
+        self.downstream_tx.send(message).is_ok()
+    }
+
     pub async fn initialize_session(
         &self,
         message: ClientJsonRpcMessage,
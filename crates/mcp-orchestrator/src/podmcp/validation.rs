@@ -0,0 +1,280 @@
+use std::time::Duration;
+
+use futures::{Stream, StreamExt};
+use k8s_openapi::api::core::v1::{Node, Pod};
+use kube::{
+    Api,
+    api::{DeleteParams, ListParams, PostParams},
+};
+use proto::mcp::orchestrator::v1::{TemplateValidation, TemplateValidationReport};
+use rmcp::{
+    model::ClientJsonRpcMessage,
+    transport::{
+        common::server_side_http::{ServerSseMessage, session_id},
+        streamable_http_server::SessionId,
+    },
+};
+use serde_json::{Map, Value, json};
+
+use crate::{
+    podmcp::{McpPodError, PodMcp, PodMcpTransport},
+    storage::{
+        McpTemplateData,
+        scheduling_feasibility::check_schedulable,
+        session_secret::{adopt_session_secrets, create_session_secrets, delete_session_secrets},
+        template_claim::SessionIdentity,
+    },
+};
+
+const DEFAULT_SMOKE_TEST_TIMEOUT: Duration = Duration::from_secs(120);
+const MAX_SMOKE_TEST_TIMEOUT: Duration = Duration::from_secs(600);
+const SMOKE_TEST_PROTOCOL_VERSION: &str = "2025-06-18";
+const VALIDATION_SUBJECT: &str = "system:mcp-orchestrator:validation";
+
+struct Handshake {
+    protocol_version: Option<String>,
+    server_name: Option<String>,
+    tool_count: u32,
+}
+
+fn client_message(message: Value) -> Result<ClientJsonRpcMessage, McpPodError> {
+    serde_json::from_value(message).map_err(|e| McpPodError::HandshakeFailed {
+        reason: format!("Invalid client message: {}", e),
+    })
+}
+
+/// Sends a request and waits for the response with the same id.
+async fn request(
+    transport: &PodMcpTransport,
+    stream: &mut (impl Stream<Item = ServerSseMessage> + Unpin),
+    message: Value,
+) -> Result<Value, McpPodError> {
+    let request_id = message["id"].clone();
+    transport.upstream_tx_send(client_message(message)?).await?;
+    while let Some(event) = stream.next().await {
+        let response = serde_json::to_value(event.message.as_ref()).unwrap_or_default();
+        if response["id"] != request_id {
+            continue;
+        }
+        if let Some(error) = response.get("error") {
+            return Err(McpPodError::HandshakeFailed {
+                reason: error["message"].as_str().unwrap_or_default().to_string(),
+            });
+        }
+        return Ok(response["result"].clone());
+    }
+    Err(McpPodError::HandshakeFailed {
+        reason: "Session closed by the MCP server".to_string(),
+    })
+}
+
+fn smoke_test_timeout(timeout_seconds: Option<u32>) -> Duration {
+    timeout_seconds
+        .map(|x| Duration::from_secs(x as u64).min(MAX_SMOKE_TEST_TIMEOUT))
+        .unwrap_or(DEFAULT_SMOKE_TEST_TIMEOUT)
+}
+
+impl PodMcp {
+    /// Renders the template pod and checks it without persisting anything.
+    /// Problems of the template are reported, only cluster access failures are errors.
+    pub async fn validate_template(
+        &self,
+        template: &McpTemplateData,
+        validation: &TemplateValidation,
+    ) -> Result<TemplateValidationReport, McpPodError> {
+        let started = std::time::Instant::now();
+        let mut report = TemplateValidationReport::default();
+        let client = self.client();
+        let id = session_id();
+        let identity = SessionIdentity {
+            subject: VALIDATION_SUBJECT.to_string(),
+            claims: Map::from_iter([("sub".to_string(), Value::from(VALIDATION_SUBJECT))]),
+        };
+
+        let (pod, secrets) = match template
+            .to_pod(&id, client, validation.args.clone(), Some(&identity), true)
+            .await
+        {
+            Ok(rendered) => rendered,
+            Err(e) => {
+                report.messages.push(format!("Failed to render pod: {}", e));
+                report.duration_ms = started.elapsed().as_millis() as u64;
+                return Ok(report);
+            }
+        };
+        // 템플릿이 아직 없으므로 owner 없이 생성하고 직접 삭제함
+        let pod = Pod {
+            metadata: kube::api::ObjectMeta {
+                owner_references: None,
+                ..pod.metadata
+            },
+            ..pod
+        };
+
+        let api = Api::<Pod>::namespaced(client.to_client(), &template.namespace);
+        let dry_run = PostParams {
+            dry_run: true,
+            ..Default::default()
+        };
+        match api.create(&dry_run, &pod).await {
+            Ok(_) => report.pod_accepted = true,
+            Err(kube::Error::Api(resp)) => {
+                report
+                    .messages
+                    .push(format!("Pod rejected by the API server: {}", resp.message));
+            }
+            Err(e) => return Err(e.into()),
+        }
+
+        let spec = pod.spec.clone().unwrap_or_default();
+        match Api::<Node>::all(client.to_client())
+            .list(&ListParams::default())
+            .await
+        {
+            Ok(nodes) => match check_schedulable(&nodes.items, &spec) {
+                Ok(()) => report.schedulable = Some(true),
+                Err(reasons) => {
+                    report.schedulable = Some(false);
+                    report.messages.extend(
+                        reasons
+                            .into_iter()
+                            .map(|reason| format!("Not schedulable on {}", reason)),
+                    );
+                }
+            },
+            Err(e) => {
+                tracing::warn!("Failed to list nodes for template validation: {}", e);
+                report
+                    .messages
+                    .push(format!("Scheduling feasibility unknown: {}", e));
+            }
+        }
+
+        if validation.smoke_test && report.pod_accepted && report.schedulable != Some(false) {
+            let timeout = smoke_test_timeout(validation.timeout_seconds);
+            let deadline = tokio::time::Instant::now() + timeout;
+            let kube = client.to_client();
+            // 세션과 같이 Secret 을 먼저 만들고 Pod 에 소유권을 넘김
+            create_session_secrets(kube.clone(), &template.namespace, &secrets).await?;
+            let created = match api.create(&PostParams::default(), &pod).await {
+                Ok(created) => created,
+                Err(e) => {
+                    delete_session_secrets(kube, &template.namespace, &secrets).await;
+                    return Err(e.into());
+                }
+            };
+            if let Err(e) = adopt_session_secrets(kube.clone(), &created, &secrets).await {
+                tracing::error!(
+                    "Failed to hand secrets over to smoke test pod {}: {}",
+                    id,
+                    e
+                );
+            }
+            let result =
+                tokio::time::timeout_at(deadline, self.smoke_test(template, &id, deadline)).await;
+            if let Err(e) = api.delete(&id, &DeleteParams::default()).await {
+                tracing::error!("Failed to delete smoke test pod {}: {}", id, e);
+            }
+            delete_session_secrets(kube, &template.namespace, &secrets).await;
+            match result {
+                Ok(Ok(handshake)) => {
+                    report.handshake_ok = Some(true);
+                    report.protocol_version = handshake.protocol_version;
+                    report.server_name = handshake.server_name;
+                    report.tool_count = Some(handshake.tool_count);
+                }
+                Ok(Err(e)) => {
+                    report.handshake_ok = Some(false);
+                    report.messages.push(format!("Smoke test failed: {}", e));
+                }
+                Err(_) => {
+                    report.handshake_ok = Some(false);
+                    report.messages.push(format!(
+                        "Smoke test timed out after {} seconds",
+                        timeout.as_secs()
+                    ));
+                }
+            }
+        }
+
+        report.passed = report.pod_accepted
+            && report.schedulable != Some(false)
+            && report.handshake_ok != Some(false);
+        report.duration_ms = started.elapsed().as_millis() as u64;
+        Ok(report)
+    }
+
+    /// Completes initialize and tools/list on the session pod.
+    async fn smoke_test(
+        &self,
+        template: &McpTemplateData,
+        id: &SessionId,
+        deadline: tokio::time::Instant,
+    ) -> Result<Handshake, McpPodError> {
+        // 이미지 pull 이 느린 경우를 위해 기본 연결 대기 시간 대신 smoke test 기한까지 재시도
+        let transport = PodMcpTransport::connect_until(
+            self.client().clone(),
+            &template.namespace,
+            id,
+            self.clone(),
+            deadline,
+        )
+        .await?;
+        let mut stream = Box::pin(transport.downstream_rx_stream().await);
+        let initialize = request(
+            &transport,
+            &mut stream,
+            json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": "initialize",
+                "params": {
+                    "protocolVersion": SMOKE_TEST_PROTOCOL_VERSION,
+                    "capabilities": {},
+                    "clientInfo": {
+                        "name": "mcp-orchestrator-smoke-test",
+                        "version": env!("CARGO_PKG_VERSION"),
+                    },
+                },
+            }),
+        )
+        .await?;
+        transport
+            .upstream_tx_send(client_message(json!({
+                "jsonrpc": "2.0",
+                "method": "notifications/initialized",
+            }))?)
+            .await?;
+        let tools = request(
+            &transport,
+            &mut stream,
+            json!({
+                "jsonrpc": "2.0",
+                "id": 2,
+                "method": "tools/list",
+                "params": {},
+            }),
+        )
+        .await?;
+
+        Ok(Handshake {
+            protocol_version: initialize["protocolVersion"].as_str().map(str::to_string),
+            server_name: initialize["serverInfo"]["name"]
+                .as_str()
+                .map(str::to_string),
+            tool_count: tools["tools"].as_array().map_or(0, |x| x.len() as u32),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_smoke_test_timeout() {
+        assert_eq!(smoke_test_timeout(None), DEFAULT_SMOKE_TEST_TIMEOUT);
+        assert_eq!(smoke_test_timeout(Some(30)), Duration::from_secs(30));
+        assert_eq!(smoke_test_timeout(Some(u32::MAX)), MAX_SMOKE_TEST_TIMEOUT);
+    }
+}
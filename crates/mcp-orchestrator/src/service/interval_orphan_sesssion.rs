@@ -8,7 +8,7 @@ use crate::{
     state::AppState,
     storage::{
         annotations::ANNOTATION_LAST_ACCESS_AT, label_query::build_label_query,
-        resource_type::RESOURCE_TYPE_MCP_SERVER,
+        resource_type::RESOURCE_TYPE_MCP_SERVER, session_secret::delete_unowned_session_secrets,
     },
 };
 
@@ -69,6 +69,13 @@ pub async fn check_orphan_session(state: &AppState) {
             match api.delete(&pod_name, &DeleteParams::default()).await {
                 Ok(_) => {
                     tracing::info!("Deleted orphan MCP server pod: {}/{}", namespace, pod_name);
+                    if let Err(e) = state.kube_store.usage().close(&pod_name).await {
+                        tracing::error!(
+                            "Failed to close usage record of session {}: {}",
+                            pod_name,
+                            e
+                        );
+                    }
                 }
                 Err(e) => {
                     tracing::error!(
@@ -81,6 +88,12 @@ pub async fn check_orphan_session(state: &AppState) {
             }
         }
     }
+
+    if let Err(e) =
+        delete_unowned_session_secrets(state.kube_client.clone(), now - Duration::minutes(1)).await
+    {
+        tracing::error!("Failed to clean up unowned session secrets: {}", e);
+    }
 }
 
 fn is_pod_orphan(session_max_idle_time: &Duration, now: &DateTime<Utc>, pod: &Pod) -> bool {
@@ -0,0 +1,18 @@
+use chrono::Duration;
+
+use crate::state::AppState;
+
+pub async fn recycle_sessions(state: &AppState) {
+    tracing::debug!("Starting session recycle check");
+    let Ok(drain_timeout) = Duration::from_std(state.config.mcp.recycle_drain_timeout) else {
+        tracing::error!("Invalid recycle drain timeout");
+        return;
+    };
+    if let Err(e) = state
+        .podmcp
+        .recycle_sessions(&state.kube_recorder, drain_timeout)
+        .await
+    {
+        tracing::error!("Failed to recycle sessions: {}", e);
+    }
+}
@@ -0,0 +1,23 @@
+use chrono::Duration;
+
+use crate::state::AppState;
+
+pub async fn meter_usage(state: &AppState) {
+    tracing::debug!("Starting usage metering");
+    let config = &state.config.metering;
+    let (Ok(interval), Ok(retention)) = (
+        Duration::from_std(config.interval),
+        Duration::from_std(config.retention),
+    ) else {
+        tracing::error!("Invalid metering interval or retention");
+        return;
+    };
+    if let Err(e) = state
+        .kube_store
+        .usage()
+        .meter(interval, retention, config.sample_metrics)
+        .await
+    {
+        tracing::error!("Failed to meter session usage: {}", e);
+    }
+}
@@ -0,0 +1,96 @@
+use chrono::Utc;
+use k8s_openapi::api::core::v1::PersistentVolumeClaim;
+use kube::Api;
+
+use crate::{
+    state::AppState,
+    storage::{
+        label_query::build_label_query, resource_type::RESOURCE_TYPE_WORKSPACE,
+        store_workspace::WorkspaceData,
+    },
+};
+
+pub async fn check_idle_workspace(state: &AppState) {
+    tracing::debug!("Starting idle workspace check");
+    let api = Api::<PersistentVolumeClaim>::all(state.kube_client.clone());
+    let Ok(label_query) = build_label_query(RESOURCE_TYPE_WORKSPACE, &[]) else {
+        tracing::error!("Failed to build label query");
+        return;
+    };
+    let now = Utc::now();
+    let mut list_params = kube::api::ListParams::default()
+        .labels(&label_query)
+        .limit(32);
+    let mut workspaces = Vec::new();
+    loop {
+        let pvcs = match api.list(&list_params).await {
+            Ok(pvcs) => pvcs,
+            Err(e) => {
+                tracing::error!("Failed to list workspace PVCs: {}", e);
+                return;
+            }
+        };
+        for pvc in pvcs.items {
+            match WorkspaceData::try_from_pvc(pvc) {
+                Ok(workspace) => workspaces.push(workspace),
+                Err(e) => tracing::warn!("Skipping invalid workspace PVC: {}", e),
+            }
+        }
+        if let Some(continue_token) = &pvcs.metadata.continue_ {
+            list_params = list_params.continue_token(continue_token);
+        } else {
+            break;
+        }
+    }
+
+    for workspace in workspaces {
+        if workspace.deleted_at.is_some() {
+            continue;
+        }
+        let store = state
+            .kube_store
+            .workspaces(Some(workspace.namespace.clone()));
+        match store.is_in_use(&workspace).await {
+            Ok(true) => {
+                // 사용중인 workspace 는 마지막 사용 시각만 갱신
+                if let Err(e) = store.touch(&workspace.name).await {
+                    tracing::error!(
+                        "Failed to touch workspace {}/{}: {}",
+                        workspace.namespace,
+                        workspace.name,
+                        e
+                    );
+                }
+            }
+            Ok(false) if workspace.is_expired(&now) => {
+                match store.delete_idle(&workspace.name, &now).await {
+                    Ok(true) => {
+                        tracing::info!(
+                            "Deleted idle workspace: {}/{}",
+                            workspace.namespace,
+                            workspace.name
+                        );
+                    }
+                    Ok(false) => {}
+                    Err(e) => {
+                        tracing::error!(
+                            "Failed to delete idle workspace {}/{}: {}",
+                            workspace.namespace,
+                            workspace.name,
+                            e
+                        );
+                    }
+                }
+            }
+            Ok(false) => {}
+            Err(e) => {
+                tracing::error!(
+                    "Failed to check usage of workspace {}/{}: {}",
+                    workspace.namespace,
+                    workspace.name,
+                    e
+                );
+            }
+        }
+    }
+}
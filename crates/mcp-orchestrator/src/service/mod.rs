@@ -11,6 +11,9 @@ use kube::{
 use tokio_util::sync::CancellationToken;
 
 mod interval_orphan_sesssion;
+mod interval_session_recycle;
+mod interval_usage_metering;
+mod interval_workspace_gc;
 pub(crate) mod util;
 
 use crate::{
@@ -19,9 +22,9 @@ use crate::{
     storage::{
         label_query::build_label_query,
         resource_type::{
-            RESOURCE_TYPE_MCP_TEMPLATE, RESOURCE_TYPE_NAMESPACE, RESOURCE_TYPE_PREFIX_MCP_TEMPLATE,
-            RESOURCE_TYPE_PREFIX_RESOURCE_LIMIT, RESOURCE_TYPE_PREFIX_SECRET,
-            RESOURCE_TYPE_RESOURCE_LIMIT, RESOURCE_TYPE_SECRET,
+            RESOURCE_TYPE_AUTHORIZATION, RESOURCE_TYPE_MCP_TEMPLATE, RESOURCE_TYPE_NAMESPACE,
+            RESOURCE_TYPE_PREFIX_MCP_TEMPLATE, RESOURCE_TYPE_PREFIX_RESOURCE_LIMIT,
+            RESOURCE_TYPE_PREFIX_SECRET, RESOURCE_TYPE_RESOURCE_LIMIT, RESOURCE_TYPE_SECRET,
         },
         resource_uname::filter_relpath,
         store::KubeStore,
@@ -38,11 +41,29 @@ pub async fn listeners(state: AppState, ct: CancellationToken) {
     tokio::spawn(mcp_template_listener(state.clone()));
     tokio::spawn(resource_limit_listener(state.clone()));
     interval_handler(
-        state,
+        state.clone(),
         Duration::seconds(15),
         ct.clone(),
         crate::make_interval_handler!(interval_orphan_sesssion::check_orphan_session),
     );
+    interval_handler(
+        state.clone(),
+        Duration::seconds(10),
+        ct.clone(),
+        crate::make_interval_handler!(interval_session_recycle::recycle_sessions),
+    );
+    interval_handler(
+        state.clone(),
+        Duration::minutes(5),
+        ct.clone(),
+        crate::make_interval_handler!(interval_workspace_gc::check_idle_workspace),
+    );
+    interval_handler(
+        state.clone(),
+        Duration::from_std(state.config.metering.interval).unwrap_or(Duration::minutes(1)),
+        ct.clone(),
+        crate::make_interval_handler!(interval_usage_metering::meter_usage),
+    );
 }
 
 async fn secret_listener(state: AppState) {
@@ -438,6 +459,8 @@ fn after_delete<T: ResourceExt<DynamicType = ()> + Send + 'static>(
                         handle_delete_resource_limit(kubestore, namespace, raw_name).await;
                     });
                 }
+                // authorization 은 finalizer 없이 삭제 요청 시점에 확인
+                RESOURCE_TYPE_AUTHORIZATION => {}
                 _ => {
                     tracing::warn!(
                         "Unexpected dependency type '{}' with name '{}' on Secret {}",
@@ -9,7 +9,7 @@ use crate::{config::AppConfig, podmcp::PodMcp, storage::store::KubeStore};
 pub struct AppState {
     pub kube_client: Client,
     pub kube_store: KubeStore,
-    pub _kube_recorder: Recorder,
+    pub kube_recorder: Recorder,
     pub podmcp: PodMcp,
     pub config: Arc<AppConfig>,
     pub oidc_manager: Option<AuthManager>,
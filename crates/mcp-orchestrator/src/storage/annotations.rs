@@ -1,5 +1,21 @@
 pub const ANNOTATION_DESCRIPTION: &str = "mcp-orchestrator.egoavara.net/description";
 pub const ANNOTATION_LAST_ACCESS_AT: &str = "mcp-orchestrator.egoavara.net/last-access-at";
+pub const ANNOTATION_WORKSPACE_SUBJECT: &str = "mcp-orchestrator.egoavara.net/workspace-subject";
+pub const ANNOTATION_WORKSPACE_RETENTION: &str =
+    "mcp-orchestrator.egoavara.net/workspace-retention";
+pub const ANNOTATION_WORKSPACE_IDLE_TTL: &str = "mcp-orchestrator.egoavara.net/workspace-idle-ttl";
+pub const ANNOTATION_WORKSPACE_LEASE: &str = "mcp-orchestrator.egoavara.net/workspace-lease";
+pub const ANNOTATION_USER_SUBJECT: &str = "mcp-orchestrator.egoavara.net/user-subject";
+pub const ANNOTATION_SESSION_QUOTA: &str = "mcp-orchestrator.egoavara.net/session-quota";
+pub const ANNOTATION_DEFAULT_RESOURCE_LIMIT: &str =
+    "mcp-orchestrator.egoavara.net/default-resource-limit";
+pub const ANNOTATION_SESSION_RESERVATIONS: &str =
+    "mcp-orchestrator.egoavara.net/session-reservations";
+pub const ANNOTATION_SESSION_RECYCLE_AT: &str = "mcp-orchestrator.egoavara.net/recycle-at";
+pub const ANNOTATION_SESSION_STALE_AT: &str = "mcp-orchestrator.egoavara.net/stale-at";
+pub const ANNOTATION_ROTATED_SECRET: &str = "mcp-orchestrator.egoavara.net/rotated-secret";
+pub const ANNOTATION_SESSION_AUTHORIZATION: &str = "mcp-orchestrator.egoavara.net/authorization";
+pub const ANNOTATION_PREFIX_USER_CLAIM: &str = "mcp-orchestrator.egoavara.net/claim-";
 
 pub fn annotation_description(description: &str) -> (String, String) {
     (ANNOTATION_DESCRIPTION.to_string(), description.to_string())
@@ -0,0 +1,223 @@
+use std::{
+    collections::HashMap,
+    sync::{LazyLock, Mutex},
+    time::{Duration, Instant},
+};
+
+use argon2::{
+    Argon2,
+    password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString, rand_core::OsRng},
+};
+use chrono::{DateTime, Utc};
+use rand::{Rng, distributions::Alphanumeric};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::error::AppError;
+
+const API_KEY_PREFIX: &str = "mcpk";
+const API_KEY_ID_LEN: usize = 12;
+const API_KEY_SECRET_LEN: usize = 40;
+
+/// Verified when the key id is unknown, so lookups take as long as a real verification.
+static LAZY_DUMMY_HASH: LazyLock<String> =
+    LazyLock::new(|| hash_api_key("dummy").expect("hash dummy api key"));
+
+/// Key of an API_KEY authorization, only the argon2 hash of the secret is stored.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApiKeyEntry {
+    pub name: String,
+    pub id: String,
+    pub hash: String,
+    pub created_at: DateTime<Utc>,
+    #[serde(default)]
+    pub expire_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub last_used_at: Option<DateTime<Utc>>,
+}
+
+impl ApiKeyEntry {
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expire_at.is_some_and(|x| x <= now)
+    }
+}
+
+fn random_string(len: usize) -> String {
+    rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(len)
+        .map(char::from)
+        .collect()
+}
+
+fn hash_api_key(secret: &str) -> Result<String, AppError> {
+    let salt = SaltString::generate(&mut OsRng);
+    Argon2::default()
+        .hash_password(secret.as_bytes(), &salt)
+        .map(|x| x.to_string())
+        .map_err(|e| AppError::Internal(format!("Failed to hash api key: {}", e)))
+}
+
+/// Generates `mcpk_<id>_<secret>`, returning the entry to store and the key shown once.
+pub fn generate_api_key(
+    name: &str,
+    now: DateTime<Utc>,
+    expire_at: Option<DateTime<Utc>>,
+) -> Result<(ApiKeyEntry, String), AppError> {
+    let id = random_string(API_KEY_ID_LEN);
+    let secret = random_string(API_KEY_SECRET_LEN);
+    let entry = ApiKeyEntry {
+        name: name.to_string(),
+        id: id.clone(),
+        hash: hash_api_key(&secret)?,
+        created_at: now,
+        expire_at,
+        last_used_at: None,
+    };
+    Ok((entry, format!("{}_{}_{}", API_KEY_PREFIX, id, secret)))
+}
+
+/// Splits a key into its id and secret, None when it is not an api key.
+pub fn parse_api_key(key: &str) -> Option<(&str, &str)> {
+    let rest = key.strip_prefix(API_KEY_PREFIX)?.strip_prefix('_')?;
+    let (id, secret) = rest.split_once('_')?;
+    (id.len() == API_KEY_ID_LEN && !secret.is_empty()).then_some((id, secret))
+}
+
+fn verify_hash(secret: &str, hash: &str) -> bool {
+    PasswordHash::new(hash)
+        .map(|hash| {
+            Argon2::default()
+                .verify_password(secret.as_bytes(), &hash)
+                .is_ok()
+        })
+        .unwrap_or(false)
+}
+
+/// Finds the entry of the key, comparing the secret in constant time through argon2.
+pub fn verify_api_key<'a>(
+    entries: &'a [ApiKeyEntry],
+    key: &str,
+    now: DateTime<Utc>,
+) -> Result<&'a ApiKeyEntry, String> {
+    let Some((id, secret)) = parse_api_key(key) else {
+        return Err("Token is not an api key".to_string());
+    };
+    let Some(entry) = entries.iter().find(|x| x.id == id) else {
+        verify_hash(secret, &LAZY_DUMMY_HASH);
+        return Err("Api key is not valid".to_string());
+    };
+    if !verify_hash(secret, &entry.hash) {
+        return Err("Api key is not valid".to_string());
+    }
+    if entry.is_expired(now) {
+        return Err(format!("Api key {} is expired", entry.name));
+    }
+    Ok(entry)
+}
+
+/// Keys verified recently, by SHA-256 of the authorization and the key,
+/// so the requests of a session do not pay an argon2 verification each.
+pub struct ApiKeyCache {
+    ttl: Duration,
+    verified: Mutex<HashMap<String, (String, String, Instant)>>,
+}
+
+impl ApiKeyCache {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            verified: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn cache_key(authorization: &str, key: &str) -> String {
+        let digest = Sha256::new()
+            .chain_update(authorization.as_bytes())
+            .chain_update([0])
+            .chain_update(key.as_bytes())
+            .finalize();
+        digest.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    /// Entry of a key verified within the ttl, as long as it is still stored and not expired.
+    pub fn get<'a>(
+        &self,
+        authorization: &str,
+        entries: &'a [ApiKeyEntry],
+        key: &str,
+        now: DateTime<Utc>,
+    ) -> Option<&'a ApiKeyEntry> {
+        let verified = self.verified.lock().ok()?;
+        let (id, hash, verified_at) = verified.get(&Self::cache_key(authorization, key))?;
+        if verified_at.elapsed() >= self.ttl {
+            return None;
+        }
+        // 키가 삭제되거나 재발급되면 hash 가 달라지므로 캐시를 쓰지 않음
+        entries
+            .iter()
+            .find(|x| &x.id == id && &x.hash == hash && !x.is_expired(now))
+    }
+
+    pub fn insert(&self, authorization: &str, key: &str, entry: &ApiKeyEntry) {
+        let Ok(mut verified) = self.verified.lock() else {
+            return;
+        };
+        verified.retain(|_, (_, _, verified_at)| verified_at.elapsed() < self.ttl);
+        verified.insert(
+            Self::cache_key(authorization, key),
+            (entry.id.clone(), entry.hash.clone(), Instant::now()),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verify_api_key() {
+        let now = Utc::now();
+        let (entry, key) = generate_api_key("ci", now, None).unwrap();
+        let (id, secret) = parse_api_key(&key).unwrap();
+        assert_eq!(id, entry.id);
+        assert!(!entry.hash.contains(secret));
+        assert_eq!(parse_api_key("Bearer eyJhbGciOi"), None);
+
+        let (expired, expired_key) =
+            generate_api_key("old", now, Some(now - chrono::Duration::minutes(1))).unwrap();
+        let entries = vec![entry.clone(), expired];
+
+        assert_eq!(verify_api_key(&entries, &key, now).unwrap().name, "ci");
+        assert!(verify_api_key(&entries, &expired_key, now).is_err());
+        let wrong = format!("{}_{}_{}", API_KEY_PREFIX, entry.id, "x".repeat(40));
+        assert!(verify_api_key(&entries, &wrong, now).is_err());
+        let unknown = format!("{}_{}_{}", API_KEY_PREFIX, "y".repeat(12), "x".repeat(40));
+        assert!(verify_api_key(&entries, &unknown, now).is_err());
+    }
+
+    #[test]
+    fn test_api_key_cache() {
+        let now = Utc::now();
+        let (entry, key) = generate_api_key("ci", now, None).unwrap();
+        let entries = vec![entry.clone()];
+        let cache = ApiKeyCache::new(Duration::from_secs(60));
+        assert!(cache.get("default/ci", &entries, &key, now).is_none());
+
+        cache.insert("default/ci", &key, &entry);
+        assert_eq!(cache.get("default/ci", &entries, &key, now), Some(&entry));
+        assert!(cache.get("default/other", &entries, &key, now).is_none());
+        assert!(cache.get("default/ci", &entries, "other", now).is_none());
+        // 삭제되거나 만료된 키는 캐시되어 있어도 거부
+        assert!(cache.get("default/ci", &[], &key, now).is_none());
+        let expired = ApiKeyEntry {
+            expire_at: Some(now),
+            ..entry.clone()
+        };
+        assert!(cache.get("default/ci", &[expired], &key, now).is_none());
+
+        let cache = ApiKeyCache::new(Duration::ZERO);
+        cache.insert("default/ci", &key, &entry);
+        assert!(cache.get("default/ci", &entries, &key, now).is_none());
+    }
+}
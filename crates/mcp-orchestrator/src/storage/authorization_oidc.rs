@@ -0,0 +1,127 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{error::AppError, storage::template_claim::SessionIdentity};
+
+/// Claim the token must carry, with one of `values` when any are given.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ClaimCondition {
+    /// Claim path such as `email_verified` or `claims.tenant.id`.
+    pub path: String,
+    #[serde(default)]
+    pub values: Vec<String>,
+}
+
+/// `data` of an OIDC authorization, empty audiences and issuers fall back to the provider defaults.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct OidcAuthorization {
+    #[serde(default)]
+    pub audiences: Vec<String>,
+    #[serde(default)]
+    pub issuers: Vec<String>,
+    #[serde(default)]
+    pub claims: Vec<ClaimCondition>,
+}
+
+impl OidcAuthorization {
+    pub fn from_data(data: &Value) -> Result<Self, AppError> {
+        if data.is_null() {
+            return Ok(Self::default());
+        }
+        let oidc: Self = serde_json::from_value(data.clone()).map_err(|e| {
+            AppError::InvalidInput(format!("Invalid OIDC authorization data: {}", e))
+        })?;
+        if let Some(condition) = oidc.claims.iter().find(|x| x.path.is_empty()) {
+            return Err(AppError::InvalidInput(format!(
+                "Claim condition {:?} requires a path",
+                condition
+            )));
+        }
+        Ok(oidc)
+    }
+
+    /// Checks the claim conditions, returning the reason of the first unmet one.
+    pub fn check(&self, identity: &SessionIdentity) -> Result<(), String> {
+        for condition in &self.claims {
+            let Some(value) = identity.claim_value(&condition.path) else {
+                return Err(format!("Claim {} is missing", condition.path));
+            };
+            if condition.values.is_empty() {
+                continue;
+            }
+            let matched = match value {
+                Value::Array(items) => items.iter().any(|x| condition.accepts(x)),
+                _ => condition.accepts(value),
+            };
+            if !matched {
+                return Err(format!(
+                    "Claim {} must be one of {}",
+                    condition.path,
+                    condition.values.join(", ")
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl ClaimCondition {
+    fn accepts(&self, value: &Value) -> bool {
+        let value = match value {
+            Value::String(s) => s.clone(),
+            Value::Bool(_) | Value::Number(_) => value.to_string(),
+            _ => return false,
+        };
+        self.values.contains(&value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_claims() {
+        let identity = SessionIdentity::from_claims(
+            serde_json::json!({
+                "sub": "user-1",
+                "email_verified": true,
+                "groups": ["dev", "ops"],
+                "tenant": { "id": "acme" },
+            })
+            .as_object()
+            .unwrap()
+            .clone(),
+        );
+        assert_eq!(identity.subject, "user-1");
+
+        let oidc = OidcAuthorization::from_data(&serde_json::json!({
+            "audiences": ["mcp"],
+            "claims": [
+                { "path": "email_verified", "values": ["true"] },
+                { "path": "groups", "values": ["ops", "admin"] },
+                { "path": "claims.tenant.id" },
+            ],
+        }))
+        .unwrap();
+        assert_eq!(oidc.audiences, vec!["mcp".to_string()]);
+        assert!(oidc.check(&identity).is_ok());
+
+        let oidc = OidcAuthorization::from_data(&serde_json::json!({
+            "claims": [{ "path": "groups", "values": ["admin"] }],
+        }))
+        .unwrap();
+        assert!(oidc.check(&identity).is_err());
+        let oidc = OidcAuthorization::from_data(&serde_json::json!({
+            "claims": [{ "path": "tenant.name" }],
+        }))
+        .unwrap();
+        assert!(oidc.check(&identity).is_err());
+
+        assert_eq!(
+            OidcAuthorization::from_data(&Value::Null).unwrap(),
+            OidcAuthorization::default()
+        );
+        assert!(OidcAuthorization::from_data(&serde_json::json!({ "claims": [{}] })).is_err());
+    }
+}
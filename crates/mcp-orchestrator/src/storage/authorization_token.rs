@@ -0,0 +1,130 @@
+use chrono::{DateTime, Utc};
+use k8s_openapi::api::core::v1::Secret;
+use kube::ResourceExt;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::AppError,
+    storage::{
+        resource_type::RESOURCE_TYPE_PREFIX_AUTHORIZATION_TOKEN,
+        util_name::decode_k8sname,
+        utils::{parse_secret_elem, pick_created_at},
+    },
+};
+
+pub const DATA_TOKEN_RECORD: &str = "record";
+
+/// What is known about a token issued by GenerateToken, the token itself is never stored.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TokenRecord {
+    pub authorization: String,
+    #[serde(default)]
+    pub requester: Option<String>,
+    pub audience: String,
+    /// The token is bound to the Secret holding this record, deleting it revokes the token.
+    pub bound: bool,
+    #[serde(default)]
+    pub expire_at: Option<DateTime<Utc>>,
+}
+
+pub struct IssuedTokenData {
+    pub raw: Secret,
+    pub id: String,
+    pub record: TokenRecord,
+    pub created_at: DateTime<Utc>,
+}
+
+impl IssuedTokenData {
+    pub fn try_from_secret(raw: Secret) -> Result<Self, AppError> {
+        Ok(Self {
+            id: decode_k8sname(RESOURCE_TYPE_PREFIX_AUTHORIZATION_TOKEN, &raw.name_any())
+                .ok_or_else(|| {
+                    AppError::Internal(format!(
+                        "Failed to decode token record name: {}, it must start with {}-",
+                        raw.name_any(),
+                        RESOURCE_TYPE_PREFIX_AUTHORIZATION_TOKEN
+                    ))
+                })?,
+            record: parse_secret_elem(&raw.data, DATA_TOKEN_RECORD)?,
+            created_at: pick_created_at(&raw),
+            raw,
+        })
+    }
+
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.record.expire_at.is_some_and(|x| x <= now)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+    use kube::api::ObjectMeta;
+
+    use super::*;
+    use crate::storage::utils::data_secret;
+
+    fn record_secret(name: &str, record: &TokenRecord) -> Secret {
+        Secret {
+            metadata: ObjectMeta {
+                name: Some(name.to_string()),
+                ..Default::default()
+            },
+            data: Some(
+                [data_secret(DATA_TOKEN_RECORD, record).unwrap()]
+                    .into_iter()
+                    .collect(),
+            ),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_issued_token_data() {
+        let now = Utc::now();
+        let record = TokenRecord {
+            authorization: "ci".to_string(),
+            requester: Some("alice".to_string()),
+            audience: "mcp".to_string(),
+            bound: true,
+            expire_at: Some(now + Duration::hours(1)),
+        };
+        let name = format!("{}-0123abcd", RESOURCE_TYPE_PREFIX_AUTHORIZATION_TOKEN);
+        let issued = IssuedTokenData::try_from_secret(record_secret(&name, &record)).unwrap();
+        assert_eq!(issued.id, "0123abcd");
+        assert_eq!(issued.record, record);
+
+        assert!(IssuedTokenData::try_from_secret(record_secret("sc-github", &record)).is_err());
+        let empty = Secret {
+            metadata: ObjectMeta {
+                name: Some(name),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        assert!(IssuedTokenData::try_from_secret(empty).is_err());
+    }
+
+    #[test]
+    fn test_is_expired() {
+        let now = Utc::now();
+        let record = TokenRecord {
+            authorization: "ci".to_string(),
+            requester: None,
+            audience: "mcp".to_string(),
+            bound: false,
+            expire_at: Some(now),
+        };
+        let name = format!("{}-0123abcd", RESOURCE_TYPE_PREFIX_AUTHORIZATION_TOKEN);
+        let issued = IssuedTokenData::try_from_secret(record_secret(&name, &record)).unwrap();
+        assert!(issued.is_expired(now));
+        assert!(!issued.is_expired(now - Duration::seconds(1)));
+
+        let record = TokenRecord {
+            expire_at: None,
+            ..record
+        };
+        let issued = IssuedTokenData::try_from_secret(record_secret(&name, &record)).unwrap();
+        assert!(!issued.is_expired(now + Duration::days(365)));
+    }
+}
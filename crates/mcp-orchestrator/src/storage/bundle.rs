@@ -0,0 +1,938 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use proto::mcp::orchestrator::v1::{
+    self, AuthorizationType, BundleConflictPolicy, BundleImportAction,
+};
+use serde::{Deserialize, Deserializer, Serialize};
+
+use crate::{
+    error::AppError,
+    storage::{
+        McpTemplateCreate, McpTemplateData, ResourceLimitData, SecretData,
+        labels::LABEL_CUSTOM_PREFIX,
+        resource_type::{
+            RESOURCE_TYPE_AUTHORIZATION, RESOURCE_TYPE_MCP_TEMPLATE, RESOURCE_TYPE_RESOURCE_LIMIT,
+            RESOURCE_TYPE_SECRET,
+        },
+        secret_backend::SecretRef,
+        secret_type::{KEY_DOCKER_CONFIG_JSON, SECRET_TYPE_DOCKER_CONFIG_JSON},
+        store::KubeStore,
+        store_authorization::AuthorizationData,
+        util_list::ListOption,
+    },
+};
+
+pub const BUNDLE_API_VERSION: &str = "mcp-orchestrator.egoavara.net/v1";
+pub const BUNDLE_KIND: &str = "Bundle";
+
+const LIST_PAGE_SIZE: i32 = 100;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Bundle {
+    pub api_version: String,
+    pub kind: String,
+    #[serde(default)]
+    pub resource_limits: Vec<BundleResourceLimit>,
+    #[serde(default)]
+    pub authorizations: Vec<BundleAuthorization>,
+    #[serde(default)]
+    pub secrets: Vec<BundleSecret>,
+    #[serde(default)]
+    pub templates: Vec<BundleTemplate>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BundleResourceLimit {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub labels: BTreeMap<String, String>,
+    pub limits: v1::ResourceLimit,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BundleAuthorization {
+    pub name: String,
+    #[serde(default)]
+    pub labels: BTreeMap<String, String>,
+    pub r#type: String,
+    #[serde(default)]
+    pub data: serde_json::Value,
+}
+
+/// Secret values are never exported, only the key names a template expects.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BundleSecret {
+    pub name: String,
+    #[serde(default)]
+    pub labels: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub r#type: Option<String>,
+    #[serde(default)]
+    pub keys: BTreeSet<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BundleTemplate {
+    pub name: String,
+    #[serde(default)]
+    pub labels: BTreeMap<String, String>,
+    pub image: String,
+    #[serde(default)]
+    pub command: Vec<String>,
+    #[serde(default)]
+    pub args: Vec<String>,
+    #[serde(default)]
+    pub envs: BTreeMap<String, String>,
+    #[serde(default)]
+    pub arg_envs: BTreeMap<String, String>,
+    #[serde(default)]
+    pub arg_env_specs: BTreeMap<String, v1::ArgEnvSpec>,
+    #[serde(default)]
+    pub claim_envs: BTreeMap<String, String>,
+    #[serde(default)]
+    pub secret_envs: Vec<String>,
+    pub resource_limit_name: String,
+    /// Bundles exported before templates had several authorizations carry a single name.
+    #[serde(alias = "authorization_name", deserialize_with = "one_or_many")]
+    pub authorization_names: Vec<String>,
+    #[serde(default)]
+    pub volume_mounts: Vec<v1::VolumeMount>,
+    #[serde(default)]
+    pub secret_mounts: Vec<v1::SecretMount>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub workspace: Option<v1::WorkspaceConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub package: Option<v1::PackageSource>,
+    #[serde(default)]
+    pub init_containers: Vec<v1::TemplateContainer>,
+    #[serde(default)]
+    pub sidecars: Vec<v1::TemplateContainer>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub security: Option<v1::SecurityProfile>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub egress: Option<v1::EgressPolicy>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub image_pull_secrets: Vec<String>,
+    #[serde(default)]
+    pub secret_rotation: v1::SecretRotationPolicy,
+}
+
+fn one_or_many<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<String>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany {
+        One(String),
+        Many(Vec<String>),
+    }
+    Ok(match OneOrMany::deserialize(deserializer)? {
+        OneOrMany::One(x) => vec![x],
+        OneOrMany::Many(x) => x,
+    })
+}
+
+fn custom_labels(labels: &HashMap<String, String>) -> BTreeMap<String, String> {
+    let prefix = format!("{}/", LABEL_CUSTOM_PREFIX);
+    labels
+        .iter()
+        .filter_map(|(k, v)| k.strip_prefix(&prefix).map(|k| (k.to_string(), v.clone())))
+        .collect()
+}
+
+impl From<&ResourceLimitData> for BundleResourceLimit {
+    fn from(data: &ResourceLimitData) -> Self {
+        Self {
+            name: data.name.clone(),
+            description: data.description.clone(),
+            labels: custom_labels(&data.labels),
+            limits: data.to_resource_limit(),
+        }
+    }
+}
+
+impl From<&AuthorizationData> for BundleAuthorization {
+    fn from(data: &AuthorizationData) -> Self {
+        Self {
+            name: data.name.clone(),
+            labels: custom_labels(&data.labels),
+            r#type: data.r#type.as_str_name().to_string(),
+            data: data.data.clone(),
+        }
+    }
+}
+
+impl From<&SecretData> for BundleSecret {
+    fn from(data: &SecretData) -> Self {
+        Self {
+            name: data.name.clone(),
+            labels: custom_labels(&data.labels),
+            r#type: data.raw.type_.clone(),
+            keys: data
+                .raw
+                .data
+                .iter()
+                .flatten()
+                .map(|(k, _)| k.clone())
+                .collect(),
+        }
+    }
+}
+
+impl From<&McpTemplateData> for BundleTemplate {
+    fn from(data: &McpTemplateData) -> Self {
+        Self {
+            name: data.name.clone(),
+            labels: custom_labels(&data.labels),
+            image: data.image.clone(),
+            // package 템플릿의 command 는 생성 시 다시 만들어짐
+            command: if data.package.is_some() {
+                Vec::new()
+            } else {
+                data.command.clone()
+            },
+            args: data.args.clone(),
+            envs: data.envs.clone().into_iter().collect(),
+            arg_envs: data.arg_envs.clone().into_iter().collect(),
+            // arg_env_specs 에는 arg_envs 가 병합되어 있으므로 제외
+            arg_env_specs: data
+                .arg_env_specs
+                .iter()
+                .filter(|(k, _)| !data.arg_envs.contains_key(*k))
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect(),
+            claim_envs: data.claim_envs.clone().into_iter().collect(),
+            secret_envs: data.secret_envs.clone(),
+            resource_limit_name: data.resource_limit_name.clone(),
+            authorization_names: data.authorization_names.clone(),
+            volume_mounts: data.volume_mounts.clone(),
+            secret_mounts: data.secret_mounts.clone(),
+            workspace: data.workspace.clone(),
+            package: data.package.clone(),
+            init_containers: data.init_containers.clone(),
+            sidecars: data.sidecars.clone(),
+            security: data.security.clone(),
+            egress: data.egress.clone(),
+            image_pull_secrets: data.image_pull_secrets.clone(),
+            secret_rotation: data.secret_rotation,
+        }
+    }
+}
+
+impl BundleTemplate {
+    fn to_create(&self) -> McpTemplateCreate {
+        McpTemplateCreate {
+            image: self.image.clone(),
+            command: self.command.clone(),
+            args: self.args.clone(),
+            envs: self.envs.clone().into_iter().collect(),
+            arg_envs: self.arg_envs.clone().into_iter().collect(),
+            arg_env_specs: self.arg_env_specs.clone().into_iter().collect(),
+            secret_envs: self.secret_envs.clone(),
+            resource_limit_name: self.resource_limit_name.clone(),
+            authorization_names: self.authorization_names.clone(),
+            volume_mounts: self.volume_mounts.clone(),
+            secret_mounts: self.secret_mounts.clone(),
+            workspace: self.workspace.clone(),
+            claim_envs: self.claim_envs.clone().into_iter().collect(),
+            package: self.package.clone(),
+            init_containers: self.init_containers.clone(),
+            sidecars: self.sidecars.clone(),
+            security: self.security.clone(),
+            egress: self.egress.clone(),
+            image_pull_secrets: self.image_pull_secrets.clone(),
+            secret_rotation: self.secret_rotation,
+        }
+    }
+
+    fn rename_references(&mut self, renames: &HashMap<(&'static str, String), String>) {
+        let renamed = |kind: &'static str, name: &String| {
+            renames
+                .get(&(kind, name.clone()))
+                .cloned()
+                .unwrap_or_else(|| name.clone())
+        };
+        self.resource_limit_name = renamed(RESOURCE_TYPE_RESOURCE_LIMIT, &self.resource_limit_name);
+        for authorization in self.authorization_names.iter_mut() {
+            *authorization = renamed(RESOURCE_TYPE_AUTHORIZATION, authorization);
+        }
+        for secret in self.secret_envs.iter_mut() {
+            *secret = renamed(RESOURCE_TYPE_SECRET, secret);
+        }
+        for mount in self.secret_mounts.iter_mut() {
+            mount.name = renamed(RESOURCE_TYPE_SECRET, &mount.name);
+        }
+        for secret in self.image_pull_secrets.iter_mut() {
+            *secret = renamed(RESOURCE_TYPE_SECRET, secret);
+        }
+    }
+}
+
+impl Bundle {
+    pub fn from_yaml(yaml: &str) -> Result<Self, AppError> {
+        let bundle: Bundle = serde_yaml::from_str(yaml)
+            .map_err(|e| AppError::InvalidInput(format!("Invalid bundle: {}", e)))?;
+        if bundle.api_version != BUNDLE_API_VERSION || bundle.kind != BUNDLE_KIND {
+            return Err(AppError::InvalidInput(format!(
+                "Unsupported bundle {}/{}, expected {}/{}",
+                bundle.api_version, bundle.kind, BUNDLE_API_VERSION, BUNDLE_KIND
+            )));
+        }
+        Ok(bundle)
+    }
+
+    pub fn to_yaml(&self) -> Result<String, AppError> {
+        to_yaml(self)
+    }
+}
+
+/// Values the secret is created with on import, the values themselves are never in the bundle.
+fn placeholder_secret_data(secret: &BundleSecret) -> Vec<(String, String)> {
+    let mut data = secret
+        .keys
+        .iter()
+        .map(|k| (k.clone(), String::new()))
+        .collect::<BTreeMap<_, _>>();
+    // API 서버가 .dockerconfigjson 을 JSON 으로 검증하므로 빈 값 대신 빈 auths 를 채움
+    if secret.r#type.as_deref() == Some(SECRET_TYPE_DOCKER_CONFIG_JSON) {
+        data.insert(
+            KEY_DOCKER_CONFIG_JSON.to_string(),
+            r#"{"auths":{}}"#.to_string(),
+        );
+    }
+    data.into_iter().collect()
+}
+
+/// Secrets the bundle uses as image pull secrets, of templates and resource limits.
+fn pull_secret_names(bundle: &Bundle) -> BTreeSet<String> {
+    bundle
+        .templates
+        .iter()
+        .flat_map(|x| x.image_pull_secrets.iter())
+        .chain(
+            bundle
+                .resource_limits
+                .iter()
+                .flat_map(|x| x.limits.image_pull_secrets.iter()),
+        )
+        .cloned()
+        .collect()
+}
+
+fn to_yaml<T: Serialize>(value: &T) -> Result<String, AppError> {
+    serde_yaml::to_string(value)
+        .map_err(|e| AppError::Internal(format!("Failed to serialize bundle: {}", e)))
+}
+
+/// Line based diff of two documents, unchanged lines are prefixed with two spaces.
+pub fn line_diff(old: &str, new: &str) -> String {
+    let old = old.lines().collect::<Vec<_>>();
+    let new = new.lines().collect::<Vec<_>>();
+    let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+    let mut out = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            out.push(format!("  {}", old[i]));
+            i += 1;
+            j += 1;
+        } else if j < new.len() && (i == old.len() || lcs[i][j + 1] >= lcs[i + 1][j]) {
+            out.push(format!("+ {}", new[j]));
+            j += 1;
+        } else {
+            out.push(format!("- {}", old[i]));
+            i += 1;
+        }
+    }
+    out.join("\n")
+}
+
+pub async fn export_bundle(
+    store: &KubeStore,
+    namespace: Option<String>,
+    template_names: &[String],
+) -> Result<Bundle, AppError> {
+    let template_store = store.mcp_templates(namespace.clone());
+    let mut templates = Vec::new();
+    if template_names.is_empty() {
+        let mut option = ListOption {
+            first: Some(LIST_PAGE_SIZE),
+            after: None,
+        };
+        loop {
+            let (items, after, _) = template_store.list(&[], option.clone()).await?;
+            templates.extend(items);
+            if after.is_none() {
+                break;
+            }
+            option.after = after;
+        }
+    } else {
+        for name in template_names {
+            let template = template_store
+                .get(name)
+                .await?
+                .ok_or_else(|| AppError::NotFound(format!("McpTemplate {} not found", name)))?;
+            templates.push(template);
+        }
+    }
+    templates.retain(|x| x.deleted_at.is_none());
+    templates.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let resource_limit_names = templates
+        .iter()
+        .map(|x| x.resource_limit_name.clone())
+        .collect::<BTreeSet<_>>();
+    let authorization_names = templates
+        .iter()
+        .flat_map(|x| x.authorization_names.iter().cloned())
+        .collect::<BTreeSet<_>>();
+    let mut bundle = Bundle {
+        api_version: BUNDLE_API_VERSION.to_string(),
+        kind: BUNDLE_KIND.to_string(),
+        resource_limits: Vec::new(),
+        authorizations: Vec::new(),
+        secrets: Vec::new(),
+        templates: templates.iter().map(BundleTemplate::from).collect(),
+    };
+    let resource_limit_store = store.resource_limits();
+    for name in resource_limit_names {
+        match resource_limit_store.get(&name).await? {
+            Some(data) => bundle.resource_limits.push((&data).into()),
+            None => tracing::warn!("ResourceLimit {} referenced by a template not found", name),
+        }
+    }
+    let pull_secrets = pull_secret_names(&bundle);
+    let secret_names = templates
+        .iter()
+        .flat_map(|x| {
+            x.secret_envs
+                .iter()
+                .chain(x.secret_mounts.iter().map(|m| &m.name))
+        })
+        .chain(&pull_secrets)
+        // 외부 백엔드의 시크릿은 번들에 담지 않음
+        .filter_map(|x| {
+            SecretRef::parse(x)
+                .ok()
+                .filter(SecretRef::is_kubernetes)
+                .map(|x| x.path)
+        })
+        .collect::<BTreeSet<_>>();
+    let authorization_store = store.authorization(namespace.clone());
+    for name in authorization_names {
+        match authorization_store.get(&name).await? {
+            Some(data) => bundle.authorizations.push((&data).into()),
+            None => tracing::warn!("Authorization {} referenced by a template not found", name),
+        }
+    }
+    let secret_store = store.secrets(namespace);
+    for name in secret_names {
+        match secret_store.get(&name).await? {
+            Some(data) => {
+                if pull_secrets.contains(&name)
+                    && data.raw.type_.as_deref() != Some(SECRET_TYPE_DOCKER_CONFIG_JSON)
+                {
+                    return Err(AppError::InvalidInput(format!(
+                        "Image pull secret {} must be a {} secret",
+                        name, SECRET_TYPE_DOCKER_CONFIG_JSON
+                    )));
+                }
+                bundle.secrets.push((&data).into())
+            }
+            None => tracing::warn!("Secret {} referenced by a template not found", name),
+        }
+    }
+    Ok(bundle)
+}
+
+struct Importer<'a> {
+    store: &'a KubeStore,
+    namespace: Option<String>,
+    policy: BundleConflictPolicy,
+    dry_run: bool,
+    renames: HashMap<(&'static str, String), String>,
+    items: Vec<v1::BundleImportItem>,
+}
+
+enum Decision {
+    Create,
+    Unchanged,
+    Skip,
+    Overwrite,
+    Rename(String),
+}
+
+impl Importer<'_> {
+    fn item(&self, kind: &str, name: &str, action: BundleImportAction) -> v1::BundleImportItem {
+        v1::BundleImportItem {
+            kind: kind.to_string(),
+            name: name.to_string(),
+            action: action.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Compares the existing resource with the bundle and picks what to do by the conflict policy.
+    async fn decide<T, F, Fut>(
+        &mut self,
+        kind: &'static str,
+        name: &str,
+        existing: Option<T>,
+        incoming: &T,
+        exists: F,
+    ) -> Result<(Decision, Option<String>), AppError>
+    where
+        T: Serialize + PartialEq,
+        F: Fn(String) -> Fut,
+        Fut: Future<Output = Result<bool, AppError>>,
+    {
+        let Some(existing) = existing else {
+            return Ok((Decision::Create, None));
+        };
+        if &existing == incoming {
+            return Ok((Decision::Unchanged, None));
+        }
+        let diff = line_diff(&to_yaml(&existing)?, &to_yaml(incoming)?);
+        let decision = match self.policy {
+            BundleConflictPolicy::Skip => Decision::Skip,
+            BundleConflictPolicy::Overwrite => Decision::Overwrite,
+            BundleConflictPolicy::Rename => {
+                let mut index = 2;
+                let renamed = loop {
+                    let candidate = format!("{}-{}", name, index);
+                    if !exists(candidate.clone()).await? {
+                        break candidate;
+                    }
+                    index += 1;
+                };
+                self.renames
+                    .insert((kind, name.to_string()), renamed.clone());
+                Decision::Rename(renamed)
+            }
+        };
+        Ok((decision, Some(diff)))
+    }
+
+    fn record(
+        &mut self,
+        kind: &str,
+        name: &str,
+        decision: &Decision,
+        diff: Option<String>,
+        result: Result<Option<String>, AppError>,
+    ) {
+        let mut item = match decision {
+            Decision::Create => self.item(kind, name, BundleImportAction::Create),
+            Decision::Unchanged => self.item(kind, name, BundleImportAction::Unchanged),
+            Decision::Skip => self.item(kind, name, BundleImportAction::Skip),
+            Decision::Overwrite => self.item(kind, name, BundleImportAction::Overwrite),
+            Decision::Rename(renamed) => v1::BundleImportItem {
+                renamed_to: Some(renamed.clone()),
+                ..self.item(kind, name, BundleImportAction::Rename)
+            },
+        };
+        item.diff = diff;
+        match result {
+            Ok(message) => item.message = message,
+            Err(e) => {
+                tracing::warn!("Failed to import {} {}: {}", kind, name, e);
+                item.set_action(BundleImportAction::Failed);
+                item.message = Some(e.to_string());
+            }
+        }
+        self.items.push(item);
+    }
+
+    fn target_name<'n>(decision: &'n Decision, name: &'n str) -> Option<&'n str> {
+        match decision {
+            Decision::Create | Decision::Overwrite => Some(name),
+            Decision::Rename(renamed) => Some(renamed),
+            Decision::Unchanged | Decision::Skip => None,
+        }
+    }
+
+    async fn import_resource_limit(&mut self, incoming: &BundleResourceLimit) {
+        let kind = RESOURCE_TYPE_RESOURCE_LIMIT;
+        let store = self.store.resource_limits();
+        let mut current = None;
+        let planned = match store.get(&incoming.name).await {
+            Ok(existing) => {
+                current = existing;
+                let existing = current.as_ref().map(BundleResourceLimit::from);
+                let kube = self.store;
+                self.decide(kind, &incoming.name, existing, incoming, |name| {
+                    let store = kube.resource_limits();
+                    async move { Ok(store.get(&name).await?.is_some()) }
+                })
+                .await
+            }
+            Err(e) => Err(e),
+        };
+        let (decision, diff) = match planned {
+            Ok(planned) => planned,
+            Err(e) => return self.record(kind, &incoming.name, &Decision::Create, None, Err(e)),
+        };
+        let result = match Self::target_name(&decision, &incoming.name) {
+            Some(target) if !self.dry_run => {
+                let overwrite = async {
+                    let labels = incoming.labels.clone().into_iter();
+                    let overwritten = current
+                        .as_ref()
+                        .filter(|_| matches!(decision, Decision::Overwrite));
+                    let Some(current) = overwritten else {
+                        return store
+                            .create(target, labels, &incoming.description, &incoming.limits)
+                            .await
+                            .map(|_| None);
+                    };
+                    if !store.is_deletable(target).await? {
+                        return Err(AppError::InvalidInput(format!(
+                            "ResourceLimit {} is used by other templates, it can not be overwritten",
+                            target
+                        )));
+                    }
+                    // 삭제 후 재생성은 실패 시 리소스가 사라지므로 resourceVersion 으로 교체
+                    store
+                        .replace(current, labels, &incoming.description, &incoming.limits)
+                        .await
+                        .map(|_| None)
+                };
+                overwrite.await
+            }
+            _ => Ok(None),
+        };
+        self.record(kind, &incoming.name, &decision, diff, result);
+    }
+
+    async fn import_authorization(&mut self, incoming: &BundleAuthorization) {
+        let kind = RESOURCE_TYPE_AUTHORIZATION;
+        let store = self.store.authorization(self.namespace.clone());
+        let Some(r#type) = AuthorizationType::from_str_name(&incoming.r#type) else {
+            return self.record(
+                kind,
+                &incoming.name,
+                &Decision::Create,
+                None,
+                Err(AppError::InvalidInput(format!(
+                    "Unknown authorization type {}",
+                    incoming.r#type
+                ))),
+            );
+        };
+        let mut current = None;
+        let planned = match store.get(&incoming.name).await {
+            Ok(existing) => {
+                current = existing;
+                let existing = current.as_ref().map(BundleAuthorization::from);
+                let (kube, namespace) = (self.store, self.namespace.clone());
+                self.decide(kind, &incoming.name, existing, incoming, |name| {
+                    let store = kube.authorization(namespace.clone());
+                    async move { Ok(store.get(&name).await?.is_some()) }
+                })
+                .await
+            }
+            Err(e) => Err(e),
+        };
+        let (decision, diff) = match planned {
+            Ok(planned) => planned,
+            Err(e) => return self.record(kind, &incoming.name, &Decision::Create, None, Err(e)),
+        };
+        let result = match Self::target_name(&decision, &incoming.name) {
+            Some(target) if !self.dry_run => {
+                let overwrite = async {
+                    let labels = incoming.labels.clone().into_iter();
+                    let overwritten = current
+                        .as_ref()
+                        .filter(|_| matches!(decision, Decision::Overwrite));
+                    let Some(current) = overwritten else {
+                        return store
+                            .create(target, labels, r#type, &incoming.data)
+                            .await
+                            .map(|_| None);
+                    };
+                    if !store.is_deletable(target).await? {
+                        return Err(AppError::InvalidInput(format!(
+                            "Authorization {} is used by other templates, it can not be overwritten",
+                            target
+                        )));
+                    }
+                    store
+                        .replace(current, labels, r#type, &incoming.data)
+                        .await
+                        .map(|_| None)
+                };
+                overwrite.await
+            }
+            _ => Ok(None),
+        };
+        self.record(kind, &incoming.name, &decision, diff, result);
+    }
+
+    async fn import_secret(&mut self, incoming: &BundleSecret, pull_secret: bool) {
+        let kind = RESOURCE_TYPE_SECRET;
+        let store = self.store.secrets(self.namespace.clone());
+        if pull_secret && incoming.r#type.as_deref() != Some(SECRET_TYPE_DOCKER_CONFIG_JSON) {
+            return self.record(
+                kind,
+                &incoming.name,
+                &Decision::Create,
+                None,
+                Err(AppError::InvalidInput(format!(
+                    "Image pull secret {} must be a {} secret",
+                    incoming.name, SECRET_TYPE_DOCKER_CONFIG_JSON
+                ))),
+            );
+        }
+        let planned = match store.get(&incoming.name).await {
+            Ok(existing) => {
+                let existing = existing.as_ref().map(BundleSecret::from);
+                let (kube, namespace) = (self.store, self.namespace.clone());
+                self.decide(kind, &incoming.name, existing, incoming, |name| {
+                    let store = kube.secrets(namespace.clone());
+                    async move { Ok(store.get(&name).await?.is_some()) }
+                })
+                .await
+            }
+            Err(e) => Err(e),
+        };
+        let (decision, diff) = match planned {
+            Ok(planned) => planned,
+            Err(e) => return self.record(kind, &incoming.name, &Decision::Create, None, Err(e)),
+        };
+        // 시크릿 값은 번들에 없으므로 기존 시크릿은 덮어쓰지 않음
+        let decision = match decision {
+            Decision::Overwrite => Decision::Skip,
+            decision => decision,
+        };
+        let result = match Self::target_name(&decision, &incoming.name) {
+            Some(target) if !self.dry_run => store
+                .create(
+                    target,
+                    incoming.r#type.clone(),
+                    incoming.labels.clone().into_iter(),
+                    placeholder_secret_data(incoming).into_iter(),
+                )
+                .await
+                .map(|_| Some("Created with empty values, fill in the secret values".to_string())),
+            Some(_) => Ok(Some("Will be created with empty values".to_string())),
+            None if matches!(decision, Decision::Skip) => {
+                Ok(Some("Secret values are never overwritten".to_string()))
+            }
+            None => Ok(None),
+        };
+        self.record(kind, &incoming.name, &decision, diff, result);
+    }
+
+    async fn import_template(&mut self, incoming: &BundleTemplate) {
+        let kind = RESOURCE_TYPE_MCP_TEMPLATE;
+        let store = self.store.mcp_templates(self.namespace.clone());
+        let mut incoming = incoming.clone();
+        incoming.rename_references(&self.renames);
+        let mut current = None;
+        let planned = match store.get(&incoming.name).await {
+            Ok(existing) => {
+                current = existing;
+                let existing = current.as_ref().map(BundleTemplate::from);
+                let (kube, namespace) = (self.store, self.namespace.clone());
+                self.decide(kind, &incoming.name, existing, &incoming, |name| {
+                    let store = kube.mcp_templates(namespace.clone());
+                    async move { Ok(store.get(&name).await?.is_some()) }
+                })
+                .await
+            }
+            Err(e) => Err(e),
+        };
+        let (decision, diff) = match planned {
+            Ok(planned) => planned,
+            Err(e) => return self.record(kind, &incoming.name, &Decision::Create, None, Err(e)),
+        };
+        let result = match Self::target_name(&decision, &incoming.name) {
+            Some(target) if !self.dry_run => {
+                let overwrite = async {
+                    let labels = incoming.labels.clone().into_iter();
+                    let overwritten = current
+                        .as_ref()
+                        .filter(|_| matches!(decision, Decision::Overwrite));
+                    let Some(current) = overwritten else {
+                        return store
+                            .create(target, labels, incoming.to_create())
+                            .await
+                            .map(|_| None);
+                    };
+                    if !store.is_deletable(target).await? {
+                        return Err(AppError::InvalidInput(format!(
+                            "McpTemplate {} has running sessions, it can not be overwritten",
+                            target
+                        )));
+                    }
+                    store
+                        .replace(current, labels, incoming.to_create())
+                        .await
+                        .map(|_| None)
+                };
+                overwrite.await
+            }
+            _ => Ok(None),
+        };
+        self.record(kind, &incoming.name, &decision, diff, result);
+    }
+}
+
+/// Imports the bundle in dependency order, resource limits, authorizations, secrets and then templates.
+pub async fn import_bundle(
+    store: &KubeStore,
+    namespace: Option<String>,
+    bundle: &Bundle,
+    policy: BundleConflictPolicy,
+    dry_run: bool,
+) -> Vec<v1::BundleImportItem> {
+    let mut importer = Importer {
+        store,
+        namespace,
+        policy,
+        dry_run,
+        renames: HashMap::new(),
+        items: Vec::new(),
+    };
+    for resource_limit in &bundle.resource_limits {
+        importer.import_resource_limit(resource_limit).await;
+    }
+    for authorization in &bundle.authorizations {
+        importer.import_authorization(authorization).await;
+    }
+    let pull_secrets = pull_secret_names(bundle);
+    for secret in &bundle.secrets {
+        importer
+            .import_secret(secret, pull_secrets.contains(&secret.name))
+            .await;
+    }
+    for template in &bundle.templates {
+        importer.import_template(template).await;
+    }
+    importer.items
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_diff() {
+        let diff = line_diff("a\nb\nc", "a\nc\nd");
+        assert_eq!(diff, "  a\n- b\n  c\n+ d");
+        assert_eq!(line_diff("a\nb", "a\nb"), "  a\n  b");
+    }
+
+    #[test]
+    fn test_bundle_roundtrip() {
+        let bundle = Bundle {
+            api_version: BUNDLE_API_VERSION.to_string(),
+            kind: BUNDLE_KIND.to_string(),
+            resource_limits: vec![],
+            authorizations: vec![],
+            secrets: vec![BundleSecret {
+                name: "github".to_string(),
+                labels: BTreeMap::new(),
+                r#type: None,
+                keys: ["GITHUB_TOKEN".to_string()].into(),
+            }],
+            templates: vec![],
+        };
+        let yaml = bundle.to_yaml().unwrap();
+        assert!(yaml.contains("apiVersion: mcp-orchestrator.egoavara.net/v1"));
+        let parsed = Bundle::from_yaml(&yaml).unwrap();
+        assert_eq!(parsed.secrets, bundle.secrets);
+
+        assert!(Bundle::from_yaml("apiVersion: v0\nkind: Bundle\n").is_err());
+    }
+
+    #[test]
+    fn test_placeholder_secret_data() {
+        let secret = BundleSecret {
+            name: "github".to_string(),
+            labels: BTreeMap::new(),
+            r#type: None,
+            keys: ["GITHUB_TOKEN".to_string()].into(),
+        };
+        assert_eq!(
+            placeholder_secret_data(&secret),
+            vec![("GITHUB_TOKEN".to_string(), String::new())]
+        );
+        let secret = BundleSecret {
+            r#type: Some(SECRET_TYPE_DOCKER_CONFIG_JSON.to_string()),
+            keys: BTreeSet::new(),
+            ..secret
+        };
+        assert_eq!(
+            placeholder_secret_data(&secret),
+            vec![(
+                KEY_DOCKER_CONFIG_JSON.to_string(),
+                r#"{"auths":{}}"#.to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn test_rename_references() {
+        let mut template = BundleTemplate {
+            name: "github".to_string(),
+            labels: BTreeMap::new(),
+            image: "ghcr.io/github/github-mcp-server".to_string(),
+            command: vec![],
+            args: vec![],
+            envs: BTreeMap::new(),
+            arg_envs: BTreeMap::new(),
+            arg_env_specs: BTreeMap::new(),
+            claim_envs: BTreeMap::new(),
+            secret_envs: vec!["github".to_string()],
+            resource_limit_name: "small".to_string(),
+            authorization_names: vec!["anonymous".to_string()],
+            volume_mounts: vec![],
+            secret_mounts: vec![v1::SecretMount {
+                name: "github".to_string(),
+                mount_path: "/etc/github".to_string(),
+            }],
+            workspace: None,
+            package: None,
+            init_containers: vec![],
+            sidecars: vec![],
+            security: None,
+            egress: None,
+            image_pull_secrets: vec![],
+            secret_rotation: v1::SecretRotationPolicy::None,
+        };
+        let renames = [
+            (
+                (RESOURCE_TYPE_SECRET, "github".to_string()),
+                "github-2".to_string(),
+            ),
+            (
+                (RESOURCE_TYPE_RESOURCE_LIMIT, "small".to_string()),
+                "small-2".to_string(),
+            ),
+        ]
+        .into();
+        template.rename_references(&renames);
+        assert_eq!(template.secret_envs, vec!["github-2".to_string()]);
+        assert_eq!(template.secret_mounts[0].name, "github-2");
+        assert_eq!(template.resource_limit_name, "small-2");
+        assert_eq!(template.authorization_names, vec!["anonymous".to_string()]);
+
+        let legacy: BundleTemplate = serde_yaml::from_str(
+            "name: github\nimage: github-mcp\nresource_limit_name: small\nauthorization_name: ci\n",
+        )
+        .unwrap();
+        assert_eq!(legacy.authorization_names, vec!["ci".to_string()]);
+    }
+}
@@ -17,6 +17,12 @@ pub const LABEL_TYPE_OF: &str = "mcp-orchestrator.egoavara.net/type-of";
 pub const LABEL_AUTH_TYPE_OF: &str = "mcp-orchestrator.egoavara.net/auth-type-of";
 
 pub const LABEL_SESSION_ID: &str = "mcp-orchestrator.egoavara.net/session-id";
+pub const LABEL_SESSION_SUBJECT_HASH: &str = "mcp-orchestrator.egoavara.net/subject-hash";
+pub const LABEL_WORKSPACE_TEMPLATE: &str = "mcp-orchestrator.egoavara.net/workspace-template";
+pub const LABEL_WORKSPACE_SUBJECT_HASH: &str =
+    "mcp-orchestrator.egoavara.net/workspace-subject-hash";
+pub const LABEL_USER_SECRET_SUBJECT_HASH: &str =
+    "mcp-orchestrator.egoavara.net/user-secret-subject-hash";
 
 lazy_static::lazy_static! {
     pub static ref LABEL_REGEX: regex::Regex = regex::Regex::new(r"^(([A-Za-z0-9][-A-Za-z0-9_.]*)?[A-Za-z0-9])/(([A-Za-z0-9][-A-Za-z0-9_.]*)?[A-Za-z0-9])$")
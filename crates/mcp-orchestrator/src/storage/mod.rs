@@ -1,16 +1,40 @@
 pub mod annotations;
+pub mod authorization_api_key;
+pub mod authorization_oidc;
+pub mod authorization_token;
+pub mod bundle;
 pub mod label_query;
 pub mod labels;
 pub mod mcp_server_store;
+pub mod namespace_guard;
 pub mod resource_type;
 pub mod resource_uname;
+pub mod scheduling_feasibility;
 pub mod scheduling_validation;
+pub mod secret_backend;
+pub mod secret_type;
+pub mod session_quota;
+pub mod session_secret;
 pub mod store;
 pub mod store_authorization;
 pub mod store_mcp_template;
 pub mod store_namespace;
 pub mod store_resource_limit;
 pub mod store_secret;
+pub mod store_usage;
+pub mod store_user_secret;
+pub mod store_workspace;
+pub mod template_arg;
+pub mod template_claim;
+pub mod template_container;
+pub mod template_egress;
+pub mod template_image;
+pub mod template_import;
+pub mod template_package;
+pub mod template_scheduling;
+pub mod template_security;
+pub mod template_volume;
+pub mod usage_report;
 pub mod util_delete;
 pub mod util_list;
 pub mod util_name;
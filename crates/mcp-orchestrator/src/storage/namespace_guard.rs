@@ -0,0 +1,277 @@
+use std::collections::{BTreeMap, HashMap};
+
+use k8s_openapi::{
+    api::core::v1::{
+        LimitRange, LimitRangeItem, LimitRangeSpec, Namespace, ResourceQuota, ResourceQuotaSpec,
+    },
+    apimachinery::pkg::api::resource::Quantity,
+};
+use kube::{
+    Api, Client, Resource, ResourceExt,
+    api::{DeleteParams, ListParams, ObjectMeta, PostParams},
+};
+use proto::mcp::orchestrator::v1::NamespaceResourceQuota;
+use serde::de::DeserializeOwned;
+
+use crate::{
+    error::AppError,
+    storage::{
+        ResourceLimitData,
+        label_query::build_label_query,
+        labels::{label_dependency_tuple, setup_labels},
+        resource_type::{
+            RESOURCE_TYPE_LIMIT_RANGE, RESOURCE_TYPE_RESOURCE_LIMIT, RESOURCE_TYPE_RESOURCE_QUOTA,
+        },
+        template_container::parse_quantity,
+    },
+};
+
+/// Name of the ResourceQuota and LimitRange the orchestrator owns in each namespace.
+pub const GUARD_NAME: &str = "mcp-orchestrator";
+
+/// ResourceQuota and default LimitRange requested for a namespace.
+#[derive(Debug, Clone, Default)]
+pub struct NamespaceGuardSpec {
+    pub resource_quota: Option<NamespaceResourceQuota>,
+    pub default_resource_limit: Option<String>,
+}
+
+/// Hard limits of the ResourceQuota with its `status.used`.
+pub type ResourceQuotaStatus = (NamespaceResourceQuota, HashMap<String, String>);
+
+const HARD_PODS: &str = "pods";
+const HARD_REQUESTS_CPU: &str = "requests.cpu";
+const HARD_REQUESTS_MEMORY: &str = "requests.memory";
+const HARD_REQUESTS_EPHEMERAL_STORAGE: &str = "requests.ephemeral-storage";
+
+pub fn validate_resource_quota(quota: &NamespaceResourceQuota) -> Result<(), AppError> {
+    for (name, value) in [
+        (HARD_REQUESTS_CPU, &quota.requests_cpu),
+        (HARD_REQUESTS_MEMORY, &quota.requests_memory),
+        (
+            HARD_REQUESTS_EPHEMERAL_STORAGE,
+            &quota.requests_ephemeral_storage,
+        ),
+    ] {
+        if let Some(value) = value.as_deref().filter(|x| parse_quantity(x).is_none()) {
+            return Err(AppError::InvalidInput(format!(
+                "Invalid {} quantity: {}",
+                name, value
+            )));
+        }
+    }
+    Ok(())
+}
+
+pub fn resource_quota_hard(quota: &NamespaceResourceQuota) -> BTreeMap<String, Quantity> {
+    [
+        (HARD_PODS, quota.pods.map(|x| x.to_string())),
+        (HARD_REQUESTS_CPU, quota.requests_cpu.clone()),
+        (HARD_REQUESTS_MEMORY, quota.requests_memory.clone()),
+        (
+            HARD_REQUESTS_EPHEMERAL_STORAGE,
+            quota.requests_ephemeral_storage.clone(),
+        ),
+    ]
+    .into_iter()
+    .filter_map(|(key, value)| Some((key.to_string(), Quantity(value?))))
+    .collect()
+}
+
+pub fn resource_quota_from_hard(hard: &BTreeMap<String, Quantity>) -> NamespaceResourceQuota {
+    let get = |key: &str| hard.get(key).map(|x| x.0.clone());
+    NamespaceResourceQuota {
+        pods: get(HARD_PODS).and_then(|x| x.parse().ok()),
+        requests_cpu: get(HARD_REQUESTS_CPU),
+        requests_memory: get(HARD_REQUESTS_MEMORY),
+        requests_ephemeral_storage: get(HARD_REQUESTS_EPHEMERAL_STORAGE),
+    }
+}
+
+/// Container defaults of the resource limit, limits are only defaulted when the limit sets them.
+pub fn limit_range_item(resource_limit: &ResourceLimitData) -> LimitRangeItem {
+    let quantities = |values: [(&str, Option<&String>); 3]| {
+        Some(
+            values
+                .into_iter()
+                .filter_map(|(key, value)| Some((key.to_string(), Quantity(value?.clone()))))
+                .collect::<BTreeMap<_, _>>(),
+        )
+        .filter(|x| !x.is_empty())
+    };
+    LimitRangeItem {
+        type_: "Container".to_string(),
+        default_request: quantities([
+            ("cpu", Some(&resource_limit.cpu)),
+            ("memory", Some(&resource_limit.memory)),
+            (
+                "ephemeral-storage",
+                resource_limit.ephemeral_storage.as_ref(),
+            ),
+        ]),
+        default: quantities([
+            ("cpu", resource_limit.cpu_limit.as_ref()),
+            ("memory", resource_limit.memory_limit.as_ref()),
+            ("ephemeral-storage", None),
+        ]),
+        ..Default::default()
+    }
+}
+
+fn guard_metadata(
+    ns: &Namespace,
+    r#typeof: &str,
+    dependency: Option<(String, String)>,
+) -> ObjectMeta {
+    ObjectMeta {
+        name: Some(GUARD_NAME.to_string()),
+        namespace: Some(ns.name_any()),
+        labels: Some(
+            setup_labels(r#typeof, std::iter::empty())
+                .chain(dependency)
+                .collect(),
+        ),
+        owner_references: ns.controller_owner_ref(&()).map(|x| vec![x]),
+        ..Default::default()
+    }
+}
+
+/// Creates, replaces or removes the guard object so it matches `desired`.
+async fn reconcile<K>(api: Api<K>, desired: Option<K>) -> Result<(), AppError>
+where
+    K: Resource<DynamicType = ()> + Clone + DeserializeOwned + serde::Serialize + std::fmt::Debug,
+{
+    let current = api.get_opt(GUARD_NAME).await?;
+    match (current, desired) {
+        (None, Some(desired)) => {
+            api.create(&PostParams::default(), &desired).await?;
+        }
+        (Some(current), Some(mut desired)) => {
+            desired.meta_mut().resource_version = current.resource_version();
+            api.replace(GUARD_NAME, &PostParams::default(), &desired)
+                .await?;
+        }
+        (Some(_), None) => match api.delete(GUARD_NAME, &DeleteParams::default()).await {
+            Ok(_) => {}
+            Err(kube::Error::Api(ae)) if ae.code == 404 => {}
+            Err(err) => return Err(AppError::from(err)),
+        },
+        (None, None) => {}
+    }
+    Ok(())
+}
+
+/// Materializes the ResourceQuota and the default LimitRange of the namespace.
+pub async fn reconcile_guards(
+    client: Client,
+    ns: &Namespace,
+    resource_quota: Option<&NamespaceResourceQuota>,
+    default_resource_limit: Option<&ResourceLimitData>,
+) -> Result<(), AppError> {
+    let namespace = ns.name_any();
+    let resource_quota = resource_quota
+        .map(resource_quota_hard)
+        .filter(|x| !x.is_empty())
+        .map(|hard| ResourceQuota {
+            metadata: guard_metadata(ns, RESOURCE_TYPE_RESOURCE_QUOTA, None),
+            spec: Some(ResourceQuotaSpec {
+                hard: Some(hard),
+                ..Default::default()
+            }),
+            ..Default::default()
+        });
+    reconcile(
+        Api::<ResourceQuota>::namespaced(client.clone(), &namespace),
+        resource_quota,
+    )
+    .await?;
+
+    let limit_range = default_resource_limit.map(|resource_limit| LimitRange {
+        metadata: guard_metadata(
+            ns,
+            RESOURCE_TYPE_LIMIT_RANGE,
+            Some(label_dependency_tuple(
+                RESOURCE_TYPE_RESOURCE_LIMIT,
+                &resource_limit.name,
+            )),
+        ),
+        spec: Some(LimitRangeSpec {
+            limits: vec![limit_range_item(resource_limit)],
+        }),
+    });
+    reconcile(
+        Api::<LimitRange>::namespaced(client, &namespace),
+        limit_range,
+    )
+    .await
+}
+
+fn resource_quota_status(quota: ResourceQuota) -> ResourceQuotaStatus {
+    let hard = quota
+        .spec
+        .and_then(|x| x.hard)
+        .map(|x| resource_quota_from_hard(&x))
+        .unwrap_or_default();
+    let used = quota
+        .status
+        .and_then(|x| x.used)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(k, v)| (k, v.0))
+        .collect();
+    (hard, used)
+}
+
+pub async fn load_resource_quota(
+    client: Client,
+    namespace: &str,
+) -> Result<Option<ResourceQuotaStatus>, AppError> {
+    let api = Api::<ResourceQuota>::namespaced(client, namespace);
+    Ok(api.get_opt(GUARD_NAME).await?.map(resource_quota_status))
+}
+
+/// ResourceQuotas of every namespace, keyed by the namespace name.
+pub async fn list_resource_quotas(
+    client: Client,
+) -> Result<HashMap<String, ResourceQuotaStatus>, AppError> {
+    let api = Api::<ResourceQuota>::all(client);
+    let label = build_label_query(RESOURCE_TYPE_RESOURCE_QUOTA, &[])?.to_string();
+    let list = api
+        .list(&ListParams::default().labels(&label))
+        .await
+        .map_err(AppError::from)?;
+    Ok(list
+        .items
+        .into_iter()
+        .filter(|x| x.name_any() == GUARD_NAME)
+        .map(|x| (x.namespace().unwrap_or_default(), resource_quota_status(x)))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resource_quota_hard() {
+        let quota = NamespaceResourceQuota {
+            pods: Some(20),
+            requests_cpu: Some("8".to_string()),
+            requests_memory: None,
+            requests_ephemeral_storage: Some("20Gi".to_string()),
+        };
+        let hard = resource_quota_hard(&quota);
+        assert_eq!(hard.len(), 3);
+        assert_eq!(hard[HARD_PODS].0, "20");
+        assert_eq!(resource_quota_from_hard(&hard), quota);
+
+        assert!(validate_resource_quota(&quota).is_ok());
+        assert!(
+            validate_resource_quota(&NamespaceResourceQuota {
+                requests_memory: Some("lots".to_string()),
+                ..Default::default()
+            })
+            .is_err()
+        );
+    }
+}
@@ -4,9 +4,24 @@ pub const RESOURCE_TYPE_MCP_TEMPLATE: &str = "mcp-template";
 pub const RESOURCE_TYPE_RESOURCE_LIMIT: &str = "resource-limit";
 pub const RESOURCE_TYPE_MCP_SERVER: &str = "mcp-server";
 pub const RESOURCE_TYPE_AUTHORIZATION: &str = "authorization";
+pub const RESOURCE_TYPE_WORKSPACE: &str = "workspace";
+pub const RESOURCE_TYPE_PACKAGE_CACHE: &str = "package-cache";
+pub const RESOURCE_TYPE_EGRESS_POLICY: &str = "egress-policy";
+pub const RESOURCE_TYPE_RESOURCE_QUOTA: &str = "resource-quota";
+pub const RESOURCE_TYPE_LIMIT_RANGE: &str = "limit-range";
+pub const RESOURCE_TYPE_USAGE_RECORD: &str = "usage-record";
+pub const RESOURCE_TYPE_SESSION_SECRET: &str = "session-secret";
+pub const RESOURCE_TYPE_USER_SECRET: &str = "user-secret";
+pub const RESOURCE_TYPE_AUTHORIZATION_TOKEN: &str = "authorization-token";
 
 pub const RESOURCE_TYPE_PREFIX_SECRET: &str = "sc";
 pub const RESOURCE_TYPE_PREFIX_MCP_TEMPLATE: &str = "mt";
 pub const RESOURCE_TYPE_PREFIX_RESOURCE_LIMIT: &str = "rl";
 pub const RESOURCE_TYPE_PREFIX_AUTHORIZATION: &str = "at";
 pub const RESOURCE_TYPE_PREFIX_AUTHORIZATION_SA: &str = "sa";
+pub const RESOURCE_TYPE_PREFIX_WORKSPACE: &str = "ws";
+pub const RESOURCE_TYPE_PREFIX_PACKAGE_CACHE: &str = "pc";
+pub const RESOURCE_TYPE_PREFIX_USAGE_RECORD: &str = "us";
+pub const RESOURCE_TYPE_PREFIX_SESSION_SECRET: &str = "ss";
+pub const RESOURCE_TYPE_PREFIX_USER_SECRET: &str = "uc";
+pub const RESOURCE_TYPE_PREFIX_AUTHORIZATION_TOKEN: &str = "tk";
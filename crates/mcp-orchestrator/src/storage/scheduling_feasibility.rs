@@ -0,0 +1,375 @@
+use std::collections::BTreeMap;
+
+use k8s_openapi::api::core::v1::{
+    Container, Node, NodeSelectorRequirement, NodeSelectorTerm, Pod, PodSpec, Taint, Toleration,
+};
+use kube::ResourceExt;
+
+use crate::storage::{
+    ResourceLimitData,
+    template_container::{MAIN_CONTAINER_NAME, parse_quantity},
+    template_scheduling::build_scheduling,
+};
+
+const RESOURCE_PODS: &str = "pods";
+
+/// Requests of the pod, the larger of the containers sum and the largest init container.
+pub fn pod_requests(spec: &PodSpec) -> BTreeMap<String, f64> {
+    fn requests(container: &Container) -> impl Iterator<Item = (&String, f64)> {
+        container
+            .resources
+            .iter()
+            .flat_map(|x| x.requests.iter().flatten())
+            .filter_map(|(resource, quantity)| Some((resource, parse_quantity(&quantity.0)?)))
+    }
+    let mut total = BTreeMap::<String, f64>::new();
+    for container in &spec.containers {
+        for (resource, value) in requests(container) {
+            *total.entry(resource.clone()).or_default() += value;
+        }
+    }
+    for container in spec.init_containers.iter().flatten() {
+        for (resource, value) in requests(container) {
+            let entry = total.entry(resource.clone()).or_default();
+            *entry = entry.max(value);
+        }
+    }
+    total
+}
+
+fn is_tolerated(taint: &Taint, tolerations: &[Toleration]) -> bool {
+    tolerations.iter().any(|toleration| {
+        let effect_matches = toleration
+            .effect
+            .as_deref()
+            .is_none_or(|effect| effect.is_empty() || effect == taint.effect);
+        let key_matches = match toleration.key.as_deref() {
+            None | Some("") => toleration.operator.as_deref() == Some("Exists"),
+            Some(key) => key == taint.key,
+        };
+        let value_matches = match toleration.operator.as_deref() {
+            Some("Exists") => true,
+            _ => {
+                toleration.value.as_deref().unwrap_or_default()
+                    == taint.value.as_deref().unwrap_or_default()
+            }
+        };
+        effect_matches && key_matches && value_matches
+    })
+}
+
+fn requirement_matches(
+    requirement: &NodeSelectorRequirement,
+    labels: &BTreeMap<String, String>,
+) -> bool {
+    let value = labels.get(&requirement.key);
+    let values = requirement.values.as_deref().unwrap_or_default();
+    let number = |x: &String| x.parse::<i64>().ok();
+    match requirement.operator.as_str() {
+        "In" => value.is_some_and(|v| values.contains(v)),
+        "NotIn" => value.is_none_or(|v| !values.contains(v)),
+        "Exists" => value.is_some(),
+        "DoesNotExist" => value.is_none(),
+        "Gt" | "Lt" => {
+            let (Some(value), Some(bound)) =
+                (value.and_then(number), values.first().and_then(number))
+            else {
+                return false;
+            };
+            if requirement.operator == "Gt" {
+                value > bound
+            } else {
+                value < bound
+            }
+        }
+        _ => false,
+    }
+}
+
+fn term_matches(term: &NodeSelectorTerm, node: &Node) -> bool {
+    let labels = node.labels();
+    let name = node.name_any();
+    term.match_expressions
+        .iter()
+        .flatten()
+        .all(|requirement| requirement_matches(requirement, labels))
+        && term.match_fields.iter().flatten().all(|requirement| {
+            let fields = BTreeMap::from([("metadata.name".to_string(), name.clone())]);
+            requirement_matches(requirement, &fields)
+        })
+}
+
+/// Explains why the pod does not fit the node, None when it fits.
+pub fn node_unfit_reason(node: &Node, spec: &PodSpec) -> Option<String> {
+    let node_spec = node.spec.clone().unwrap_or_default();
+    if node_spec.unschedulable == Some(true) {
+        return Some("node is cordoned".to_string());
+    }
+    let ready = node
+        .status
+        .iter()
+        .flat_map(|x| x.conditions.iter().flatten())
+        .any(|x| x.type_ == "Ready" && x.status == "True");
+    if !ready {
+        return Some("node is not ready".to_string());
+    }
+
+    let tolerations = spec.tolerations.as_deref().unwrap_or_default();
+    if let Some(taint) = node_spec.taints.iter().flatten().find(|taint| {
+        (taint.effect == "NoSchedule" || taint.effect == "NoExecute")
+            && !is_tolerated(taint, tolerations)
+    }) {
+        return Some(format!("untolerated taint {}:{}", taint.key, taint.effect));
+    }
+
+    let labels = node.labels();
+    if let Some((key, value)) = spec
+        .node_selector
+        .iter()
+        .flatten()
+        .find(|(key, value)| labels.get(*key) != Some(*value))
+    {
+        return Some(format!("node selector {}={} does not match", key, value));
+    }
+    let required = spec
+        .affinity
+        .as_ref()
+        .and_then(|x| x.node_affinity.as_ref())
+        .and_then(|x| {
+            x.required_during_scheduling_ignored_during_execution
+                .as_ref()
+        });
+    if required.is_some_and(|required| {
+        !required
+            .node_selector_terms
+            .iter()
+            .any(|term| term_matches(term, node))
+    }) {
+        return Some("required node affinity does not match".to_string());
+    }
+
+    let allocatable = node
+        .status
+        .as_ref()
+        .and_then(|x| x.allocatable.clone())
+        .unwrap_or_default();
+    for (resource, requested) in pod_requests(spec) {
+        let Some(available) = allocatable
+            .get(&resource)
+            .and_then(|x| parse_quantity(&x.0))
+        else {
+            continue;
+        };
+        if requested > available {
+            return Some(format!(
+                "insufficient {}, requested {} of allocatable {}",
+                resource, requested, available
+            ));
+        }
+    }
+    None
+}
+
+/// Requests of the pods bound to each node, every pod also requests one `pods`.
+pub fn node_requests(pods: &[Pod]) -> BTreeMap<String, BTreeMap<String, f64>> {
+    let mut total = BTreeMap::<String, BTreeMap<String, f64>>::new();
+    for pod in pods {
+        let phase = pod.status.as_ref().and_then(|x| x.phase.as_deref());
+        if matches!(phase, Some("Succeeded") | Some("Failed")) {
+            continue;
+        }
+        let Some(spec) = &pod.spec else {
+            continue;
+        };
+        let Some(node_name) = &spec.node_name else {
+            continue;
+        };
+        let requested = total.entry(node_name.clone()).or_default();
+        for (resource, value) in pod_requests(spec) {
+            *requested.entry(resource).or_default() += value;
+        }
+        *requested.entry(RESOURCE_PODS.to_string()).or_default() += 1.0;
+    }
+    total
+}
+
+/// Number of pods fitting the free capacity of the node, allocatable minus `requested`.
+pub fn node_capacity(
+    node: &Node,
+    spec: &PodSpec,
+    requested: &BTreeMap<String, f64>,
+) -> Result<u32, String> {
+    if let Some(reason) = node_unfit_reason(node, spec) {
+        return Err(reason);
+    }
+    let allocatable = node
+        .status
+        .as_ref()
+        .and_then(|x| x.allocatable.clone())
+        .unwrap_or_default();
+    let mut requests = pod_requests(spec);
+    requests.insert(RESOURCE_PODS.to_string(), 1.0);
+
+    let mut capacity = u32::MAX;
+    for (resource, request) in requests.into_iter().filter(|(_, x)| *x > 0.0) {
+        let Some(available) = allocatable
+            .get(&resource)
+            .and_then(|x| parse_quantity(&x.0))
+        else {
+            continue;
+        };
+        let free = (available - requested.get(&resource).copied().unwrap_or_default()).max(0.0);
+        let fits = (free / request).floor().min(u32::MAX as f64) as u32;
+        if fits == 0 {
+            return Err(format!(
+                "insufficient free {}, requested {} of free {}",
+                resource, request, free
+            ));
+        }
+        capacity = capacity.min(fits);
+    }
+    Ok(capacity)
+}
+
+/// Pod spec of a session using the resource limit, without template specific fields.
+pub fn resource_limit_pod_spec(resource_limit: &ResourceLimitData) -> PodSpec {
+    let scheduling = build_scheduling(resource_limit, "", None);
+    PodSpec {
+        containers: vec![Container {
+            name: MAIN_CONTAINER_NAME.to_string(),
+            resources: Some(resource_limit.to_resource_requirements()),
+            ..Default::default()
+        }],
+        node_selector: resource_limit.node_selector.clone(),
+        affinity: scheduling.affinity,
+        tolerations: scheduling.tolerations,
+        priority_class_name: scheduling.priority_class_name,
+        ..Default::default()
+    }
+}
+
+/// Checks that at least one node can run the pod, returning the reason of every node otherwise.
+/// Current usage of the nodes is not accounted, only allocatable capacity.
+pub fn check_schedulable(nodes: &[Node], spec: &PodSpec) -> Result<(), Vec<String>> {
+    let mut reasons = Vec::new();
+    for node in nodes {
+        match node_unfit_reason(node, spec) {
+            None => return Ok(()),
+            Some(reason) => reasons.push(format!("{}: {}", node.name_any(), reason)),
+        }
+    }
+    if reasons.is_empty() {
+        reasons.push("cluster has no nodes".to_string());
+    }
+    Err(reasons)
+}
+
+#[cfg(test)]
+mod tests {
+    use k8s_openapi::{
+        api::core::v1::{NodeCondition, NodeSpec, NodeStatus, ResourceRequirements},
+        apimachinery::pkg::api::resource::Quantity,
+    };
+    use kube::api::ObjectMeta;
+
+    use super::*;
+
+    fn node(name: &str, zone: &str, memory: &str, taints: Vec<Taint>) -> Node {
+        Node {
+            metadata: ObjectMeta {
+                name: Some(name.to_string()),
+                labels: Some(BTreeMap::from([(
+                    "topology.kubernetes.io/zone".to_string(),
+                    zone.to_string(),
+                )])),
+                ..Default::default()
+            },
+            spec: Some(NodeSpec {
+                taints: Some(taints),
+                ..Default::default()
+            }),
+            status: Some(NodeStatus {
+                allocatable: Some(BTreeMap::from([
+                    ("cpu".to_string(), Quantity("2".to_string())),
+                    ("memory".to_string(), Quantity(memory.to_string())),
+                ])),
+                conditions: Some(vec![NodeCondition {
+                    type_: "Ready".to_string(),
+                    status: "True".to_string(),
+                    ..Default::default()
+                }]),
+                ..Default::default()
+            }),
+        }
+    }
+
+    fn spec(memory: &str, zone: &str) -> PodSpec {
+        PodSpec {
+            containers: vec![Container {
+                name: "main".to_string(),
+                resources: Some(ResourceRequirements {
+                    requests: Some(BTreeMap::from([
+                        ("cpu".to_string(), Quantity("500m".to_string())),
+                        ("memory".to_string(), Quantity(memory.to_string())),
+                    ])),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }],
+            node_selector: Some(BTreeMap::from([(
+                "topology.kubernetes.io/zone".to_string(),
+                zone.to_string(),
+            )])),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_check_schedulable() {
+        let gpu_taint = Taint {
+            key: "gpu".to_string(),
+            effect: "NoSchedule".to_string(),
+            ..Default::default()
+        };
+        let nodes = vec![
+            node("a", "zone-a", "4Gi", vec![]),
+            node("b", "zone-b", "1Gi", vec![]),
+            node("c", "zone-c", "8Gi", vec![gpu_taint]),
+        ];
+        assert!(check_schedulable(&nodes, &spec("2Gi", "zone-a")).is_ok());
+        let reasons = check_schedulable(&nodes, &spec("2Gi", "zone-b")).unwrap_err();
+        assert!(reasons[1].starts_with("b: insufficient memory"));
+        let reasons = check_schedulable(&nodes, &spec("2Gi", "zone-c")).unwrap_err();
+        assert_eq!(reasons[2], "c: untolerated taint gpu:NoSchedule");
+
+        let mut tolerated = spec("2Gi", "zone-c");
+        tolerated.tolerations = Some(vec![Toleration {
+            key: Some("gpu".to_string()),
+            operator: Some("Exists".to_string()),
+            ..Default::default()
+        }]);
+        assert!(check_schedulable(&nodes, &tolerated).is_ok());
+        assert!(check_schedulable(&[], &tolerated).is_err());
+    }
+
+    #[test]
+    fn test_node_capacity() {
+        let node = node("a", "zone-a", "4Gi", vec![]);
+        let pod_spec = spec("1Gi", "zone-a");
+        assert_eq!(node_capacity(&node, &pod_spec, &BTreeMap::new()), Ok(4));
+
+        let pods = vec![Pod {
+            spec: Some(PodSpec {
+                node_name: Some("a".to_string()),
+                ..pod_spec.clone()
+            }),
+            ..Default::default()
+        }];
+        let requested = node_requests(&pods);
+        // cpu 2 중 500m 사용, memory 4Gi 중 1Gi 사용
+        assert_eq!(node_capacity(&node, &pod_spec, &requested["a"]), Ok(3));
+
+        let reason = node_capacity(&node, &spec("4Gi", "zone-a"), &requested["a"]).unwrap_err();
+        assert!(reason.starts_with("insufficient free memory"));
+    }
+}
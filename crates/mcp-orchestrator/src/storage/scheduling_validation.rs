@@ -1,5 +1,6 @@
 use crate::error::AppError;
 use k8s_openapi::api::core::v1::{Affinity, NodeSelectorRequirement, NodeSelectorTerm};
+use proto::mcp::orchestrator::v1::{PodAntiAffinity, Toleration, TopologySpread};
 use regex::Regex;
 use std::collections::BTreeMap;
 
@@ -179,13 +180,11 @@ pub fn validate_node_selector_requirement(req: &NodeSelectorRequirement) -> Resu
                 }
             }
         }
-        "Exists" | "DoesNotExist" => {
-            if req.values.as_ref().is_some_and(|v| !v.is_empty()) {
-                return Err(AppError::InvalidInput(format!(
-                    "Operator '{}' must not have values",
-                    req.operator
-                )));
-            }
+        "Exists" | "DoesNotExist" if req.values.as_ref().is_some_and(|v| !v.is_empty()) => {
+            return Err(AppError::InvalidInput(format!(
+                "Operator '{}' must not have values",
+                req.operator
+            )));
         }
         _ => {}
     }
@@ -193,6 +192,105 @@ pub fn validate_node_selector_requirement(req: &NodeSelectorRequirement) -> Resu
     Ok(())
 }
 
+pub fn validate_toleration(toleration: &Toleration) -> Result<(), AppError> {
+    let key = toleration.key.as_deref().unwrap_or_default();
+    if !key.is_empty() {
+        validate_label_key(key)?;
+    }
+
+    match toleration.operator.as_deref().unwrap_or("Equal") {
+        "Equal" => {
+            if key.is_empty() {
+                return Err(AppError::InvalidInput(
+                    "Toleration with operator 'Equal' requires a key".into(),
+                ));
+            }
+            validate_label_value(toleration.value.as_deref().unwrap_or_default())?;
+        }
+        "Exists" => {
+            if toleration.value.as_ref().is_some_and(|v| !v.is_empty()) {
+                return Err(AppError::InvalidInput(
+                    "Toleration with operator 'Exists' must not have a value".into(),
+                ));
+            }
+        }
+        operator => {
+            return Err(AppError::InvalidInput(format!(
+                "Invalid toleration operator: '{}'. Must be one of: [\"Equal\", \"Exists\"]",
+                operator
+            )));
+        }
+    }
+
+    let valid_effects = ["NoSchedule", "PreferNoSchedule", "NoExecute"];
+    if let Some(effect) = &toleration.effect
+        && !valid_effects.contains(&effect.as_str())
+    {
+        return Err(AppError::InvalidInput(format!(
+            "Invalid toleration effect: '{}'. Must be one of: {:?}",
+            effect, valid_effects
+        )));
+    }
+
+    if let Some(seconds) = toleration.toleration_seconds {
+        if toleration.effect.as_deref() != Some("NoExecute") {
+            return Err(AppError::InvalidInput(
+                "Toleration seconds is only allowed with effect 'NoExecute'".into(),
+            ));
+        }
+        if seconds < 0 {
+            return Err(AppError::InvalidInput(format!(
+                "Toleration seconds must be >= 0, got {}",
+                seconds
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+pub fn validate_priority_class_name(name: &str) -> Result<(), AppError> {
+    // system- 접두사는 kube-system 전용 클래스
+    if name.starts_with("system-") {
+        return Err(AppError::InvalidInput(format!(
+            "PriorityClass '{}' is reserved for system components",
+            name
+        )));
+    }
+    validate_dns_subdomain(name)
+}
+
+pub fn validate_topology_spread(spread: &TopologySpread) -> Result<(), AppError> {
+    validate_label_key(&spread.topology_key)?;
+    if spread.max_skew < 1 {
+        return Err(AppError::InvalidInput(format!(
+            "Topology spread max skew must be >= 1, got {}",
+            spread.max_skew
+        )));
+    }
+    Ok(())
+}
+
+pub fn validate_pod_anti_affinity(anti_affinity: &PodAntiAffinity) -> Result<(), AppError> {
+    if !anti_affinity.topology_key.is_empty() {
+        validate_label_key(&anti_affinity.topology_key)?;
+    }
+    if let Some(weight) = anti_affinity.weight {
+        if anti_affinity.required {
+            return Err(AppError::InvalidInput(
+                "Weight is only allowed for preferred pod anti-affinity".into(),
+            ));
+        }
+        if !(1..=100).contains(&weight) {
+            return Err(AppError::InvalidInput(format!(
+                "Pod anti-affinity weight must be 1-100, got {}",
+                weight
+            )));
+        }
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -275,4 +373,51 @@ mod tests {
         };
         assert!(validate_node_selector_requirement(&req).is_err());
     }
+
+    #[test]
+    fn test_validate_toleration() {
+        let toleration = Toleration {
+            key: Some("dedicated".to_string()),
+            operator: None,
+            value: Some("mcp".to_string()),
+            effect: Some("NoSchedule".to_string()),
+            toleration_seconds: None,
+        };
+        assert!(validate_toleration(&toleration).is_ok());
+
+        let exists_all = Toleration {
+            operator: Some("Exists".to_string()),
+            ..Default::default()
+        };
+        assert!(validate_toleration(&exists_all).is_ok());
+    }
+
+    #[test]
+    fn test_validate_toleration_invalid() {
+        let no_key = Toleration {
+            value: Some("mcp".to_string()),
+            ..Default::default()
+        };
+        assert!(validate_toleration(&no_key).is_err());
+
+        let seconds_without_no_execute = Toleration {
+            key: Some("dedicated".to_string()),
+            effect: Some("NoSchedule".to_string()),
+            toleration_seconds: Some(60),
+            ..Default::default()
+        };
+        assert!(validate_toleration(&seconds_without_no_execute).is_err());
+    }
+
+    #[test]
+    fn test_validate_topology_spread() {
+        let mut spread = TopologySpread {
+            topology_key: "topology.kubernetes.io/zone".to_string(),
+            max_skew: 1,
+            ..Default::default()
+        };
+        assert!(validate_topology_spread(&spread).is_ok());
+        spread.max_skew = 0;
+        assert!(validate_topology_spread(&spread).is_err());
+    }
 }
@@ -0,0 +1,376 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::{Component, Path, PathBuf},
+    sync::Arc,
+};
+
+use futures::future::BoxFuture;
+use k8s_openapi::{ByteString, api::core::v1::Secret};
+use kube::Client;
+use reqwest::StatusCode;
+use serde::Deserialize;
+
+use crate::{
+    config::{FileBackendConfig, SecretBackendConfig, VaultBackendConfig},
+    error::AppError,
+    storage::SecretStore,
+};
+
+/// Backend of the references without a `backend:` prefix.
+pub const SECRET_BACKEND_KUBERNETES: &str = "kubernetes";
+pub const SECRET_BACKEND_VAULT: &str = "vault";
+pub const SECRET_BACKEND_FILE: &str = "file";
+/// Secret of the user calling the session, resolved from its subject instead of a backend.
+pub const SECRET_BACKEND_USER: &str = "user";
+
+/// Secret referenced by a template, `vault:team/github` or a plain Kubernetes secret name.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SecretRef {
+    pub backend: String,
+    pub path: String,
+}
+
+impl SecretRef {
+    pub fn parse(reference: &str) -> Result<Self, AppError> {
+        let (backend, path) = reference
+            .split_once(':')
+            .unwrap_or((SECRET_BACKEND_KUBERNETES, reference));
+        if backend.is_empty() || path.is_empty() {
+            return Err(AppError::InvalidInput(format!(
+                "Invalid secret reference '{}', expected <backend>:<path> or a secret name",
+                reference
+            )));
+        }
+        Ok(Self {
+            backend: backend.to_string(),
+            path: path.to_string(),
+        })
+    }
+
+    pub fn is_kubernetes(&self) -> bool {
+        self.backend == SECRET_BACKEND_KUBERNETES
+    }
+
+    pub fn is_user(&self) -> bool {
+        self.backend == SECRET_BACKEND_USER
+    }
+}
+
+pub enum ResolvedSecret {
+    /// Secret of the template namespace, referenced by the pod as it is.
+    Kubernetes(Box<Secret>),
+    /// Values copied into a Secret owned by the session.
+    Values(BTreeMap<String, ByteString>),
+}
+
+pub trait SecretBackend: Send + Sync {
+    fn resolve<'a>(
+        &'a self,
+        namespace: &'a str,
+        path: &'a str,
+    ) -> BoxFuture<'a, Result<ResolvedSecret, AppError>>;
+}
+
+/// Rejects paths escaping the backend root, e.g. `../other` or `/etc/passwd`.
+fn validate_path(path: &str) -> Result<(), AppError> {
+    if Path::new(path)
+        .components()
+        .all(|x| matches!(x, Component::Normal(_)))
+    {
+        Ok(())
+    } else {
+        Err(AppError::InvalidInput(format!(
+            "Secret path '{}' must be relative without '..'",
+            path
+        )))
+    }
+}
+
+/// Path of the secret in the backend, under the namespace when the backend is namespaced.
+fn scoped_path(namespaced: bool, namespace: &str, path: &str) -> Result<String, AppError> {
+    validate_path(path)?;
+    if namespaced {
+        validate_path(namespace)?;
+        Ok(format!("{}/{}", namespace, path))
+    } else {
+        Ok(path.to_string())
+    }
+}
+
+pub struct KubernetesSecretBackend {
+    client: Client,
+}
+
+impl SecretBackend for KubernetesSecretBackend {
+    fn resolve<'a>(
+        &'a self,
+        namespace: &'a str,
+        path: &'a str,
+    ) -> BoxFuture<'a, Result<ResolvedSecret, AppError>> {
+        Box::pin(async move {
+            let secret = SecretStore::new(self.client.clone(), namespace)
+                .get(path)
+                .await?
+                .ok_or_else(|| AppError::NotFound(format!("Secret {} not found", path)))?;
+            Ok(ResolvedSecret::Kubernetes(Box::new(secret.raw)))
+        })
+    }
+}
+
+#[derive(Deserialize)]
+struct VaultKvResponse {
+    data: VaultKvData,
+}
+
+#[derive(Deserialize)]
+struct VaultKvData {
+    data: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Reads the latest version of a KV v2 secret.
+pub struct VaultSecretBackend {
+    config: VaultBackendConfig,
+    http: reqwest::Client,
+}
+
+impl VaultSecretBackend {
+    pub fn new(config: VaultBackendConfig) -> Result<Self, AppError> {
+        let http = reqwest::Client::builder()
+            .timeout(config.timeout)
+            .build()
+            .map_err(|e| AppError::Internal(format!("Failed to build Vault client: {}", e)))?;
+        Ok(Self { config, http })
+    }
+
+    async fn token(&self) -> Result<String, AppError> {
+        if let Some(file) = &self.config.token_file {
+            let token = tokio::fs::read_to_string(file).await.map_err(|e| {
+                AppError::Internal(format!(
+                    "Failed to read Vault token file {}: {}",
+                    file.display(),
+                    e
+                ))
+            })?;
+            return Ok(token.trim().to_string());
+        }
+        self.config
+            .token
+            .clone()
+            .ok_or_else(|| AppError::Internal("Vault token is not configured".to_string()))
+    }
+
+    async fn read(
+        &self,
+        namespace: &str,
+        path: &str,
+    ) -> Result<BTreeMap<String, ByteString>, AppError> {
+        let path = scoped_path(self.config.namespaced, namespace, path)?;
+        let url = format!(
+            "{}/v1/{}/data/{}",
+            self.config.address.trim_end_matches('/'),
+            self.config.mount.trim_matches('/'),
+            path
+        );
+        let mut request = self
+            .http
+            .get(&url)
+            .header("X-Vault-Token", self.token().await?);
+        if let Some(vault_namespace) = &self.config.namespace {
+            request = request.header("X-Vault-Namespace", vault_namespace);
+        }
+        let failed = |reason: String| {
+            AppError::Internal(format!("Failed to read Vault secret {}: {}", path, reason))
+        };
+        let response = request.send().await.map_err(|e| failed(e.to_string()))?;
+        if response.status() == StatusCode::NOT_FOUND {
+            return Err(AppError::NotFound(format!(
+                "Vault secret {} not found",
+                path
+            )));
+        }
+        if !response.status().is_success() {
+            return Err(failed(format!("Vault responded {}", response.status())));
+        }
+        let body: VaultKvResponse = response.json().await.map_err(|e| failed(e.to_string()))?;
+        Ok(body
+            .data
+            .data
+            .into_iter()
+            .map(|(key, value)| {
+                let value = match value {
+                    serde_json::Value::String(x) => x,
+                    other => other.to_string(),
+                };
+                (key, ByteString(value.into_bytes()))
+            })
+            .collect())
+    }
+}
+
+impl SecretBackend for VaultSecretBackend {
+    fn resolve<'a>(
+        &'a self,
+        namespace: &'a str,
+        path: &'a str,
+    ) -> BoxFuture<'a, Result<ResolvedSecret, AppError>> {
+        Box::pin(async move { Ok(ResolvedSecret::Values(self.read(namespace, path).await?)) })
+    }
+}
+
+/// Every regular file of `<root>/<path>` is a key, the layout of a mounted Kubernetes Secret.
+pub struct FileSecretBackend {
+    root: PathBuf,
+    namespaced: bool,
+}
+
+impl FileSecretBackend {
+    async fn read(
+        &self,
+        namespace: &str,
+        path: &str,
+    ) -> Result<BTreeMap<String, ByteString>, AppError> {
+        let dir = self
+            .root
+            .join(scoped_path(self.namespaced, namespace, path)?);
+        let failed = |e: std::io::Error| match e.kind() {
+            std::io::ErrorKind::NotFound => {
+                AppError::NotFound(format!("Secret directory {} not found", dir.display()))
+            }
+            _ => AppError::Internal(format!(
+                "Failed to read secret directory {}: {}",
+                dir.display(),
+                e
+            )),
+        };
+        let mut entries = tokio::fs::read_dir(&dir).await.map_err(failed)?;
+        let mut values = BTreeMap::new();
+        while let Some(entry) = entries.next_entry().await.map_err(failed)? {
+            let key = entry.file_name().to_string_lossy().to_string();
+            // 마운트된 Secret 의 ..data 심볼릭 링크 등은 제외
+            if key.starts_with('.') || !entry.path().is_file() {
+                continue;
+            }
+            let value = tokio::fs::read(entry.path()).await.map_err(failed)?;
+            values.insert(key, ByteString(value));
+        }
+        Ok(values)
+    }
+}
+
+impl SecretBackend for FileSecretBackend {
+    fn resolve<'a>(
+        &'a self,
+        namespace: &'a str,
+        path: &'a str,
+    ) -> BoxFuture<'a, Result<ResolvedSecret, AppError>> {
+        Box::pin(async move { Ok(ResolvedSecret::Values(self.read(namespace, path).await?)) })
+    }
+}
+
+/// Configured secret backends by name, Kubernetes is always available.
+#[derive(Clone)]
+pub struct SecretBackends(Arc<HashMap<&'static str, Arc<dyn SecretBackend>>>);
+
+impl SecretBackends {
+    pub fn kubernetes(client: Client) -> Self {
+        Self(Arc::new(HashMap::from([(
+            SECRET_BACKEND_KUBERNETES,
+            Arc::new(KubernetesSecretBackend { client }) as Arc<dyn SecretBackend>,
+        )])))
+    }
+
+    pub fn new(client: Client, config: &SecretBackendConfig) -> Result<Self, AppError> {
+        let mut backends = HashMap::<&'static str, Arc<dyn SecretBackend>>::from([(
+            SECRET_BACKEND_KUBERNETES,
+            Arc::new(KubernetesSecretBackend { client }) as Arc<dyn SecretBackend>,
+        )]);
+        if let Some(vault) = &config.vault {
+            backends.insert(
+                SECRET_BACKEND_VAULT,
+                Arc::new(VaultSecretBackend::new(vault.clone())?),
+            );
+        }
+        if let Some(FileBackendConfig { root, namespaced }) = &config.file {
+            backends.insert(
+                SECRET_BACKEND_FILE,
+                Arc::new(FileSecretBackend {
+                    root: root.clone(),
+                    namespaced: *namespaced,
+                }),
+            );
+        }
+        Ok(Self(Arc::new(backends)))
+    }
+
+    pub fn get(&self, reference: &SecretRef) -> Result<&dyn SecretBackend, AppError> {
+        self.0
+            .get(reference.backend.as_str())
+            .map(|x| x.as_ref())
+            .ok_or_else(|| {
+                AppError::InvalidInput(format!(
+                    "Secret backend '{}' is not configured",
+                    reference.backend
+                ))
+            })
+    }
+
+    /// Checks the reference when a template is saved, before any session resolves it.
+    pub fn check(&self, reference: &SecretRef) -> Result<(), AppError> {
+        self.get(reference)?;
+        // 외부 백엔드는 경로가 namespace 경계를 벗어나지 못하도록 저장 시점에 거부
+        if !reference.is_kubernetes() {
+            validate_path(&reference.path)?;
+        }
+        Ok(())
+    }
+
+    pub async fn resolve(
+        &self,
+        namespace: &str,
+        reference: &SecretRef,
+    ) -> Result<ResolvedSecret, AppError> {
+        self.get(reference)?
+            .resolve(namespace, &reference.path)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_secret_ref() {
+        assert_eq!(
+            SecretRef::parse("github").unwrap(),
+            SecretRef {
+                backend: SECRET_BACKEND_KUBERNETES.to_string(),
+                path: "github".to_string(),
+            }
+        );
+        let reference = SecretRef::parse("vault:team-a/github").unwrap();
+        assert_eq!(reference.backend, SECRET_BACKEND_VAULT);
+        assert_eq!(reference.path, "team-a/github");
+        assert!(!reference.is_kubernetes());
+        let reference = SecretRef::parse("user:github").unwrap();
+        assert!(reference.is_user());
+        assert_eq!(reference.path, "github");
+        assert!(SecretRef::parse("vault:").is_err());
+        assert!(SecretRef::parse(":github").is_err());
+
+        assert!(validate_path("team-a/github").is_ok());
+        assert!(validate_path("../github").is_err());
+        assert!(validate_path("/etc/github").is_err());
+    }
+
+    #[test]
+    fn test_scoped_path() {
+        assert_eq!(
+            scoped_path(true, "team-a", "github").unwrap(),
+            "team-a/github"
+        );
+        assert_eq!(scoped_path(false, "team-a", "github").unwrap(), "github");
+        assert!(scoped_path(true, "team-a", "../team-b/github").is_err());
+        assert!(scoped_path(true, "..", "github").is_err());
+    }
+}
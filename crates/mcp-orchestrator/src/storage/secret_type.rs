@@ -0,0 +1,268 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use k8s_openapi::ByteString;
+use proto::mcp::orchestrator::v1::{RegistryCredential, SecretType, SecretUpdateStrategy};
+use serde_json::{Value, json};
+
+use crate::error::AppError;
+
+pub const SECRET_TYPE_DOCKER_CONFIG_JSON: &str = "kubernetes.io/dockerconfigjson";
+const SECRET_TYPE_TLS: &str = "kubernetes.io/tls";
+const SECRET_TYPE_BASIC_AUTH: &str = "kubernetes.io/basic-auth";
+pub const KEY_DOCKER_CONFIG_JSON: &str = ".dockerconfigjson";
+const KEY_TLS_CRT: &str = "tls.crt";
+const KEY_TLS_KEY: &str = "tls.key";
+const KEY_USERNAME: &str = "username";
+const KEY_PASSWORD: &str = "password";
+
+/// Kubernetes type of the secret, None is Opaque.
+pub fn secret_type_name(r#type: SecretType) -> Option<&'static str> {
+    match r#type {
+        SecretType::Opaque => None,
+        SecretType::DockerConfigJson => Some(SECRET_TYPE_DOCKER_CONFIG_JSON),
+        SecretType::Tls => Some(SECRET_TYPE_TLS),
+        SecretType::BasicAuth => Some(SECRET_TYPE_BASIC_AUTH),
+    }
+}
+
+pub fn secret_type_from_name(name: Option<&str>) -> SecretType {
+    match name {
+        Some(SECRET_TYPE_DOCKER_CONFIG_JSON) => SecretType::DockerConfigJson,
+        Some(SECRET_TYPE_TLS) => SecretType::Tls,
+        Some(SECRET_TYPE_BASIC_AUTH) => SecretType::BasicAuth,
+        _ => SecretType::Opaque,
+    }
+}
+
+fn docker_config_json(registry: &RegistryCredential) -> Result<String, AppError> {
+    if registry.server.is_empty() || registry.username.is_empty() || registry.password.is_empty() {
+        return Err(AppError::InvalidInput(
+            "Registry credential requires server, username and password".to_string(),
+        ));
+    }
+    let mut entry = json!({
+        "username": registry.username,
+        "password": registry.password,
+    });
+    if let Some(email) = &registry.email {
+        entry["email"] = Value::from(email.clone());
+    }
+    Ok(json!({ "auths": { registry.server.clone(): entry } }).to_string())
+}
+
+fn require_keys(
+    r#type: &str,
+    data: &HashMap<String, String>,
+    keys: &[&str],
+) -> Result<(), AppError> {
+    match keys.iter().find(|key| !data.contains_key(**key)) {
+        Some(key) => Err(AppError::InvalidInput(format!(
+            "{} secret requires the {} key",
+            r#type, key
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// Validates the keys the secret type requires, generating `.dockerconfigjson` from `registry`.
+pub fn build_secret_data(
+    r#type: SecretType,
+    registry: Option<&RegistryCredential>,
+    data: HashMap<String, String>,
+) -> Result<HashMap<String, String>, AppError> {
+    if registry.is_some() && r#type != SecretType::DockerConfigJson {
+        return Err(AppError::InvalidInput(
+            "Registry credential is only allowed for DOCKER_CONFIG_JSON secrets".to_string(),
+        ));
+    }
+    match r#type {
+        SecretType::Opaque => Ok(data),
+        SecretType::DockerConfigJson => {
+            if let Some(registry) = registry {
+                return Ok(HashMap::from([(
+                    KEY_DOCKER_CONFIG_JSON.to_string(),
+                    docker_config_json(registry)?,
+                )]));
+            }
+            require_keys(
+                SECRET_TYPE_DOCKER_CONFIG_JSON,
+                &data,
+                &[KEY_DOCKER_CONFIG_JSON],
+            )?;
+            let valid = serde_json::from_str::<Value>(&data[KEY_DOCKER_CONFIG_JSON])
+                .is_ok_and(|x| x["auths"].is_object());
+            if !valid {
+                return Err(AppError::InvalidInput(format!(
+                    "{} must be a JSON object with auths",
+                    KEY_DOCKER_CONFIG_JSON
+                )));
+            }
+            Ok(data)
+        }
+        SecretType::Tls => {
+            require_keys(SECRET_TYPE_TLS, &data, &[KEY_TLS_CRT, KEY_TLS_KEY])?;
+            if !data[KEY_TLS_CRT].contains("-----BEGIN")
+                || !data[KEY_TLS_KEY].contains("-----BEGIN")
+            {
+                return Err(AppError::InvalidInput(format!(
+                    "{} and {} must be PEM encoded",
+                    KEY_TLS_CRT, KEY_TLS_KEY
+                )));
+            }
+            Ok(data)
+        }
+        SecretType::BasicAuth => {
+            if !data.contains_key(KEY_USERNAME) && !data.contains_key(KEY_PASSWORD) {
+                return Err(AppError::InvalidInput(format!(
+                    "{} secret requires {} or {}",
+                    SECRET_TYPE_BASIC_AUTH, KEY_USERNAME, KEY_PASSWORD
+                )));
+            }
+            Ok(data)
+        }
+    }
+}
+
+/// Data of the secret after the update, with the keys whose value changed.
+/// MERGE only adds missing keys and PATCH only updates existing ones.
+pub fn apply_secret_update(
+    strategy: SecretUpdateStrategy,
+    current: &BTreeMap<String, ByteString>,
+    data: HashMap<String, String>,
+) -> Result<(BTreeMap<String, ByteString>, Vec<String>), AppError> {
+    let mut next = match strategy {
+        SecretUpdateStrategy::Unspecified => {
+            return Err(AppError::InvalidInput(
+                "Secret update strategy is required".to_string(),
+            ));
+        }
+        SecretUpdateStrategy::Replace => BTreeMap::new(),
+        SecretUpdateStrategy::Merge | SecretUpdateStrategy::Patch => current.clone(),
+    };
+    for (key, value) in data {
+        match strategy {
+            SecretUpdateStrategy::Merge if next.contains_key(&key) => continue,
+            SecretUpdateStrategy::Patch if !next.contains_key(&key) => {
+                return Err(AppError::InvalidInput(format!(
+                    "Secret has no {} key to patch",
+                    key
+                )));
+            }
+            _ => {}
+        }
+        next.insert(key, ByteString(value.into_bytes()));
+    }
+    let changed = current
+        .keys()
+        .chain(next.keys())
+        .filter(|key| current.get(*key) != next.get(*key))
+        .cloned()
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+    Ok((next, changed))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_secret_data() {
+        let registry = RegistryCredential {
+            server: "ghcr.io".to_string(),
+            username: "bot".to_string(),
+            password: "token".to_string(),
+            email: None,
+        };
+        let data = build_secret_data(
+            SecretType::DockerConfigJson,
+            Some(&registry),
+            HashMap::new(),
+        )
+        .unwrap();
+        let config: Value = serde_json::from_str(&data[KEY_DOCKER_CONFIG_JSON]).unwrap();
+        assert_eq!(config["auths"]["ghcr.io"]["username"], "bot");
+
+        assert!(build_secret_data(SecretType::Opaque, Some(&registry), HashMap::new()).is_err());
+        assert!(
+            build_secret_data(
+                SecretType::DockerConfigJson,
+                None,
+                HashMap::from([(KEY_DOCKER_CONFIG_JSON.to_string(), "{}".to_string())])
+            )
+            .is_err()
+        );
+        assert!(
+            build_secret_data(
+                SecretType::Tls,
+                None,
+                HashMap::from([(
+                    KEY_TLS_CRT.to_string(),
+                    "-----BEGIN CERTIFICATE-----".to_string()
+                )])
+            )
+            .is_err()
+        );
+        assert!(
+            build_secret_data(
+                SecretType::BasicAuth,
+                None,
+                HashMap::from([(KEY_USERNAME.to_string(), "admin".to_string())])
+            )
+            .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_apply_secret_update() {
+        let bytes = |x: &str| ByteString(x.as_bytes().to_vec());
+        let current = BTreeMap::from([
+            ("token".to_string(), bytes("old")),
+            ("user".to_string(), bytes("bot")),
+        ]);
+        let data = |pairs: &[(&str, &str)]| {
+            pairs
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect::<HashMap<_, _>>()
+        };
+
+        let (next, changed) = apply_secret_update(
+            SecretUpdateStrategy::Replace,
+            &current,
+            data(&[("token", "new")]),
+        )
+        .unwrap();
+        assert_eq!(next.len(), 1);
+        assert_eq!(changed, vec!["token", "user"]);
+
+        let (next, changed) = apply_secret_update(
+            SecretUpdateStrategy::Merge,
+            &current,
+            data(&[("token", "new"), ("scope", "repo")]),
+        )
+        .unwrap();
+        assert_eq!(next["token"], bytes("old"));
+        assert_eq!(changed, vec!["scope"]);
+
+        let (_, changed) = apply_secret_update(
+            SecretUpdateStrategy::Patch,
+            &current,
+            data(&[("token", "new"), ("user", "bot")]),
+        )
+        .unwrap();
+        assert_eq!(changed, vec!["token"]);
+        assert!(
+            apply_secret_update(
+                SecretUpdateStrategy::Patch,
+                &current,
+                data(&[("scope", "repo")])
+            )
+            .is_err()
+        );
+        assert!(
+            apply_secret_update(SecretUpdateStrategy::Unspecified, &current, data(&[])).is_err()
+        );
+    }
+}
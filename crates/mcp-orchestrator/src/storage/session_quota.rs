@@ -0,0 +1,359 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Duration, Utc};
+use k8s_openapi::api::core::v1::{Namespace, Pod};
+use kube::{
+    Api, Client, ResourceExt,
+    api::{ListParams, PostParams},
+};
+use proto::mcp::orchestrator::v1::{SessionQuota, SessionUsage};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::AppError,
+    storage::{
+        annotations::{ANNOTATION_SESSION_QUOTA, ANNOTATION_SESSION_RESERVATIONS},
+        label_query::build_label_query,
+        labels::{LABEL_SESSION_ID, LABEL_SESSION_SUBJECT_HASH},
+        resource_type::{RESOURCE_TYPE_MCP_SERVER, RESOURCE_TYPE_MCP_TEMPLATE},
+        resource_uname::filter_relpath,
+        scheduling_feasibility::pod_requests,
+        template_container::parse_quantity,
+    },
+};
+
+const RESERVATION_TTL_SECONDS: i64 = 60;
+const ADMIT_RETRIES: usize = 10;
+
+/// A session counted against the quota, either a pod or a reservation made before creating it.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SessionEntry {
+    pub session_id: String,
+    pub template: String,
+    pub subject_hash: Option<String>,
+    pub cpu: f64,
+    pub memory: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SessionReservation {
+    #[serde(flatten)]
+    entry: SessionEntry,
+    expires_at: DateTime<Utc>,
+}
+
+impl SessionEntry {
+    pub fn from_pod(pod: &Pod) -> Option<Self> {
+        let phase = pod.status.as_ref().and_then(|x| x.phase.as_deref());
+        if matches!(phase, Some("Succeeded" | "Failed")) {
+            return None;
+        }
+        let labels = pod.labels();
+        let template = labels
+            .keys()
+            .filter_map(filter_relpath)
+            .find(|(r#type, _)| r#type == RESOURCE_TYPE_MCP_TEMPLATE)
+            .map(|(_, name)| name)?;
+        let requests = pod.spec.as_ref().map(pod_requests).unwrap_or_default();
+        Some(Self {
+            session_id: labels.get(LABEL_SESSION_ID).cloned().unwrap_or_default(),
+            template,
+            subject_hash: labels.get(LABEL_SESSION_SUBJECT_HASH).cloned(),
+            cpu: requests.get("cpu").copied().unwrap_or_default(),
+            memory: requests.get("memory").copied().unwrap_or_default(),
+        })
+    }
+}
+
+pub fn namespace_quota(ns: &Namespace) -> SessionQuota {
+    ns.annotations()
+        .get(ANNOTATION_SESSION_QUOTA)
+        .and_then(|x| serde_json::from_str(x).ok())
+        .unwrap_or_default()
+}
+
+pub fn is_unlimited(quota: &SessionQuota) -> bool {
+    quota.max_sessions.is_none()
+        && quota.max_sessions_per_template.is_none()
+        && quota.max_sessions_per_user.is_none()
+        && quota.cpu.is_none()
+        && quota.memory.is_none()
+}
+
+fn parse_quota_quantity(name: &str, value: Option<&str>) -> Result<Option<f64>, AppError> {
+    value
+        .map(|x| {
+            parse_quantity(x).ok_or_else(|| {
+                AppError::InvalidInput(format!("Invalid {} quota quantity: {}", name, x))
+            })
+        })
+        .transpose()
+}
+
+pub fn validate_quota(quota: &SessionQuota) -> Result<(), AppError> {
+    parse_quota_quantity("cpu", quota.cpu.as_deref())?;
+    parse_quota_quantity("memory", quota.memory.as_deref())?;
+    Ok(())
+}
+
+pub fn session_usage<'a>(entries: impl IntoIterator<Item = &'a SessionEntry>) -> SessionUsage {
+    let mut usage = SessionUsage::default();
+    for entry in entries {
+        usage.sessions += 1;
+        usage.cpu += entry.cpu;
+        usage.memory += entry.memory;
+        *usage
+            .template_sessions
+            .entry(entry.template.clone())
+            .or_default() += 1;
+    }
+    usage
+}
+
+/// Checks whether `entry` fits in the quota beside the `entries` already running.
+pub fn check_quota(
+    quota: &SessionQuota,
+    entries: &[SessionEntry],
+    entry: &SessionEntry,
+) -> Result<(), AppError> {
+    let exceeded = |what: String| Err(AppError::QuotaExceeded(what));
+    let usage = session_usage(entries);
+    let template_sessions = usage
+        .template_sessions
+        .get(&entry.template)
+        .copied()
+        .unwrap_or_default();
+    // 익명 세션은 사용자별 제한에서 제외
+    let user_sessions = entry.subject_hash.as_ref().map(|subject_hash| {
+        entries
+            .iter()
+            .filter(|x| x.subject_hash.as_ref() == Some(subject_hash))
+            .count() as u32
+    });
+    let cpu = usage.cpu + entry.cpu;
+    let memory = usage.memory + entry.memory;
+
+    if let Some(max) = quota.max_sessions.filter(|max| usage.sessions >= *max) {
+        return exceeded(format!("namespace allows at most {} sessions", max));
+    }
+    if let Some(max) = quota
+        .max_sessions_per_template
+        .filter(|max| template_sessions >= *max)
+    {
+        return exceeded(format!(
+            "template {} allows at most {} sessions",
+            entry.template, max
+        ));
+    }
+    if let Some(max) = quota
+        .max_sessions_per_user
+        .filter(|max| user_sessions.is_some_and(|count| count >= *max))
+    {
+        return exceeded(format!("user allows at most {} sessions", max));
+    }
+    if let Some(max) = parse_quota_quantity("cpu", quota.cpu.as_deref())?.filter(|max| cpu > *max) {
+        return exceeded(format!("cpu requests {} over the quota {}", cpu, max));
+    }
+    if let Some(max) =
+        parse_quota_quantity("memory", quota.memory.as_deref())?.filter(|max| memory > *max)
+    {
+        return exceeded(format!("memory requests {} over the quota {}", memory, max));
+    }
+    Ok(())
+}
+
+/// Unexpired reservations whose pod does not exist yet.
+fn live_reservations(
+    ns: &Namespace,
+    entries: &[SessionEntry],
+    now: DateTime<Utc>,
+) -> Vec<SessionReservation> {
+    ns.annotations()
+        .get(ANNOTATION_SESSION_RESERVATIONS)
+        .and_then(|x| serde_json::from_str::<Vec<SessionReservation>>(x).ok())
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|x| x.expires_at > now)
+        .filter(|x| entries.iter().all(|e| e.session_id != x.entry.session_id))
+        .collect()
+}
+
+pub struct SessionQuotaStore {
+    client: Client,
+}
+
+impl SessionQuotaStore {
+    pub fn new(client: Client) -> Self {
+        Self { client }
+    }
+
+    async fn list_pods(&self, namespace: Option<&str>) -> Result<Vec<Pod>, AppError> {
+        let api = match namespace {
+            Some(namespace) => Api::<Pod>::namespaced(self.client.clone(), namespace),
+            None => Api::<Pod>::all(self.client.clone()),
+        };
+        let label = build_label_query(RESOURCE_TYPE_MCP_SERVER, &[])?.to_string();
+        let list = api
+            .list(&ListParams::default().labels(&label))
+            .await
+            .map_err(AppError::from)?;
+        Ok(list.items)
+    }
+
+    /// Usage of every namespace, keyed by the namespace name.
+    pub async fn usage_all(&self) -> Result<HashMap<String, SessionUsage>, AppError> {
+        let mut entries = HashMap::<String, Vec<SessionEntry>>::new();
+        for pod in self.list_pods(None).await? {
+            if let Some(entry) = SessionEntry::from_pod(&pod) {
+                entries
+                    .entry(pod.namespace().unwrap_or_default())
+                    .or_default()
+                    .push(entry);
+            }
+        }
+        Ok(entries
+            .into_iter()
+            .map(|(namespace, entries)| (namespace, session_usage(&entries)))
+            .collect())
+    }
+
+    pub async fn usage(&self, namespace: &str) -> Result<SessionUsage, AppError> {
+        let entries = self
+            .list_pods(Some(namespace))
+            .await?
+            .iter()
+            .filter_map(SessionEntry::from_pod)
+            .collect::<Vec<_>>();
+        Ok(session_usage(&entries))
+    }
+
+    /// Reserves a slot for the session, the reservation is written to the namespace with its
+    /// resource version so concurrent admissions can not both take the last slot.
+    pub async fn admit(&self, namespace: &str, entry: SessionEntry) -> Result<(), AppError> {
+        let api = Api::<Namespace>::all(self.client.clone());
+        for _ in 0..ADMIT_RETRIES {
+            let mut ns = api.get(namespace).await.map_err(AppError::from)?;
+            let quota = namespace_quota(&ns);
+            if is_unlimited(&quota) {
+                return Ok(());
+            }
+            let now = Utc::now();
+            let mut entries = self
+                .list_pods(Some(namespace))
+                .await?
+                .iter()
+                .filter_map(SessionEntry::from_pod)
+                .collect::<Vec<_>>();
+            let mut reservations = live_reservations(&ns, &entries, now);
+            entries.extend(reservations.iter().map(|x| x.entry.clone()));
+            check_quota(&quota, &entries, &entry)?;
+
+            reservations.push(SessionReservation {
+                entry: entry.clone(),
+                expires_at: now + Duration::seconds(RESERVATION_TTL_SECONDS),
+            });
+            ns.annotations_mut().insert(
+                ANNOTATION_SESSION_RESERVATIONS.to_string(),
+                serde_json::to_string(&reservations)?,
+            );
+            match api.replace(namespace, &PostParams::default(), &ns).await {
+                Ok(_) => return Ok(()),
+                Err(kube::Error::Api(ae)) if ae.code == 409 => continue,
+                Err(err) => return Err(AppError::from(err)),
+            }
+        }
+        Err(AppError::QuotaExceeded(format!(
+            "too many concurrent session requests in namespace {}",
+            namespace
+        )))
+    }
+
+    /// Drops the reservation of a session whose pod was never created.
+    pub async fn release(&self, namespace: &str, session_id: &str) -> Result<(), AppError> {
+        let api = Api::<Namespace>::all(self.client.clone());
+        for _ in 0..ADMIT_RETRIES {
+            let mut ns = api.get(namespace).await.map_err(AppError::from)?;
+            let Some(reservations) = ns
+                .annotations()
+                .get(ANNOTATION_SESSION_RESERVATIONS)
+                .and_then(|x| serde_json::from_str::<Vec<SessionReservation>>(x).ok())
+            else {
+                return Ok(());
+            };
+            if reservations
+                .iter()
+                .all(|x| x.entry.session_id != session_id)
+            {
+                return Ok(());
+            }
+            let reservations = reservations
+                .into_iter()
+                .filter(|x| x.entry.session_id != session_id)
+                .collect::<Vec<_>>();
+            ns.annotations_mut().insert(
+                ANNOTATION_SESSION_RESERVATIONS.to_string(),
+                serde_json::to_string(&reservations)?,
+            );
+            match api.replace(namespace, &PostParams::default(), &ns).await {
+                Ok(_) => return Ok(()),
+                Err(kube::Error::Api(ae)) if ae.code == 409 => continue,
+                Err(err) => return Err(AppError::from(err)),
+            }
+        }
+        Err(AppError::Internal(format!(
+            "Failed to release the reservation of session {} in namespace {}",
+            session_id, namespace
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(session_id: &str, template: &str, subject_hash: Option<&str>) -> SessionEntry {
+        SessionEntry {
+            session_id: session_id.to_string(),
+            template: template.to_string(),
+            subject_hash: subject_hash.map(str::to_string),
+            cpu: 0.5,
+            memory: 512.0 * 1024.0 * 1024.0,
+        }
+    }
+
+    #[test]
+    fn test_check_quota() {
+        let entries = vec![
+            entry("a", "github", Some("u1")),
+            entry("b", "github", None),
+            entry("c", "slack", Some("u1")),
+        ];
+        let quota = SessionQuota {
+            max_sessions: Some(4),
+            max_sessions_per_template: Some(2),
+            max_sessions_per_user: Some(2),
+            cpu: Some("2".to_string()),
+            memory: None,
+        };
+        assert!(check_quota(&quota, &entries, &entry("d", "jira", None)).is_ok());
+        assert!(matches!(
+            check_quota(&quota, &entries, &entry("d", "github", None)),
+            Err(AppError::QuotaExceeded(_))
+        ));
+        assert!(check_quota(&quota, &entries, &entry("d", "jira", Some("u1"))).is_err());
+        assert!(check_quota(&quota, &entries, &entry("d", "jira", Some("u2"))).is_ok());
+
+        let quota = SessionQuota {
+            cpu: Some("1500m".to_string()),
+            ..Default::default()
+        };
+        assert!(check_quota(&quota, &entries, &entry("d", "jira", None)).is_err());
+        assert!(
+            validate_quota(&SessionQuota {
+                memory: Some("lots".to_string()),
+                ..Default::default()
+            })
+            .is_err()
+        );
+    }
+}
@@ -0,0 +1,228 @@
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::{DateTime, Utc};
+
+use k8s_openapi::{
+    ByteString,
+    api::core::v1::{Pod, Secret},
+};
+use kube::{
+    Api, Client, Resource, ResourceExt,
+    api::{DeleteParams, ListParams, ObjectMeta, Patch, PatchParams, PostParams},
+};
+
+use crate::{
+    error::AppError,
+    storage::{
+        label_query::build_label_query,
+        labels::{LABEL_SESSION_ID, setup_labels},
+        resource_type::{RESOURCE_TYPE_PREFIX_SESSION_SECRET, RESOURCE_TYPE_SESSION_SECRET},
+        secret_backend::{ResolvedSecret, SecretRef},
+        store::KubeStore,
+        store_user_secret::user_secret_name,
+        util_name::encode_k8sname,
+    },
+};
+
+/// Secrets of a session pod, keyed by the reference of the template.
+#[derive(Default)]
+pub struct SessionSecrets {
+    /// Name of the Kubernetes Secret the pod references with its keys.
+    pub refs: HashMap<String, (String, Vec<String>)>,
+    /// Secrets holding the values resolved from external backends, created with the pod.
+    pub owned: Vec<Secret>,
+}
+
+impl SessionSecrets {
+    pub fn secret_name(&self, reference: &str) -> Option<&str> {
+        self.refs.get(reference).map(|(name, _)| name.as_str())
+    }
+
+    /// Adds a Secret owned by the session holding `data`, returns its name.
+    pub fn own(
+        &mut self,
+        namespace: &str,
+        session_id: &str,
+        data: BTreeMap<String, ByteString>,
+    ) -> String {
+        let name = session_secret_name(session_id, self.owned.len());
+        self.owned.push(Secret {
+            metadata: ObjectMeta {
+                name: Some(name.clone()),
+                namespace: Some(namespace.to_string()),
+                labels: Some(
+                    setup_labels(RESOURCE_TYPE_SESSION_SECRET, std::iter::empty())
+                        .chain([(LABEL_SESSION_ID.to_string(), session_id.to_string())])
+                        .collect(),
+                ),
+                ..Default::default()
+            },
+            data: Some(data),
+            immutable: Some(true),
+            ..Default::default()
+        });
+        name
+    }
+}
+
+pub fn session_secret_name(session_id: &str, index: usize) -> String {
+    encode_k8sname(
+        RESOURCE_TYPE_PREFIX_SESSION_SECRET,
+        &format!("{}-{}", session_id, index),
+    )
+}
+
+/// Resolves the `user:<name>` secret of the subject opening the session.
+/// A `dry_run` resolves a placeholder without keys since the validation subject owns no secrets.
+async fn resolve_user_secret(
+    store: &KubeStore,
+    namespace: &str,
+    subject: Option<&str>,
+    dry_run: bool,
+    name: &str,
+) -> Result<ResolvedSecret, AppError> {
+    let Some(subject) = subject else {
+        return Err(AppError::InvalidInput(format!(
+            "User secret '{}' requires an authenticated user",
+            name
+        )));
+    };
+    if dry_run {
+        return Ok(ResolvedSecret::Kubernetes(Box::new(Secret {
+            metadata: ObjectMeta {
+                name: Some(user_secret_name(subject, name)),
+                ..Default::default()
+            },
+            ..Default::default()
+        })));
+    }
+    let secret = store
+        .user_secrets(Some(namespace.to_string()))
+        .get(subject, name)
+        .await?
+        .ok_or_else(|| {
+            AppError::InvalidInput(format!(
+                "User secret '{}' is required by this template, create it in namespace {} for your account from the My Secrets page or with PutUserSecret",
+                name, namespace
+            ))
+        })?;
+    Ok(ResolvedSecret::Kubernetes(Box::new(secret.raw)))
+}
+
+/// Resolves every reference through its backend, external values become session-owned Secrets.
+pub async fn resolve_session_secrets<'a>(
+    store: &KubeStore,
+    namespace: &str,
+    session_id: &str,
+    subject: Option<&str>,
+    dry_run: bool,
+    references: impl Iterator<Item = &'a String>,
+) -> Result<SessionSecrets, AppError> {
+    let mut secrets = SessionSecrets::default();
+    for reference in references {
+        if secrets.refs.contains_key(reference) {
+            continue;
+        }
+        let secret_ref = SecretRef::parse(reference)?;
+        let resolved = if secret_ref.is_user() {
+            resolve_user_secret(store, namespace, subject, dry_run, &secret_ref.path).await?
+        } else {
+            store
+                .secret_backends()
+                .resolve(namespace, &secret_ref)
+                .await?
+        };
+        let entry = match resolved {
+            ResolvedSecret::Kubernetes(secret) => (
+                secret.name_any(),
+                secret
+                    .data
+                    .unwrap_or_default()
+                    .into_keys()
+                    .collect::<Vec<_>>(),
+            ),
+            ResolvedSecret::Values(data) => {
+                let keys = data.keys().cloned().collect();
+                (secrets.own(namespace, session_id, data), keys)
+            }
+        };
+        secrets.refs.insert(reference.clone(), entry);
+    }
+    Ok(secrets)
+}
+
+pub async fn create_session_secrets(
+    client: Client,
+    namespace: &str,
+    secrets: &[Secret],
+) -> Result<(), AppError> {
+    let api = Api::<Secret>::namespaced(client.clone(), namespace);
+    for (index, secret) in secrets.iter().enumerate() {
+        if let Err(e) = api.create(&PostParams::default(), secret).await {
+            delete_session_secrets(client, namespace, &secrets[..index]).await;
+            return Err(AppError::from(e));
+        }
+    }
+    Ok(())
+}
+
+pub async fn delete_session_secrets(client: Client, namespace: &str, secrets: &[Secret]) {
+    let api = Api::<Secret>::namespaced(client, namespace);
+    for secret in secrets {
+        if let Err(e) = api
+            .delete(&secret.name_any(), &DeleteParams::default())
+            .await
+        {
+            tracing::error!(
+                "Failed to delete session secret {}: {}",
+                secret.name_any(),
+                e
+            );
+        }
+    }
+}
+
+/// Makes the pod own the secrets so they are garbage collected with the session.
+pub async fn adopt_session_secrets(
+    client: Client,
+    pod: &Pod,
+    secrets: &[Secret],
+) -> Result<(), AppError> {
+    let Some(owner) = pod.controller_owner_ref(&()) else {
+        return Ok(());
+    };
+    let api = Api::<Secret>::namespaced(client, &pod.namespace().unwrap_or_default());
+    let patch = serde_json::json!({
+        "metadata": { "ownerReferences": [owner] }
+    });
+    for secret in secrets {
+        api.patch(
+            &secret.name_any(),
+            &PatchParams::default(),
+            &Patch::Merge(&patch),
+        )
+        .await?;
+    }
+    Ok(())
+}
+
+/// Deletes session secrets never adopted by their pod, e.g. when the orchestrator stopped midway.
+pub async fn delete_unowned_session_secrets(
+    client: Client,
+    created_before: DateTime<Utc>,
+) -> Result<(), AppError> {
+    let label = build_label_query(RESOURCE_TYPE_SESSION_SECRET, &[])?.to_string();
+    let list = Api::<Secret>::all(client.clone())
+        .list(&ListParams::default().labels(&label))
+        .await?;
+    for secret in list.items {
+        let created_at = secret.creation_timestamp().map(|x| x.0);
+        if !secret.owner_references().is_empty() || created_at.is_none_or(|x| x > created_before) {
+            continue;
+        }
+        tracing::info!("Deleting unowned session secret {}", secret.name_any());
+        let namespace = secret.namespace().unwrap_or_default();
+        delete_session_secrets(client.clone(), &namespace, &[secret]).await;
+    }
+    Ok(())
+}
@@ -1,10 +1,15 @@
+use std::sync::Arc;
+
 use kube::Client;
 
 use crate::{
+    config::{ImageAdmissionConfig, SecretBackendConfig},
     error::AppError,
     storage::{
         McpTemplateStore, NamespaceStore, ResourceLimitStore, SecretStore,
-        store_authorization::AuthorizationStore,
+        secret_backend::SecretBackends, session_quota::SessionQuotaStore,
+        store_authorization::AuthorizationStore, store_usage::UsageStore,
+        store_user_secret::UserSecretStore, store_workspace::WorkspaceStore,
     },
 };
 
@@ -12,16 +17,36 @@ use crate::{
 pub struct KubeStore {
     client: Client,
     default_namespace: String,
+    image_admission: Arc<ImageAdmissionConfig>,
+    secret_backends: SecretBackends,
 }
 
 impl KubeStore {
     pub fn new(client: Client, default_namespace: impl Into<String>) -> Self {
         Self {
+            secret_backends: SecretBackends::kubernetes(client.clone()),
             client,
             default_namespace: default_namespace.into(),
+            image_admission: Default::default(),
         }
     }
 
+    /// Image admission policy enforced when templates are created.
+    pub fn with_image_admission(mut self, config: ImageAdmissionConfig) -> Self {
+        self.image_admission = Arc::new(config);
+        self
+    }
+
+    /// Backends resolving the secrets referenced by templates.
+    pub fn with_secret_backends(mut self, config: &SecretBackendConfig) -> Result<Self, AppError> {
+        self.secret_backends = SecretBackends::new(self.client.clone(), config)?;
+        Ok(self)
+    }
+
+    pub fn secret_backends(&self) -> &SecretBackends {
+        &self.secret_backends
+    }
+
     pub fn to_client(&self) -> Client {
         self.client.clone()
     }
@@ -42,17 +67,28 @@ impl KubeStore {
         NamespaceStore::new(self.client.clone(), self.default_namespace.clone())
     }
 
+    pub fn session_quotas(&self) -> SessionQuotaStore {
+        SessionQuotaStore::new(self.client.clone())
+    }
+
     pub fn secrets(&self, namespace: Option<String>) -> SecretStore {
         let ns = namespace.unwrap_or_else(|| self.default_namespace.clone());
         SecretStore::new(self.client.clone(), ns)
     }
 
+    pub fn user_secrets(&self, namespace: Option<String>) -> UserSecretStore {
+        let ns = namespace.unwrap_or_else(|| self.default_namespace.clone());
+        UserSecretStore::new(self.client.clone(), ns)
+    }
+
     pub fn mcp_templates(&self, namespace: Option<String>) -> McpTemplateStore {
         let target_namespace = namespace.unwrap_or_else(|| self.default_namespace.clone());
         McpTemplateStore::new(
             self.client.clone(),
             target_namespace,
             self.default_namespace.clone(),
+            self.image_admission.clone(),
+            self.secret_backends.clone(),
         )
     }
 
@@ -70,6 +106,16 @@ impl KubeStore {
         AuthorizationStore::new(self.client.clone(), ns)
     }
 
+    pub fn workspaces(&self, namespace: Option<String>) -> WorkspaceStore {
+        let ns = namespace.unwrap_or_else(|| self.default_namespace.clone());
+        WorkspaceStore::new(self.client.clone(), ns)
+    }
+
+    /// Usage records are kept in the default namespace so they outlive deleted namespaces.
+    pub fn usage(&self) -> UsageStore {
+        UsageStore::new(self.client.clone(), self.default_namespace.clone())
+    }
+
     pub async fn ensure_default_namespace(&self) -> Result<(), AppError> {
         self.namespaces().ensure_default_namespace().await?;
         Ok(())
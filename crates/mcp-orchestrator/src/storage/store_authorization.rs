@@ -3,13 +3,18 @@ use std::vec;
 
 use super::label_query::LabelQuery;
 use super::labels::setup_labels;
+use crate::storage::authorization_api_key::{ApiKeyEntry, generate_api_key};
+use crate::storage::authorization_oidc::OidcAuthorization;
+use crate::storage::authorization_token::{DATA_TOKEN_RECORD, IssuedTokenData, TokenRecord};
 use crate::storage::label_query::build_label_query;
 use crate::storage::labels::{
-    LABEL_AUTH_TYPE_OF, decode_label_optmap, label_auth_type_of, label_dependency_tuple,
+    LABEL_AUTH_TYPE_OF, decode_label_optmap, label_auth_type_of, label_dependency_query,
+    label_dependency_tuple,
 };
 use crate::storage::resource_type::{
-    RESOURCE_TYPE_AUTHORIZATION, RESOURCE_TYPE_NAMESPACE, RESOURCE_TYPE_PREFIX_AUTHORIZATION,
-    RESOURCE_TYPE_PREFIX_AUTHORIZATION_SA,
+    RESOURCE_TYPE_AUTHORIZATION, RESOURCE_TYPE_AUTHORIZATION_TOKEN, RESOURCE_TYPE_MCP_TEMPLATE,
+    RESOURCE_TYPE_NAMESPACE, RESOURCE_TYPE_PREFIX_AUTHORIZATION,
+    RESOURCE_TYPE_PREFIX_AUTHORIZATION_SA, RESOURCE_TYPE_PREFIX_AUTHORIZATION_TOKEN,
 };
 use crate::storage::util_list::ListOption;
 use crate::storage::util_name::{decode_k8sname, encode_k8sname};
@@ -25,23 +30,23 @@ use crate::{
     },
 };
 use chrono::{DateTime, Duration, Utc};
-use k8s_openapi::api::authentication::v1::{TokenRequest, TokenRequestSpec};
+use k8s_openapi::api::authentication::v1::{BoundObjectReference, TokenRequest, TokenRequestSpec};
 use k8s_openapi::api::core::v1::{
-    Secret, ServiceAccount,
+    ConfigMap, Secret, ServiceAccount,
 };
 use k8s_openapi::apimachinery::pkg::apis::meta::v1::OwnerReference;
 use kube::Resource;
 use kube::{
     Api, Client, ResourceExt,
-    api::{DeleteParams, ObjectMeta, PostParams},
+    api::{DeleteParams, ListParams, ObjectMeta, Patch, PatchParams, PostParams},
 };
 use proto::mcp::orchestrator::v1::AuthorizationType;
 
 const DATA_DATA: &str = "data";
 const DATA_SA_NAME: &str = "service_account_name";
+const DATA_API_KEYS: &str = "api_keys";
 
 pub struct AuthorizationData {
-    #[allow(dead_code)]
     pub raw: Secret,
     pub namespace: String,
     pub labels: HashMap<String, String>,
@@ -49,6 +54,7 @@ pub struct AuthorizationData {
     pub r#type: AuthorizationType,
     pub data: serde_json::Value,
     pub sa_name: Option<String>,
+    pub api_keys: Vec<ApiKeyEntry>,
     pub created_at: DateTime<Utc>,
     pub deleted_at: Option<DateTime<Utc>>,
 }
@@ -79,6 +85,14 @@ impl AuthorizationData {
             )?,
             data: parse_secret_elem(&raw.data, "data")?,
             sa_name: parse_secret_elem(&raw.data, "service_account_name")?,
+            api_keys: raw
+                .data
+                .as_ref()
+                .and_then(|x| x.get(DATA_API_KEYS))
+                .map(|x| serde_json::from_slice(&x.0))
+                .transpose()
+                .map_err(AppError::SerializationError)?
+                .unwrap_or_default(),
             created_at: pick_created_at(&raw),
             deleted_at: pick_deleted_at(&raw),
         })
@@ -106,6 +120,9 @@ impl AuthorizationStore {
         r#type: AuthorizationType,
         data: &serde_json::Value,
     ) -> Result<AuthorizationData, AppError> {
+        if r#type == AuthorizationType::Oidc {
+            OidcAuthorization::from_data(data)?;
+        }
         let api_secret = self.api();
         let api_sa = Api::<ServiceAccount>::namespaced(self.client.clone(), &self.namespace);
         let sa_name = encode_k8sname(RESOURCE_TYPE_PREFIX_AUTHORIZATION_SA, name);
@@ -164,6 +181,39 @@ impl AuthorizationStore {
         AuthorizationData::try_from_secret(secret)
     }
 
+    /// Replaces the type and data of `current` in place, its service account is kept.
+    pub async fn replace<L: Iterator<Item = (String, String)>>(
+        &self,
+        current: &AuthorizationData,
+        labels: L,
+        r#type: AuthorizationType,
+        data: &serde_json::Value,
+    ) -> Result<AuthorizationData, AppError> {
+        if r#type == AuthorizationType::Oidc {
+            OidcAuthorization::from_data(data)?;
+        }
+        let mut secret = current.raw.clone();
+        secret.metadata.labels = Some(
+            setup_labels(RESOURCE_TYPE_AUTHORIZATION, labels)
+                .chain(vec![
+                    label_dependency_tuple(RESOURCE_TYPE_NAMESPACE, &self.namespace),
+                    label_auth_type_of(r#type),
+                ])
+                .collect(),
+        );
+        let secret_data = secret.data.get_or_insert_default();
+        let (key, value) = data_secret(DATA_DATA, data)?;
+        secret_data.insert(key, value);
+        if r#type != AuthorizationType::ApiKey {
+            secret_data.remove(DATA_API_KEYS);
+        }
+        self.api()
+            .replace(&current.raw.name_any(), &PostParams::default(), &secret)
+            .await
+            .map_err(AppError::from)
+            .and_then(AuthorizationData::try_from_secret)
+    }
+
     pub async fn get(&self, name: &str) -> Result<Option<AuthorizationData>, AppError> {
         let name = encode_k8sname(RESOURCE_TYPE_PREFIX_AUTHORIZATION, name);
         self.api()
@@ -255,33 +305,292 @@ impl AuthorizationStore {
         Ok(result)
     }
 
+    pub async fn is_deletable(&self, name: &str) -> Result<bool, AppError> {
+        let raw_name = encode_k8sname(RESOURCE_TYPE_PREFIX_AUTHORIZATION, name);
+        let Some(_secret) = self.api().get_opt(&raw_name).await? else {
+            return Ok(false);
+        };
+
+        let has_dep_mcp_templates = self.has_dep_mcp_templates(name).await?;
+        Ok(!has_dep_mcp_templates)
+    }
+
+    async fn has_dep_mcp_templates(&self, name: &str) -> Result<bool, AppError> {
+        let mcp_template_store = Api::<ConfigMap>::namespaced(self.client.clone(), &self.namespace);
+
+        let label = build_label_query(
+            RESOURCE_TYPE_MCP_TEMPLATE,
+            &[label_dependency_query(RESOURCE_TYPE_AUTHORIZATION, name)],
+        )?
+        .to_string();
+        let lp = ListParams::default().labels(&label).limit(1);
+        let list = mcp_template_store.list(&lp).await.map_err(AppError::from)?;
+        Ok(!list.items.is_empty())
+    }
+
+    /// Applies `f` to the api keys of the authorization, retried when it was modified concurrently.
+    async fn update_api_keys<T>(
+        &self,
+        name: &str,
+        mut f: impl FnMut(&mut Vec<ApiKeyEntry>) -> Result<T, AppError>,
+    ) -> Result<T, AppError> {
+        let raw_name = encode_k8sname(RESOURCE_TYPE_PREFIX_AUTHORIZATION, name);
+        let mut retry = 3;
+        loop {
+            let Some(current) = self.get(name).await? else {
+                return Err(AppError::NotFound(format!(
+                    "Authorization {}/{} not found",
+                    self.namespace, name
+                )));
+            };
+            if current.r#type != AuthorizationType::ApiKey {
+                return Err(AppError::InvalidInput(format!(
+                    "Authorization {} is not of type ApiKey",
+                    name
+                )));
+            }
+            let mut api_keys = current.api_keys;
+            let result = f(&mut api_keys)?;
+            let mut secret = current.raw;
+            let (key, value) = data_secret(DATA_API_KEYS, &api_keys)?;
+            secret.data.get_or_insert_default().insert(key, value);
+            match self
+                .api()
+                .replace(&raw_name, &PostParams::default(), &secret)
+                .await
+            {
+                Ok(_) => return Ok(result),
+                Err(kube::Error::Api(ae)) if ae.code == 409 && retry > 0 => retry -= 1,
+                Err(err) => return Err(AppError::from(err)),
+            }
+        }
+    }
+
+    /// Issues a named api key, the returned key is not stored and can't be shown again.
+    pub async fn issue_api_key(
+        &self,
+        name: &str,
+        key_name: &str,
+        duration: Option<Duration>,
+    ) -> Result<(ApiKeyEntry, String), AppError> {
+        if key_name.is_empty() || key_name.len() > 63 {
+            return Err(AppError::InvalidInput(
+                "Api key name must be 1 to 63 characters".to_string(),
+            ));
+        }
+        let now = Utc::now();
+        let (entry, key) = generate_api_key(key_name, now, duration.map(|x| now + x))?;
+        self.update_api_keys(name, |api_keys| {
+            if api_keys.iter().any(|x| x.name == key_name) {
+                return Err(AppError::InvalidInput(format!(
+                    "Api key {} already exists in authorization {}",
+                    key_name, name
+                )));
+            }
+            api_keys.push(entry.clone());
+            Ok(())
+        })
+        .await?;
+        Ok((entry, key))
+    }
+
+    pub async fn revoke_api_key(&self, name: &str, key_name: &str) -> Result<(), AppError> {
+        self.update_api_keys(name, |api_keys| {
+            let len = api_keys.len();
+            api_keys.retain(|x| x.name != key_name);
+            if api_keys.len() == len {
+                return Err(AppError::NotFound(format!(
+                    "Api key {} not found in authorization {}",
+                    key_name, name
+                )));
+            }
+            Ok(())
+        })
+        .await
+    }
+
+    pub async fn touch_api_key(&self, name: &str, id: &str) -> Result<(), AppError> {
+        let now = Utc::now();
+        self.update_api_keys(name, |api_keys| {
+            if let Some(entry) = api_keys.iter_mut().find(|x| x.id == id) {
+                entry.last_used_at = Some(now);
+            }
+            Ok(())
+        })
+        .await
+    }
+
+    /// Issues a service account token and records it, a bound token dies with its record.
     pub async fn generate_token(
         &self,
         name: &str,
         audience: &str,
         duration: Option<Duration>,
-    ) -> Result<(String, DateTime<Utc>), AppError> {
-        let audience = audience.to_string();
+        requester: Option<&str>,
+        bound: bool,
+    ) -> Result<(IssuedTokenData, String), AppError> {
+        let Some(authorization) = self.get(name).await? else {
+            return Err(AppError::NotFound(format!(
+                "Authorization {} not found",
+                name
+            )));
+        };
+        let owner_ref = authorization.raw.controller_owner_ref(&()).ok_or_else(|| {
+            AppError::Internal(format!(
+                "Failed to set owner reference for token record: {}",
+                name
+            ))
+        })?;
+        let mut record = TokenRecord {
+            authorization: name.to_string(),
+            requester: requester.map(str::to_string),
+            audience: audience.to_string(),
+            bound,
+            expire_at: None,
+        };
+        let api = self.api();
+        let id = uuid::Uuid::new_v4().simple().to_string();
+        let record_name = encode_k8sname(RESOURCE_TYPE_PREFIX_AUTHORIZATION_TOKEN, &id);
+        let record_labels = setup_labels(RESOURCE_TYPE_AUTHORIZATION_TOKEN, std::iter::empty())
+            .chain(vec![label_dependency_tuple(
+                RESOURCE_TYPE_AUTHORIZATION,
+                name,
+            )])
+            .collect();
+        let record_secret = api
+            .create(
+                &PostParams::default(),
+                &Secret {
+                    metadata: ObjectMeta {
+                        name: Some(record_name.clone()),
+                        labels: Some(record_labels),
+                        owner_references: Some(vec![owner_ref]),
+                        ..Default::default()
+                    },
+                    data: Some(
+                        vec![data_secret(DATA_TOKEN_RECORD, &record)?]
+                            .into_iter()
+                            .collect(),
+                    ),
+                    ..Default::default()
+                },
+            )
+            .await
+            .map_err(AppError::from)?;
+
+        let bound_object_ref = bound.then(|| BoundObjectReference {
+            api_version: Some("v1".to_string()),
+            kind: Some("Secret".to_string()),
+            name: Some(record_name.clone()),
+            uid: record_secret.uid(),
+        });
         let token = Api::<ServiceAccount>::namespaced(self.client.clone(), &self.namespace)
             .create_token_request(
                 &encode_k8sname(RESOURCE_TYPE_PREFIX_AUTHORIZATION_SA, name),
                 &PostParams::default(),
                 &TokenRequest {
                     spec: TokenRequestSpec {
-                        audiences: vec![audience],
+                        audiences: vec![audience.to_string()],
                         expiration_seconds: duration.map(|d| d.num_seconds()),
-                        bound_object_ref: None,
+                        bound_object_ref,
                     },
                     ..Default::default()
                 },
             )
             .await
-            .map_err(|e| AppError::Internal(format!("Failed to get service account: {}", e)))?;
-        let Some(status) = &token.status else {
-            return Err(AppError::Internal(
-                "TokenRequest status is missing".to_string(),
-            ));
+            .map_err(|e| AppError::Internal(format!("Failed to get service account: {}", e)));
+        let status = match token.map(|x| x.status) {
+            Ok(Some(status)) => status,
+            result => {
+                // 토큰 발급에 실패하면 기록도 남기지 않는다
+                if let Err(e) = api.delete(&record_name, &DeleteParams::default()).await {
+                    tracing::warn!("Failed to delete token record {}: {}", record_name, e);
+                }
+                return Err(result.err().unwrap_or_else(|| {
+                    AppError::Internal("TokenRequest status is missing".to_string())
+                }));
+            }
         };
-        Ok((status.token.clone(), status.expiration_timestamp.0))
+
+        record.expire_at = Some(status.expiration_timestamp.0);
+        let patch = Secret {
+            data: Some(
+                vec![data_secret(DATA_TOKEN_RECORD, &record)?]
+                    .into_iter()
+                    .collect(),
+            ),
+            ..Default::default()
+        };
+        let record_secret = match api
+            .patch(&record_name, &PatchParams::default(), &Patch::Merge(&patch))
+            .await
+        {
+            Ok(secret) => secret,
+            Err(e) => {
+                tracing::warn!("Failed to record token expiration {}: {}", record_name, e);
+                record_secret
+            }
+        };
+        let mut issued = IssuedTokenData::try_from_secret(record_secret)?;
+        issued.record = record;
+        Ok((issued, status.token))
+    }
+
+    pub async fn list_tokens(&self, name: &str) -> Result<Vec<IssuedTokenData>, AppError> {
+        let api = self.api();
+        let label = build_label_query(
+            RESOURCE_TYPE_AUTHORIZATION_TOKEN,
+            &[label_dependency_query(RESOURCE_TYPE_AUTHORIZATION, name)],
+        )?
+        .to_string();
+        let list = api
+            .list(&ListParams::default().labels(&label))
+            .await
+            .map_err(AppError::from)?;
+        let now = Utc::now();
+        let mut tokens = Vec::with_capacity(list.items.len());
+        for secret in list.items {
+            let token = IssuedTokenData::try_from_secret(secret)?;
+            if token.is_expired(now) {
+                // 만료된 토큰의 기록은 조회하는 김에 정리한다
+                let record_name = token.raw.name_any();
+                if let Err(e) = api.delete(&record_name, &DeleteParams::default()).await {
+                    tracing::warn!("Failed to prune token record {}: {}", record_name, e);
+                }
+                continue;
+            }
+            tokens.push(token);
+        }
+        tokens.sort_by_key(|t| std::cmp::Reverse(t.created_at));
+        Ok(tokens)
+    }
+
+    pub async fn revoke_token(&self, name: &str, id: &str) -> Result<(), AppError> {
+        let record_name = encode_k8sname(RESOURCE_TYPE_PREFIX_AUTHORIZATION_TOKEN, id);
+        let api = self.api();
+        let token = api
+            .get_opt(&record_name)
+            .await
+            .map_err(AppError::from)?
+            .filter(|x| is_managed_label(RESOURCE_TYPE_AUTHORIZATION_TOKEN, x.labels()))
+            .map(IssuedTokenData::try_from_secret)
+            .transpose()?
+            .filter(|x| x.record.authorization == name);
+        let Some(token) = token else {
+            return Err(AppError::NotFound(format!(
+                "Token {} not found in authorization {}",
+                id, name
+            )));
+        };
+        if !token.record.bound {
+            return Err(AppError::InvalidInput(format!(
+                "Token {} is not bound to its record and stays valid until it expires, delete the authorization to revoke it",
+                id
+            )));
+        }
+        api.delete(&record_name, &DeleteParams::default())
+            .await
+            .map_err(AppError::from)?;
+        Ok(())
     }
 }
@@ -1,38 +1,74 @@
 use std::{
-    collections::{BTreeMap, HashMap},
-    sync::LazyLock,
+    collections::{BTreeMap, HashMap, HashSet},
+    sync::{Arc, LazyLock},
 };
 
 use chrono::{DateTime, Duration, Utc};
-use k8s_openapi::api::core::v1::{ConfigMap, Container, EnvVar, Pod, PodSpec};
+use k8s_openapi::{
+    ByteString,
+    api::core::v1::{
+        ConfigMap, Container, EmptyDirVolumeSource, EnvVar, LocalObjectReference, Pod, PodSpec,
+        Secret, Volume,
+    },
+};
 use kube::{
     Api, Client, Resource, ResourceExt,
     api::{DeleteParams, ListParams, ObjectMeta, PostParams},
 };
-use proto::mcp::orchestrator::v1;
+use proto::mcp::orchestrator::v1::{self, AuthorizationType};
 use rmcp::transport::streamable_http_server::SessionId;
 
 use super::label_query::{LabelQuery, build_label_query};
 use super::labels::setup_labels;
 use crate::{
+    config::ImageAdmissionConfig,
     error::AppError,
     storage::{
         ResourceLimitStore, SecretData, SecretStore,
         labels::{
-            LABEL_SESSION_ID, is_managed_label, label_dependency, label_dependency_query,
-            label_dependency_tuple,
+            LABEL_SESSION_ID, LABEL_SESSION_SUBJECT_HASH, is_managed_label, label_dependency,
+            label_dependency_query, label_dependency_tuple,
         },
         resource_type::{
-            RESOURCE_TYPE_MCP_SERVER, RESOURCE_TYPE_MCP_TEMPLATE, RESOURCE_TYPE_NAMESPACE,
-            RESOURCE_TYPE_PREFIX_MCP_TEMPLATE, RESOURCE_TYPE_RESOURCE_LIMIT, RESOURCE_TYPE_SECRET,
+            RESOURCE_TYPE_AUTHORIZATION, RESOURCE_TYPE_MCP_SERVER, RESOURCE_TYPE_MCP_TEMPLATE,
+            RESOURCE_TYPE_NAMESPACE, RESOURCE_TYPE_PREFIX_MCP_TEMPLATE,
+            RESOURCE_TYPE_PREFIX_SECRET, RESOURCE_TYPE_RESOURCE_LIMIT, RESOURCE_TYPE_SECRET,
         },
+        scheduling_feasibility::pod_requests,
+        secret_backend::{SecretBackends, SecretRef},
+        secret_type::{KEY_DOCKER_CONFIG_JSON, SECRET_TYPE_DOCKER_CONFIG_JSON},
+        session_secret::resolve_session_secrets,
         store::KubeStore,
         store_authorization::{AuthorizationData, AuthorizationStore},
+        store_workspace::{subject_hash, workspace_pod_labels},
+        template_arg::{
+            arg_env_type_from_name, env_name_of, resolve_arg_env, validate_arg_env_spec,
+        },
+        template_claim::{
+            SessionIdentity, identity_annotations, resolve_claim_envs, validate_claim_envs,
+        },
+        template_container::{
+            MAIN_CONTAINER_NAME, build_init_containers, build_sidecars, main_container_percent,
+            scale_requirements, validate_containers,
+        },
+        template_egress::{ensure_egress_policy, validate_egress_policy},
+        template_image::admit_image,
+        template_package::{
+            ensure_package_cache, package_cache_envs, package_cache_volume, package_command,
+            validate_package,
+        },
+        template_scheduling::build_scheduling,
+        template_security::{resolve_security, validate_security_profile},
+        template_volume::{
+            build_volumes, resolve_volume_pattern, validate_mounts, validate_workspace,
+            workspace_volume,
+        },
         util_delete::{DeleteOption, DeleteResult},
         util_list::ListOption,
         util_name::{decode_k8sname, encode_k8sname},
         utils::{
             add_safe_finalizer, data_elem, del_safe_finalizer, interval_timeout, parse_data_elem,
+            parse_data_elem_or_default,
         },
     },
 };
@@ -52,8 +88,19 @@ const DATA_ARGS: &str = "args";
 const DATA_SECRET_ENVS: &str = "secret_env";
 const DATA_RESOURCE_LIMIT_NAME: &str = "resource_limit_name";
 const DATA_AUTHORIZATION_NAME: &str = "authorization_name";
+const DATA_AUTHORIZATION_NAMES: &str = "authorization_names";
 const DATA_VOLUME_MOUNTS: &str = "volume_mounts";
 const DATA_SECRET_MOUNTS: &str = "secret_mounts";
+const DATA_WORKSPACE: &str = "workspace";
+const DATA_ARG_ENV_SPECS: &str = "arg_env_specs";
+const DATA_CLAIM_ENVS: &str = "claim_envs";
+const DATA_PACKAGE: &str = "package";
+const DATA_INIT_CONTAINERS: &str = "init_containers";
+const DATA_SIDECARS: &str = "sidecars";
+const DATA_SECURITY: &str = "security";
+const DATA_EGRESS: &str = "egress";
+const DATA_IMAGE_PULL_SECRETS: &str = "image_pull_secrets";
+const DATA_SECRET_ROTATION: &str = "secret_rotation";
 
 fn data_env_var(name: &str) -> String {
     format!("env_{}", name)
@@ -92,6 +139,23 @@ fn assert_valid_arg_env_key(key: &str) -> Result<(), AppError> {
     }
 }
 
+/// Converts the `ENV_NAME: type` shorthand of `arg_envs` into an [`v1::ArgEnvSpec`].
+fn legacy_arg_env_spec(key: &str, value: &str) -> Result<v1::ArgEnvSpec, AppError> {
+    let (env_name, typename) = assert_valid_arg_env_value(key, value)?;
+    let (typename, optional) = match typename.strip_suffix('?') {
+        Some(typename) => (typename, true),
+        None => (typename.as_str(), false),
+    };
+    let r#type = arg_env_type_from_name(typename)
+        .ok_or_else(|| AppError::InvalidArgEnv(value.to_string()))?;
+    Ok(v1::ArgEnvSpec {
+        env_name: Some(env_name),
+        r#type: r#type.into(),
+        optional,
+        ..Default::default()
+    })
+}
+
 fn assert_valid_arg_env_value(key: &str, value: &str) -> Result<(String, String), AppError> {
     LAZY_ARG_ENV_VALUE
         .captures(value)
@@ -116,11 +180,22 @@ pub struct McpTemplateData {
     pub args: Vec<String>,
     pub envs: HashMap<String, String>,
     pub arg_envs: HashMap<String, String>,
+    pub arg_env_specs: HashMap<String, v1::ArgEnvSpec>,
     pub secret_envs: Vec<String>,
     pub resource_limit_name: String,
-    pub authorization_name: String,
+    /// Any one of them authorizes a session, the first provides the pod service account.
+    pub authorization_names: Vec<String>,
     pub volume_mounts: Vec<v1::VolumeMount>,
     pub secret_mounts: Vec<v1::SecretMount>,
+    pub workspace: Option<v1::WorkspaceConfig>,
+    pub claim_envs: HashMap<String, String>,
+    pub package: Option<v1::PackageSource>,
+    pub init_containers: Vec<v1::TemplateContainer>,
+    pub sidecars: Vec<v1::TemplateContainer>,
+    pub security: Option<v1::SecurityProfile>,
+    pub egress: Option<v1::EgressPolicy>,
+    pub image_pull_secrets: Vec<String>,
+    pub secret_rotation: v1::SecretRotationPolicy,
     pub created_at: DateTime<Utc>,
     pub deleted_at: Option<DateTime<Utc>>,
 }
@@ -132,9 +207,33 @@ impl McpTemplateData {
         let args: Vec<String> = parse_data_elem(&cm.data, DATA_ARGS)?;
         let secret_envs: Vec<String> = parse_data_elem(&cm.data, DATA_SECRET_ENVS)?;
         let resource_limit_name: String = parse_data_elem(&cm.data, DATA_RESOURCE_LIMIT_NAME)?;
-        let authorization_name: String = parse_data_elem(&cm.data, DATA_AUTHORIZATION_NAME)?;
+        let mut authorization_names: Vec<String> =
+            parse_data_elem_or_default(&cm.data, DATA_AUTHORIZATION_NAMES)?;
+        if authorization_names.is_empty() {
+            // 목록 도입 이전의 템플릿은 authorization 하나만 가짐
+            authorization_names.push(parse_data_elem(&cm.data, DATA_AUTHORIZATION_NAME)?);
+        }
         let volume_mounts: Vec<v1::VolumeMount> = parse_data_elem(&cm.data, DATA_VOLUME_MOUNTS)?;
         let secret_mounts: Vec<v1::SecretMount> = parse_data_elem(&cm.data, DATA_SECRET_MOUNTS)?;
+        let workspace: Option<v1::WorkspaceConfig> =
+            parse_data_elem_or_default(&cm.data, DATA_WORKSPACE)?;
+        let mut arg_env_specs: HashMap<String, v1::ArgEnvSpec> =
+            parse_data_elem_or_default(&cm.data, DATA_ARG_ENV_SPECS)?;
+        let claim_envs: HashMap<String, String> =
+            parse_data_elem_or_default(&cm.data, DATA_CLAIM_ENVS)?;
+        let package: Option<v1::PackageSource> =
+            parse_data_elem_or_default(&cm.data, DATA_PACKAGE)?;
+        let init_containers: Vec<v1::TemplateContainer> =
+            parse_data_elem_or_default(&cm.data, DATA_INIT_CONTAINERS)?;
+        let sidecars: Vec<v1::TemplateContainer> =
+            parse_data_elem_or_default(&cm.data, DATA_SIDECARS)?;
+        let security: Option<v1::SecurityProfile> =
+            parse_data_elem_or_default(&cm.data, DATA_SECURITY)?;
+        let egress: Option<v1::EgressPolicy> = parse_data_elem_or_default(&cm.data, DATA_EGRESS)?;
+        let image_pull_secrets: Vec<String> =
+            parse_data_elem_or_default(&cm.data, DATA_IMAGE_PULL_SECRETS)?;
+        let secret_rotation: v1::SecretRotationPolicy =
+            parse_data_elem_or_default(&cm.data, DATA_SECRET_ROTATION)?;
 
         let mut envs: HashMap<String, String> = HashMap::new();
         let mut arg_envs: HashMap<String, String> = HashMap::new();
@@ -148,6 +247,17 @@ impl McpTemplateData {
                 }
             }
         }
+        for (key, value) in arg_envs.iter() {
+            match legacy_arg_env_spec(key, value) {
+                Ok(spec) => {
+                    arg_env_specs.insert(key.clone(), spec);
+                }
+                Err(e) => {
+                    // to_pod 에서 다시 검증되어 세션 생성이 실패함
+                    tracing::warn!("Invalid arg_env {} of {}: {}", key, cm.name_any(), e);
+                }
+            }
+        }
 
         Ok(Self {
             namespace: cm.namespace().unwrap_or_else(|| "default".to_string()),
@@ -170,11 +280,21 @@ impl McpTemplateData {
             args,
             envs,
             arg_envs,
+            arg_env_specs,
             secret_envs,
             resource_limit_name,
-            authorization_name,
+            authorization_names,
             volume_mounts,
             secret_mounts,
+            workspace,
+            claim_envs,
+            package,
+            init_containers,
+            sidecars,
+            security,
+            egress,
+            image_pull_secrets,
+            secret_rotation,
             created_at: cm
                 .creation_timestamp()
                 .map(|x| x.0)
@@ -186,8 +306,7 @@ impl McpTemplateData {
 
     async fn load_secrets(
         store: SecretStore,
-        names: &Vec<String>,
-        mounts: &Vec<v1::SecretMount>,
+        names: &[String],
     ) -> Result<HashMap<String, SecretData>, AppError> {
         let mut secrets: HashMap<String, SecretData> = HashMap::new();
         for name in names {
@@ -202,43 +321,47 @@ impl McpTemplateData {
             };
             secrets.insert(name.clone(), secret);
         }
-        for mount in mounts {
-            let name = &mount.name;
-            if secrets.contains_key(name) {
-                continue;
-            }
-            let Some(secret) = store.get(name).await? else {
-                return Err(AppError::Internal(format!(
-                    "Secret {} not found for McpTemplate",
-                    name
-                )));
-            };
-            secrets.insert(name.clone(), secret);
-        }
         Ok(secrets)
     }
 
-    pub async fn get_authorization(
+    /// Secret references of envs and mounts, in the order they are resolved for a session.
+    fn secret_references(&self) -> impl Iterator<Item = &String> {
+        self.secret_envs
+            .iter()
+            .chain(self.secret_mounts.iter().map(|x| &x.name))
+    }
+
+    /// Authorizations of the template in the order they were declared.
+    pub async fn get_authorizations(
         &self,
         client: &KubeStore,
-    ) -> Result<AuthorizationData, AppError> {
+    ) -> Result<Vec<AuthorizationData>, AppError> {
         let store_auth = client.authorization(Some(self.namespace.clone()));
-        let Some(authorization) = store_auth.get(&self.authorization_name).await? else {
-            tracing::error!("Authorization {} not found", self.authorization_name);
-            return Err(AppError::Internal(format!(
-                "Authorization {} required by McpTemplate {}/{} not found",
-                self.authorization_name, self.namespace, self.name
-            )));
-        };
-        Ok(authorization)
+        let mut authorizations = Vec::with_capacity(self.authorization_names.len());
+        for name in &self.authorization_names {
+            let Some(authorization) = store_auth.get(name).await? else {
+                tracing::error!("Authorization {} not found", name);
+                return Err(AppError::Internal(format!(
+                    "Authorization {} required by McpTemplate {}/{} not found",
+                    name, self.namespace, self.name
+                )));
+            };
+            authorizations.push(authorization);
+        }
+        Ok(authorizations)
     }
 
+    /// Renders the session pod with the Secrets it owns for values of external secret backends.
+    /// A `dry_run` pod creates no claims, workspace and package cache are mounted as emptyDir
+    /// volumes instead.
     pub async fn to_pod(
         &self,
         session_id: &SessionId,
         client: &KubeStore,
         args: HashMap<String, String>,
-    ) -> Result<(Pod, AuthorizationData), AppError> {
+        identity: Option<&SessionIdentity>,
+        dry_run: bool,
+    ) -> Result<(Pod, Vec<Secret>), AppError> {
         let resource_limit_store = client.resource_limits();
         let _store_auth = client.authorization(Some(self.namespace.clone()));
 
@@ -250,14 +373,23 @@ impl McpTemplateData {
                 self.resource_limit_name, self.namespace, self.name
             )));
         };
-        let secrets = McpTemplateData::load_secrets(
-            client.secrets(Some(self.namespace.clone())),
-            &self.secret_envs,
-            &self.secret_mounts,
+        let mut session_secrets = resolve_session_secrets(
+            client,
+            &self.namespace,
+            session_id,
+            identity.map(|x| x.subject.as_str()),
+            dry_run,
+            self.secret_references(),
         )
         .await?;
 
-        let authorization = self.get_authorization(client).await?;
+        let authorizations = self.get_authorizations(client).await?;
+        let Some(authorization) = authorizations.first() else {
+            return Err(AppError::Internal(format!(
+                "McpTemplate {}/{} has no authorization",
+                self.namespace, self.name
+            )));
+        };
 
         let mut envs = HashMap::new();
 
@@ -272,21 +404,21 @@ impl McpTemplateData {
             );
         }
 
-        for secret_name in self.secret_envs.iter() {
-            let secret = secrets.get(secret_name).ok_or_else(|| {
+        for secret_ref in self.secret_envs.iter() {
+            let (secret_name, keys) = session_secrets.refs.get(secret_ref).ok_or_else(|| {
                 AppError::Internal(format!(
                     "Secret {} not found for McpTemplate {}/{}",
-                    secret_name, self.namespace, self.name
+                    secret_ref, self.namespace, self.name
                 ))
             })?;
-            for (key, _) in secret.raw.data.as_ref().unwrap_or(&BTreeMap::new()).iter() {
+            for key in keys {
                 envs.insert(
                     key.clone(),
                     EnvVar {
                         name: key.clone(),
                         value_from: Some(k8s_openapi::api::core::v1::EnvVarSource {
                             secret_key_ref: Some(k8s_openapi::api::core::v1::SecretKeySelector {
-                                name: secret.raw.name_any(),
+                                name: secret_name.clone(),
                                 key: key.clone(),
                                 ..Default::default()
                             }),
@@ -299,35 +431,138 @@ impl McpTemplateData {
         }
 
         for (arg_key, arg_val) in self.arg_envs.iter() {
-            let (env_name, env_type) = assert_valid_arg_env_value(arg_key, arg_val)?;
-            match env_type.as_str() {
-                "string" => {
-                    if !args.contains_key(arg_key.as_str()) {
-                        return Err(AppError::InvalidArgEnv(format!(
-                            "Argument {} not provided for McpTemplate {}/{}",
-                            arg_key, self.namespace, self.name
-                        )));
-                    }
-                }
-                "string?" => {}
-                _ => {
-                    return Err(AppError::InvalidArgEnv(arg_val.clone()));
-                }
+            if !self.arg_env_specs.contains_key(arg_key) {
+                return Err(AppError::InvalidArgEnv(arg_val.clone()));
             }
-            let Some(real_val) = args.get(arg_key).cloned() else {
+        }
+
+        let mut secret_args = BTreeMap::new();
+        for (arg_key, spec) in self.arg_env_specs.iter() {
+            let Some(value) =
+                resolve_arg_env(arg_key, spec, args.get(arg_key).map(String::as_str))?
+            else {
                 continue;
             };
+            let env_name = env_name_of(arg_key, spec);
+            if spec.secret {
+                secret_args.insert(env_name, ByteString(value.into_bytes()));
+                continue;
+            }
             envs.insert(
                 env_name.clone(),
                 EnvVar {
                     name: env_name,
-                    value: Some(real_val),
+                    value: Some(value),
                     ..Default::default()
                 },
             );
         }
+        // secret 인자는 Pod 스펙에 평문으로 남지 않도록 세션 Secret 으로 전달
+        if !secret_args.is_empty() {
+            let keys = secret_args.keys().cloned().collect::<Vec<_>>();
+            let secret_name = session_secrets.own(&self.namespace, session_id, secret_args);
+            for key in keys {
+                envs.insert(
+                    key.clone(),
+                    EnvVar {
+                        name: key.clone(),
+                        value_from: Some(k8s_openapi::api::core::v1::EnvVarSource {
+                            secret_key_ref: Some(k8s_openapi::api::core::v1::SecretKeySelector {
+                                name: secret_name.clone(),
+                                key,
+                                ..Default::default()
+                            }),
+                            ..Default::default()
+                        }),
+                        ..Default::default()
+                    },
+                );
+            }
+        }
 
-        let envs = envs.into_values().collect();
+        let mut annotations = BTreeMap::new();
+        if let Some(identity) = identity {
+            let resolved = resolve_claim_envs(&self.claim_envs, identity);
+            for (env_name, value) in resolved.iter() {
+                envs.insert(
+                    env_name.clone(),
+                    EnvVar {
+                        name: env_name.clone(),
+                        value: Some(value.clone()),
+                        ..Default::default()
+                    },
+                );
+            }
+            annotations.extend(identity_annotations(identity, &resolved));
+        }
+
+        let mut envs: Vec<EnvVar> = envs.into_values().collect();
+        let (mut volumes, mut volume_mounts) = build_volumes(
+            &self.volume_mounts,
+            &self.secret_mounts,
+            &resource_limit.volumes,
+            |name| {
+                session_secrets
+                    .secret_name(name)
+                    .map(str::to_string)
+                    .unwrap_or_else(|| encode_k8sname(RESOURCE_TYPE_PREFIX_SECRET, name))
+            },
+        )?;
+        let mut labels = setup_labels(RESOURCE_TYPE_MCP_SERVER, std::iter::empty())
+            .chain(vec![(LABEL_SESSION_ID.to_string(), session_id.to_string())])
+            .chain(label_dependency(RESOURCE_TYPE_MCP_TEMPLATE, &self.name))
+            .collect::<BTreeMap<_, _>>();
+        if let Some(identity) = identity {
+            labels.insert(
+                LABEL_SESSION_SUBJECT_HASH.to_string(),
+                subject_hash(&identity.subject),
+            );
+        }
+        if let Some(workspace) = &self.workspace {
+            let Some(identity) = identity else {
+                return Err(AppError::InvalidInput(format!(
+                    "McpTemplate {}/{} requires an authenticated subject for its workspace",
+                    self.namespace, self.name
+                )));
+            };
+            let (_, limit) = resolve_volume_pattern(&workspace.volume, &resource_limit.volumes)?;
+            if dry_run {
+                let (volume, volume_mount) = workspace_volume("", &workspace.mount_path);
+                volumes.push(ephemeral_volume(volume));
+                volume_mounts.push(volume_mount);
+            } else {
+                let workspace_data = client
+                    .workspaces(Some(self.namespace.clone()))
+                    .ensure(self, workspace, limit, &identity.subject, session_id)
+                    .await?;
+                let (volume, volume_mount) =
+                    workspace_volume(&workspace_data.raw.name_any(), &workspace.mount_path);
+                volumes.push(volume);
+                volume_mounts.push(volume_mount);
+                labels.extend(workspace_pod_labels(&self.name, &identity.subject));
+            }
+        }
+        let package_cache = self
+            .package
+            .as_ref()
+            .and_then(|package| Some((package, package.cache_volume.as_ref()?)));
+        if let Some((package, cache_volume)) = package_cache {
+            let (volume_name, limit) =
+                resolve_volume_pattern(cache_volume, &resource_limit.volumes)?;
+            if dry_run {
+                let (volume, volume_mount) = package_cache_volume("");
+                volumes.push(ephemeral_volume(volume));
+                volume_mounts.push(volume_mount);
+            } else {
+                let claim_name =
+                    ensure_package_cache(client.to_client(), &self.namespace, volume_name, limit)
+                        .await?;
+                let (volume, volume_mount) = package_cache_volume(&claim_name);
+                volumes.push(volume);
+                volume_mounts.push(volume_mount);
+            }
+            envs.extend(package_cache_envs(package));
+        }
         let requirement = resource_limit.to_resource_requirements();
         tracing::debug!(
             "Creating Pod for session {} with resource limit {:?}",
@@ -335,42 +570,178 @@ impl McpTemplateData {
             requirement
         );
 
+        let mut init_containers =
+            build_init_containers(&self.init_containers, &requirement, &self.volume_mounts)?;
+        let sidecars = build_sidecars(&self.sidecars, &requirement, &self.volume_mounts)?;
+        let main_requirement =
+            scale_requirements(&requirement, main_container_percent(&self.sidecars))?;
+
+        let security = resolve_security(self.security.as_ref(), resource_limit.security.as_ref())?;
+        if let Some((volume, volume_mount)) = &security.tmp_volume {
+            volumes.push(volume.clone());
+            volume_mounts.push(volume_mount.clone());
+            // 읽기 전용 루트 파일시스템에서 npm, uv 등이 캐시를 쓸 수 있도록
+            if !envs.iter().any(|env| env.name == "HOME") {
+                envs.push(EnvVar {
+                    name: "HOME".to_string(),
+                    value: Some(volume_mount.mount_path.clone()),
+                    ..Default::default()
+                });
+            }
+        }
+
+        let mut containers: Vec<Container> = std::iter::once(Container {
+            name: MAIN_CONTAINER_NAME.to_string(),
+            image: Some(self.image.clone()),
+            command: Some(self.command.clone()),
+            args: Some(self.args.clone()),
+            stdin: Some(true),
+            tty: Some(false),
+            env: Some(envs),
+            resources: Some(main_requirement),
+            volume_mounts: Some(volume_mounts),
+            ..Default::default()
+        })
+        .chain(sidecars)
+        .collect();
+        for container in containers.iter_mut().chain(init_containers.iter_mut()) {
+            container.security_context = security.container.clone();
+            if container.name == MAIN_CONTAINER_NAME {
+                continue;
+            }
+            if let Some((_, volume_mount)) = &security.tmp_volume {
+                container
+                    .volume_mounts
+                    .get_or_insert_default()
+                    .push(volume_mount.clone());
+            }
+        }
+
+        let scheduling = build_scheduling(
+            &resource_limit,
+            &self.name,
+            identity.map(|x| x.subject.as_str()),
+        );
+
         let pod_spec = PodSpec {
-            containers: vec![Container {
-                name: "main".to_string(),
-                image: Some(self.image.clone()),
-                command: Some(self.command.clone()),
-                args: Some(self.args.clone()),
-                stdin: Some(true),
-                tty: Some(false),
-                env: Some(envs),
-                resources: Some(requirement),
-                ..Default::default()
-            }],
+            containers,
+            init_containers: Some(init_containers).filter(|x| !x.is_empty()),
+            volumes: Some(volumes),
             service_account_name: authorization.sa_name.clone(),
+            image_pull_secrets: Some(
+                image_pull_secret_names(
+                    &self.image_pull_secrets,
+                    &resource_limit.image_pull_secrets,
+                )
+                .into_iter()
+                .map(|name| LocalObjectReference {
+                    name: encode_k8sname(RESOURCE_TYPE_PREFIX_SECRET, &name),
+                })
+                .collect::<Vec<_>>(),
+            )
+            .filter(|x| !x.is_empty()),
+            automount_service_account_token: (security.hardened
+                && authorization.r#type == AuthorizationType::Anonymous)
+                .then_some(false),
+            security_context: security.pod,
+            runtime_class_name: security.runtime_class_name,
             node_selector: resource_limit.node_selector.clone(),
-            affinity: resource_limit.node_affinity.clone(),
+            affinity: scheduling.affinity,
+            tolerations: scheduling.tolerations,
+            priority_class_name: scheduling.priority_class_name,
+            topology_spread_constraints: scheduling.topology_spread_constraints,
             ..Default::default()
         };
 
-        Ok((
-            Pod {
-                metadata: ObjectMeta {
-                    name: Some(session_id.to_string()),
-                    namespace: Some(self.namespace.clone()),
-                    labels: Some(
-                        setup_labels(RESOURCE_TYPE_MCP_SERVER, std::iter::empty())
-                            .chain(vec![(LABEL_SESSION_ID.to_string(), session_id.to_string())])
-                            .collect(),
-                    ),
-                    owner_references: Some(vec![self.raw.controller_owner_ref(&()).unwrap()]),
-                    ..Default::default()
-                },
-                spec: Some(pod_spec),
+        let pod = Pod {
+            metadata: ObjectMeta {
+                name: Some(session_id.to_string()),
+                namespace: Some(self.namespace.clone()),
+                labels: Some(labels),
+                annotations: Some(annotations),
+                owner_references: self.raw.controller_owner_ref(&()).map(|x| vec![x]),
                 ..Default::default()
             },
-            authorization,
-        ))
+            spec: Some(pod_spec),
+            ..Default::default()
+        };
+        Ok((pod, session_secrets.owned))
+    }
+
+    /// Resource requests of the session pod, computed without rendering it so the session quota
+    /// is admitted before any claim or secret of the session is created.
+    pub async fn session_requests(
+        &self,
+        client: &KubeStore,
+    ) -> Result<BTreeMap<String, f64>, AppError> {
+        let Some(resource_limit) = client
+            .resource_limits()
+            .get(&self.resource_limit_name)
+            .await?
+        else {
+            return Err(AppError::Internal(format!(
+                "ResourceLimit {} required by McpTemplate {}/{} not found",
+                self.resource_limit_name, self.namespace, self.name
+            )));
+        };
+        let requirement = resource_limit.to_resource_requirements();
+        let init_containers =
+            build_init_containers(&self.init_containers, &requirement, &self.volume_mounts)?;
+        let containers = std::iter::once(Container {
+            name: MAIN_CONTAINER_NAME.to_string(),
+            resources: Some(scale_requirements(
+                &requirement,
+                main_container_percent(&self.sidecars),
+            )?),
+            ..Default::default()
+        })
+        .chain(build_sidecars(
+            &self.sidecars,
+            &requirement,
+            &self.volume_mounts,
+        )?)
+        .collect();
+        Ok(pod_requests(&PodSpec {
+            containers,
+            init_containers: Some(init_containers),
+            ..Default::default()
+        }))
+    }
+}
+
+/// Pull secrets of the template followed by the ones of the resource limit.
+fn image_pull_secret_names(template: &[String], resource_limit: &[String]) -> Vec<String> {
+    let mut names = Vec::new();
+    for name in template.iter().chain(resource_limit) {
+        if !names.contains(name) {
+            names.push(name.clone());
+        }
+    }
+    names
+}
+
+fn validate_authorization_names(names: &[String]) -> Result<(), AppError> {
+    if names.is_empty() {
+        return Err(AppError::InvalidInput(
+            "At least one authorization is required".to_string(),
+        ));
+    }
+    for (i, name) in names.iter().enumerate() {
+        if names[..i].contains(name) {
+            return Err(AppError::InvalidInput(format!(
+                "Authorization {} is referenced more than once",
+                name
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn ephemeral_volume(volume: Volume) -> Volume {
+    Volume {
+        persistent_volume_claim: None,
+        empty_dir: Some(EmptyDirVolumeSource::default()),
+        ..volume
     }
 }
 
@@ -378,19 +749,32 @@ pub struct McpTemplateStore {
     client: Client,
     target_namespace: String,
     default_namespace: String,
+    image_admission: Arc<ImageAdmissionConfig>,
+    secret_backends: SecretBackends,
 }
 
+#[derive(Clone)]
 pub struct McpTemplateCreate {
     pub image: String,
     pub command: Vec<String>,
     pub args: Vec<String>,
     pub envs: HashMap<String, String>,
     pub arg_envs: HashMap<String, String>,
+    pub arg_env_specs: HashMap<String, v1::ArgEnvSpec>,
     pub secret_envs: Vec<String>,
     pub resource_limit_name: String,
-    pub authorization_name: String,
+    pub authorization_names: Vec<String>,
     pub volume_mounts: Vec<v1::VolumeMount>,
     pub secret_mounts: Vec<v1::SecretMount>,
+    pub workspace: Option<v1::WorkspaceConfig>,
+    pub claim_envs: HashMap<String, String>,
+    pub package: Option<v1::PackageSource>,
+    pub init_containers: Vec<v1::TemplateContainer>,
+    pub sidecars: Vec<v1::TemplateContainer>,
+    pub security: Option<v1::SecurityProfile>,
+    pub egress: Option<v1::EgressPolicy>,
+    pub image_pull_secrets: Vec<String>,
+    pub secret_rotation: v1::SecretRotationPolicy,
 }
 
 impl McpTemplateStore {
@@ -398,11 +782,15 @@ impl McpTemplateStore {
         client: Client,
         target_namespace: impl Into<String>,
         default_namespace: impl Into<String>,
+        image_admission: Arc<ImageAdmissionConfig>,
+        secret_backends: SecretBackends,
     ) -> Self {
         Self {
             client,
             target_namespace: target_namespace.into(),
             default_namespace: default_namespace.into(),
+            image_admission,
+            secret_backends,
         }
     }
 
@@ -415,13 +803,110 @@ impl McpTemplateStore {
         name: &str,
         labels: L,
         data: McpTemplateCreate,
+    ) -> Result<McpTemplateData, AppError> {
+        self.create_with(name, labels, data, &PostParams::default(), None)
+            .await
+    }
+
+    /// Replaces `current` in place, guarded by its resourceVersion.
+    pub async fn replace<L: Iterator<Item = (String, String)>>(
+        &self,
+        current: &McpTemplateData,
+        labels: L,
+        data: McpTemplateCreate,
+    ) -> Result<McpTemplateData, AppError> {
+        self.create_with(
+            &current.name,
+            labels,
+            data,
+            &PostParams::default(),
+            Some(current),
+        )
+        .await
+    }
+
+    /// Validates the template with a server-side dry-run, nothing is persisted.
+    pub async fn dry_run_create<L: Iterator<Item = (String, String)>>(
+        &self,
+        name: &str,
+        labels: L,
+        data: McpTemplateCreate,
+    ) -> Result<McpTemplateData, AppError> {
+        let pp = PostParams {
+            dry_run: true,
+            ..Default::default()
+        };
+        self.create_with(name, labels, data, &pp, None).await
+    }
+
+    async fn create_with<L: Iterator<Item = (String, String)>>(
+        &self,
+        name: &str,
+        labels: L,
+        mut data: McpTemplateCreate,
+        pp: &PostParams,
+        current: Option<&McpTemplateData>,
     ) -> Result<McpTemplateData, AppError> {
         let name = encode_k8sname(RESOURCE_TYPE_PREFIX_MCP_TEMPLATE, name);
         let api = self.api();
         // 검증
         for (arg_key, arg_val) in &data.arg_envs {
             assert_valid_arg_env_key(arg_key)?;
-            assert_valid_arg_env_value(arg_key, arg_val)?;
+            legacy_arg_env_spec(arg_key, arg_val)?;
+        }
+        for (arg_key, spec) in &data.arg_env_specs {
+            assert_valid_arg_env_key(arg_key)?;
+            if data.arg_envs.contains_key(arg_key) {
+                return Err(AppError::InvalidInput(format!(
+                    "arg_env {} is declared in both arg_envs and arg_env_specs",
+                    arg_key
+                )));
+            }
+            validate_arg_env_spec(arg_key, spec)?;
+        }
+        validate_claim_envs(&data.claim_envs)?;
+        validate_containers(&data.init_containers, &data.sidecars, &data.volume_mounts)?;
+        if let Some(security) = &data.security {
+            validate_security_profile(security)?;
+        }
+        if let Some(egress) = &data.egress {
+            validate_egress_policy(egress)?;
+        }
+        let command = match &data.package {
+            Some(package) => {
+                validate_package(package)?;
+                if !data.command.is_empty() {
+                    return Err(AppError::InvalidInput(
+                        "command is generated for package templates, use args instead".to_string(),
+                    ));
+                }
+                if data.image.is_empty() {
+                    return Err(AppError::InvalidInput(format!(
+                        "Runner image of package {} is required",
+                        package.name
+                    )));
+                }
+                package_command(package)
+            }
+            None => data.command.clone(),
+        };
+        let arg_env_names = data
+            .arg_env_specs
+            .iter()
+            .map(|(key, spec)| env_name_of(key, spec))
+            .chain(data.arg_envs.iter().filter_map(|(key, value)| {
+                assert_valid_arg_env_value(key, value)
+                    .ok()
+                    .map(|(name, _)| name)
+            }))
+            .collect::<HashSet<_>>();
+        for env_name in data.claim_envs.keys() {
+            if data.envs.contains_key(env_name) || arg_env_names.contains(env_name) {
+                return Err(AppError::InvalidInput(format!(
+                    "Claim env {} conflicts with another env of the template",
+                    env_name
+                )));
+            }
         }
 
         //
@@ -437,28 +922,135 @@ impl McpTemplateStore {
                     data.resource_limit_name, self.target_namespace, name
                 ))
             })?;
+        validate_mounts(
+            &data.volume_mounts,
+            &data.secret_mounts,
+            &resource_limit.volumes,
+        )?;
+        if let Some(cache_volume) = data.package.as_ref().and_then(|x| x.cache_volume.as_ref()) {
+            resolve_volume_pattern(cache_volume, &resource_limit.volumes)?;
+        }
+        resolve_security(data.security.as_ref(), resource_limit.security.as_ref())?;
+        let pull_secret_names =
+            image_pull_secret_names(&data.image_pull_secrets, &resource_limit.image_pull_secrets);
+        if let Some(name) = pull_secret_names.iter().find(|x| x.contains(':')) {
+            return Err(AppError::InvalidInput(format!(
+                "Image pull secret {} must be a Kubernetes secret",
+                name
+            )));
+        }
+        // 외부 백엔드의 값은 세션 생성 시점에 읽으므로 백엔드 설정 여부만 확인
+        let mut kubernetes_secret_names = pull_secret_names.clone();
+        for reference in data
+            .secret_envs
+            .iter()
+            .chain(data.secret_mounts.iter().map(|x| &x.name))
+        {
+            let secret_ref = SecretRef::parse(reference)?;
+            // 사용자 secret 은 세션을 여는 사용자 기준으로 해석
+            if secret_ref.is_user() {
+                continue;
+            }
+            self.secret_backends.check(&secret_ref)?;
+            if secret_ref.is_kubernetes() {
+                kubernetes_secret_names.push(secret_ref.path);
+            }
+        }
         let secrets = McpTemplateData::load_secrets(
             SecretStore::new(self.client.clone(), self.target_namespace.clone()),
-            &data.secret_envs,
-            &data.secret_mounts,
+            &kubernetes_secret_names,
         )
         .await?;
+        for name in &pull_secret_names {
+            let secret_type = secrets.get(name).and_then(|x| x.raw.type_.as_deref());
+            if secret_type != Some(SECRET_TYPE_DOCKER_CONFIG_JSON) {
+                return Err(AppError::InvalidInput(format!(
+                    "Image pull secret {} must be a {} secret",
+                    name, SECRET_TYPE_DOCKER_CONFIG_JSON
+                )));
+            }
+        }
+        // private registry 의 digest 조회에 pod 와 같은 pull secret 을 사용
+        let docker_configs = pull_secret_names
+            .iter()
+            .filter_map(|name| {
+                secrets
+                    .get(name)?
+                    .raw
+                    .data
+                    .as_ref()?
+                    .get(KEY_DOCKER_CONFIG_JSON)
+            })
+            .filter_map(|x| String::from_utf8(x.0.clone()).ok())
+            .collect::<Vec<_>>();
+        validate_authorization_names(&data.authorization_names)?;
         let store_authorization =
             AuthorizationStore::new(self.client.clone(), self.target_namespace.clone());
-        let _authorization = store_authorization
-            .get(&data.authorization_name)
-            .await?
-            .ok_or_else(|| {
-                AppError::NotFound(format!(
-                    "Authorization {} required by McpTemplate {}/{} not found",
-                    data.authorization_name, self.target_namespace, name
-                ))
-            })?;
+        let mut anonymous = None;
+        for authorization_name in &data.authorization_names {
+            let authorization = store_authorization
+                .get(authorization_name)
+                .await?
+                .ok_or_else(|| {
+                    AppError::NotFound(format!(
+                        "Authorization {} required by McpTemplate {}/{} not found",
+                        authorization_name, self.target_namespace, name
+                    ))
+                })?;
+            if authorization.r#type == AuthorizationType::Anonymous {
+                anonymous = Some(authorization.name);
+            }
+        }
+        if let Some(anonymous) = &anonymous {
+            // anonymous 는 모든 요청을 통과시키므로 다른 authorization 과 함께 쓰면 의미가 없음
+            if data.authorization_names.len() > 1 {
+                return Err(AppError::InvalidInput(format!(
+                    "Authorization {} is anonymous and can't be combined with other authorizations",
+                    anonymous
+                )));
+            }
+            if !data.claim_envs.is_empty() {
+                return Err(AppError::InvalidInput(format!(
+                    "Claim envs require an authenticated user, Authorization {} is anonymous",
+                    anonymous
+                )));
+            }
+        }
+        if let Some(workspace) = &data.workspace {
+            validate_workspace(
+                workspace,
+                &data.volume_mounts,
+                &data.secret_mounts,
+                &resource_limit.volumes,
+                anonymous.as_deref(),
+            )?;
+        }
+
+        data.image = admit_image(
+            &self.image_admission,
+            &self.target_namespace,
+            &data.image,
+            &docker_configs,
+        )
+        .await?;
+        for container in data.init_containers.iter_mut().chain(&mut data.sidecars) {
+            container.image = admit_image(
+                &self.image_admission,
+                &self.target_namespace,
+                &container.image,
+                &docker_configs,
+            )
+            .await?;
+        }
 
-        let configmap = ConfigMap {
+        let authorization_labels = data
+            .authorization_names
+            .iter()
+            .map(|name| label_dependency_tuple(RESOURCE_TYPE_AUTHORIZATION, name));
+        let mut configmap = ConfigMap {
             metadata: ObjectMeta {
                 namespace: Some(self.target_namespace.clone()),
-                name: Some(name),
+                name: Some(name.clone()),
                 labels: Some(
                     setup_labels(RESOURCE_TYPE_MCP_TEMPLATE, labels)
                         .chain(label_dependency(
@@ -474,6 +1066,7 @@ impl McpTemplateStore {
                                 .keys()
                                 .map(|name| label_dependency_tuple(RESOURCE_TYPE_SECRET, name)),
                         )
+                        .chain(authorization_labels)
                         .collect(),
                 ),
                 ..Default::default()
@@ -481,13 +1074,25 @@ impl McpTemplateStore {
             data: Some(
                 vec![
                     data_elem(DATA_IMAGE, &data.image)?,
-                    data_elem(DATA_COMMAND, &data.command)?,
+                    data_elem(DATA_COMMAND, &command)?,
                     data_elem(DATA_ARGS, &data.args)?,
                     data_elem(DATA_SECRET_ENVS, &data.secret_envs)?,
                     data_elem(DATA_RESOURCE_LIMIT_NAME, &data.resource_limit_name)?,
-                    data_elem(DATA_AUTHORIZATION_NAME, &data.authorization_name)?,
+                    // 이전 버전 호환용
+                    data_elem(DATA_AUTHORIZATION_NAME, &data.authorization_names[0])?,
+                    data_elem(DATA_AUTHORIZATION_NAMES, &data.authorization_names)?,
                     data_elem(DATA_VOLUME_MOUNTS, &data.volume_mounts)?,
                     data_elem(DATA_SECRET_MOUNTS, &data.secret_mounts)?,
+                    data_elem(DATA_WORKSPACE, &data.workspace)?,
+                    data_elem(DATA_ARG_ENV_SPECS, &data.arg_env_specs)?,
+                    data_elem(DATA_CLAIM_ENVS, &data.claim_envs)?,
+                    data_elem(DATA_PACKAGE, &data.package)?,
+                    data_elem(DATA_INIT_CONTAINERS, &data.init_containers)?,
+                    data_elem(DATA_SIDECARS, &data.sidecars)?,
+                    data_elem(DATA_SECURITY, &data.security)?,
+                    data_elem(DATA_EGRESS, &data.egress)?,
+                    data_elem(DATA_IMAGE_PULL_SECRETS, &data.image_pull_secrets)?,
+                    data_elem(DATA_SECRET_ROTATION, &data.secret_rotation)?,
                 ]
                 .into_iter()
                 .chain(
@@ -505,10 +1110,47 @@ impl McpTemplateStore {
             ..Default::default()
         };
 
-        api.create(&PostParams::default(), &configmap)
-            .await
-            .map_err(AppError::from)
-            .and_then(McpTemplateData::try_from_config_map)
+        let configmap = match current {
+            Some(current) => {
+                configmap.metadata.resource_version = current.raw.resource_version();
+                configmap.metadata.finalizers = current.raw.metadata.finalizers.clone();
+                api.replace(&name, pp, &configmap).await
+            }
+            None => api.create(pp, &configmap).await,
+        }
+        .map_err(AppError::from)?;
+        if data.egress.is_some() || current.is_some() {
+            let template_name =
+                decode_k8sname(RESOURCE_TYPE_PREFIX_MCP_TEMPLATE, &configmap.name_any())
+                    .unwrap_or_default();
+            let egress = data.egress.as_ref();
+            if let Err(e) =
+                ensure_egress_policy(self.client.clone(), &configmap, &template_name, egress, pp)
+                    .await
+            {
+                // 정책 없이 세션이 열리지 않도록 템플릿을 되돌림
+                if !pp.dry_run {
+                    let rolled_back = match current {
+                        Some(current) => {
+                            let mut previous = current.raw.clone();
+                            previous.metadata.resource_version = configmap.resource_version();
+                            api.replace(&name, &PostParams::default(), &previous)
+                                .await
+                                .map(|_| ())
+                        }
+                        None => api
+                            .delete(&name, &DeleteParams::default())
+                            .await
+                            .map(|_| ()),
+                    };
+                    if let Err(e) = rolled_back {
+                        tracing::error!("Failed to roll back McpTemplate {}: {}", name, e);
+                    }
+                }
+                return Err(e);
+            }
+        }
+        McpTemplateData::try_from_config_map(configmap)
     }
 
     pub async fn get(&self, name: &str) -> Result<Option<McpTemplateData>, AppError> {
@@ -623,3 +1265,15 @@ impl McpTemplateStore {
         Ok(!list.items.is_empty())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_authorization_names() {
+        assert!(validate_authorization_names(&["oidc".to_string(), "ci".to_string()]).is_ok());
+        assert!(validate_authorization_names(&[]).is_err());
+        assert!(validate_authorization_names(&["ci".to_string(), "ci".to_string()]).is_err());
+    }
+}
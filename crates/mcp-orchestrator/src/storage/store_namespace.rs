@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use chrono::{DateTime, Duration, Utc};
 use k8s_openapi::api::core::v1::{Namespace, Pod, Secret};
@@ -6,16 +6,20 @@ use kube::{
     Api, Client, Resource, ResourceExt,
     api::{DeleteParams, ListParams, ObjectMeta, PostParams},
 };
-use proto::mcp::orchestrator::v1::AuthorizationType;
+use proto::mcp::orchestrator::v1::{AuthorizationType, SessionQuota};
 
 use super::label_query::LabelQuery;
 use super::labels::{LABEL_MANAGED_BY, LABEL_MANAGED_BY_VALUE, setup_labels};
 use crate::{
     error::AppError,
     storage::{
+        ResourceLimitData, ResourceLimitStore,
+        annotations::{ANNOTATION_DEFAULT_RESOURCE_LIMIT, ANNOTATION_SESSION_QUOTA},
         label_query::build_label_query,
         labels::{LABEL_MANAGED_BY_QUERY, LABEL_TYPE_OF, is_managed_label},
+        namespace_guard::{NamespaceGuardSpec, reconcile_guards, validate_resource_quota},
         resource_type::RESOURCE_TYPE_NAMESPACE,
+        session_quota::{is_unlimited, namespace_quota, validate_quota},
         store_authorization::AuthorizationStore,
         util_delete::{DeleteOption, DeleteResult},
         util_list::ListOption,
@@ -28,6 +32,8 @@ pub struct NamespaceData {
     pub raw: Namespace,
     pub name: String,
     pub labels: HashMap<String, String>,
+    pub quota: SessionQuota,
+    pub default_resource_limit: Option<String>,
     pub created_at: DateTime<Utc>,
     pub deleted_at: Option<DateTime<Utc>>,
 }
@@ -41,6 +47,11 @@ impl NamespaceData {
                 .iter()
                 .map(|(k, v)| (k.clone(), v.clone()))
                 .collect(),
+            quota: namespace_quota(&ns),
+            default_resource_limit: ns
+                .annotations()
+                .get(ANNOTATION_DEFAULT_RESOURCE_LIMIT)
+                .cloned(),
             created_at: ns
                 .creation_timestamp()
                 .map(|x| x.0)
@@ -156,33 +167,68 @@ impl NamespaceStore {
         &self,
         name: &str,
         labels: L,
+        quota: Option<&SessionQuota>,
+        guards: &NamespaceGuardSpec,
     ) -> Result<NamespaceData, AppError> {
+        let quota = quota.filter(|x| !is_unlimited(x));
+        if let Some(quota) = quota {
+            validate_quota(quota)?;
+        }
+        let default_resource_limit = self.resolve_guards(guards).await?;
+        let mut annotations = BTreeMap::new();
+        if let Some(quota) = quota {
+            annotations.insert(
+                ANNOTATION_SESSION_QUOTA.to_string(),
+                serde_json::to_string(quota)?,
+            );
+        }
+        if let Some(resource_limit) = &default_resource_limit {
+            annotations.insert(
+                ANNOTATION_DEFAULT_RESOURCE_LIMIT.to_string(),
+                resource_limit.name.clone(),
+            );
+        }
         let store_at = AuthorizationStore::new(self.client.clone(), name.to_string());
         let namespace = Namespace {
             metadata: ObjectMeta {
                 name: Some(name.to_string()),
                 labels: Some(setup_labels(RESOURCE_TYPE_NAMESPACE, labels).collect()),
+                annotations: Some(annotations).filter(|x| !x.is_empty()),
                 ..Default::default()
             },
             ..Default::default()
         };
 
-        let ns = self
+        let raw = self
             .api
             .create(&PostParams::default(), &namespace)
             .await
-            .map(NamespaceData::from_namespace)
             .map_err(AppError::from)?;
-
-        store_at
-            .create(
-                "anonymous",
-                vec![].into_iter(),
-                AuthorizationType::Anonymous,
-                &serde_json::json!({}),
+        let populated = async {
+            store_at
+                .create(
+                    "anonymous",
+                    vec![].into_iter(),
+                    AuthorizationType::Anonymous,
+                    &serde_json::json!({}),
+                )
+                .await?;
+            reconcile_guards(
+                self.client.clone(),
+                &raw,
+                guards.resource_quota.as_ref(),
+                default_resource_limit.as_ref(),
             )
-            .await?;
-        Ok(ns)
+            .await
+        };
+        if let Err(e) = populated.await {
+            // 일부만 만들어진 namespace 가 남으면 재시도가 AlreadyExists 로 실패하므로 삭제
+            if let Err(e) = self.api.delete(name, &DeleteParams::default()).await {
+                tracing::error!("Failed to roll back namespace {}: {}", name, e);
+            }
+            return Err(e);
+        }
+        Ok(NamespaceData::from_namespace(raw))
     }
 
     pub async fn get(&self, name: &str) -> Result<Option<NamespaceData>, AppError> {
@@ -200,6 +246,79 @@ impl NamespaceStore {
             .map_err(AppError::from)
     }
 
+    /// Validates the guard spec and loads its default resource limit.
+    async fn resolve_guards(
+        &self,
+        guards: &NamespaceGuardSpec,
+    ) -> Result<Option<ResourceLimitData>, AppError> {
+        if let Some(resource_quota) = &guards.resource_quota {
+            validate_resource_quota(resource_quota)?;
+        }
+        let Some(name) = &guards.default_resource_limit else {
+            return Ok(None);
+        };
+        ResourceLimitStore::new(self.client.clone(), self.default_namespace.clone())
+            .get(name)
+            .await?
+            .map(Some)
+            .ok_or_else(|| AppError::InvalidInput(format!("ResourceLimit {} not found", name)))
+    }
+
+    /// Replaces the session quota, ResourceQuota and LimitRange of the namespace,
+    /// unset ones are removed.
+    pub async fn update(
+        &self,
+        name: &str,
+        quota: Option<&SessionQuota>,
+        guards: &NamespaceGuardSpec,
+    ) -> Result<NamespaceData, AppError> {
+        let quota = quota.filter(|x| !is_unlimited(x));
+        if let Some(quota) = quota {
+            validate_quota(quota)?;
+        }
+        let Some(ns) = self.get(name).await? else {
+            return Err(AppError::NotFound(format!("Namespace {} not found", name)));
+        };
+        let default_resource_limit = self.resolve_guards(guards).await?;
+        let mut raw = ns.raw;
+        match quota {
+            Some(quota) => {
+                raw.annotations_mut().insert(
+                    ANNOTATION_SESSION_QUOTA.to_string(),
+                    serde_json::to_string(quota)?,
+                );
+            }
+            None => {
+                raw.annotations_mut().remove(ANNOTATION_SESSION_QUOTA);
+            }
+        }
+        match &default_resource_limit {
+            Some(resource_limit) => {
+                raw.annotations_mut().insert(
+                    ANNOTATION_DEFAULT_RESOURCE_LIMIT.to_string(),
+                    resource_limit.name.clone(),
+                );
+            }
+            None => {
+                raw.annotations_mut()
+                    .remove(ANNOTATION_DEFAULT_RESOURCE_LIMIT);
+            }
+        }
+        let raw = self
+            .api
+            .replace(name, &PostParams::default(), &raw)
+            .await
+            .map_err(AppError::from)?;
+        reconcile_guards(
+            self.client.clone(),
+            &raw,
+            guards.resource_quota.as_ref(),
+            default_resource_limit.as_ref(),
+        )
+        .await?;
+        Ok(NamespaceData::from_namespace(raw))
+    }
+
     pub async fn list(
         &self,
         queries: &[LabelQuery],
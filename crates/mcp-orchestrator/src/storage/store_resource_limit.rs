@@ -7,14 +7,17 @@ use crate::storage::annotations::{ANNOTATION_DESCRIPTION, annotation_description
 use crate::storage::label_query::build_label_query;
 use crate::storage::labels::{label_dependency, label_dependency_query};
 use crate::storage::resource_type::{
-    RESOURCE_TYPE_MCP_TEMPLATE, RESOURCE_TYPE_NAMESPACE, RESOURCE_TYPE_PREFIX_RESOURCE_LIMIT,
-    RESOURCE_TYPE_RESOURCE_LIMIT,
+    RESOURCE_TYPE_LIMIT_RANGE, RESOURCE_TYPE_MCP_TEMPLATE, RESOURCE_TYPE_NAMESPACE,
+    RESOURCE_TYPE_PREFIX_RESOURCE_LIMIT, RESOURCE_TYPE_RESOURCE_LIMIT,
+};
+use crate::storage::scheduling_feasibility::{
+    node_capacity, node_requests, resource_limit_pod_spec,
 };
 use crate::storage::util_list::ListOption;
 use crate::storage::util_name::{decode_k8sname, encode_k8sname};
 use crate::storage::utils::{
     add_safe_finalizer, data_elem, data_elem_ojsonstr, del_safe_finalizer,
-    parse_data_elem,
+    parse_data_elem, parse_data_elem_or_default,
 };
 use crate::{
     error::AppError,
@@ -25,7 +28,9 @@ use crate::{
     },
 };
 use chrono::{DateTime, Duration, Utc};
-use k8s_openapi::api::core::v1::{Affinity, ConfigMap, ResourceRequirements};
+use k8s_openapi::api::core::v1::{
+    Affinity, ConfigMap, LimitRange, Node, Pod, ResourceRequirements,
+};
 use k8s_openapi::apimachinery::pkg::api::resource::Quantity;
 use kube::Resource;
 use kube::{
@@ -43,6 +48,12 @@ const DATA_EPHEMERAL_STORAGE: &str = "ephemeral_storage";
 const DATA_VOLUMES: &str = "volumes";
 const DATA_NODE_SELECTOR: &str = "node_selector";
 const DATA_NODE_AFFINITY: &str = "node_affinity";
+const DATA_SECURITY: &str = "security";
+const DATA_IMAGE_PULL_SECRETS: &str = "image_pull_secrets";
+const DATA_TOLERATIONS: &str = "tolerations";
+const DATA_PRIORITY_CLASS_NAME: &str = "priority_class_name";
+const DATA_TOPOLOGY_SPREAD: &str = "topology_spread";
+const DATA_ANTI_AFFINITY: &str = "anti_affinity";
 
 pub struct ResourceLimitData {
     pub raw: ConfigMap,
@@ -57,6 +68,12 @@ pub struct ResourceLimitData {
     pub volumes: HashMap<String, VolumeLimit>,
     pub node_selector: Option<BTreeMap<String, String>>,
     pub node_affinity: Option<Affinity>,
+    pub security: Option<v1::SecurityProfile>,
+    pub image_pull_secrets: Vec<String>,
+    pub tolerations: Vec<v1::Toleration>,
+    pub priority_class_name: Option<String>,
+    pub topology_spread: Vec<v1::TopologySpread>,
+    pub anti_affinity: Vec<v1::PodAntiAffinity>,
     pub created_at: DateTime<Utc>,
     pub deleted_at: Option<DateTime<Utc>>,
 }
@@ -91,6 +108,12 @@ impl ResourceLimitData {
             ephemeral_storage: parse_data_elem(&cm.data, DATA_EPHEMERAL_STORAGE)?,
             node_selector: parse_data_elem(&cm.data, DATA_NODE_SELECTOR)?,
             node_affinity: parse_data_elem(&cm.data, DATA_NODE_AFFINITY)?,
+            security: parse_data_elem_or_default(&cm.data, DATA_SECURITY)?,
+            image_pull_secrets: parse_data_elem_or_default(&cm.data, DATA_IMAGE_PULL_SECRETS)?,
+            tolerations: parse_data_elem_or_default(&cm.data, DATA_TOLERATIONS)?,
+            priority_class_name: parse_data_elem_or_default(&cm.data, DATA_PRIORITY_CLASS_NAME)?,
+            topology_spread: parse_data_elem_or_default(&cm.data, DATA_TOPOLOGY_SPREAD)?,
+            anti_affinity: parse_data_elem_or_default(&cm.data, DATA_ANTI_AFFINITY)?,
             created_at: cm
                 .creation_timestamp()
                 .map(|x| x.0)
@@ -137,6 +160,33 @@ impl ResourceLimitData {
             ..Default::default()
         }
     }
+
+    pub fn to_resource_limit(&self) -> v1::ResourceLimit {
+        v1::ResourceLimit {
+            cpu: self.cpu.clone(),
+            memory: self.memory.clone(),
+            cpu_limit: self.cpu_limit.clone(),
+            memory_limit: self.memory_limit.clone(),
+            ephemeral_storage: self.ephemeral_storage.clone(),
+            volumes: self.volumes.clone(),
+            node_selector: self
+                .node_selector
+                .iter()
+                .flatten()
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect(),
+            node_affinity: self
+                .node_affinity
+                .as_ref()
+                .and_then(|na| serde_json::to_string(na).ok()),
+            security: self.security.clone(),
+            image_pull_secrets: self.image_pull_secrets.clone(),
+            tolerations: self.tolerations.clone(),
+            priority_class_name: self.priority_class_name.clone(),
+            topology_spread: self.topology_spread.clone(),
+            anti_affinity: self.anti_affinity.clone(),
+        }
+    }
 }
 
 pub struct ResourceLimitStore {
@@ -153,13 +203,13 @@ impl ResourceLimitStore {
         Api::namespaced(self.client.clone(), &self.namespace)
     }
 
-    pub async fn create<L: Iterator<Item = (String, String)>>(
+    fn to_config_map<L: Iterator<Item = (String, String)>>(
         &self,
         name: &str,
         labels: L,
         description: &str,
         data: &v1::ResourceLimit,
-    ) -> Result<ResourceLimitData, AppError> {
+    ) -> Result<ConfigMap, AppError> {
         let name = encode_k8sname(RESOURCE_TYPE_PREFIX_RESOURCE_LIMIT, name);
 
         let data_vec = vec![
@@ -171,9 +221,15 @@ impl ResourceLimitStore {
             data_elem(DATA_EPHEMERAL_STORAGE, &data.ephemeral_storage)?,
             data_elem(DATA_NODE_SELECTOR, &data.node_selector)?,
             data_elem_ojsonstr(DATA_NODE_AFFINITY, data.node_affinity.as_deref())?,
+            data_elem(DATA_SECURITY, &data.security)?,
+            data_elem(DATA_IMAGE_PULL_SECRETS, &data.image_pull_secrets)?,
+            data_elem(DATA_TOLERATIONS, &data.tolerations)?,
+            data_elem(DATA_PRIORITY_CLASS_NAME, &data.priority_class_name)?,
+            data_elem(DATA_TOPOLOGY_SPREAD, &data.topology_spread)?,
+            data_elem(DATA_ANTI_AFFINITY, &data.anti_affinity)?,
         ];
 
-        let configmap = ConfigMap {
+        Ok(ConfigMap {
             metadata: ObjectMeta {
                 name: Some(name),
                 labels: Some(
@@ -190,8 +246,17 @@ impl ResourceLimitStore {
             },
             data: Some(data_vec.into_iter().collect()),
             ..Default::default()
-        };
+        })
+    }
 
+    pub async fn create<L: Iterator<Item = (String, String)>>(
+        &self,
+        name: &str,
+        labels: L,
+        description: &str,
+        data: &v1::ResourceLimit,
+    ) -> Result<ResourceLimitData, AppError> {
+        let configmap = self.to_config_map(name, labels, description, data)?;
         self.api()
             .create(&PostParams::default(), &configmap)
             .await
@@ -199,6 +264,72 @@ impl ResourceLimitStore {
             .and_then(ResourceLimitData::try_from_config_map)
     }
 
+    /// Replaces `current` in place, guarded by its resourceVersion.
+    pub async fn replace<L: Iterator<Item = (String, String)>>(
+        &self,
+        current: &ResourceLimitData,
+        labels: L,
+        description: &str,
+        data: &v1::ResourceLimit,
+    ) -> Result<ResourceLimitData, AppError> {
+        let mut configmap = self.to_config_map(&current.name, labels, description, data)?;
+        configmap.metadata.resource_version = current.raw.resource_version();
+        configmap.metadata.finalizers = current.raw.metadata.finalizers.clone();
+        self.api()
+            .replace(&current.raw.name_any(), &PostParams::default(), &configmap)
+            .await
+            .map_err(AppError::from)
+            .and_then(ResourceLimitData::try_from_config_map)
+    }
+
+    /// Resource limit as it would be stored, without creating it.
+    pub fn render(
+        &self,
+        name: &str,
+        data: &v1::ResourceLimit,
+    ) -> Result<ResourceLimitData, AppError> {
+        ResourceLimitData::try_from_config_map(self.to_config_map(
+            name,
+            std::iter::empty(),
+            "",
+            data,
+        )?)
+    }
+
+    /// Evaluates a session pod of the resource limit against every node of the cluster.
+    pub async fn check(
+        &self,
+        resource_limit: &ResourceLimitData,
+    ) -> Result<v1::CheckResourceLimitResponse, AppError> {
+        let nodes = Api::<Node>::all(self.client.clone())
+            .list(&ListParams::default())
+            .await?;
+        let pods = Api::<Pod>::all(self.client.clone())
+            .list(&ListParams::default().fields("status.phase!=Succeeded,status.phase!=Failed"))
+            .await?;
+        let requested = node_requests(&pods.items);
+        let spec = resource_limit_pod_spec(resource_limit);
+
+        let mut response = v1::CheckResourceLimitResponse::default();
+        for node in &nodes.items {
+            let name = node.name_any();
+            let capacity = node_capacity(
+                node,
+                &spec,
+                requested.get(&name).unwrap_or(&BTreeMap::new()),
+            );
+            response.schedulable_sessions = response
+                .schedulable_sessions
+                .saturating_add(*capacity.as_ref().unwrap_or(&0));
+            response.nodes.push(v1::NodeFeasibility {
+                node: name,
+                sessions: capacity.clone().unwrap_or_default(),
+                reason: capacity.err(),
+            });
+        }
+        Ok(response)
+    }
+
     pub async fn get(&self, name: &str) -> Result<Option<ResourceLimitData>, AppError> {
         let name = encode_k8sname(RESOURCE_TYPE_PREFIX_RESOURCE_LIMIT, name);
         self.api()
@@ -295,7 +426,22 @@ impl ResourceLimitStore {
         };
 
         let has_dep_mcp_templates = self.has_dep_mcp_templates(name).await?;
-        Ok(!has_dep_mcp_templates)
+        let has_dep_limit_ranges = self.has_dep_limit_ranges(name).await?;
+        Ok(!has_dep_mcp_templates && !has_dep_limit_ranges)
+    }
+
+    /// Namespaces using the resource limit as their default LimitRange.
+    async fn has_dep_limit_ranges(&self, name: &str) -> Result<bool, AppError> {
+        let limit_range_store = Api::<LimitRange>::all(self.client.clone());
+
+        let label = build_label_query(
+            RESOURCE_TYPE_LIMIT_RANGE,
+            &[label_dependency_query(RESOURCE_TYPE_RESOURCE_LIMIT, name)],
+        )?
+        .to_string();
+        let lp = ListParams::default().labels(&label).limit(1);
+        let list = limit_range_store.list(&lp).await.map_err(AppError::from)?;
+        Ok(!list.items.is_empty())
     }
 
     async fn has_dep_mcp_templates(&self, name: &str) -> Result<bool, AppError> {
@@ -6,6 +6,7 @@ use kube::{
     Api, Client, Resource, ResourceExt,
     api::{DeleteParams, ListParams, ObjectMeta, PostParams},
 };
+use proto::mcp::orchestrator::v1::SecretUpdateStrategy;
 
 use super::label_query::{LabelQuery, build_label_query};
 use super::labels::setup_labels;
@@ -17,6 +18,7 @@ use crate::{
             RESOURCE_TYPE_MCP_TEMPLATE, RESOURCE_TYPE_NAMESPACE, RESOURCE_TYPE_PREFIX_SECRET,
             RESOURCE_TYPE_SECRET,
         },
+        secret_type::{apply_secret_update, build_secret_data, secret_type_from_name},
         util_delete::{DeleteOption, DeleteResult},
         util_list::ListOption,
         util_name::{decode_k8sname, encode_k8sname},
@@ -131,6 +133,59 @@ impl SecretStore {
             .transpose()
     }
 
+    /// Updates the data with the strategy, returning the keys whose value changed.
+    pub async fn update(
+        &self,
+        name: &str,
+        strategy: SecretUpdateStrategy,
+        data: std::collections::HashMap<String, String>,
+    ) -> Result<(SecretData, Vec<String>), AppError> {
+        let raw_name = encode_k8sname(RESOURCE_TYPE_PREFIX_SECRET, name);
+        let mut retry = 3;
+        loop {
+            let Some(current) = self.get(name).await? else {
+                return Err(AppError::NotFound(format!(
+                    "Secret {}/{} not found",
+                    self.namespace, name
+                )));
+            };
+            if current.deleted_at.is_some() {
+                return Err(AppError::InvalidInput(format!(
+                    "Secret {}/{} is being deleted",
+                    self.namespace, name
+                )));
+            }
+            let mut secret = current.raw;
+            let (next, changed) = apply_secret_update(
+                strategy,
+                secret.data.as_ref().unwrap_or(&Default::default()),
+                data.clone(),
+            )?;
+            build_secret_data(
+                secret_type_from_name(secret.type_.as_deref()),
+                None,
+                next.iter()
+                    .map(|(k, v)| (k.clone(), String::from_utf8_lossy(&v.0).to_string()))
+                    .collect(),
+            )?;
+            if changed.is_empty() {
+                return Ok((SecretData::try_from_secret(secret)?, changed));
+            }
+            secret.data = Some(next);
+            secret.string_data = None;
+            // resourceVersion 이 같을 때만 교체되므로 동시 수정은 다시 읽어서 적용
+            match self
+                .api()
+                .replace(&raw_name, &PostParams::default(), &secret)
+                .await
+            {
+                Ok(secret) => return Ok((SecretData::try_from_secret(secret)?, changed)),
+                Err(kube::Error::Api(ae)) if ae.code == 409 && retry > 0 => retry -= 1,
+                Err(err) => return Err(AppError::from(err)),
+            }
+        }
+    }
+
     pub async fn list(
         &self,
         queries: &[LabelQuery],
@@ -0,0 +1,392 @@
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::{DateTime, Duration, Utc};
+use k8s_openapi::api::core::v1::{ConfigMap, Pod};
+use kube::{
+    Api, Client, Resource, ResourceExt,
+    api::{
+        ApiResource, DeleteParams, DynamicObject, GroupVersionKind, ListParams, ObjectMeta,
+        PostParams,
+    },
+};
+
+use crate::{
+    error::AppError,
+    storage::{
+        annotations::ANNOTATION_USER_SUBJECT,
+        label_query::build_label_query,
+        labels::{label_dependency, label_dependency_query, setup_labels},
+        resource_type::{
+            RESOURCE_TYPE_MCP_SERVER, RESOURCE_TYPE_MCP_TEMPLATE, RESOURCE_TYPE_NAMESPACE,
+            RESOURCE_TYPE_PREFIX_USAGE_RECORD, RESOURCE_TYPE_USAGE_RECORD,
+        },
+        resource_uname::filter_relpath,
+        scheduling_feasibility::pod_requests,
+        template_container::parse_quantity,
+        usage_report::UsageRecord,
+        util_name::{decode_k8sname, encode_k8sname},
+        utils::{data_elem, parse_data_elem, parse_data_elem_or_default},
+    },
+};
+
+const DATA_NAMESPACE: &str = "namespace";
+const DATA_TEMPLATE: &str = "template";
+const DATA_SUBJECT: &str = "subject";
+const DATA_CPU: &str = "cpu";
+const DATA_MEMORY: &str = "memory";
+const DATA_STARTED_AT: &str = "started_at";
+const DATA_LAST_SEEN_AT: &str = "last_seen_at";
+const DATA_ENDED_AT: &str = "ended_at";
+const DATA_USED_CPU_SECONDS: &str = "used_cpu_seconds";
+const DATA_USED_MEMORY_BYTE_SECONDS: &str = "used_memory_byte_seconds";
+
+impl UsageRecord {
+    pub fn try_from_config_map(cm: &ConfigMap) -> Result<Self, AppError> {
+        Ok(Self {
+            session_id: decode_k8sname(RESOURCE_TYPE_PREFIX_USAGE_RECORD, &cm.name_any())
+                .ok_or_else(|| {
+                    AppError::Internal(format!(
+                        "Failed to decode configmap name: {}, it must start with {}-",
+                        cm.name_any(),
+                        RESOURCE_TYPE_PREFIX_USAGE_RECORD
+                    ))
+                })?,
+            namespace: parse_data_elem(&cm.data, DATA_NAMESPACE)?,
+            template: parse_data_elem(&cm.data, DATA_TEMPLATE)?,
+            subject: parse_data_elem_or_default(&cm.data, DATA_SUBJECT)?,
+            cpu: parse_data_elem(&cm.data, DATA_CPU)?,
+            memory: parse_data_elem(&cm.data, DATA_MEMORY)?,
+            started_at: parse_data_elem(&cm.data, DATA_STARTED_AT)?,
+            last_seen_at: parse_data_elem(&cm.data, DATA_LAST_SEEN_AT)?,
+            ended_at: parse_data_elem_or_default(&cm.data, DATA_ENDED_AT)?,
+            used_cpu_seconds: parse_data_elem_or_default(&cm.data, DATA_USED_CPU_SECONDS)?,
+            used_memory_byte_seconds: parse_data_elem_or_default(
+                &cm.data,
+                DATA_USED_MEMORY_BYTE_SECONDS,
+            )?,
+        })
+    }
+
+    fn to_config_map(&self) -> Result<ConfigMap, AppError> {
+        let data_vec = vec![
+            data_elem(DATA_NAMESPACE, &self.namespace)?,
+            data_elem(DATA_TEMPLATE, &self.template)?,
+            data_elem(DATA_SUBJECT, &self.subject)?,
+            data_elem(DATA_CPU, &self.cpu)?,
+            data_elem(DATA_MEMORY, &self.memory)?,
+            data_elem(DATA_STARTED_AT, &self.started_at)?,
+            data_elem(DATA_LAST_SEEN_AT, &self.last_seen_at)?,
+            data_elem(DATA_ENDED_AT, &self.ended_at)?,
+            data_elem(DATA_USED_CPU_SECONDS, &self.used_cpu_seconds)?,
+            data_elem(
+                DATA_USED_MEMORY_BYTE_SECONDS,
+                &self.used_memory_byte_seconds,
+            )?,
+        ];
+        Ok(ConfigMap {
+            metadata: ObjectMeta {
+                name: Some(encode_k8sname(
+                    RESOURCE_TYPE_PREFIX_USAGE_RECORD,
+                    &self.session_id,
+                )),
+                labels: Some(
+                    setup_labels(RESOURCE_TYPE_USAGE_RECORD, std::iter::empty())
+                        .chain(label_dependency(RESOURCE_TYPE_NAMESPACE, &self.namespace))
+                        .collect(),
+                ),
+                ..Default::default()
+            },
+            data: Some(data_vec.into_iter().collect()),
+            ..Default::default()
+        })
+    }
+
+    /// New record of a session pod, None for pods which are not sessions of a template.
+    fn from_pod(pod: &Pod) -> Option<Self> {
+        let template = pod
+            .labels()
+            .keys()
+            .filter_map(filter_relpath)
+            .find(|(r#type, _)| r#type == RESOURCE_TYPE_MCP_TEMPLATE)
+            .map(|(_, name)| name)?;
+        let requests = pod.spec.as_ref().map(pod_requests).unwrap_or_default();
+        let started_at = pod
+            .creation_timestamp()
+            .map(|x| x.0)
+            .unwrap_or_else(Utc::now);
+        Some(Self {
+            namespace: pod.namespace().unwrap_or_default(),
+            session_id: pod.name_any(),
+            template,
+            subject: pod.annotations().get(ANNOTATION_USER_SUBJECT).cloned(),
+            cpu: requests.get("cpu").copied().unwrap_or_default(),
+            memory: requests.get("memory").copied().unwrap_or_default(),
+            started_at,
+            last_seen_at: started_at,
+            ended_at: None,
+            used_cpu_seconds: None,
+            used_memory_byte_seconds: None,
+        })
+    }
+}
+
+pub fn is_running(pod: &Pod) -> bool {
+    let phase = pod.status.as_ref().and_then(|x| x.phase.as_deref());
+    pod.meta().deletion_timestamp.is_none() && !matches!(phase, Some("Succeeded" | "Failed"))
+}
+
+/// Usage of the session pods in cores and bytes from metrics.k8s.io, keyed by namespace and name.
+async fn pod_metrics(
+    client: Client,
+    label: &str,
+) -> Result<HashMap<(String, String), (f64, f64)>, kube::Error> {
+    let resource = ApiResource::from_gvk_with_plural(
+        &GroupVersionKind::gvk("metrics.k8s.io", "v1beta1", "PodMetrics"),
+        "pods",
+    );
+    let api = Api::<DynamicObject>::all_with(client, &resource);
+    let list = api.list(&ListParams::default().labels(label)).await?;
+    Ok(list
+        .items
+        .into_iter()
+        .map(|metrics| {
+            let mut cpu = 0.0;
+            let mut memory = 0.0;
+            for container in metrics.data["containers"].as_array().into_iter().flatten() {
+                let usage = |key: &str| {
+                    container["usage"][key]
+                        .as_str()
+                        .and_then(parse_quantity)
+                        .unwrap_or_default()
+                };
+                cpu += usage("cpu");
+                memory += usage("memory");
+            }
+            (
+                (metrics.namespace().unwrap_or_default(), metrics.name_any()),
+                (cpu, memory),
+            )
+        })
+        .collect())
+}
+
+pub struct UsageStore {
+    client: Client,
+    namespace: String,
+}
+
+impl UsageStore {
+    pub fn new(client: Client, namespace: String) -> Self {
+        Self { client, namespace }
+    }
+
+    fn api(&self) -> Api<ConfigMap> {
+        Api::namespaced(self.client.clone(), &self.namespace)
+    }
+
+    /// Creates the record when `resource_version` is None, otherwise replaces the read version.
+    /// Returns false when another replica wrote the record first.
+    async fn save(
+        &self,
+        record: &UsageRecord,
+        resource_version: Option<String>,
+    ) -> Result<bool, AppError> {
+        let mut cm = record.to_config_map()?;
+        let result = match resource_version {
+            None => self.api().create(&PostParams::default(), &cm).await,
+            Some(resource_version) => {
+                cm.metadata.resource_version = Some(resource_version);
+                self.api()
+                    .replace(&cm.name_any(), &PostParams::default(), &cm)
+                    .await
+            }
+        };
+        match result {
+            Ok(_) => Ok(true),
+            Err(kube::Error::Api(ae)) if ae.code == 409 => Ok(false),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Records with the resourceVersion they were read at.
+    async fn list_versioned(
+        &self,
+        namespace: Option<&str>,
+    ) -> Result<Vec<(UsageRecord, Option<String>)>, AppError> {
+        let queries = namespace
+            .map(|x| vec![label_dependency_query(RESOURCE_TYPE_NAMESPACE, x)])
+            .unwrap_or_default();
+        let label = build_label_query(RESOURCE_TYPE_USAGE_RECORD, &queries)?.to_string();
+        let list = self
+            .api()
+            .list(&ListParams::default().labels(&label))
+            .await?;
+        Ok(list
+            .items
+            .iter()
+            .filter_map(|cm| match UsageRecord::try_from_config_map(cm) {
+                Ok(record) => Some((record, cm.resource_version())),
+                Err(e) => {
+                    tracing::warn!("Skipping invalid usage record {}: {}", cm.name_any(), e);
+                    None
+                }
+            })
+            .collect())
+    }
+
+    pub async fn list(&self, namespace: Option<&str>) -> Result<Vec<UsageRecord>, AppError> {
+        Ok(self
+            .list_versioned(namespace)
+            .await?
+            .into_iter()
+            .map(|(record, _)| record)
+            .collect())
+    }
+
+    /// Opens the record of a session pod as soon as it is created.
+    pub async fn open(&self, pod: &Pod) -> Result<(), AppError> {
+        let Some(record) = UsageRecord::from_pod(pod) else {
+            return Ok(());
+        };
+        self.save(&record, None).await.map(|_| ())
+    }
+
+    /// Closes the record of a deleted session pod.
+    pub async fn close(&self, session_id: &str) -> Result<(), AppError> {
+        let name = encode_k8sname(RESOURCE_TYPE_PREFIX_USAGE_RECORD, session_id);
+        // 다른 replica 의 샘플링과 겹치면 다시 읽어서 닫음
+        for _ in 0..3 {
+            let Some(cm) = self.api().get_opt(&name).await? else {
+                return Ok(());
+            };
+            let mut record = UsageRecord::try_from_config_map(&cm)?;
+            if record.ended_at.is_some() {
+                return Ok(());
+            }
+            let now = Utc::now();
+            record.last_seen_at = now;
+            record.ended_at = Some(now);
+            if self.save(&record, cm.resource_version()).await? {
+                return Ok(());
+            }
+        }
+        Err(AppError::Internal(format!(
+            "Usage record {} kept changing while closing",
+            name
+        )))
+    }
+
+    /// Samples every session pod, closes the records of sessions removed without `close`
+    /// and deletes the records ended before the retention.
+    /// Writes are conditional on the read resourceVersion so concurrent replicas never
+    /// sample the same interval twice.
+    pub async fn meter(
+        &self,
+        interval: Duration,
+        retention: Duration,
+        sample_metrics: bool,
+    ) -> Result<(), AppError> {
+        let now = Utc::now();
+        let label = build_label_query(RESOURCE_TYPE_MCP_SERVER, &[])?.to_string();
+        let pods = Api::<Pod>::all(self.client.clone())
+            .list(&ListParams::default().labels(&label))
+            .await?
+            .items
+            .into_iter()
+            .filter(is_running)
+            .collect::<Vec<_>>();
+        let metrics = if sample_metrics {
+            pod_metrics(self.client.clone(), &label)
+                .await
+                .unwrap_or_else(|e| {
+                    // metrics-server 가 없는 클러스터는 요청량만 기록
+                    tracing::debug!("Pod metrics are not available: {}", e);
+                    HashMap::new()
+                })
+        } else {
+            HashMap::new()
+        };
+        let mut records = self
+            .list_versioned(None)
+            .await?
+            .into_iter()
+            .map(|x| (x.0.session_id.clone(), x))
+            .collect::<BTreeMap<_, _>>();
+
+        for pod in &pods {
+            let Some(fresh) = UsageRecord::from_pod(pod) else {
+                continue;
+            };
+            let (mut record, resource_version) = match records.remove(&fresh.session_id) {
+                Some((record, resource_version)) if record.ended_at.is_none() => {
+                    (record, resource_version)
+                }
+                Some(_) => continue,
+                None => (fresh, None),
+            };
+            // 측정 주기보다 오래 비었던 구간은 샘플로 채우지 않음
+            let elapsed = (now - record.last_seen_at).min(interval * 2);
+            let seconds = elapsed.num_milliseconds().max(0) as f64 / 1000.0;
+            if let Some((cpu, memory)) =
+                metrics.get(&(record.namespace.clone(), record.session_id.clone()))
+            {
+                record.used_cpu_seconds =
+                    Some(record.used_cpu_seconds.unwrap_or_default() + cpu * seconds);
+                record.used_memory_byte_seconds =
+                    Some(record.used_memory_byte_seconds.unwrap_or_default() + memory * seconds);
+            }
+            record.last_seen_at = now;
+            match self.save(&record, resource_version).await {
+                Ok(true) => {}
+                Ok(false) => {
+                    tracing::debug!(
+                        "Usage record {} sampled by another replica",
+                        record.session_id
+                    )
+                }
+                Err(e) => {
+                    tracing::error!("Failed to save usage record {}: {}", record.session_id, e)
+                }
+            }
+        }
+
+        for (mut record, resource_version) in records.into_values() {
+            match record.ended_at {
+                None => {
+                    record.ended_at = Some(record.last_seen_at);
+                    if let Err(e) = self.save(&record, resource_version).await {
+                        tracing::error!(
+                            "Failed to close usage record {}: {}",
+                            record.session_id,
+                            e
+                        );
+                    }
+                }
+                Some(ended_at) if ended_at < now - retention => {
+                    let name =
+                        encode_k8sname(RESOURCE_TYPE_PREFIX_USAGE_RECORD, &record.session_id);
+                    if let Err(e) = self.api().delete(&name, &DeleteParams::default()).await {
+                        tracing::error!("Failed to delete usage record {}: {}", name, e);
+                    }
+                }
+                Some(_) => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Records overlapping the range.
+    pub async fn report_records(
+        &self,
+        namespace: Option<&str>,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<UsageRecord>, AppError> {
+        Ok(self
+            .list(namespace)
+            .await?
+            .into_iter()
+            .filter(|x| x.started_at < end && x.end() > start)
+            .collect())
+    }
+}
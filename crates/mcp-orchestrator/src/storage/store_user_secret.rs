@@ -0,0 +1,268 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use k8s_openapi::api::core::v1::Secret;
+use kube::{
+    Api, Client, ResourceExt,
+    api::{DeleteParams, ListParams, ObjectMeta, PostParams},
+};
+
+use crate::{
+    error::AppError,
+    storage::{
+        annotations::ANNOTATION_USER_SUBJECT,
+        label_query::{LabelQuery, build_label_query},
+        labels::{LABEL_USER_SECRET_SUBJECT_HASH, setup_labels},
+        resource_type::{RESOURCE_TYPE_PREFIX_USER_SECRET, RESOURCE_TYPE_USER_SECRET},
+        scheduling_validation::validate_dns_subdomain,
+        store_workspace::subject_hash,
+        util_delete::DeleteResult,
+        util_name::{decode_k8sname, encode_k8sname},
+        utils::pick_created_at,
+    },
+};
+
+/// Secret of a single user, only visible to the subject that owns it.
+pub struct UserSecretData {
+    pub raw: Secret,
+    pub namespace: String,
+    pub name: String,
+    pub keys: Vec<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl UserSecretData {
+    fn try_from_secret(secret: Secret) -> Result<Self, AppError> {
+        // `uc-<subject hash>-<name>`
+        let name = decode_k8sname(RESOURCE_TYPE_PREFIX_USER_SECRET, &secret.name_any())
+            .and_then(|x| x.split_once('-').map(|(_, name)| name.to_string()))
+            .ok_or_else(|| {
+                AppError::Internal(format!(
+                    "Failed to decode user secret name: {}, it must start with {}-",
+                    secret.name_any(),
+                    RESOURCE_TYPE_PREFIX_USER_SECRET
+                ))
+            })?;
+        Ok(Self {
+            namespace: secret.namespace().unwrap_or_else(|| "default".to_string()),
+            name,
+            keys: secret
+                .data
+                .as_ref()
+                .map(|x| x.keys().cloned().collect())
+                .unwrap_or_default(),
+            created_at: pick_created_at(&secret),
+            raw: secret,
+        })
+    }
+}
+
+/// Kubernetes name of the user secret, the subject is hashed so it fits in a name.
+pub fn user_secret_name(subject: &str, name: &str) -> String {
+    encode_k8sname(
+        RESOURCE_TYPE_PREFIX_USER_SECRET,
+        &format!("{}-{}", subject_hash(subject), name),
+    )
+}
+
+/// The name is a part of the Kubernetes name, after the prefix and the subject hash.
+fn validate_user_secret_name(subject: &str, name: &str) -> Result<(), AppError> {
+    if name.is_empty() {
+        return Err(AppError::InvalidInput(
+            "User secret name is required".to_string(),
+        ));
+    }
+    validate_dns_subdomain(&user_secret_name(subject, name)).map_err(|_| {
+        AppError::InvalidInput(format!(
+            "Invalid user secret name '{}', it must be lowercase alphanumeric or '-'",
+            name
+        ))
+    })
+}
+
+fn is_owner(secret: &Secret, subject: &str) -> bool {
+    secret
+        .annotations()
+        .get(ANNOTATION_USER_SUBJECT)
+        .map(String::as_str)
+        == Some(subject)
+}
+
+pub struct UserSecretStore {
+    client: Client,
+    namespace: String,
+}
+
+impl UserSecretStore {
+    pub fn new(client: Client, namespace: impl Into<String>) -> Self {
+        Self {
+            client,
+            namespace: namespace.into(),
+        }
+    }
+
+    fn api(&self) -> Api<Secret> {
+        Api::namespaced(self.client.clone(), &self.namespace)
+    }
+
+    /// Creates the secret of the subject or replaces all of its keys.
+    pub async fn put(
+        &self,
+        subject: &str,
+        name: &str,
+        data: HashMap<String, String>,
+    ) -> Result<UserSecretData, AppError> {
+        validate_user_secret_name(subject, name)?;
+        if data.is_empty() {
+            return Err(AppError::InvalidInput(
+                "User secret requires at least one key".to_string(),
+            ));
+        }
+        let raw_name = user_secret_name(subject, name);
+        let mut secret = Secret {
+            metadata: ObjectMeta {
+                name: Some(raw_name.clone()),
+                labels: Some(
+                    setup_labels(RESOURCE_TYPE_USER_SECRET, std::iter::empty())
+                        .chain([(
+                            LABEL_USER_SECRET_SUBJECT_HASH.to_string(),
+                            subject_hash(subject),
+                        )])
+                        .collect(),
+                ),
+                annotations: Some(
+                    [(ANNOTATION_USER_SUBJECT.to_string(), subject.to_string())]
+                        .into_iter()
+                        .collect(),
+                ),
+                ..Default::default()
+            },
+            string_data: Some(data.into_iter().collect()),
+            ..Default::default()
+        };
+        let secret = match self.api().get_opt(&raw_name).await? {
+            Some(current) if !is_owner(&current, subject) => {
+                return Err(AppError::Internal(format!(
+                    "User secret {} belongs to another subject",
+                    raw_name
+                )));
+            }
+            Some(current) => {
+                secret.metadata.resource_version = current.resource_version();
+                self.api()
+                    .replace(&raw_name, &PostParams::default(), &secret)
+                    .await?
+            }
+            None => self.api().create(&PostParams::default(), &secret).await?,
+        };
+        UserSecretData::try_from_secret(secret)
+    }
+
+    pub async fn get(&self, subject: &str, name: &str) -> Result<Option<UserSecretData>, AppError> {
+        self.api()
+            .get_opt(&user_secret_name(subject, name))
+            .await?
+            .filter(|x| is_owner(x, subject))
+            .map(UserSecretData::try_from_secret)
+            .transpose()
+    }
+
+    pub async fn list(&self, subject: &str) -> Result<Vec<UserSecretData>, AppError> {
+        let label = build_label_query(
+            RESOURCE_TYPE_USER_SECRET,
+            &[LabelQuery::equal(
+                LABEL_USER_SECRET_SUBJECT_HASH,
+                subject_hash(subject),
+            )],
+        )?
+        .to_string();
+        let list = self
+            .api()
+            .list(&ListParams::default().labels(&label))
+            .await?;
+        list.items
+            .into_iter()
+            .filter(|x| is_owner(x, subject))
+            .map(UserSecretData::try_from_secret)
+            .collect()
+    }
+
+    pub async fn delete(&self, subject: &str, name: &str) -> Result<DeleteResult, AppError> {
+        if self.get(subject, name).await?.is_none() {
+            return Err(AppError::NotFound(format!(
+                "User secret {}/{} not found",
+                self.namespace, name
+            )));
+        }
+        self.api()
+            .delete(&user_secret_name(subject, name), &DeleteParams::default())
+            .await
+            .map(|ok| {
+                ok.map_left(|_x| DeleteResult::Deleting)
+                    .map_right(|_x| DeleteResult::Deleted)
+                    .into_inner()
+            })
+            .map_err(AppError::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn user_secret(subject: &str, name: &str, owner: Option<&str>) -> Secret {
+        Secret {
+            metadata: ObjectMeta {
+                name: Some(user_secret_name(subject, name)),
+                annotations: owner.map(|x| {
+                    [(ANNOTATION_USER_SUBJECT.to_string(), x.to_string())]
+                        .into_iter()
+                        .collect()
+                }),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_user_secret_name() {
+        let name = user_secret_name("alice@example.com", "github-token");
+        assert!(name.starts_with("uc-"));
+        let data = UserSecretData::try_from_secret(user_secret(
+            "alice@example.com",
+            "github-token",
+            Some("alice@example.com"),
+        ))
+        .unwrap();
+        assert_eq!(data.name, "github-token");
+        assert_ne!(name, user_secret_name("bob@example.com", "github-token"));
+
+        let invalid = Secret {
+            metadata: ObjectMeta {
+                name: Some("sc-github".to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        assert!(UserSecretData::try_from_secret(invalid).is_err());
+    }
+
+    #[test]
+    fn test_validate_user_secret_name() {
+        assert!(validate_user_secret_name("alice", "github-token").is_ok());
+        assert!(validate_user_secret_name("alice", "").is_err());
+        assert!(validate_user_secret_name("alice", "GitHub").is_err());
+        assert!(validate_user_secret_name("alice", "github/token").is_err());
+        assert!(validate_user_secret_name("alice", "github-").is_err());
+        assert!(validate_user_secret_name("alice", &"a".repeat(250)).is_err());
+    }
+
+    #[test]
+    fn test_is_owner() {
+        let owned = user_secret("alice", "github", Some("alice"));
+        assert!(is_owner(&owned, "alice"));
+        assert!(!is_owner(&owned, "bob"));
+        assert!(!is_owner(&user_secret("alice", "github", None), "alice"));
+    }
+}
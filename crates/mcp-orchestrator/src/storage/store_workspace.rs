@@ -0,0 +1,550 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Duration, Utc};
+use k8s_openapi::{
+    api::core::v1::{
+        PersistentVolumeClaim, PersistentVolumeClaimSpec, Pod, VolumeResourceRequirements,
+    },
+    apimachinery::pkg::api::resource::Quantity,
+};
+use kube::{
+    Api, Client, Resource, ResourceExt,
+    api::{DeleteParams, ListParams, ObjectMeta, Patch, PatchParams, PostParams, Preconditions},
+};
+use proto::mcp::orchestrator::v1::{self, VolumeLimit, WorkspaceAccessMode, WorkspaceRetention};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sha2::{Digest, Sha256};
+
+use super::label_query::{LabelQuery, build_label_query};
+use super::labels::setup_labels;
+use crate::{
+    error::AppError,
+    storage::{
+        McpTemplateData,
+        annotations::{
+            ANNOTATION_LAST_ACCESS_AT, ANNOTATION_WORKSPACE_IDLE_TTL, ANNOTATION_WORKSPACE_LEASE,
+            ANNOTATION_WORKSPACE_RETENTION, ANNOTATION_WORKSPACE_SUBJECT,
+        },
+        labels::{LABEL_WORKSPACE_SUBJECT_HASH, LABEL_WORKSPACE_TEMPLATE, is_managed_label},
+        resource_type::{
+            RESOURCE_TYPE_MCP_SERVER, RESOURCE_TYPE_PREFIX_WORKSPACE, RESOURCE_TYPE_WORKSPACE,
+        },
+        store_usage::is_running,
+        util_delete::DeleteResult,
+        util_list::ListOption,
+        util_name::{decode_k8sname, encode_k8sname},
+        utils::{pick_created_at, pick_deleted_at},
+    },
+};
+
+/// A ReadWriteOnce workspace stays taken this long for a session whose pod is not created yet.
+const LEASE_TTL_SECONDS: i64 = 60;
+const LEASE_RETRIES: usize = 10;
+
+/// Session which took a ReadWriteOnce workspace, recorded on the claim.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct WorkspaceLease {
+    session_id: String,
+    expires_at: DateTime<Utc>,
+}
+
+pub fn subject_hash(subject: &str) -> String {
+    let digest = Sha256::digest(subject.as_bytes());
+    digest
+        .iter()
+        .take(16)
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+pub fn pvc_access_mode(mode: WorkspaceAccessMode) -> &'static str {
+    match mode {
+        WorkspaceAccessMode::ReadWriteOnce => "ReadWriteOnce",
+        WorkspaceAccessMode::ReadWriteMany => "ReadWriteMany",
+    }
+}
+
+/// Labels put on session pods which mount the workspace of `subject`.
+pub fn workspace_pod_labels(
+    template_name: &str,
+    subject: &str,
+) -> impl Iterator<Item = (String, String)> {
+    vec![
+        (
+            LABEL_WORKSPACE_TEMPLATE.to_string(),
+            template_name.to_string(),
+        ),
+        (
+            LABEL_WORKSPACE_SUBJECT_HASH.to_string(),
+            subject_hash(subject),
+        ),
+    ]
+    .into_iter()
+}
+
+pub struct WorkspaceData {
+    pub raw: PersistentVolumeClaim,
+    pub namespace: String,
+    pub name: String,
+    pub template_name: String,
+    pub subject: String,
+    pub retention: WorkspaceRetention,
+    pub idle_ttl: Option<Duration>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub deleted_at: Option<DateTime<Utc>>,
+}
+
+impl WorkspaceData {
+    pub fn try_from_pvc(pvc: PersistentVolumeClaim) -> Result<Self, AppError> {
+        let annotations = pvc.annotations();
+        Ok(Self {
+            namespace: pvc.namespace().unwrap_or_else(|| "default".to_string()),
+            name: decode_k8sname(RESOURCE_TYPE_PREFIX_WORKSPACE, &pvc.name_any()).ok_or_else(
+                || {
+                    AppError::Internal(format!(
+                        "Failed to decode workspace name: {}, it must start with {}-",
+                        pvc.name_any(),
+                        RESOURCE_TYPE_PREFIX_WORKSPACE
+                    ))
+                },
+            )?,
+            template_name: pvc
+                .labels()
+                .get(LABEL_WORKSPACE_TEMPLATE)
+                .cloned()
+                .unwrap_or_default(),
+            subject: annotations
+                .get(ANNOTATION_WORKSPACE_SUBJECT)
+                .cloned()
+                .unwrap_or_default(),
+            retention: annotations
+                .get(ANNOTATION_WORKSPACE_RETENTION)
+                .and_then(|x| WorkspaceRetention::from_str_name(x))
+                .unwrap_or(WorkspaceRetention::Retain),
+            idle_ttl: annotations
+                .get(ANNOTATION_WORKSPACE_IDLE_TTL)
+                .and_then(|x| x.parse::<i64>().ok())
+                .map(Duration::seconds),
+            last_used_at: annotations
+                .get(ANNOTATION_LAST_ACCESS_AT)
+                .and_then(|x| DateTime::parse_from_rfc3339(x).ok())
+                .map(|x| x.with_timezone(&Utc)),
+            created_at: pick_created_at(&pvc),
+            deleted_at: pick_deleted_at(&pvc),
+            raw: pvc,
+        })
+    }
+
+    pub fn size(&self) -> String {
+        self.raw
+            .spec
+            .as_ref()
+            .and_then(|spec| spec.resources.as_ref())
+            .and_then(|res| res.requests.as_ref())
+            .and_then(|req| req.get("storage"))
+            .map(|q| q.0.clone())
+            .unwrap_or_default()
+    }
+
+    pub fn storage_class(&self) -> Option<String> {
+        self.raw
+            .spec
+            .as_ref()
+            .and_then(|spec| spec.storage_class_name.clone())
+    }
+
+    pub fn phase(&self) -> String {
+        self.raw
+            .status
+            .as_ref()
+            .and_then(|status| status.phase.clone())
+            .unwrap_or_else(|| "Unknown".to_string())
+    }
+
+    /// Session holding an unexpired lease on the workspace.
+    pub fn leased_by(&self, now: &DateTime<Utc>) -> Option<String> {
+        self.raw
+            .annotations()
+            .get(ANNOTATION_WORKSPACE_LEASE)
+            .and_then(|x| serde_json::from_str::<WorkspaceLease>(x).ok())
+            .filter(|x| x.expires_at > *now)
+            .map(|x| x.session_id)
+    }
+
+    pub fn is_expired(&self, now: &DateTime<Utc>) -> bool {
+        let Some(idle_ttl) = self.idle_ttl else {
+            return false;
+        };
+        let last_used_at = self.last_used_at.unwrap_or(self.created_at);
+        now.signed_duration_since(last_used_at) > idle_ttl
+    }
+}
+
+pub struct WorkspaceStore {
+    client: Client,
+    namespace: String,
+}
+
+impl WorkspaceStore {
+    pub fn new(client: Client, namespace: impl Into<String>) -> Self {
+        Self {
+            client,
+            namespace: namespace.into(),
+        }
+    }
+
+    fn api(&self) -> Api<PersistentVolumeClaim> {
+        Api::namespaced(self.client.clone(), &self.namespace)
+    }
+
+    pub fn workspace_name(template_name: &str, subject: &str) -> String {
+        format!("{}-{}", template_name, subject_hash(subject))
+    }
+
+    /// Returns the workspace of `subject` for the template, creating it on first use.
+    pub async fn ensure(
+        &self,
+        template: &McpTemplateData,
+        config: &v1::WorkspaceConfig,
+        limit: &VolumeLimit,
+        subject: &str,
+        session_id: &str,
+    ) -> Result<WorkspaceData, AppError> {
+        let name = Self::workspace_name(&template.name, subject);
+        if let Some(workspace) = self.get(&name).await? {
+            if workspace.subject != subject {
+                return Err(AppError::Internal(format!(
+                    "Workspace {}/{} belongs to another subject",
+                    self.namespace, name
+                )));
+            }
+            if workspace.deleted_at.is_some() {
+                return Err(AppError::InvalidInput(format!(
+                    "Workspace {}/{} is being deleted",
+                    self.namespace, name
+                )));
+            }
+            // ReadWriteOnce 볼륨은 다른 노드의 두번째 세션이 attach 에서 멈추므로 미리 거절
+            if config.access_mode() == WorkspaceAccessMode::ReadWriteOnce {
+                return self.lease(&name, session_id).await;
+            }
+            self.touch(&name).await?;
+            return Ok(workspace);
+        }
+
+        let retention = config.retention();
+        let owner_references = match retention {
+            WorkspaceRetention::DeleteWithTemplate => template.raw.owner_ref(&()).map(|x| vec![x]),
+            WorkspaceRetention::Retain => None,
+        };
+        let lease = WorkspaceLease {
+            session_id: session_id.to_string(),
+            expires_at: Utc::now() + Duration::seconds(LEASE_TTL_SECONDS),
+        };
+        let annotations = vec![
+            (
+                ANNOTATION_WORKSPACE_SUBJECT.to_string(),
+                subject.to_string(),
+            ),
+            (
+                ANNOTATION_WORKSPACE_RETENTION.to_string(),
+                retention.as_str_name().to_string(),
+            ),
+            (
+                ANNOTATION_LAST_ACCESS_AT.to_string(),
+                Utc::now().to_rfc3339(),
+            ),
+        ]
+        .into_iter()
+        .chain(config.idle_ttl.as_ref().map(|ttl| {
+            (
+                ANNOTATION_WORKSPACE_IDLE_TTL.to_string(),
+                ttl.seconds.to_string(),
+            )
+        }))
+        .chain(
+            (config.access_mode() == WorkspaceAccessMode::ReadWriteOnce)
+                .then(|| serde_json::to_string(&lease))
+                .transpose()?
+                .map(|x| (ANNOTATION_WORKSPACE_LEASE.to_string(), x)),
+        )
+        .collect();
+
+        let pvc = PersistentVolumeClaim {
+            metadata: ObjectMeta {
+                name: Some(encode_k8sname(RESOURCE_TYPE_PREFIX_WORKSPACE, &name)),
+                namespace: Some(self.namespace.clone()),
+                labels: Some(
+                    setup_labels(RESOURCE_TYPE_WORKSPACE, std::iter::empty())
+                        .chain(workspace_pod_labels(&template.name, subject))
+                        .collect(),
+                ),
+                annotations: Some(annotations),
+                owner_references,
+                ..Default::default()
+            },
+            spec: Some(PersistentVolumeClaimSpec {
+                access_modes: Some(vec![pvc_access_mode(config.access_mode()).to_string()]),
+                storage_class_name: limit.storage_class.clone(),
+                resources: Some(VolumeResourceRequirements {
+                    requests: Some(
+                        [("storage".to_string(), Quantity(limit.size.clone()))]
+                            .into_iter()
+                            .collect(),
+                    ),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        tracing::info!("Creating workspace {}/{}", self.namespace, name);
+        self.api()
+            .create(&PostParams::default(), &pvc)
+            .await
+            .map_err(AppError::from)
+            .and_then(WorkspaceData::try_from_pvc)
+    }
+
+    pub async fn get(&self, name: &str) -> Result<Option<WorkspaceData>, AppError> {
+        let name = encode_k8sname(RESOURCE_TYPE_PREFIX_WORKSPACE, name);
+        self.api()
+            .get_opt(&name)
+            .await
+            .map_err(AppError::from)?
+            .and_then(|x| {
+                if is_managed_label(RESOURCE_TYPE_WORKSPACE, x.labels()) {
+                    Some(x)
+                } else {
+                    None
+                }
+            })
+            .map(WorkspaceData::try_from_pvc)
+            .transpose()
+    }
+
+    pub async fn list(
+        &self,
+        template_name: Option<&str>,
+        subject: Option<&str>,
+        option: ListOption,
+    ) -> Result<(Vec<WorkspaceData>, Option<String>, bool), AppError> {
+        let queries = template_name
+            .map(|x| LabelQuery::equal(LABEL_WORKSPACE_TEMPLATE, x))
+            .into_iter()
+            .chain(
+                subject.map(|x| LabelQuery::equal(LABEL_WORKSPACE_SUBJECT_HASH, subject_hash(x))),
+            )
+            .collect::<Vec<_>>();
+        let label_query = build_label_query(RESOURCE_TYPE_WORKSPACE, &queries)?;
+        let lp = option.to_list_param(label_query);
+        let list = self.api().list(&lp).await.map_err(AppError::from)?;
+        Ok((
+            list.items
+                .into_iter()
+                .take(option.get_limit())
+                .map(WorkspaceData::try_from_pvc)
+                .collect::<Result<Vec<_>, _>>()?,
+            list.metadata.continue_.clone(),
+            option.has_more(&list.metadata),
+        ))
+    }
+
+    pub async fn touch(&self, name: &str) -> Result<(), AppError> {
+        let name = encode_k8sname(RESOURCE_TYPE_PREFIX_WORKSPACE, name);
+        let patch = json!({
+            "metadata": {
+                "annotations": HashMap::from([(ANNOTATION_LAST_ACCESS_AT, Utc::now().to_rfc3339())]),
+            }
+        });
+        self.api()
+            .patch(&name, &PatchParams::default(), &Patch::Merge(&patch))
+            .await
+            .map_err(AppError::from)?;
+        Ok(())
+    }
+
+    /// Takes the ReadWriteOnce workspace for the session. The lease is written with the resource
+    /// version it was checked at, so concurrent sessions can not both find the workspace free.
+    async fn lease(&self, name: &str, session_id: &str) -> Result<WorkspaceData, AppError> {
+        let api = self.api();
+        let pvc_name = encode_k8sname(RESOURCE_TYPE_PREFIX_WORKSPACE, name);
+        for _ in 0..LEASE_RETRIES {
+            let workspace =
+                WorkspaceData::try_from_pvc(api.get(&pvc_name).await.map_err(AppError::from)?)?;
+            if workspace.deleted_at.is_some() {
+                return Err(AppError::InvalidInput(format!(
+                    "Workspace {}/{} is being deleted",
+                    self.namespace, name
+                )));
+            }
+            let now = Utc::now();
+            let leased = workspace.leased_by(&now).is_some_and(|x| x != session_id);
+            if leased || self.is_in_use(&workspace).await? {
+                return Err(AppError::QuotaExceeded(format!(
+                    "Workspace {}/{} is already in use by another session",
+                    self.namespace, name
+                )));
+            }
+            let lease = WorkspaceLease {
+                session_id: session_id.to_string(),
+                expires_at: now + Duration::seconds(LEASE_TTL_SECONDS),
+            };
+            let mut pvc = workspace.raw;
+            let annotations = pvc.annotations_mut();
+            annotations.insert(
+                ANNOTATION_WORKSPACE_LEASE.to_string(),
+                serde_json::to_string(&lease)?,
+            );
+            annotations.insert(ANNOTATION_LAST_ACCESS_AT.to_string(), now.to_rfc3339());
+            match api.replace(&pvc_name, &PostParams::default(), &pvc).await {
+                Ok(pvc) => return WorkspaceData::try_from_pvc(pvc),
+                Err(kube::Error::Api(ae)) if ae.code == 409 => continue,
+                Err(err) => return Err(AppError::from(err)),
+            }
+        }
+        Err(AppError::QuotaExceeded(format!(
+            "Workspace {}/{} is contended by concurrent sessions",
+            self.namespace, name
+        )))
+    }
+
+    /// Whether a session pod which is not finished or terminating mounts the workspace.
+    pub async fn is_in_use(&self, workspace: &WorkspaceData) -> Result<bool, AppError> {
+        let pod_store = Api::<Pod>::namespaced(self.client.clone(), &self.namespace);
+        let label = build_label_query(
+            RESOURCE_TYPE_MCP_SERVER,
+            &workspace_pod_labels(&workspace.template_name, &workspace.subject)
+                .map(|(key, value)| LabelQuery::equal(key, value))
+                .collect::<Vec<_>>(),
+        )?;
+        let lp = ListParams::default().labels(&label);
+        let list = pod_store.list(&lp).await.map_err(AppError::from)?;
+        Ok(list.items.iter().any(is_running))
+    }
+
+    /// Deletes the workspace if it is still idle when read again, the deletion is bound to the
+    /// resource version it was checked at so a session taking it in between keeps it.
+    pub async fn delete_idle(&self, name: &str, now: &DateTime<Utc>) -> Result<bool, AppError> {
+        let Some(workspace) = self.get(name).await? else {
+            return Ok(false);
+        };
+        if workspace.deleted_at.is_some()
+            || !workspace.is_expired(now)
+            || workspace.leased_by(now).is_some()
+            || self.is_in_use(&workspace).await?
+        {
+            return Ok(false);
+        }
+        let dp = DeleteParams {
+            preconditions: Some(Preconditions {
+                resource_version: workspace.raw.resource_version(),
+                uid: workspace.raw.uid(),
+            }),
+            ..Default::default()
+        };
+        match self.api().delete(&workspace.raw.name_any(), &dp).await {
+            Ok(_) => Ok(true),
+            Err(kube::Error::Api(ae)) if ae.code == 409 || ae.code == 404 => Ok(false),
+            Err(err) => Err(AppError::from(err)),
+        }
+    }
+
+    pub async fn delete(&self, name: &str) -> Result<DeleteResult, AppError> {
+        if self.get(name).await?.is_none() {
+            return Err(AppError::NotFound(format!(
+                "Workspace {}/{} not found",
+                self.namespace, name
+            )));
+        }
+        let name = encode_k8sname(RESOURCE_TYPE_PREFIX_WORKSPACE, name);
+        // 사용중인 PVC는 kubernetes.io/pvc-protection 에 의해 세션 종료 후 삭제됨
+        self.api()
+            .delete(&name, &DeleteParams::default())
+            .await
+            .map(|ok| {
+                ok.map_left(|_x| DeleteResult::Deleting)
+                    .map_right(|_x| DeleteResult::Deleted)
+                    .into_inner()
+            })
+            .map_err(AppError::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_subject_hash() {
+        let hash = subject_hash("user-1");
+        assert_eq!(hash.len(), 32);
+        assert!(hash.chars().all(|c| c.is_ascii_hexdigit()));
+        assert_eq!(hash, subject_hash("user-1"));
+        assert_ne!(hash, subject_hash("user-2"));
+    }
+
+    #[test]
+    fn test_workspace_name() {
+        let name = WorkspaceStore::workspace_name("github", "user-1");
+        assert_eq!(name, format!("github-{}", subject_hash("user-1")));
+    }
+
+    #[test]
+    fn test_workspace_pod_labels() {
+        let labels = workspace_pod_labels("github", "user-1").collect::<HashMap<_, _>>();
+        assert_eq!(labels[LABEL_WORKSPACE_TEMPLATE], "github");
+        assert_eq!(labels[LABEL_WORKSPACE_SUBJECT_HASH], subject_hash("user-1"));
+    }
+
+    #[test]
+    fn test_leased_by() {
+        let now = Utc::now();
+        let workspace = |lease: &WorkspaceLease| {
+            WorkspaceData::try_from_pvc(PersistentVolumeClaim {
+                metadata: ObjectMeta {
+                    name: Some(encode_k8sname(RESOURCE_TYPE_PREFIX_WORKSPACE, "github-1")),
+                    annotations: Some(
+                        [(
+                            ANNOTATION_WORKSPACE_LEASE.to_string(),
+                            serde_json::to_string(lease).unwrap(),
+                        )]
+                        .into_iter()
+                        .collect(),
+                    ),
+                    ..Default::default()
+                },
+                ..Default::default()
+            })
+            .unwrap()
+        };
+        let lease = WorkspaceLease {
+            session_id: "session-1".to_string(),
+            expires_at: now + Duration::seconds(LEASE_TTL_SECONDS),
+        };
+        assert_eq!(
+            workspace(&lease).leased_by(&now).as_deref(),
+            Some("session-1")
+        );
+        let expired = WorkspaceLease {
+            expires_at: now - Duration::seconds(1),
+            ..lease
+        };
+        assert_eq!(workspace(&expired).leased_by(&now), None);
+    }
+
+    #[test]
+    fn test_pvc_access_mode() {
+        assert_eq!(
+            pvc_access_mode(WorkspaceAccessMode::ReadWriteOnce),
+            "ReadWriteOnce"
+        );
+        assert_eq!(
+            pvc_access_mode(WorkspaceAccessMode::ReadWriteMany),
+            "ReadWriteMany"
+        );
+    }
+}
@@ -0,0 +1,255 @@
+use std::sync::LazyLock;
+
+use proto::mcp::orchestrator::v1::{ArgEnvSpec, ArgEnvType};
+
+use crate::error::AppError;
+
+static LAZY_ENV_NAME: LazyLock<regex::Regex> =
+    LazyLock::new(|| regex::Regex::new(r"^[A-Za-z0-9_-]+$").unwrap());
+
+static LAZY_URL: LazyLock<regex::Regex> =
+    LazyLock::new(|| regex::Regex::new(r"^[A-Za-z][A-Za-z0-9+.-]*://[^\s/?#]+[^\s]*$").unwrap());
+
+/// Maps the type names of the `ENV_NAME: type` shorthand, `?` suffix is handled by the caller.
+pub fn arg_env_type_from_name(name: &str) -> Option<ArgEnvType> {
+    match name {
+        "string" => Some(ArgEnvType::String),
+        "int" => Some(ArgEnvType::Int),
+        "bool" => Some(ArgEnvType::Bool),
+        "url" => Some(ArgEnvType::Url),
+        _ => None,
+    }
+}
+
+pub fn env_name_of(key: &str, spec: &ArgEnvSpec) -> String {
+    spec.env_name
+        .clone()
+        .filter(|x| !x.is_empty())
+        .unwrap_or_else(|| key.to_string())
+}
+
+fn display_value<'a>(spec: &ArgEnvSpec, value: &'a str) -> &'a str {
+    if spec.secret { "<redacted>" } else { value }
+}
+
+fn check_value(key: &str, spec: &ArgEnvSpec, value: &str) -> Result<String, AppError> {
+    let invalid = |expect: &str| {
+        AppError::InvalidArgEnv(format!(
+            "Argument {} has invalid value {}, expected {}",
+            key,
+            display_value(spec, value),
+            expect
+        ))
+    };
+    match spec.r#type() {
+        ArgEnvType::String => {
+            if let Some(pattern) = &spec.pattern {
+                let regex = regex::Regex::new(&format!("^(?:{})$", pattern))
+                    .map_err(|_| invalid("a valid pattern"))?;
+                if !regex.is_match(value) {
+                    return Err(invalid(&format!("a value matching /{}/", pattern)));
+                }
+            }
+            Ok(value.to_string())
+        }
+        ArgEnvType::Int => value
+            .trim()
+            .parse::<i64>()
+            .map(|x| x.to_string())
+            .map_err(|_| invalid("an integer")),
+        ArgEnvType::Bool => match value.trim().to_ascii_lowercase().as_str() {
+            "true" | "1" | "yes" | "on" => Ok("true".to_string()),
+            "false" | "0" | "no" | "off" => Ok("false".to_string()),
+            _ => Err(invalid("a boolean")),
+        },
+        ArgEnvType::Enum => {
+            if spec.allowed_values.iter().any(|x| x == value) {
+                Ok(value.to_string())
+            } else {
+                Err(invalid(&format!("one of {:?}", spec.allowed_values)))
+            }
+        }
+        ArgEnvType::Url => {
+            if LAZY_URL.is_match(value) {
+                Ok(value.to_string())
+            } else {
+                Err(invalid("an absolute URL"))
+            }
+        }
+    }
+}
+
+pub fn validate_arg_env_spec(key: &str, spec: &ArgEnvSpec) -> Result<(), AppError> {
+    if let Some(env_name) = spec
+        .env_name
+        .as_ref()
+        .filter(|x| !x.is_empty() && !LAZY_ENV_NAME.is_match(x))
+    {
+        return Err(AppError::InvalidArgEnv(format!(
+            "Argument {} has invalid env name {}",
+            key, env_name
+        )));
+    }
+    match spec.r#type() {
+        ArgEnvType::Enum if spec.allowed_values.is_empty() => {
+            return Err(AppError::InvalidArgEnv(format!(
+                "Argument {} is an enum without allowed values",
+                key
+            )));
+        }
+        ArgEnvType::Enum => {}
+        _ if !spec.allowed_values.is_empty() => {
+            return Err(AppError::InvalidArgEnv(format!(
+                "Argument {} declares allowed values but is not an enum",
+                key
+            )));
+        }
+        _ => {}
+    }
+    if let Some(pattern) = &spec.pattern {
+        if spec.r#type() != ArgEnvType::String {
+            return Err(AppError::InvalidArgEnv(format!(
+                "Argument {} declares a pattern but is not a string",
+                key
+            )));
+        }
+        regex::Regex::new(pattern).map_err(|e| {
+            AppError::InvalidArgEnv(format!("Argument {} has invalid pattern: {}", key, e))
+        })?;
+    }
+    if let Some(default_value) = &spec.default_value {
+        check_value(key, spec, default_value)?;
+    }
+    Ok(())
+}
+
+/// Validates the provided value against the spec and returns the value to put into the pod env.
+pub fn resolve_arg_env(
+    key: &str,
+    spec: &ArgEnvSpec,
+    provided: Option<&str>,
+) -> Result<Option<String>, AppError> {
+    match provided.or(spec.default_value.as_deref()) {
+        Some(value) => check_value(key, spec, value).map(Some),
+        None if spec.optional => Ok(None),
+        None => Err(AppError::InvalidArgEnv(format!(
+            "Argument {} is required, provide it with the arg-{} header",
+            key, key
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec(r#type: ArgEnvType) -> ArgEnvSpec {
+        ArgEnvSpec {
+            r#type: r#type.into(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_resolve_required_and_default() {
+        let required = spec(ArgEnvType::String);
+        assert!(resolve_arg_env("token", &required, None).is_err());
+
+        let optional = ArgEnvSpec {
+            optional: true,
+            ..spec(ArgEnvType::String)
+        };
+        assert_eq!(resolve_arg_env("token", &optional, None).unwrap(), None);
+
+        let with_default = ArgEnvSpec {
+            default_value: Some("8080".to_string()),
+            ..spec(ArgEnvType::Int)
+        };
+        assert_eq!(
+            resolve_arg_env("port", &with_default, None).unwrap(),
+            Some("8080".to_string())
+        );
+        assert_eq!(
+            resolve_arg_env("port", &with_default, Some(" 9090 ")).unwrap(),
+            Some("9090".to_string())
+        );
+    }
+
+    #[test]
+    fn test_resolve_typed_values() {
+        assert!(resolve_arg_env("port", &spec(ArgEnvType::Int), Some("abc")).is_err());
+        assert_eq!(
+            resolve_arg_env("debug", &spec(ArgEnvType::Bool), Some("Yes")).unwrap(),
+            Some("true".to_string())
+        );
+        assert!(resolve_arg_env("debug", &spec(ArgEnvType::Bool), Some("maybe")).is_err());
+        assert!(
+            resolve_arg_env(
+                "api",
+                &spec(ArgEnvType::Url),
+                Some("https://example.com/v1")
+            )
+            .is_ok()
+        );
+        assert!(resolve_arg_env("api", &spec(ArgEnvType::Url), Some("example.com")).is_err());
+
+        let region = ArgEnvSpec {
+            allowed_values: vec!["us".to_string(), "eu".to_string()],
+            ..spec(ArgEnvType::Enum)
+        };
+        assert!(resolve_arg_env("region", &region, Some("eu")).is_ok());
+        assert!(resolve_arg_env("region", &region, Some("ap")).is_err());
+
+        let project = ArgEnvSpec {
+            pattern: Some("[a-z]+".to_string()),
+            ..spec(ArgEnvType::String)
+        };
+        assert!(resolve_arg_env("project", &project, Some("abc")).is_ok());
+        assert!(resolve_arg_env("project", &project, Some("abc1")).is_err());
+    }
+
+    #[test]
+    fn test_secret_value_is_redacted() {
+        let secret = ArgEnvSpec {
+            secret: true,
+            ..spec(ArgEnvType::Int)
+        };
+        let err = resolve_arg_env("pin", &secret, Some("hunter2")).unwrap_err();
+        assert!(!err.to_string().contains("hunter2"));
+    }
+
+    #[test]
+    fn test_validate_spec() {
+        assert!(validate_arg_env_spec("region", &spec(ArgEnvType::Enum)).is_err());
+        assert!(
+            validate_arg_env_spec(
+                "port",
+                &ArgEnvSpec {
+                    default_value: Some("http".to_string()),
+                    ..spec(ArgEnvType::Int)
+                }
+            )
+            .is_err()
+        );
+        assert!(
+            validate_arg_env_spec(
+                "port",
+                &ArgEnvSpec {
+                    pattern: Some("[0-9]+".to_string()),
+                    ..spec(ArgEnvType::Int)
+                }
+            )
+            .is_err()
+        );
+        assert!(
+            validate_arg_env_spec(
+                "token",
+                &ArgEnvSpec {
+                    env_name: Some("API TOKEN".to_string()),
+                    ..spec(ArgEnvType::String)
+                }
+            )
+            .is_err()
+        );
+    }
+}
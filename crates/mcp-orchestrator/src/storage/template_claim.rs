@@ -0,0 +1,222 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::LazyLock,
+};
+
+use k8s_openapi::api::authentication::v1::UserInfo;
+use serde_json::{Map, Value};
+
+use crate::{
+    error::AppError,
+    storage::annotations::{ANNOTATION_PREFIX_USER_CLAIM, ANNOTATION_USER_SUBJECT},
+};
+
+static LAZY_CLAIM_ENV_NAME: LazyLock<regex::Regex> =
+    LazyLock::new(|| regex::Regex::new(r"^[A-Za-z_][A-Za-z0-9_]{0,56}$").unwrap());
+
+static LAZY_CLAIM_PATH: LazyLock<regex::Regex> =
+    LazyLock::new(|| regex::Regex::new(r"^[A-Za-z0-9_:/-]+(\.[A-Za-z0-9_:/-]+)*$").unwrap());
+
+const CLAIM_PATH_PREFIX: &str = "claims.";
+
+/// Authenticated user of a session, claims are the validated token claims or TokenReview user info.
+#[derive(Debug, Clone)]
+pub struct SessionIdentity {
+    pub subject: String,
+    pub claims: Map<String, Value>,
+}
+
+impl SessionIdentity {
+    pub fn from_user_info(user: UserInfo) -> Self {
+        let subject = user.username.clone().unwrap_or_default();
+        let mut claims = Map::new();
+        claims.insert("sub".to_string(), Value::String(subject.clone()));
+        if let Some(username) = user.username {
+            claims.insert("username".to_string(), Value::String(username));
+        }
+        if let Some(uid) = user.uid {
+            claims.insert("uid".to_string(), Value::String(uid));
+        }
+        if let Some(groups) = user.groups {
+            claims.insert(
+                "groups".to_string(),
+                Value::Array(groups.into_iter().map(Value::String).collect()),
+            );
+        }
+        if let Some(extra) = user.extra {
+            claims.insert(
+                "extra".to_string(),
+                Value::Object(
+                    extra
+                        .into_iter()
+                        .map(|(k, v)| (k, Value::Array(v.into_iter().map(Value::String).collect())))
+                        .collect(),
+                ),
+            );
+        }
+        Self { subject, claims }
+    }
+
+    /// Identity of a validated JWT, the subject is its `sub` claim.
+    pub fn from_claims(claims: Map<String, Value>) -> Self {
+        let subject = claims
+            .get("sub")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string();
+        Self { subject, claims }
+    }
+
+    pub fn claim_value(&self, path: &str) -> Option<&Value> {
+        let path = path.strip_prefix(CLAIM_PATH_PREFIX).unwrap_or(path);
+        let mut segments = path.split('.');
+        let mut value = self.claims.get(segments.next()?)?;
+        for segment in segments {
+            value = value.as_object()?.get(segment)?;
+        }
+        Some(value)
+    }
+
+    /// Resolves a claim path such as `email` or `claims.tenant.id`.
+    pub fn claim(&self, path: &str) -> Option<String> {
+        let value = self.claim_value(path)?;
+        match value {
+            Value::Null => None,
+            Value::String(s) => Some(s.clone()),
+            Value::Bool(_) | Value::Number(_) => Some(value.to_string()),
+            Value::Array(items) => Some(
+                items
+                    .iter()
+                    .map(|item| match item {
+                        Value::String(s) => s.clone(),
+                        _ => item.to_string(),
+                    })
+                    .collect::<Vec<_>>()
+                    .join(","),
+            ),
+            Value::Object(_) => Some(value.to_string()),
+        }
+    }
+}
+
+pub fn validate_claim_envs(claim_envs: &HashMap<String, String>) -> Result<(), AppError> {
+    for (env_name, path) in claim_envs {
+        if !LAZY_CLAIM_ENV_NAME.is_match(env_name) {
+            return Err(AppError::InvalidInput(format!(
+                "Claim env name '{}' must match [A-Za-z_][A-Za-z0-9_]* and be at most 57 characters",
+                env_name
+            )));
+        }
+        if !LAZY_CLAIM_PATH.is_match(path) {
+            return Err(AppError::InvalidInput(format!(
+                "Claim path '{}' of {} is invalid",
+                path, env_name
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Resolves the claim envs of the identity, claims missing from the identity are skipped.
+pub fn resolve_claim_envs(
+    claim_envs: &HashMap<String, String>,
+    identity: &SessionIdentity,
+) -> BTreeMap<String, String> {
+    claim_envs
+        .iter()
+        .filter_map(|(env_name, path)| {
+            let value = identity.claim(path);
+            if value.is_none() {
+                tracing::debug!("Claim {} not found for {}, skipping", path, env_name);
+            }
+            Some((env_name.clone(), value?))
+        })
+        .collect()
+}
+
+pub fn identity_annotations(
+    identity: &SessionIdentity,
+    resolved: &BTreeMap<String, String>,
+) -> BTreeMap<String, String> {
+    std::iter::once((
+        ANNOTATION_USER_SUBJECT.to_string(),
+        identity.subject.clone(),
+    ))
+    .chain(resolved.iter().map(|(env_name, value)| {
+        (
+            format!("{}{}", ANNOTATION_PREFIX_USER_CLAIM, env_name),
+            value.clone(),
+        )
+    }))
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn identity() -> SessionIdentity {
+        let claims = serde_json::json!({
+            "sub": "user-1",
+            "email": "user@example.com",
+            "email_verified": true,
+            "groups": ["dev", "ops"],
+            "tenant": { "id": "acme" },
+        });
+        SessionIdentity {
+            subject: "user-1".to_string(),
+            claims: claims.as_object().unwrap().clone(),
+        }
+    }
+
+    #[test]
+    fn test_claim_path() {
+        let identity = identity();
+        assert_eq!(identity.claim("email").as_deref(), Some("user@example.com"));
+        assert_eq!(
+            identity.claim("claims.email_verified").as_deref(),
+            Some("true")
+        );
+        assert_eq!(identity.claim("groups").as_deref(), Some("dev,ops"));
+        assert_eq!(identity.claim("claims.tenant.id").as_deref(), Some("acme"));
+        assert_eq!(identity.claim("claims.tenant.name"), None);
+    }
+
+    #[test]
+    fn test_validate_claim_envs() {
+        let valid = [("USER_EMAIL".to_string(), "email".to_string())].into();
+        assert!(validate_claim_envs(&valid).is_ok());
+        let invalid_env = [("USER-EMAIL".to_string(), "email".to_string())].into();
+        assert!(validate_claim_envs(&invalid_env).is_err());
+        let invalid_path = [("TENANT".to_string(), "claims..tenant".to_string())].into();
+        assert!(validate_claim_envs(&invalid_path).is_err());
+    }
+
+    #[test]
+    fn test_from_user_info() {
+        let identity = SessionIdentity::from_user_info(UserInfo {
+            username: Some("system:serviceaccount:default:sa-github".to_string()),
+            groups: Some(vec!["system:serviceaccounts".to_string()]),
+            ..Default::default()
+        });
+        assert_eq!(identity.subject, "system:serviceaccount:default:sa-github");
+        assert_eq!(
+            identity.claim("username").as_deref(),
+            Some("system:serviceaccount:default:sa-github")
+        );
+        let resolved = resolve_claim_envs(
+            &[
+                ("GROUPS".to_string(), "groups".to_string()),
+                ("EMAIL".to_string(), "email".to_string()),
+            ]
+            .into(),
+            &identity,
+        );
+        assert_eq!(resolved.len(), 1);
+        let annotations = identity_annotations(&identity, &resolved);
+        assert_eq!(
+            annotations.get("mcp-orchestrator.egoavara.net/claim-GROUPS"),
+            Some(&"system:serviceaccounts".to_string())
+        );
+    }
+}
@@ -0,0 +1,346 @@
+use std::{
+    collections::{BTreeMap, HashSet},
+    sync::LazyLock,
+};
+
+use k8s_openapi::{
+    api::core::v1::{Container, EnvVar, ResourceRequirements, VolumeMount},
+    apimachinery::pkg::api::resource::Quantity,
+};
+use proto::mcp::orchestrator::v1::{self, TemplateContainer};
+
+use crate::{error::AppError, storage::template_volume::volume_mount_name};
+
+pub const MAIN_CONTAINER_NAME: &str = "main";
+
+const DEFAULT_INIT_PERCENT: u32 = 100;
+
+static LAZY_CONTAINER_NAME: LazyLock<regex::Regex> =
+    LazyLock::new(|| regex::Regex::new(r"^[a-z0-9]([-a-z0-9]{0,61}[a-z0-9])?$").unwrap());
+
+static LAZY_ENV_NAME: LazyLock<regex::Regex> =
+    LazyLock::new(|| regex::Regex::new(r"^[A-Za-z_][A-Za-z0-9_.-]*$").unwrap());
+
+const QUANTITY_SUFFIXES: [(&str, f64); 15] = [
+    ("Ki", 1024.0),
+    ("Mi", 1024.0 * 1024.0),
+    ("Gi", 1024.0 * 1024.0 * 1024.0),
+    ("Ti", 1024.0 * 1024.0 * 1024.0 * 1024.0),
+    ("Pi", 1024.0 * 1024.0 * 1024.0 * 1024.0 * 1024.0),
+    ("Ei", 1024.0 * 1024.0 * 1024.0 * 1024.0 * 1024.0 * 1024.0),
+    ("n", 1e-9),
+    ("u", 1e-6),
+    ("m", 1e-3),
+    ("k", 1e3),
+    ("M", 1e6),
+    ("G", 1e9),
+    ("T", 1e12),
+    ("P", 1e15),
+    ("E", 1e18),
+];
+
+/// Parses a Kubernetes quantity into cores or bytes.
+pub fn parse_quantity(value: &str) -> Option<f64> {
+    let value = value.trim();
+    for (suffix, factor) in QUANTITY_SUFFIXES {
+        if let Some(number) = value.strip_suffix(suffix) {
+            return number.parse::<f64>().ok().map(|x| x * factor);
+        }
+    }
+    value.parse::<f64>().ok()
+}
+
+fn scale_quantity(resource: &str, quantity: &Quantity, percent: u32) -> Result<Quantity, AppError> {
+    let value = parse_quantity(&quantity.0).ok_or_else(|| {
+        AppError::Internal(format!("Invalid {} quantity: {}", resource, quantity.0))
+    })?;
+    let scaled = value * percent as f64 / 100.0;
+    Ok(if resource == "cpu" {
+        Quantity(format!("{}m", (scaled * 1000.0).ceil().max(1.0) as u64))
+    } else {
+        Quantity(format!("{}", scaled.ceil().max(1.0) as u64))
+    })
+}
+
+/// Gives `percent` of every request and limit of the ResourceLimit to a container.
+pub fn scale_requirements(
+    requirement: &ResourceRequirements,
+    percent: u32,
+) -> Result<ResourceRequirements, AppError> {
+    if percent >= 100 {
+        return Ok(requirement.clone());
+    }
+    let scale = |values: &Option<BTreeMap<String, Quantity>>| {
+        values
+            .as_ref()
+            .map(|values| {
+                values
+                    .iter()
+                    .map(|(resource, quantity)| {
+                        Ok((
+                            resource.clone(),
+                            scale_quantity(resource, quantity, percent)?,
+                        ))
+                    })
+                    .collect::<Result<BTreeMap<_, _>, AppError>>()
+            })
+            .transpose()
+    };
+    Ok(ResourceRequirements {
+        requests: scale(&requirement.requests)?,
+        limits: scale(&requirement.limits)?,
+        ..Default::default()
+    })
+}
+
+/// Percent of the ResourceLimit left to the MCP container.
+pub fn main_container_percent(sidecars: &[TemplateContainer]) -> u32 {
+    100u32.saturating_sub(
+        sidecars
+            .iter()
+            .map(|x| x.resource_percent.unwrap_or_default())
+            .sum(),
+    )
+}
+
+fn validate_container(
+    container: &TemplateContainer,
+    volume_mounts: &[v1::VolumeMount],
+) -> Result<(), AppError> {
+    if !LAZY_CONTAINER_NAME.is_match(&container.name) || container.name == MAIN_CONTAINER_NAME {
+        return Err(AppError::InvalidInput(format!(
+            "Invalid container name '{}', it must be a DNS label other than {}",
+            container.name, MAIN_CONTAINER_NAME
+        )));
+    }
+    if container.image.trim().is_empty() {
+        return Err(AppError::InvalidInput(format!(
+            "Container {} requires an image",
+            container.name
+        )));
+    }
+    for env_name in container.envs.keys() {
+        if !LAZY_ENV_NAME.is_match(env_name) {
+            return Err(AppError::InvalidInput(format!(
+                "Invalid env name {} of container {}",
+                env_name, container.name
+            )));
+        }
+    }
+    for mount in &container.volume_mounts {
+        if volume_mount_name(volume_mounts, &mount.pattern).is_none() {
+            return Err(AppError::InvalidInput(format!(
+                "Volume '{}' of container {} is not mounted by the template",
+                mount.pattern, container.name
+            )));
+        }
+        if !mount.mount_path.starts_with('/') {
+            return Err(AppError::InvalidInput(format!(
+                "Mount path '{}' of container {} must be absolute",
+                mount.mount_path, container.name
+            )));
+        }
+    }
+    Ok(())
+}
+
+pub fn validate_containers(
+    init_containers: &[TemplateContainer],
+    sidecars: &[TemplateContainer],
+    volume_mounts: &[v1::VolumeMount],
+) -> Result<(), AppError> {
+    let mut names = HashSet::new();
+    for container in init_containers.iter().chain(sidecars) {
+        validate_container(container, volume_mounts)?;
+        if !names.insert(container.name.as_str()) {
+            return Err(AppError::InvalidInput(format!(
+                "Container name {} is used more than once",
+                container.name
+            )));
+        }
+    }
+    for container in init_containers {
+        if container
+            .resource_percent
+            .is_some_and(|percent| percent == 0 || percent > 100)
+        {
+            return Err(AppError::InvalidInput(format!(
+                "Resource percent of init container {} must be between 1 and 100",
+                container.name
+            )));
+        }
+    }
+    let mut total = 0;
+    for container in sidecars {
+        let percent = container.resource_percent.unwrap_or_default();
+        if percent == 0 {
+            return Err(AppError::InvalidInput(format!(
+                "Sidecar {} requires a resource percent",
+                container.name
+            )));
+        }
+        total += percent;
+    }
+    if total >= 100 {
+        return Err(AppError::InvalidInput(format!(
+            "Sidecars take {}% of the ResourceLimit, the MCP container needs the rest",
+            total
+        )));
+    }
+    Ok(())
+}
+
+fn build_container(
+    container: &TemplateContainer,
+    percent: u32,
+    requirement: &ResourceRequirements,
+    volume_mounts: &[v1::VolumeMount],
+) -> Result<Container, AppError> {
+    let mounts = container
+        .volume_mounts
+        .iter()
+        .map(|mount| {
+            let name = volume_mount_name(volume_mounts, &mount.pattern).ok_or_else(|| {
+                AppError::InvalidInput(format!(
+                    "Volume '{}' of container {} is not mounted by the template",
+                    mount.pattern, container.name
+                ))
+            })?;
+            Ok(VolumeMount {
+                name,
+                mount_path: mount.mount_path.clone(),
+                ..Default::default()
+            })
+        })
+        .collect::<Result<Vec<_>, AppError>>()?;
+    Ok(Container {
+        name: container.name.clone(),
+        image: Some(container.image.clone()),
+        command: Some(container.command.clone()).filter(|x| !x.is_empty()),
+        args: Some(container.args.clone()).filter(|x| !x.is_empty()),
+        env: Some(
+            container
+                .envs
+                .iter()
+                .collect::<BTreeMap<_, _>>()
+                .into_iter()
+                .map(|(name, value)| EnvVar {
+                    name: name.clone(),
+                    value: Some(value.clone()),
+                    ..Default::default()
+                })
+                .collect(),
+        ),
+        resources: Some(scale_requirements(requirement, percent)?),
+        volume_mounts: Some(mounts),
+        ..Default::default()
+    })
+}
+
+pub fn build_init_containers(
+    init_containers: &[TemplateContainer],
+    requirement: &ResourceRequirements,
+    volume_mounts: &[v1::VolumeMount],
+) -> Result<Vec<Container>, AppError> {
+    init_containers
+        .iter()
+        .map(|container| {
+            let percent = container.resource_percent.unwrap_or(DEFAULT_INIT_PERCENT);
+            build_container(container, percent, requirement, volume_mounts)
+        })
+        .collect()
+}
+
+pub fn build_sidecars(
+    sidecars: &[TemplateContainer],
+    requirement: &ResourceRequirements,
+    volume_mounts: &[v1::VolumeMount],
+) -> Result<Vec<Container>, AppError> {
+    sidecars
+        .iter()
+        .map(|container| {
+            let percent = container.resource_percent.unwrap_or_default();
+            build_container(container, percent, requirement, volume_mounts)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn requirement() -> ResourceRequirements {
+        ResourceRequirements {
+            requests: Some(
+                [
+                    ("cpu".to_string(), Quantity("500m".to_string())),
+                    ("memory".to_string(), Quantity("1Gi".to_string())),
+                ]
+                .into(),
+            ),
+            limits: Some(
+                [
+                    ("cpu".to_string(), Quantity("2".to_string())),
+                    ("memory".to_string(), Quantity("2G".to_string())),
+                ]
+                .into(),
+            ),
+            ..Default::default()
+        }
+    }
+
+    fn container(name: &str, percent: Option<u32>) -> TemplateContainer {
+        TemplateContainer {
+            name: name.to_string(),
+            image: "busybox:1.36".to_string(),
+            resource_percent: percent,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_scale_requirements() {
+        let scaled = scale_requirements(&requirement(), 25).unwrap();
+        let requests = scaled.requests.unwrap();
+        assert_eq!(requests["cpu"].0, "125m");
+        assert_eq!(requests["memory"].0, "268435456");
+        let limits = scaled.limits.unwrap();
+        assert_eq!(limits["cpu"].0, "500m");
+        assert_eq!(limits["memory"].0, "500000000");
+        assert_eq!(
+            scale_requirements(&requirement(), 100).unwrap(),
+            requirement()
+        );
+    }
+
+    #[test]
+    fn test_validate_containers() {
+        let mounts = vec![v1::VolumeMount {
+            pattern: "cache".to_string(),
+            mount_path: "/data".to_string(),
+        }];
+        let mut init = container("clone", None);
+        init.volume_mounts = vec![v1::VolumeMount {
+            pattern: "cache".to_string(),
+            mount_path: "/repo".to_string(),
+        }];
+        let sidecar = container("db", Some(30));
+        assert!(
+            validate_containers(&[init.clone()], std::slice::from_ref(&sidecar), &mounts).is_ok()
+        );
+        assert_eq!(main_container_percent(std::slice::from_ref(&sidecar)), 70);
+
+        assert!(validate_containers(&[init.clone()], std::slice::from_ref(&sidecar), &[]).is_err());
+        assert!(validate_containers(&[], &[container("main", Some(10))], &mounts).is_err());
+        assert!(validate_containers(&[], &[container("db", None)], &mounts).is_err());
+        assert!(
+            validate_containers(
+                &[],
+                &[container("db", Some(60)), container("proxy", Some(40))],
+                &mounts
+            )
+            .is_err()
+        );
+        assert!(validate_containers(&[container("db", None)], &[sidecar], &mounts).is_err());
+    }
+}
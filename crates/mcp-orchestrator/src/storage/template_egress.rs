@@ -0,0 +1,541 @@
+use std::{collections::BTreeMap, net::IpAddr};
+
+use k8s_openapi::{
+    api::{
+        core::v1::{ConfigMap, Service, ServiceSpec},
+        networking::v1::{
+            IPBlock, NetworkPolicy, NetworkPolicyEgressRule, NetworkPolicyPeer, NetworkPolicyPort,
+            NetworkPolicySpec,
+        },
+    },
+    apimachinery::pkg::{apis::meta::v1::LabelSelector, util::intstr::IntOrString},
+};
+use kube::{
+    Api, Client, Resource, ResourceExt,
+    api::{DeleteParams, ObjectMeta, PostParams},
+};
+use proto::mcp::orchestrator::v1::{EgressPolicy, EgressPolicyType, EgressPort};
+
+use crate::{
+    error::AppError,
+    storage::{
+        labels::{LABEL_TYPE_OF, label_dependency, label_dependency_tuple, setup_labels},
+        resource_type::{
+            RESOURCE_TYPE_EGRESS_POLICY, RESOURCE_TYPE_MCP_SERVER, RESOURCE_TYPE_MCP_TEMPLATE,
+        },
+        scheduling_validation::validate_dns_subdomain,
+    },
+};
+
+const LABEL_NAMESPACE_NAME: &str = "kubernetes.io/metadata.name";
+const DNS_NAMESPACE: &str = "kube-system";
+const DNS_PORT: i32 = 53;
+const PROTOCOLS: [&str; 3] = ["TCP", "UDP", "SCTP"];
+
+fn validate_cidr(cidr: &str) -> Result<(), AppError> {
+    let valid = cidr.split_once('/').is_some_and(|(ip, prefix)| {
+        let max = match ip.parse::<IpAddr>() {
+            Ok(IpAddr::V4(_)) => 32,
+            Ok(IpAddr::V6(_)) => 128,
+            Err(_) => return false,
+        };
+        prefix.parse::<u8>().is_ok_and(|x| x <= max)
+    });
+    if valid {
+        Ok(())
+    } else {
+        Err(AppError::InvalidInput(format!(
+            "Invalid CIDR {}, use a form like 10.0.0.0/8",
+            cidr
+        )))
+    }
+}
+
+fn validate_port(port: &EgressPort) -> Result<(), AppError> {
+    if port.port == 0 || port.port > 65535 {
+        return Err(AppError::InvalidInput(format!(
+            "Invalid egress port {}",
+            port.port
+        )));
+    }
+    if let Some(protocol) = port
+        .protocol
+        .as_ref()
+        .filter(|x| !PROTOCOLS.contains(&x.as_str()))
+    {
+        return Err(AppError::InvalidInput(format!(
+            "Invalid egress protocol {}, it must be one of {}",
+            protocol,
+            PROTOCOLS.join(", ")
+        )));
+    }
+    Ok(())
+}
+
+/// Splits `namespace/name` of a service rule, the namespace defaults to the template namespace.
+fn split_service<'a>(service: &'a str, namespace: &'a str) -> (&'a str, &'a str) {
+    service.split_once('/').unwrap_or((namespace, service))
+}
+
+pub fn validate_egress_policy(policy: &EgressPolicy) -> Result<(), AppError> {
+    let allowlist = policy.r#type() == EgressPolicyType::Allowlist;
+    if !allowlist && !policy.rules.is_empty() {
+        return Err(AppError::InvalidInput(
+            "Egress rules are only allowed for the ALLOWLIST policy".to_string(),
+        ));
+    }
+    if allowlist && policy.rules.is_empty() {
+        return Err(AppError::InvalidInput(
+            "ALLOWLIST egress policy requires at least one rule".to_string(),
+        ));
+    }
+    for rule in &policy.rules {
+        match (&rule.cidr, &rule.service) {
+            (Some(cidr), None) => {
+                validate_cidr(cidr)?;
+                for except in &rule.except {
+                    validate_cidr(except)?;
+                }
+            }
+            (None, Some(service)) => {
+                if !rule.except.is_empty() {
+                    return Err(AppError::InvalidInput(format!(
+                        "Egress rule of service {} can not have except",
+                        service
+                    )));
+                }
+                let (namespace, name) = split_service(service, "default");
+                validate_dns_subdomain(namespace)?;
+                validate_dns_subdomain(name)?;
+            }
+            _ => {
+                return Err(AppError::InvalidInput(
+                    "Egress rule requires exactly one of cidr and service".to_string(),
+                ));
+            }
+        }
+        for port in &rule.ports {
+            validate_port(port)?;
+        }
+    }
+    Ok(())
+}
+
+/// Session pods of the template.
+fn session_pod_selector(template_name: &str) -> LabelSelector {
+    LabelSelector {
+        match_labels: Some(
+            label_dependency(RESOURCE_TYPE_MCP_TEMPLATE, template_name)
+                .chain([(
+                    LABEL_TYPE_OF.to_string(),
+                    RESOURCE_TYPE_MCP_SERVER.to_string(),
+                )])
+                .collect(),
+        ),
+        ..Default::default()
+    }
+}
+
+fn namespace_selector(namespace: &str) -> LabelSelector {
+    LabelSelector {
+        match_labels: Some(BTreeMap::from([(
+            LABEL_NAMESPACE_NAME.to_string(),
+            namespace.to_string(),
+        )])),
+        ..Default::default()
+    }
+}
+
+fn policy_ports(ports: &[EgressPort]) -> Option<Vec<NetworkPolicyPort>> {
+    let ports = ports
+        .iter()
+        .map(|port| NetworkPolicyPort {
+            port: Some(IntOrString::Int(port.port as i32)),
+            protocol: Some(port.protocol.clone().unwrap_or_else(|| "TCP".to_string())),
+            ..Default::default()
+        })
+        .collect::<Vec<_>>();
+    Some(ports).filter(|x| !x.is_empty())
+}
+
+/// NetworkPolicy matches the ports of the target pods, so service ports are resolved to their targetPort.
+fn service_policy_ports(
+    service: &str,
+    spec: &ServiceSpec,
+    ports: &[EgressPort],
+) -> Result<Option<Vec<NetworkPolicyPort>>, AppError> {
+    let service_ports = spec.ports.as_deref().unwrap_or_default();
+    let ports = ports
+        .iter()
+        .map(|port| {
+            let protocol = port.protocol.clone().unwrap_or_else(|| "TCP".to_string());
+            let service_port = service_ports
+                .iter()
+                .find(|x| {
+                    x.port == port.port as i32 && x.protocol.as_deref().unwrap_or("TCP") == protocol
+                })
+                .ok_or_else(|| {
+                    AppError::InvalidInput(format!(
+                        "Service {} does not expose port {}/{}",
+                        service, port.port, protocol
+                    ))
+                })?;
+            Ok(NetworkPolicyPort {
+                port: Some(
+                    service_port
+                        .target_port
+                        .clone()
+                        .unwrap_or(IntOrString::Int(service_port.port)),
+                ),
+                protocol: Some(protocol),
+                ..Default::default()
+            })
+        })
+        .collect::<Result<Vec<_>, AppError>>()?;
+    Ok(Some(ports).filter(|x| !x.is_empty()))
+}
+
+fn dns_rule() -> NetworkPolicyEgressRule {
+    NetworkPolicyEgressRule {
+        to: Some(vec![NetworkPolicyPeer {
+            namespace_selector: Some(namespace_selector(DNS_NAMESPACE)),
+            ..Default::default()
+        }]),
+        ports: Some(
+            ["UDP", "TCP"]
+                .into_iter()
+                .map(|protocol| NetworkPolicyPort {
+                    port: Some(IntOrString::Int(DNS_PORT)),
+                    protocol: Some(protocol.to_string()),
+                    ..Default::default()
+                })
+                .collect(),
+        ),
+    }
+}
+
+/// Renders the egress rules, `services` holds the spec of every service rule.
+fn egress_rules(
+    policy: &EgressPolicy,
+    namespace: &str,
+    services: &BTreeMap<String, ServiceSpec>,
+) -> Result<Vec<NetworkPolicyEgressRule>, AppError> {
+    let mut rules = match policy.r#type() {
+        EgressPolicyType::Unrestricted | EgressPolicyType::DenyAll => return Ok(vec![]),
+        EgressPolicyType::DnsOnly | EgressPolicyType::Allowlist => vec![dns_rule()],
+    };
+    for rule in &policy.rules {
+        let (peer, ports) = match (&rule.cidr, &rule.service) {
+            (Some(cidr), _) => (
+                NetworkPolicyPeer {
+                    ip_block: Some(IPBlock {
+                        cidr: cidr.clone(),
+                        except: Some(rule.except.clone()).filter(|x| !x.is_empty()),
+                    }),
+                    ..Default::default()
+                },
+                policy_ports(&rule.ports),
+            ),
+            (None, Some(service)) => {
+                let (service_namespace, _) = split_service(service, namespace);
+                let spec = services.get(service).ok_or_else(|| {
+                    AppError::NotFound(format!("Service {} of egress rule not found", service))
+                })?;
+                let peer = NetworkPolicyPeer {
+                    namespace_selector: Some(namespace_selector(service_namespace)),
+                    pod_selector: Some(LabelSelector {
+                        match_labels: spec.selector.clone(),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                };
+                (peer, service_policy_ports(service, spec, &rule.ports)?)
+            }
+            (None, None) => continue,
+        };
+        rules.push(NetworkPolicyEgressRule {
+            to: Some(vec![peer]),
+            ports,
+        });
+    }
+    Ok(rules)
+}
+
+/// Specs of the in-cluster services referenced by the policy, each with a pod selector.
+async fn resolve_services(
+    client: Client,
+    policy: &EgressPolicy,
+    namespace: &str,
+) -> Result<BTreeMap<String, ServiceSpec>, AppError> {
+    let mut services = BTreeMap::new();
+    for service in policy.rules.iter().filter_map(|x| x.service.as_ref()) {
+        let (service_namespace, name) = split_service(service, namespace);
+        let api = Api::<Service>::namespaced(client.clone(), service_namespace);
+        let Some(found) = api.get_opt(name).await? else {
+            return Err(AppError::NotFound(format!(
+                "Service {} of egress rule not found",
+                service
+            )));
+        };
+        let spec = found
+            .spec
+            .filter(|x| x.selector.as_ref().is_some_and(|x| !x.is_empty()))
+            .ok_or_else(|| {
+                AppError::InvalidInput(format!(
+                    "Service {} has no pod selector, use a cidr rule instead",
+                    service
+                ))
+            })?;
+        services.insert(service.clone(), spec);
+    }
+    Ok(services)
+}
+
+/// Creates or replaces the NetworkPolicy of the template, owned by the template ConfigMap.
+/// An UNRESTRICTED policy removes it.
+pub async fn ensure_egress_policy(
+    client: Client,
+    template: &ConfigMap,
+    template_name: &str,
+    policy: Option<&EgressPolicy>,
+    pp: &PostParams,
+) -> Result<(), AppError> {
+    let namespace = template
+        .namespace()
+        .unwrap_or_else(|| "default".to_string());
+    let api = Api::<NetworkPolicy>::namespaced(client.clone(), &namespace);
+    let existing = api.get_opt(&template.name_any()).await?;
+    let Some(policy) = policy.filter(|x| x.r#type() != EgressPolicyType::Unrestricted) else {
+        if existing.is_some() {
+            let dp = DeleteParams {
+                dry_run: pp.dry_run,
+                ..Default::default()
+            };
+            tracing::info!(
+                "Deleting egress policy {}/{}",
+                namespace,
+                template.name_any()
+            );
+            api.delete(&template.name_any(), &dp).await?;
+        }
+        return Ok(());
+    };
+    let services = resolve_services(client.clone(), policy, &namespace).await?;
+    let mut network_policy = NetworkPolicy {
+        metadata: ObjectMeta {
+            name: Some(template.name_any()),
+            namespace: Some(namespace.clone()),
+            labels: Some(
+                setup_labels(RESOURCE_TYPE_EGRESS_POLICY, std::iter::empty())
+                    .chain([label_dependency_tuple(
+                        RESOURCE_TYPE_MCP_TEMPLATE,
+                        template_name,
+                    )])
+                    .collect(),
+            ),
+            owner_references: template.controller_owner_ref(&()).map(|x| vec![x]),
+            ..Default::default()
+        },
+        spec: Some(NetworkPolicySpec {
+            pod_selector: Some(session_pod_selector(template_name)),
+            policy_types: Some(vec!["Egress".to_string()]),
+            egress: Some(egress_rules(policy, &namespace, &services)?),
+            ..Default::default()
+        }),
+    };
+
+    match existing {
+        Some(existing) => {
+            tracing::info!(
+                "Replacing egress policy {}/{}",
+                namespace,
+                network_policy.name_any()
+            );
+            network_policy.metadata.resource_version = existing.resource_version();
+            api.replace(&network_policy.name_any(), pp, &network_policy)
+                .await?;
+        }
+        None => {
+            tracing::info!(
+                "Creating egress policy {}/{}",
+                namespace,
+                network_policy.name_any()
+            );
+            api.create(pp, &network_policy).await?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use k8s_openapi::api::core::v1::ServicePort;
+    use proto::mcp::orchestrator::v1::EgressRule;
+
+    fn policy(r#type: EgressPolicyType, rules: Vec<EgressRule>) -> EgressPolicy {
+        EgressPolicy {
+            r#type: r#type.into(),
+            rules,
+        }
+    }
+
+    fn cidr_rule(cidr: &str, port: u32) -> EgressRule {
+        EgressRule {
+            cidr: Some(cidr.to_string()),
+            ports: vec![EgressPort {
+                port,
+                protocol: None,
+            }],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_validate_egress_policy() {
+        assert!(validate_egress_policy(&policy(EgressPolicyType::DenyAll, vec![])).is_ok());
+        assert!(validate_egress_policy(&policy(EgressPolicyType::Allowlist, vec![])).is_err());
+        assert!(
+            validate_egress_policy(&policy(
+                EgressPolicyType::DnsOnly,
+                vec![cidr_rule("10.0.0.0/8", 443)]
+            ))
+            .is_err()
+        );
+        assert!(
+            validate_egress_policy(&policy(
+                EgressPolicyType::Allowlist,
+                vec![cidr_rule("10.0.0.0/33", 443)]
+            ))
+            .is_err()
+        );
+        assert!(
+            validate_egress_policy(&policy(
+                EgressPolicyType::Allowlist,
+                vec![cidr_rule("10.0.0.0/8", 0)]
+            ))
+            .is_err()
+        );
+        assert!(
+            validate_egress_policy(&policy(
+                EgressPolicyType::Allowlist,
+                vec![
+                    cidr_rule("2001:db8::/32", 443),
+                    EgressRule {
+                        service: Some("databases/postgres".to_string()),
+                        ..Default::default()
+                    },
+                ]
+            ))
+            .is_ok()
+        );
+    }
+
+    fn service_spec(ports: Vec<ServicePort>) -> ServiceSpec {
+        ServiceSpec {
+            selector: Some(BTreeMap::from([(
+                "app".to_string(),
+                "postgres".to_string(),
+            )])),
+            ports: Some(ports),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_egress_rules() {
+        let services = BTreeMap::from([("postgres".to_string(), service_spec(vec![]))]);
+        let rules = egress_rules(
+            &policy(
+                EgressPolicyType::Allowlist,
+                vec![
+                    cidr_rule("203.0.113.7/32", 443),
+                    EgressRule {
+                        service: Some("postgres".to_string()),
+                        ..Default::default()
+                    },
+                ],
+            ),
+            "tenant-a",
+            &services,
+        )
+        .unwrap();
+        assert_eq!(rules.len(), 3);
+        assert_eq!(rules[0].ports.as_ref().unwrap().len(), 2);
+        assert_eq!(
+            rules[1].to.as_ref().unwrap()[0]
+                .ip_block
+                .as_ref()
+                .unwrap()
+                .cidr,
+            "203.0.113.7/32"
+        );
+        let peer = &rules[2].to.as_ref().unwrap()[0];
+        assert_eq!(
+            peer.namespace_selector.as_ref().unwrap().match_labels,
+            Some(BTreeMap::from([(
+                LABEL_NAMESPACE_NAME.to_string(),
+                "tenant-a".to_string()
+            )]))
+        );
+        assert!(rules[2].ports.is_none());
+
+        assert!(
+            egress_rules(
+                &policy(EgressPolicyType::DenyAll, vec![]),
+                "tenant-a",
+                &services
+            )
+            .unwrap()
+            .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_service_policy_ports() {
+        let spec = service_spec(vec![
+            ServicePort {
+                port: 80,
+                target_port: Some(IntOrString::Int(8080)),
+                ..Default::default()
+            },
+            ServicePort {
+                port: 5432,
+                target_port: Some(IntOrString::String("postgres".to_string())),
+                ..Default::default()
+            },
+            ServicePort {
+                port: 53,
+                protocol: Some("UDP".to_string()),
+                ..Default::default()
+            },
+        ]);
+        let port = |port: u32, protocol: Option<&str>| EgressPort {
+            port,
+            protocol: protocol.map(str::to_string),
+        };
+        let ports = service_policy_ports(
+            "postgres",
+            &spec,
+            &[port(80, None), port(5432, None), port(53, Some("UDP"))],
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(ports[0].port, Some(IntOrString::Int(8080)));
+        assert_eq!(
+            ports[1].port,
+            Some(IntOrString::String("postgres".to_string()))
+        );
+        // targetPort 가 없으면 port 와 같음
+        assert_eq!(ports[2].port, Some(IntOrString::Int(53)));
+        assert_eq!(ports[2].protocol.as_deref(), Some("UDP"));
+
+        assert!(
+            service_policy_ports("postgres", &spec, &[])
+                .unwrap()
+                .is_none()
+        );
+        assert!(service_policy_ports("postgres", &spec, &[port(8080, None)]).is_err());
+        assert!(service_policy_ports("postgres", &spec, &[port(53, None)]).is_err());
+    }
+}
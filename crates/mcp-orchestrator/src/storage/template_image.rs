@@ -0,0 +1,365 @@
+use std::time::Duration;
+
+use reqwest::{StatusCode, header};
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::{
+    config::{ImageAdmissionConfig, ImageAdmissionPolicy},
+    error::AppError,
+};
+
+const DOCKER_HUB: &str = "docker.io";
+const DOCKER_HUB_REGISTRY: &str = "registry-1.docker.io";
+const DOCKER_HUB_INDEX: &str = "index.docker.io";
+const DEFAULT_TAG: &str = "latest";
+const RESOLVE_TIMEOUT: Duration = Duration::from_secs(10);
+const MANIFEST_ACCEPT: &str = "application/vnd.oci.image.index.v1+json, application/vnd.docker.distribution.manifest.list.v2+json, application/vnd.oci.image.manifest.v1+json, application/vnd.docker.distribution.manifest.v2+json";
+
+#[derive(Debug, PartialEq)]
+pub struct ImageReference {
+    pub registry: String,
+    pub repository: String,
+    pub tag: Option<String>,
+    pub digest: Option<String>,
+}
+
+impl ImageReference {
+    /// `registry/repository`, the subject of the allowlist globs.
+    pub fn name(&self) -> String {
+        format!("{}/{}", self.registry, self.repository)
+    }
+}
+
+/// Parses an image reference the way the container runtime does,
+/// `node` is `docker.io/library/node:latest`.
+pub fn parse_image_reference(image: &str) -> Result<ImageReference, AppError> {
+    let invalid = || AppError::InvalidInput(format!("Invalid image reference {}", image));
+    let (name, digest) = match image.split_once('@') {
+        Some((name, digest)) => {
+            let valid = digest
+                .split_once(':')
+                .is_some_and(|(algorithm, hex)| !algorithm.is_empty() && !hex.is_empty());
+            if !valid {
+                return Err(invalid());
+            }
+            (name, Some(digest.to_string()))
+        }
+        None => (image, None),
+    };
+    let (name, tag) = match name.rsplit_once(':') {
+        Some((name, tag)) if !tag.contains('/') => (name, Some(tag.to_string())),
+        _ => (name, None),
+    };
+    let (registry, repository) = match name.split_once('/') {
+        Some((registry, repository))
+            if registry.contains('.') || registry.contains(':') || registry == "localhost" =>
+        {
+            (registry.to_string(), repository.to_string())
+        }
+        _ => (DOCKER_HUB.to_string(), name.to_string()),
+    };
+    let repository = if registry == DOCKER_HUB && !repository.contains('/') {
+        format!("library/{}", repository)
+    } else {
+        repository
+    };
+    if repository.is_empty()
+        || repository.split('/').any(|x| x.is_empty())
+        || tag.as_deref() == Some("")
+    {
+        return Err(invalid());
+    }
+    Ok(ImageReference {
+        registry,
+        repository,
+        tag,
+        digest,
+    })
+}
+
+/// `*` matches any characters, including `/`.
+fn glob_matches(glob: &str, value: &str) -> bool {
+    let pattern = regex::escape(glob).replace(r"\*", ".*");
+    regex::Regex::new(&format!("^{}$", pattern)).is_ok_and(|x| x.is_match(value))
+}
+
+pub fn check_image(policy: &ImageAdmissionPolicy, image: &str) -> Result<ImageReference, AppError> {
+    let reference = parse_image_reference(image)?;
+    let name = reference.name();
+    if !policy.allowed.is_empty() && !policy.allowed.iter().any(|x| glob_matches(x, &name)) {
+        return Err(AppError::InvalidInput(format!(
+            "Image {} is not allowed, allowed repositories are {}",
+            image,
+            policy.allowed.join(", ")
+        )));
+    }
+    if policy.require_digest && !policy.resolve_digest && reference.digest.is_none() {
+        return Err(AppError::InvalidInput(format!(
+            "Image {} must be referenced by digest, e.g. {}@sha256:<digest>",
+            image, name
+        )));
+    }
+    Ok(reference)
+}
+
+/// Credentials of a registry from an image pull secret.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RegistryAuth {
+    Basic(String, String),
+    /// `auth` of the docker config, already `base64(username:password)`.
+    Encoded(String),
+}
+
+impl RegistryAuth {
+    fn apply(&self, request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        match self {
+            RegistryAuth::Basic(username, password) => request.basic_auth(username, Some(password)),
+            RegistryAuth::Encoded(auth) => {
+                request.header(header::AUTHORIZATION, format!("Basic {}", auth))
+            }
+        }
+    }
+}
+
+/// Registry host of an `auths` key, `https://index.docker.io/v1/` is `docker.io`.
+fn auths_registry(key: &str) -> &str {
+    let host = key
+        .trim_start_matches("https://")
+        .trim_start_matches("http://")
+        .split('/')
+        .next()
+        .unwrap_or_default();
+    match host {
+        DOCKER_HUB_INDEX | DOCKER_HUB_REGISTRY => DOCKER_HUB,
+        _ => host,
+    }
+}
+
+/// Finds the credentials of the registry in `.dockerconfigjson` documents, first match wins.
+pub fn registry_auth(docker_configs: &[String], registry: &str) -> Option<RegistryAuth> {
+    for docker_config in docker_configs {
+        let Ok(value) = serde_json::from_str::<Value>(docker_config) else {
+            continue;
+        };
+        let Some(auths) = value.get("auths").and_then(|x| x.as_object()) else {
+            continue;
+        };
+        for (key, entry) in auths {
+            if auths_registry(key) != registry {
+                continue;
+            }
+            let field = |name: &str| {
+                entry
+                    .get(name)
+                    .and_then(|x| x.as_str())
+                    .filter(|x| !x.is_empty())
+                    .map(|x| x.to_string())
+            };
+            if let (Some(username), Some(password)) = (field("username"), field("password")) {
+                return Some(RegistryAuth::Basic(username, password));
+            }
+            if let Some(auth) = field("auth") {
+                return Some(RegistryAuth::Encoded(auth));
+            }
+        }
+    }
+    None
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    token: Option<String>,
+    access_token: Option<String>,
+}
+
+/// Parameters of `WWW-Authenticate: Bearer realm="...",service="...",scope="..."`.
+fn bearer_challenge(value: &str) -> Option<(String, Vec<(String, String)>)> {
+    let params = value.strip_prefix("Bearer ")?;
+    let mut realm = None;
+    let mut query = Vec::new();
+    for param in params.split(',') {
+        let (key, value) = param.trim().split_once('=')?;
+        let value = value.trim_matches('"').to_string();
+        if key == "realm" {
+            realm = Some(value);
+        } else {
+            query.push((key.to_string(), value));
+        }
+    }
+    Some((realm?, query))
+}
+
+/// Resolves the tag to the digest of the manifest,
+/// `auth` is sent to the token realm or to the registry itself for basic auth.
+async fn resolve_digest(
+    reference: &ImageReference,
+    auth: Option<&RegistryAuth>,
+) -> Result<String, AppError> {
+    let failed = |reason: String| {
+        AppError::InvalidInput(format!(
+            "Failed to resolve digest of {}: {}",
+            reference.name(),
+            reason
+        ))
+    };
+    let host = if reference.registry == DOCKER_HUB {
+        DOCKER_HUB_REGISTRY
+    } else {
+        &reference.registry
+    };
+    let url = format!(
+        "https://{}/v2/{}/manifests/{}",
+        host,
+        reference.repository,
+        reference.tag.as_deref().unwrap_or(DEFAULT_TAG)
+    );
+    let client = reqwest::Client::builder()
+        .timeout(RESOLVE_TIMEOUT)
+        .build()
+        .map_err(|e| failed(e.to_string()))?;
+    let head = || client.head(&url).header(header::ACCEPT, MANIFEST_ACCEPT);
+
+    let mut response = head().send().await.map_err(|e| failed(e.to_string()))?;
+    if response.status() == StatusCode::UNAUTHORIZED {
+        let challenge = response
+            .headers()
+            .get(header::WWW_AUTHENTICATE)
+            .and_then(|x| x.to_str().ok())
+            .unwrap_or_default()
+            .to_string();
+        let Some((realm, query)) = bearer_challenge(&challenge) else {
+            let auth = auth
+                .filter(|_| challenge.starts_with("Basic"))
+                .ok_or_else(|| failed("registry requires credentials".to_string()))?;
+            response = auth
+                .apply(head())
+                .send()
+                .await
+                .map_err(|e| failed(e.to_string()))?;
+            return digest_of(response).map_err(failed);
+        };
+        let mut request = client.get(&realm).query(&query);
+        if let Some(auth) = auth {
+            request = auth.apply(request);
+        }
+        let token: TokenResponse = request
+            .send()
+            .await
+            .and_then(|x| x.error_for_status())
+            .map_err(|e| failed(e.to_string()))?
+            .json()
+            .await
+            .map_err(|e| failed(e.to_string()))?;
+        let mut request = head();
+        if let Some(token) = token.token.or(token.access_token) {
+            request = request.bearer_auth(token);
+        }
+        response = request.send().await.map_err(|e| failed(e.to_string()))?;
+    }
+    digest_of(response).map_err(failed)
+}
+
+fn digest_of(response: reqwest::Response) -> Result<String, String> {
+    if !response.status().is_success() {
+        return Err(format!("registry responded {}", response.status()));
+    }
+    response
+        .headers()
+        .get("docker-content-digest")
+        .and_then(|x| x.to_str().ok())
+        .map(|x| x.to_string())
+        .ok_or_else(|| "registry did not return a digest".to_string())
+}
+
+/// Enforces the image admission policy of the namespace, returns the image to store.
+/// `docker_configs` are the `.dockerconfigjson` of the image pull secrets the pod will use.
+pub async fn admit_image(
+    config: &ImageAdmissionConfig,
+    namespace: &str,
+    image: &str,
+    docker_configs: &[String],
+) -> Result<String, AppError> {
+    let policy = config.policy(namespace);
+    let reference = check_image(policy, image)?;
+    if !policy.resolve_digest || reference.digest.is_some() {
+        return Ok(image.to_string());
+    }
+    let auth = registry_auth(docker_configs, &reference.registry);
+    let digest = resolve_digest(&reference, auth.as_ref()).await?;
+    tracing::info!("Resolved image {} to {}", image, digest);
+    Ok(format!("{}@{}", image, digest))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_image_reference() {
+        assert_eq!(
+            parse_image_reference("node").unwrap(),
+            ImageReference {
+                registry: "docker.io".to_string(),
+                repository: "library/node".to_string(),
+                tag: None,
+                digest: None,
+            }
+        );
+        let reference =
+            parse_image_reference("localhost:5000/acme/mcp-github:1.2@sha256:abc").unwrap();
+        assert_eq!(reference.name(), "localhost:5000/acme/mcp-github");
+        assert_eq!(reference.tag.as_deref(), Some("1.2"));
+        assert_eq!(reference.digest.as_deref(), Some("sha256:abc"));
+        assert_eq!(
+            parse_image_reference("ghcr.io/acme/server").unwrap().name(),
+            "ghcr.io/acme/server"
+        );
+        assert!(parse_image_reference("node@latest").is_err());
+        assert!(parse_image_reference("ghcr.io//server").is_err());
+    }
+
+    #[test]
+    fn test_check_image() {
+        let policy = ImageAdmissionPolicy {
+            allowed: vec![
+                "ghcr.io/acme/*".to_string(),
+                "docker.io/library/node".to_string(),
+            ],
+            require_digest: false,
+            resolve_digest: false,
+        };
+        assert!(check_image(&policy, "ghcr.io/acme/mcp/github:1.0").is_ok());
+        assert!(check_image(&policy, "node:22-alpine").is_ok());
+        assert!(check_image(&policy, "ghcr.io/other/server").is_err());
+        assert!(check_image(&policy, "python:3.12").is_err());
+
+        let policy = ImageAdmissionPolicy {
+            require_digest: true,
+            ..policy
+        };
+        assert!(check_image(&policy, "node:22-alpine").is_err());
+        assert!(check_image(&policy, "node@sha256:0123").is_ok());
+    }
+
+    #[test]
+    fn test_registry_auth() {
+        let docker_configs = vec![
+            "not json".to_string(),
+            r#"{"auths":{"https://index.docker.io/v1/":{"username":"hub","password":"p1"}}}"#
+                .to_string(),
+            r#"{"auths":{"ghcr.io":{"auth":"dTpw"},"localhost:5000":{"username":"u"}}}"#
+                .to_string(),
+        ];
+        assert_eq!(
+            registry_auth(&docker_configs, "docker.io"),
+            Some(RegistryAuth::Basic("hub".to_string(), "p1".to_string()))
+        );
+        assert_eq!(
+            registry_auth(&docker_configs, "ghcr.io"),
+            Some(RegistryAuth::Encoded("dTpw".to_string()))
+        );
+        assert_eq!(registry_auth(&docker_configs, "localhost:5000"), None);
+        assert_eq!(registry_auth(&docker_configs, "quay.io"), None);
+    }
+}
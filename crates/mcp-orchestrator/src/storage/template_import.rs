@@ -0,0 +1,718 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use proto::mcp::orchestrator::v1::{
+    ArgEnvSpec, ArgEnvType, CreateMcpTemplateRequest, McpTemplateImportFormat, PackageRegistry,
+    PackageSource,
+};
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::{config::PackageConfig, error::AppError};
+
+const SECRET_NAME_HINTS: [&str; 7] = [
+    "TOKEN",
+    "SECRET",
+    "PASSWORD",
+    "API_KEY",
+    "APIKEY",
+    "CREDENTIAL",
+    "PRIVATE_KEY",
+];
+
+/// Draft of a template imported from a server.json or mcpServers document, it is not created.
+#[derive(Debug)]
+pub struct TemplateImport {
+    pub format: McpTemplateImportFormat,
+    pub draft: CreateMcpTemplateRequest,
+    pub warnings: Vec<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ServerJson {
+    name: String,
+    #[serde(default)]
+    version: Option<String>,
+    #[serde(default)]
+    packages: Vec<ServerPackage>,
+    #[serde(default)]
+    remotes: Vec<Value>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ServerPackage {
+    #[serde(alias = "registry_type", alias = "registry_name")]
+    registry_type: String,
+    #[serde(alias = "name")]
+    identifier: String,
+    #[serde(default)]
+    version: Option<String>,
+    #[serde(default, alias = "runtime_hint")]
+    runtime_hint: Option<String>,
+    #[serde(default)]
+    transport: Option<ServerTransport>,
+    #[serde(default, alias = "runtime_arguments")]
+    runtime_arguments: Vec<ServerArgument>,
+    #[serde(default, alias = "package_arguments")]
+    package_arguments: Vec<ServerArgument>,
+    #[serde(default, alias = "environment_variables")]
+    environment_variables: Vec<ServerEnv>,
+}
+
+#[derive(Deserialize)]
+struct ServerTransport {
+    #[serde(rename = "type")]
+    kind: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ServerArgument {
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    value: Option<String>,
+    #[serde(default)]
+    default: Option<String>,
+    #[serde(default, alias = "value_hint")]
+    value_hint: Option<String>,
+    #[serde(default, alias = "is_required")]
+    is_required: bool,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ServerEnv {
+    name: String,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    value: Option<String>,
+    #[serde(default)]
+    default: Option<String>,
+    #[serde(default, alias = "is_required")]
+    is_required: bool,
+    #[serde(default, alias = "is_secret")]
+    is_secret: bool,
+    #[serde(default)]
+    format: Option<String>,
+    #[serde(default)]
+    choices: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct McpServerEntry {
+    #[serde(default)]
+    command: Option<String>,
+    #[serde(default)]
+    args: Vec<String>,
+    #[serde(default)]
+    env: BTreeMap<String, String>,
+    #[serde(default)]
+    url: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct McpServerInput {
+    id: String,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    password: bool,
+}
+
+/// Accumulates the parts of the draft shared by both formats.
+#[derive(Default)]
+struct DraftBuilder {
+    envs: HashMap<String, String>,
+    arg_env_specs: HashMap<String, ArgEnvSpec>,
+    secret_keys: BTreeSet<String>,
+    warnings: Vec<String>,
+}
+
+impl DraftBuilder {
+    fn build(
+        mut self,
+        name: String,
+        image: String,
+        command: Vec<String>,
+        args: Vec<String>,
+    ) -> (CreateMcpTemplateRequest, Vec<String>) {
+        let mut secret_envs = Vec::new();
+        if !self.secret_keys.is_empty() {
+            let secret_name = format!("{}-env", name);
+            self.warnings.push(format!(
+                "Create secret '{}' with keys {} before saving the template",
+                secret_name,
+                self.secret_keys
+                    .iter()
+                    .cloned()
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+            secret_envs.push(secret_name);
+        }
+        let draft = CreateMcpTemplateRequest {
+            name,
+            image,
+            command,
+            args,
+            envs: self.envs,
+            arg_env_specs: self.arg_env_specs,
+            secret_envs,
+            ..Default::default()
+        };
+        (draft, self.warnings)
+    }
+}
+
+pub fn import_template(
+    content: &str,
+    format: McpTemplateImportFormat,
+    server_name: Option<&str>,
+    config: &PackageConfig,
+) -> Result<TemplateImport, AppError> {
+    let document: Value = serde_json::from_str(content)
+        .map_err(|e| AppError::InvalidInput(format!("Invalid JSON document: {}", e)))?;
+    let format = match format {
+        McpTemplateImportFormat::Auto => detect_format(&document)?,
+        format => format,
+    };
+    let (draft, warnings) = match format {
+        McpTemplateImportFormat::ServerJson => import_server_json(document, config)?,
+        McpTemplateImportFormat::McpServers | McpTemplateImportFormat::Auto => {
+            import_mcp_servers(document, server_name, config)?
+        }
+    };
+    Ok(TemplateImport {
+        format,
+        draft,
+        warnings,
+    })
+}
+
+fn detect_format(document: &Value) -> Result<McpTemplateImportFormat, AppError> {
+    let object = document
+        .as_object()
+        .ok_or_else(|| AppError::InvalidInput("Document must be a JSON object".to_string()))?;
+    if ["mcpServers", "servers", "command", "url"]
+        .iter()
+        .any(|key| object.contains_key(*key))
+    {
+        Ok(McpTemplateImportFormat::McpServers)
+    } else if object.contains_key("packages") || object.contains_key("remotes") {
+        Ok(McpTemplateImportFormat::ServerJson)
+    } else {
+        Err(AppError::InvalidInput(
+            "Unknown document, expected a server.json or an mcpServers config".to_string(),
+        ))
+    }
+}
+
+/// Converts `io.github.user/weather-server` into `weather-server`.
+fn template_name(name: &str) -> String {
+    let name = name.rsplit('/').next().unwrap_or(name).to_ascii_lowercase();
+    let name: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect();
+    let name: String = name.trim_matches('-').chars().take(50).collect();
+    let name = name.trim_end_matches('-').to_string();
+    if name.is_empty() {
+        "mcp-server".to_string()
+    } else {
+        name
+    }
+}
+
+/// Arg keys are lower kebab case, the declared env name is kept as `env_name`.
+fn arg_env_spec(env_name: &str, spec: ArgEnvSpec) -> (String, ArgEnvSpec) {
+    let key: String = env_name
+        .to_ascii_lowercase()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect();
+    let key = key.trim_matches('-');
+    let key = if key.starts_with(|c: char| c.is_ascii_lowercase()) {
+        key.to_string()
+    } else {
+        format!("arg-{}", key)
+    };
+    (
+        key,
+        ArgEnvSpec {
+            env_name: Some(env_name.to_string()),
+            ..spec
+        },
+    )
+}
+
+fn is_secret_name(name: &str) -> bool {
+    let name = name.to_ascii_uppercase();
+    SECRET_NAME_HINTS.iter().any(|hint| name.contains(hint))
+}
+
+fn is_placeholder(value: &str) -> bool {
+    let value = value.trim();
+    value.is_empty()
+        || value.starts_with("${")
+        || (value.starts_with('<') && value.ends_with('>'))
+        || value.to_ascii_uppercase().contains("YOUR_")
+        || value.to_ascii_lowercase().contains("your-")
+}
+
+fn import_server_json(
+    document: Value,
+    config: &PackageConfig,
+) -> Result<(CreateMcpTemplateRequest, Vec<String>), AppError> {
+    let server: ServerJson = serde_json::from_value(document)
+        .map_err(|e| AppError::InvalidInput(format!("Invalid server.json: {}", e)))?;
+    let package = server
+        .packages
+        .iter()
+        .find(|p| {
+            matches!(p.registry_type.as_str(), "npm" | "pypi" | "oci" | "docker")
+                && p.transport.as_ref().is_none_or(|t| t.kind == "stdio")
+        })
+        .ok_or_else(|| {
+            if server.remotes.is_empty() {
+                AppError::InvalidInput(
+                    "server.json has no npm, pypi or oci package with stdio transport".to_string(),
+                )
+            } else {
+                AppError::InvalidInput(
+                    "server.json only declares remotes, remote servers can not be run as a template"
+                        .to_string(),
+                )
+            }
+        })?;
+
+    let mut builder = DraftBuilder::default();
+    let version = package.version.clone().or(server.version.clone());
+    let runtime_args = server_arguments(&package.runtime_arguments, &mut builder.warnings);
+    let package_args = server_arguments(&package.package_arguments, &mut builder.warnings);
+    let mut source = None;
+    let (image, command) = match package.registry_type.as_str() {
+        // 버전이 고정된 패키지는 package 템플릿으로 만듦
+        "npm" | "pypi" if runtime_args.is_empty() && version.is_some() => {
+            source = Some(PackageSource {
+                registry: if package.registry_type == "npm" {
+                    PackageRegistry::Npm.into()
+                } else {
+                    PackageRegistry::Pypi.into()
+                },
+                name: package.identifier.clone(),
+                version: version.clone().unwrap_or_default(),
+                cache_volume: None,
+            });
+            (String::new(), Vec::new())
+        }
+        "npm" => {
+            let runtime = package.runtime_hint.clone().unwrap_or("npx".to_string());
+            let mut command = vec![runtime.clone()];
+            command.extend(runtime_args);
+            if runtime == "npx" && !command.iter().any(|x| x == "-y" || x == "--yes") {
+                command.push("-y".to_string());
+            }
+            command.push(match &version {
+                Some(version) => format!("{}@{}", package.identifier, version),
+                None => package.identifier.clone(),
+            });
+            (config.npm_image.clone(), command)
+        }
+        "pypi" => {
+            let mut command = vec![package.runtime_hint.clone().unwrap_or("uvx".to_string())];
+            command.extend(runtime_args);
+            command.push(match &version {
+                Some(version) => format!("{}@{}", package.identifier, version),
+                None => package.identifier.clone(),
+            });
+            (config.pypi_image.clone(), command)
+        }
+        _ => {
+            if !runtime_args.is_empty() {
+                builder.warnings.push(format!(
+                    "Runtime arguments {:?} of the container are ignored",
+                    runtime_args
+                ));
+            }
+            let has_tag = package
+                .identifier
+                .rsplit('/')
+                .next()
+                .is_some_and(|x| x.contains(':') || x.contains('@'));
+            let image = match &version {
+                Some(version) if !has_tag => format!("{}:{}", package.identifier, version),
+                _ => package.identifier.clone(),
+            };
+            (image, Vec::new())
+        }
+    };
+
+    for env in &package.environment_variables {
+        if env.is_secret {
+            if env.value.is_some() {
+                builder.warnings.push(format!(
+                    "Value of secret {} is not imported, set it in the secret",
+                    env.name
+                ));
+            }
+            builder.secret_keys.insert(env.name.clone());
+            continue;
+        }
+        if let Some(value) = env.value.as_ref().filter(|x| !x.contains('{')) {
+            builder.envs.insert(env.name.clone(), value.clone());
+            continue;
+        }
+        let r#type = if !env.choices.is_empty() {
+            ArgEnvType::Enum
+        } else {
+            match env.format.as_deref() {
+                Some("number") => ArgEnvType::Int,
+                Some("boolean") => ArgEnvType::Bool,
+                _ => ArgEnvType::String,
+            }
+        };
+        let (key, spec) = arg_env_spec(
+            &env.name,
+            ArgEnvSpec {
+                r#type: r#type.into(),
+                optional: !env.is_required,
+                default_value: env.default.clone(),
+                allowed_values: env.choices.clone(),
+                description: env.description.clone(),
+                ..Default::default()
+            },
+        );
+        builder.arg_env_specs.insert(key, spec);
+    }
+
+    let (draft, warnings) =
+        builder.build(template_name(&server.name), image, command, package_args);
+    Ok((
+        CreateMcpTemplateRequest {
+            package: source,
+            ..draft
+        },
+        warnings,
+    ))
+}
+
+fn server_arguments(arguments: &[ServerArgument], warnings: &mut Vec<String>) -> Vec<String> {
+    let mut result = Vec::new();
+    for argument in arguments {
+        let value = argument.value.clone().or(argument.default.clone());
+        if value.as_deref().is_some_and(|x| x.contains('{')) {
+            warnings.push(format!(
+                "Argument {} uses variables, replace them before saving",
+                value.as_deref().unwrap_or_default()
+            ));
+        }
+        match (argument.kind.as_str(), &argument.name, value) {
+            ("named", Some(name), value) => {
+                result.push(name.clone());
+                result.extend(value);
+            }
+            ("positional", _, Some(value)) => result.push(value),
+            (_, name, _) => {
+                let label = name
+                    .clone()
+                    .or(argument.value_hint.clone())
+                    .unwrap_or(argument.kind.clone());
+                if argument.is_required {
+                    warnings.push(format!("Required argument {} has no value", label));
+                }
+            }
+        }
+    }
+    result
+}
+
+fn import_mcp_servers(
+    document: Value,
+    server_name: Option<&str>,
+    config: &PackageConfig,
+) -> Result<(CreateMcpTemplateRequest, Vec<String>), AppError> {
+    let inputs: Vec<McpServerInput> = document
+        .get("inputs")
+        .cloned()
+        .map(serde_json::from_value)
+        .transpose()
+        .map_err(|e| AppError::InvalidInput(format!("Invalid inputs: {}", e)))?
+        .unwrap_or_default();
+    let servers = document
+        .get("mcpServers")
+        .or_else(|| document.get("servers"))
+        .cloned();
+    let (name, entry) = match servers {
+        Some(Value::Object(servers)) => match server_name {
+            Some(server_name) => {
+                let entry = servers.get(server_name).cloned().ok_or_else(|| {
+                    AppError::InvalidInput(format!(
+                        "Server {} not found in the config",
+                        server_name
+                    ))
+                })?;
+                (server_name.to_string(), entry)
+            }
+            None if servers.len() == 1 => servers.into_iter().next().unwrap(),
+            None => {
+                return Err(AppError::InvalidInput(format!(
+                    "Config declares several servers, choose one of {}",
+                    servers.keys().cloned().collect::<Vec<_>>().join(", ")
+                )));
+            }
+        },
+        Some(_) => {
+            return Err(AppError::InvalidInput(
+                "mcpServers must be an object".to_string(),
+            ));
+        }
+        None => (
+            server_name.unwrap_or("mcp-server").to_string(),
+            document.clone(),
+        ),
+    };
+    let entry: McpServerEntry = serde_json::from_value(entry)
+        .map_err(|e| AppError::InvalidInput(format!("Invalid server {}: {}", name, e)))?;
+    let command = match (entry.command, entry.url) {
+        (Some(command), _) => command,
+        (None, Some(_)) => {
+            return Err(AppError::InvalidInput(format!(
+                "Server {} is a remote server, only stdio servers can be run as a template",
+                name
+            )));
+        }
+        (None, None) => {
+            return Err(AppError::InvalidInput(format!(
+                "Server {} has no command",
+                name
+            )));
+        }
+    };
+
+    let mut builder = DraftBuilder::default();
+    let mut env = entry.env;
+    let runtime = command.rsplit('/').next().unwrap_or(&command).to_string();
+    let (image, command, args) = match runtime.as_str() {
+        "npx" => (config.npm_image.clone(), vec![runtime], entry.args),
+        "uvx" | "uv" => (config.pypi_image.clone(), vec![runtime], entry.args),
+        "docker" | "podman" => {
+            let (image, args) = parse_docker_run(&entry.args, &mut env, &mut builder.warnings)?;
+            (image, Vec::new(), args)
+        }
+        _ => {
+            builder.warnings.push(format!(
+                "Command {} has no known runner image, set an image providing it",
+                runtime
+            ));
+            (String::new(), vec![command], entry.args)
+        }
+    };
+
+    for (key, value) in env {
+        let input = value
+            .trim()
+            .strip_prefix("${input:")
+            .and_then(|x| x.strip_suffix('}'))
+            .and_then(|id| inputs.iter().find(|input| input.id == id));
+        if is_secret_name(&key) || input.is_some_and(|input| input.password) {
+            if !is_placeholder(&value) {
+                builder.warnings.push(format!(
+                    "Value of secret {} is not imported, set it in the secret",
+                    key
+                ));
+            }
+            builder.secret_keys.insert(key);
+        } else if is_placeholder(&value) {
+            let (key, spec) = arg_env_spec(
+                &key,
+                ArgEnvSpec {
+                    r#type: ArgEnvType::String.into(),
+                    description: input.and_then(|input| input.description.clone()),
+                    ..Default::default()
+                },
+            );
+            builder.arg_env_specs.insert(key, spec);
+        } else {
+            builder.envs.insert(key, value);
+        }
+    }
+
+    Ok(builder.build(template_name(&name), image, command, args))
+}
+
+/// Splits `docker run [flags] image [args]` into the image and its args, `-e` flags are merged into `env`.
+fn parse_docker_run(
+    args: &[String],
+    env: &mut BTreeMap<String, String>,
+    warnings: &mut Vec<String>,
+) -> Result<(String, Vec<String>), AppError> {
+    const VALUE_FLAGS: [&str; 16] = [
+        "-v",
+        "--volume",
+        "--mount",
+        "-p",
+        "--publish",
+        "--name",
+        "--network",
+        "--env-file",
+        "-w",
+        "--workdir",
+        "-u",
+        "--user",
+        "--entrypoint",
+        "--platform",
+        "-m",
+        "--memory",
+    ];
+    let mut iter = args.iter().skip_while(|x| *x != "run").skip(1);
+    let mut image = None;
+    while let Some(arg) = iter.next() {
+        if !arg.starts_with('-') {
+            image = Some(arg.clone());
+            break;
+        }
+        let (flag, inline) = match arg.split_once('=') {
+            Some((flag, value)) => (flag, Some(value.to_string())),
+            None => (arg.as_str(), None),
+        };
+        if flag == "-e" || flag == "--env" {
+            let value = match inline {
+                Some(value) => Some(value),
+                None => iter.next().cloned(),
+            };
+            let Some(value) = value else {
+                continue;
+            };
+            match value.split_once('=') {
+                Some((key, value)) => {
+                    env.insert(key.to_string(), value.to_string());
+                }
+                None => {
+                    env.entry(value).or_default();
+                }
+            }
+        } else if VALUE_FLAGS.contains(&flag) {
+            let value = match inline {
+                Some(value) => Some(value),
+                None => iter.next().cloned(),
+            };
+            warnings.push(format!(
+                "Docker flag {} {} is ignored",
+                flag,
+                value.unwrap_or_default()
+            ));
+        }
+    }
+    let image = image.ok_or_else(|| {
+        AppError::InvalidInput("docker command has no `run <image>` arguments".to_string())
+    })?;
+    Ok((image, iter.cloned().collect()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> PackageConfig {
+        PackageConfig::default()
+    }
+
+    #[test]
+    fn test_import_server_json() {
+        let content = r#"{
+            "name": "io.github.example/weather-server",
+            "version": "1.0.2",
+            "packages": [{
+                "registryType": "npm",
+                "identifier": "@example/weather-mcp",
+                "version": "1.0.2",
+                "transport": { "type": "stdio" },
+                "packageArguments": [{ "type": "named", "name": "--units", "value": "metric" }],
+                "environmentVariables": [
+                    { "name": "WEATHER_API_KEY", "isRequired": true, "isSecret": true },
+                    { "name": "LOG_LEVEL", "value": "info" },
+                    { "name": "REGION", "description": "Forecast region", "choices": ["eu", "us"] }
+                ]
+            }]
+        }"#;
+        let import =
+            import_template(content, McpTemplateImportFormat::Auto, None, &config()).unwrap();
+        assert_eq!(import.format, McpTemplateImportFormat::ServerJson);
+        let draft = import.draft;
+        assert_eq!(draft.name, "weather-server");
+        assert!(draft.image.is_empty());
+        assert!(draft.command.is_empty());
+        let package = draft.package.clone().unwrap();
+        assert_eq!(package.registry(), PackageRegistry::Npm);
+        assert_eq!(package.name, "@example/weather-mcp");
+        assert_eq!(package.version, "1.0.2");
+        assert_eq!(draft.args, vec!["--units", "metric"]);
+        assert_eq!(
+            draft.envs.get("LOG_LEVEL").map(String::as_str),
+            Some("info")
+        );
+        assert_eq!(draft.secret_envs, vec!["weather-server-env"]);
+        let region = draft.arg_env_specs.get("region").unwrap();
+        assert_eq!(region.env_name.as_deref(), Some("REGION"));
+        assert_eq!(region.r#type(), ArgEnvType::Enum);
+        assert!(region.optional);
+        assert!(import.warnings[0].contains("WEATHER_API_KEY"));
+    }
+
+    #[test]
+    fn test_import_mcp_servers() {
+        let content = r#"{
+            "mcpServers": {
+                "github": {
+                    "command": "docker",
+                    "args": ["run", "-i", "--rm", "-e", "GITHUB_PERSONAL_ACCESS_TOKEN", "-e", "GITHUB_HOST=github.com", "ghcr.io/github/github-mcp-server", "stdio"],
+                    "env": { "GITHUB_PERSONAL_ACCESS_TOKEN": "<YOUR_TOKEN>", "GITHUB_TOOLSETS": "${input:toolsets}" }
+                },
+                "fetch": { "command": "uvx", "args": ["mcp-server-fetch"] }
+            }
+        }"#;
+        assert!(import_template(content, McpTemplateImportFormat::Auto, None, &config()).is_err());
+
+        let import = import_template(
+            content,
+            McpTemplateImportFormat::McpServers,
+            Some("github"),
+            &config(),
+        )
+        .unwrap();
+        let draft = import.draft;
+        assert_eq!(draft.image, "ghcr.io/github/github-mcp-server");
+        assert!(draft.command.is_empty());
+        assert_eq!(draft.args, vec!["stdio"]);
+        assert_eq!(
+            draft.envs.get("GITHUB_HOST").map(String::as_str),
+            Some("github.com")
+        );
+        assert_eq!(draft.secret_envs, vec!["github-env"]);
+        assert!(draft.arg_env_specs.contains_key("github-toolsets"));
+
+        let import = import_template(
+            content,
+            McpTemplateImportFormat::Auto,
+            Some("fetch"),
+            &config(),
+        )
+        .unwrap();
+        assert_eq!(import.draft.image, config().pypi_image);
+        assert_eq!(import.draft.command, vec!["uvx"]);
+        assert_eq!(import.draft.args, vec!["mcp-server-fetch"]);
+    }
+
+    #[test]
+    fn test_import_remote_only() {
+        let content = r#"{ "name": "io.example/remote", "remotes": [{ "type": "streamable-http", "url": "https://example.com/mcp" }] }"#;
+        assert!(import_template(content, McpTemplateImportFormat::Auto, None, &config()).is_err());
+        let content = r#"{ "mcpServers": { "remote": { "url": "https://example.com/mcp" } } }"#;
+        assert!(import_template(content, McpTemplateImportFormat::Auto, None, &config()).is_err());
+    }
+}
@@ -0,0 +1,220 @@
+use std::sync::LazyLock;
+
+use k8s_openapi::{
+    api::core::v1::{
+        EnvVar, PersistentVolumeClaim, PersistentVolumeClaimSpec,
+        PersistentVolumeClaimVolumeSource, Volume, VolumeMount, VolumeResourceRequirements,
+    },
+    apimachinery::pkg::api::resource::Quantity,
+};
+use kube::{
+    Api, Client, ResourceExt,
+    api::{ObjectMeta, PostParams},
+};
+use proto::mcp::orchestrator::v1::{PackageRegistry, PackageSource, VolumeLimit};
+
+use crate::{
+    config::PackageConfig,
+    error::AppError,
+    storage::{
+        labels::setup_labels,
+        resource_type::{RESOURCE_TYPE_PACKAGE_CACHE, RESOURCE_TYPE_PREFIX_PACKAGE_CACHE},
+        util_name::encode_k8sname,
+    },
+};
+
+static LAZY_NPM_NAME: LazyLock<regex::Regex> = LazyLock::new(|| {
+    regex::Regex::new(r"^(@[a-z0-9][a-z0-9._~-]*/)?[a-z0-9][a-z0-9._~-]{0,213}$").unwrap()
+});
+
+static LAZY_PYPI_NAME: LazyLock<regex::Regex> = LazyLock::new(|| {
+    regex::Regex::new(r"^[A-Za-z0-9]([A-Za-z0-9._-]{0,198}[A-Za-z0-9])?$").unwrap()
+});
+
+static LAZY_NPM_VERSION: LazyLock<regex::Regex> = LazyLock::new(|| {
+    regex::Regex::new(r"^\d+\.\d+\.\d+(-[0-9A-Za-z.-]+)?(\+[0-9A-Za-z.-]+)?$").unwrap()
+});
+
+static LAZY_PYPI_VERSION: LazyLock<regex::Regex> = LazyLock::new(|| {
+    regex::Regex::new(r"^\d+(\.\d+)*((a|b|rc)\d+)?(\.post\d+)?(\.dev\d+)?$").unwrap()
+});
+
+const VOLUME_NAME_PACKAGE_CACHE: &str = "package-cache";
+const PACKAGE_CACHE_MOUNT_PATH: &str = "/var/cache/mcp-packages";
+
+pub fn validate_package(package: &PackageSource) -> Result<(), AppError> {
+    let (name_regex, version_regex) = match package.registry() {
+        PackageRegistry::Npm => (&LAZY_NPM_NAME, &LAZY_NPM_VERSION),
+        PackageRegistry::Pypi => (&LAZY_PYPI_NAME, &LAZY_PYPI_VERSION),
+    };
+    if !name_regex.is_match(&package.name) {
+        return Err(AppError::InvalidInput(format!(
+            "Invalid {} package name: {}",
+            package.registry().as_str_name(),
+            package.name
+        )));
+    }
+    if !version_regex.is_match(&package.version) {
+        return Err(AppError::InvalidInput(format!(
+            "Package {} must be pinned to an exact version, got '{}'",
+            package.name, package.version
+        )));
+    }
+    Ok(())
+}
+
+pub fn runner_image(package: &PackageSource, config: &PackageConfig) -> String {
+    match package.registry() {
+        PackageRegistry::Npm => config.npm_image.clone(),
+        PackageRegistry::Pypi => config.pypi_image.clone(),
+    }
+}
+
+pub fn package_command(package: &PackageSource) -> Vec<String> {
+    let spec = format!("{}@{}", package.name, package.version);
+    match package.registry() {
+        PackageRegistry::Npm => vec!["npx".to_string(), "-y".to_string(), spec],
+        PackageRegistry::Pypi => vec!["uvx".to_string(), spec],
+    }
+}
+
+/// Points the package manager of the runner at the shared cache volume.
+pub fn package_cache_envs(package: &PackageSource) -> Vec<EnvVar> {
+    let env = |name: &str, value: String| EnvVar {
+        name: name.to_string(),
+        value: Some(value),
+        ..Default::default()
+    };
+    match package.registry() {
+        PackageRegistry::Npm => vec![
+            env(
+                "npm_config_cache",
+                format!("{}/npm", PACKAGE_CACHE_MOUNT_PATH),
+            ),
+            env("npm_config_prefer_offline", "true".to_string()),
+        ],
+        PackageRegistry::Pypi => vec![env(
+            "UV_CACHE_DIR",
+            format!("{}/uv", PACKAGE_CACHE_MOUNT_PATH),
+        )],
+    }
+}
+
+pub fn package_cache_volume(claim_name: &str) -> (Volume, VolumeMount) {
+    (
+        Volume {
+            name: VOLUME_NAME_PACKAGE_CACHE.to_string(),
+            persistent_volume_claim: Some(PersistentVolumeClaimVolumeSource {
+                claim_name: claim_name.to_string(),
+                read_only: Some(false),
+            }),
+            ..Default::default()
+        },
+        VolumeMount {
+            name: VOLUME_NAME_PACKAGE_CACHE.to_string(),
+            mount_path: PACKAGE_CACHE_MOUNT_PATH.to_string(),
+            ..Default::default()
+        },
+    )
+}
+
+/// Returns the cache claim of the volume in the namespace, creating it on first use.
+/// The claim is shared by every package template of the namespace, so it is not owned by a template.
+pub async fn ensure_package_cache(
+    client: Client,
+    namespace: &str,
+    volume_name: &str,
+    limit: &VolumeLimit,
+) -> Result<String, AppError> {
+    let api: Api<PersistentVolumeClaim> = Api::namespaced(client, namespace);
+    let name = encode_k8sname(RESOURCE_TYPE_PREFIX_PACKAGE_CACHE, volume_name);
+    if let Some(pvc) = api.get_opt(&name).await? {
+        return Ok(pvc.name_any());
+    }
+
+    let pvc = PersistentVolumeClaim {
+        metadata: ObjectMeta {
+            name: Some(name.clone()),
+            namespace: Some(namespace.to_string()),
+            labels: Some(setup_labels(RESOURCE_TYPE_PACKAGE_CACHE, std::iter::empty()).collect()),
+            ..Default::default()
+        },
+        spec: Some(PersistentVolumeClaimSpec {
+            access_modes: Some(vec!["ReadWriteMany".to_string()]),
+            storage_class_name: limit.storage_class.clone(),
+            resources: Some(VolumeResourceRequirements {
+                requests: Some(
+                    [("storage".to_string(), Quantity(limit.size.clone()))]
+                        .into_iter()
+                        .collect(),
+                ),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+
+    tracing::info!("Creating package cache {}/{}", namespace, name);
+    match api.create(&PostParams::default(), &pvc).await {
+        Ok(pvc) => Ok(pvc.name_any()),
+        // 다른 세션이 동시에 생성한 경우
+        Err(kube::Error::Api(resp)) if resp.code == 409 => Ok(name),
+        Err(e) => Err(AppError::from(e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn package(registry: PackageRegistry, name: &str, version: &str) -> PackageSource {
+        PackageSource {
+            registry: registry.into(),
+            name: name.to_string(),
+            version: version.to_string(),
+            cache_volume: None,
+        }
+    }
+
+    #[test]
+    fn test_validate_package() {
+        assert!(
+            validate_package(&package(
+                PackageRegistry::Npm,
+                "@modelcontextprotocol/server-github",
+                "2025.4.8"
+            ))
+            .is_ok()
+        );
+        assert!(validate_package(&package(PackageRegistry::Npm, "server", "^1.0.0")).is_err());
+        assert!(validate_package(&package(PackageRegistry::Npm, "server", "latest")).is_err());
+        assert!(validate_package(&package(PackageRegistry::Npm, "Server", "1.0.0")).is_err());
+        assert!(
+            validate_package(&package(
+                PackageRegistry::Pypi,
+                "mcp-server-fetch",
+                "2025.1.17"
+            ))
+            .is_ok()
+        );
+        assert!(validate_package(&package(PackageRegistry::Pypi, "mcp-server", "1.0rc1")).is_ok());
+        assert!(validate_package(&package(PackageRegistry::Pypi, "mcp-server", ">=1.0")).is_err());
+    }
+
+    #[test]
+    fn test_package_command() {
+        assert_eq!(
+            package_command(&package(PackageRegistry::Npm, "@scope/server", "1.2.3")),
+            vec!["npx", "-y", "@scope/server@1.2.3"]
+        );
+        assert_eq!(
+            package_command(&package(
+                PackageRegistry::Pypi,
+                "mcp-server-fetch",
+                "2025.1.17"
+            )),
+            vec!["uvx", "mcp-server-fetch@2025.1.17"]
+        );
+    }
+}
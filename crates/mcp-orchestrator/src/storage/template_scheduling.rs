@@ -0,0 +1,225 @@
+use std::collections::BTreeMap;
+
+use k8s_openapi::{
+    api::core::v1::{
+        Affinity, PodAffinityTerm, PodAntiAffinity as KubePodAntiAffinity,
+        Toleration as KubeToleration, TopologySpreadConstraint, WeightedPodAffinityTerm,
+    },
+    apimachinery::pkg::apis::meta::v1::LabelSelector,
+};
+use proto::mcp::orchestrator::v1::{PodAntiAffinity, SessionScope, Toleration, TopologySpread};
+
+use crate::storage::{
+    ResourceLimitData,
+    labels::{
+        LABEL_MANAGED_BY, LABEL_MANAGED_BY_VALUE, LABEL_SESSION_SUBJECT_HASH, LABEL_TYPE_OF,
+        label_dependency_tuple,
+    },
+    resource_type::{RESOURCE_TYPE_MCP_SERVER, RESOURCE_TYPE_MCP_TEMPLATE},
+    store_workspace::subject_hash,
+};
+
+const DEFAULT_ANTI_AFFINITY_TOPOLOGY_KEY: &str = "kubernetes.io/hostname";
+const DEFAULT_ANTI_AFFINITY_WEIGHT: i32 = 100;
+
+/// Scheduling fields of the session pod taken from the resource limit.
+#[derive(Debug, Default)]
+pub struct PodScheduling {
+    pub tolerations: Option<Vec<KubeToleration>>,
+    pub priority_class_name: Option<String>,
+    pub topology_spread_constraints: Option<Vec<TopologySpreadConstraint>>,
+    pub affinity: Option<Affinity>,
+}
+
+/// Selector of the session pods counted by the scope, None when the session has no user.
+fn scope_selector(
+    scope: SessionScope,
+    template_name: &str,
+    subject: Option<&str>,
+) -> Option<LabelSelector> {
+    let scoped = match scope {
+        SessionScope::Template => label_dependency_tuple(RESOURCE_TYPE_MCP_TEMPLATE, template_name),
+        SessionScope::User => (
+            LABEL_SESSION_SUBJECT_HASH.to_string(),
+            subject_hash(subject?),
+        ),
+        SessionScope::All => (
+            LABEL_MANAGED_BY.to_string(),
+            LABEL_MANAGED_BY_VALUE.to_string(),
+        ),
+    };
+    Some(LabelSelector {
+        match_labels: Some(BTreeMap::from([
+            (
+                LABEL_TYPE_OF.to_string(),
+                RESOURCE_TYPE_MCP_SERVER.to_string(),
+            ),
+            scoped,
+        ])),
+        ..Default::default()
+    })
+}
+
+fn toleration(toleration: &Toleration) -> KubeToleration {
+    KubeToleration {
+        key: toleration.key.clone().filter(|x| !x.is_empty()),
+        operator: toleration.operator.clone(),
+        value: toleration.value.clone(),
+        effect: toleration.effect.clone(),
+        toleration_seconds: toleration.toleration_seconds,
+    }
+}
+
+fn topology_spread_constraint(
+    spread: &TopologySpread,
+    template_name: &str,
+    subject: Option<&str>,
+) -> Option<TopologySpreadConstraint> {
+    Some(TopologySpreadConstraint {
+        label_selector: Some(scope_selector(spread.scope(), template_name, subject)?),
+        max_skew: spread.max_skew,
+        topology_key: spread.topology_key.clone(),
+        when_unsatisfiable: if spread.schedule_anyway {
+            "ScheduleAnyway".to_string()
+        } else {
+            "DoNotSchedule".to_string()
+        },
+        ..Default::default()
+    })
+}
+
+fn pod_anti_affinity(
+    presets: &[PodAntiAffinity],
+    template_name: &str,
+    subject: Option<&str>,
+) -> Option<KubePodAntiAffinity> {
+    let mut required = Vec::new();
+    let mut preferred = Vec::new();
+    for preset in presets {
+        let Some(selector) = scope_selector(preset.scope(), template_name, subject) else {
+            continue;
+        };
+        let term = PodAffinityTerm {
+            label_selector: Some(selector),
+            topology_key: Some(preset.topology_key.clone())
+                .filter(|x| !x.is_empty())
+                .unwrap_or_else(|| DEFAULT_ANTI_AFFINITY_TOPOLOGY_KEY.to_string()),
+            ..Default::default()
+        };
+        if preset.required {
+            required.push(term);
+        } else {
+            preferred.push(WeightedPodAffinityTerm {
+                pod_affinity_term: term,
+                weight: preset.weight.unwrap_or(DEFAULT_ANTI_AFFINITY_WEIGHT),
+            });
+        }
+    }
+    if required.is_empty() && preferred.is_empty() {
+        return None;
+    }
+    Some(KubePodAntiAffinity {
+        required_during_scheduling_ignored_during_execution: Some(required)
+            .filter(|x| !x.is_empty()),
+        preferred_during_scheduling_ignored_during_execution: Some(preferred)
+            .filter(|x| !x.is_empty()),
+    })
+}
+
+/// Presets are appended to the pod anti-affinity of the node affinity document.
+pub fn build_scheduling(
+    resource_limit: &ResourceLimitData,
+    template_name: &str,
+    subject: Option<&str>,
+) -> PodScheduling {
+    let mut affinity = resource_limit.node_affinity.clone();
+    if let Some(presets) = pod_anti_affinity(&resource_limit.anti_affinity, template_name, subject)
+    {
+        let anti_affinity = affinity
+            .get_or_insert_default()
+            .pod_anti_affinity
+            .get_or_insert_default();
+        if let Some(terms) = presets.required_during_scheduling_ignored_during_execution {
+            anti_affinity
+                .required_during_scheduling_ignored_during_execution
+                .get_or_insert_default()
+                .extend(terms);
+        }
+        if let Some(terms) = presets.preferred_during_scheduling_ignored_during_execution {
+            anti_affinity
+                .preferred_during_scheduling_ignored_during_execution
+                .get_or_insert_default()
+                .extend(terms);
+        }
+    }
+
+    PodScheduling {
+        tolerations: Some(resource_limit.tolerations.iter().map(toleration).collect())
+            .filter(|x: &Vec<_>| !x.is_empty()),
+        priority_class_name: resource_limit.priority_class_name.clone(),
+        topology_spread_constraints: Some(
+            resource_limit
+                .topology_spread
+                .iter()
+                .filter_map(|x| topology_spread_constraint(x, template_name, subject))
+                .collect(),
+        )
+        .filter(|x: &Vec<_>| !x.is_empty()),
+        affinity,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pod_anti_affinity() {
+        let presets = vec![
+            PodAntiAffinity {
+                scope: SessionScope::User as i32,
+                topology_key: String::new(),
+                required: false,
+                weight: None,
+            },
+            PodAntiAffinity {
+                scope: SessionScope::Template as i32,
+                topology_key: "topology.kubernetes.io/zone".to_string(),
+                required: true,
+                weight: None,
+            },
+        ];
+
+        let anti_affinity = pod_anti_affinity(&presets, "github", Some("user-1")).unwrap();
+        let preferred = anti_affinity
+            .preferred_during_scheduling_ignored_during_execution
+            .unwrap();
+        assert_eq!(preferred[0].weight, 100);
+        assert_eq!(
+            preferred[0].pod_affinity_term.topology_key,
+            "kubernetes.io/hostname"
+        );
+        let labels = preferred[0]
+            .pod_affinity_term
+            .label_selector
+            .as_ref()
+            .and_then(|x| x.match_labels.as_ref())
+            .unwrap();
+        assert_eq!(labels[LABEL_SESSION_SUBJECT_HASH], subject_hash("user-1"));
+
+        // 사용자가 없는 세션은 사용자 범위 규칙이 빠짐
+        let anti_affinity = pod_anti_affinity(&presets, "github", None).unwrap();
+        assert!(
+            anti_affinity
+                .preferred_during_scheduling_ignored_during_execution
+                .is_none()
+        );
+        assert_eq!(
+            anti_affinity
+                .required_during_scheduling_ignored_during_execution
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+}
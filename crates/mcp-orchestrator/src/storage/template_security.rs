@@ -0,0 +1,434 @@
+use std::sync::LazyLock;
+
+use k8s_openapi::api::core::v1::{
+    Capabilities, EmptyDirVolumeSource, PodSecurityContext, SeccompProfile, SecurityContext,
+    Volume, VolumeMount,
+};
+use proto::mcp::orchestrator::v1::{SecurityProfile, SecurityProfileType};
+
+use crate::{error::AppError, storage::scheduling_validation::validate_dns_subdomain};
+
+const DEFAULT_NON_ROOT_ID: i64 = 65532;
+const VOLUME_NAME_TMP: &str = "session-tmp";
+const TMP_MOUNT_PATH: &str = "/tmp";
+const SECCOMP_RUNTIME_DEFAULT: &str = "RuntimeDefault";
+const SECCOMP_UNCONFINED: &str = "Unconfined";
+const SECCOMP_LOCALHOST_PREFIX: &str = "Localhost/";
+const RESTRICTED_ADD_CAPABILITIES: [&str; 1] = ["NET_BIND_SERVICE"];
+
+static LAZY_CAPABILITY: LazyLock<regex::Regex> =
+    LazyLock::new(|| regex::Regex::new(r"^[A-Z][A-Z_]*$").unwrap());
+
+/// securityContext of the session pod and every container of it.
+#[derive(Debug, Default)]
+pub struct SessionSecurity {
+    pub pod: Option<PodSecurityContext>,
+    pub container: Option<SecurityContext>,
+    pub runtime_class_name: Option<String>,
+    /// Writable /tmp when the root filesystem is read-only.
+    pub tmp_volume: Option<(Volume, VolumeMount)>,
+    /// Set for every profile but NONE, anonymous sessions then do not mount the service account token.
+    pub hardened: bool,
+}
+
+pub fn validate_security_profile(profile: &SecurityProfile) -> Result<(), AppError> {
+    for capability in profile
+        .drop_capabilities
+        .iter()
+        .chain(&profile.add_capabilities)
+    {
+        if !LAZY_CAPABILITY.is_match(capability) {
+            return Err(AppError::InvalidInput(format!(
+                "Invalid capability {}, use names like NET_ADMIN",
+                capability
+            )));
+        }
+    }
+    if let Some(seccomp) = &profile.seccomp_profile {
+        let valid = seccomp == SECCOMP_RUNTIME_DEFAULT
+            || seccomp == SECCOMP_UNCONFINED
+            || seccomp
+                .strip_prefix(SECCOMP_LOCALHOST_PREFIX)
+                .is_some_and(|path| !path.is_empty());
+        if !valid {
+            return Err(AppError::InvalidInput(format!(
+                "Invalid seccomp profile {}, it must be RuntimeDefault, Unconfined or Localhost/<profile>",
+                seccomp
+            )));
+        }
+    }
+    if let Some(runtime_class_name) = &profile.runtime_class_name {
+        validate_dns_subdomain(runtime_class_name)?;
+    }
+    if profile.r#type() == SecurityProfileType::Restricted {
+        let weakened = profile.run_as_non_root == Some(false)
+            || profile.run_as_user == Some(0)
+            || profile.allow_privilege_escalation == Some(true)
+            || profile.seccomp_profile.as_deref() == Some(SECCOMP_UNCONFINED)
+            || profile
+                .add_capabilities
+                .iter()
+                .any(|x| !RESTRICTED_ADD_CAPABILITIES.contains(&x.as_str()));
+        if weakened {
+            return Err(AppError::InvalidInput(
+                "Restricted security profile can not run as root, escalate privileges, run unconfined or add capabilities other than NET_BIND_SERVICE".to_string(),
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn seccomp_profile(value: &str) -> SeccompProfile {
+    match value.strip_prefix(SECCOMP_LOCALHOST_PREFIX) {
+        Some(path) => SeccompProfile {
+            type_: "Localhost".to_string(),
+            localhost_profile: Some(path.to_string()),
+        },
+        None => SeccompProfile {
+            type_: value.to_string(),
+            localhost_profile: None,
+        },
+    }
+}
+
+fn capabilities(drop: Vec<String>, add: &[String]) -> Option<Capabilities> {
+    if drop.is_empty() && add.is_empty() {
+        return None;
+    }
+    Some(Capabilities {
+        drop: Some(drop).filter(|x| !x.is_empty()),
+        add: Some(add.to_vec()).filter(|x| !x.is_empty()),
+    })
+}
+
+fn capability_sets(container: Option<&SecurityContext>) -> (Vec<String>, Vec<String>) {
+    let capabilities = container.and_then(|x| x.capabilities.as_ref());
+    (
+        capabilities
+            .and_then(|x| x.drop.clone())
+            .unwrap_or_default(),
+        capabilities.and_then(|x| x.add.clone()).unwrap_or_default(),
+    )
+}
+
+/// Settings of `floor` that `security` relaxes, empty when it is at least as strict.
+fn weakened_settings(security: &SessionSecurity, floor: &SessionSecurity) -> Vec<&'static str> {
+    let mut weakened = Vec::new();
+    if floor.hardened && !security.hardened {
+        weakened.push("profile type");
+    }
+    let pod = security.pod.clone().unwrap_or_default();
+    let floor_pod = floor.pod.clone().unwrap_or_default();
+    let container = security.container.clone().unwrap_or_default();
+    let floor_container = floor.container.clone().unwrap_or_default();
+
+    let non_root = |pod: &PodSecurityContext, container: &SecurityContext| {
+        pod.run_as_non_root == Some(true) || container.run_as_non_root == Some(true)
+    };
+    if non_root(&floor_pod, &floor_container)
+        && (!non_root(&pod, &container) || pod.run_as_user == Some(0))
+    {
+        weakened.push("run_as_non_root");
+    }
+    if floor_container.allow_privilege_escalation == Some(false)
+        && container.allow_privilege_escalation != Some(false)
+    {
+        weakened.push("allow_privilege_escalation");
+    }
+    if floor_container.read_only_root_filesystem == Some(true)
+        && container.read_only_root_filesystem != Some(true)
+    {
+        weakened.push("read_only_root_filesystem");
+    }
+    let confined = |pod: &PodSecurityContext| {
+        pod.seccomp_profile
+            .as_ref()
+            .is_some_and(|x| x.type_ != SECCOMP_UNCONFINED)
+    };
+    if confined(&floor_pod) && !confined(&pod) {
+        weakened.push("seccomp_profile");
+    }
+    let (drop, add) = capability_sets(security.container.as_ref());
+    let (floor_drop, floor_add) = capability_sets(floor.container.as_ref());
+    let drops_all = drop.iter().any(|x| x == "ALL");
+    if !drops_all && floor_drop.iter().any(|x| !drop.contains(x)) {
+        weakened.push("drop_capabilities");
+    }
+    if floor.hardened && add.iter().any(|x| !floor_add.contains(x)) {
+        weakened.push("add_capabilities");
+    }
+    if floor.runtime_class_name.is_some() && security.runtime_class_name != floor.runtime_class_name
+    {
+        weakened.push("runtime_class_name");
+    }
+    weakened
+}
+
+/// Security of the session, the template profile may only tighten the profile of the resource limit.
+pub fn resolve_security(
+    template: Option<&SecurityProfile>,
+    resource_limit: Option<&SecurityProfile>,
+) -> Result<SessionSecurity, AppError> {
+    let floor = build_security(resource_limit);
+    let Some(template) = template else {
+        return Ok(floor);
+    };
+    let security = build_security(Some(template));
+    let weakened = weakened_settings(&security, &floor);
+    if !weakened.is_empty() {
+        return Err(AppError::InvalidInput(format!(
+            "Template security profile is less strict than the profile of its resource limit: {}",
+            weakened.join(", ")
+        )));
+    }
+    Ok(security)
+}
+
+/// SessionSecurity of a single profile, see `resolve_security` for the template and resource limit pair.
+pub fn build_security(profile: Option<&SecurityProfile>) -> SessionSecurity {
+    let Some(profile) = profile else {
+        return SessionSecurity::default();
+    };
+    let runtime_class_name = profile.runtime_class_name.clone();
+    let (pod, container) = match profile.r#type() {
+        SecurityProfileType::None => {
+            return SessionSecurity {
+                runtime_class_name,
+                ..Default::default()
+            };
+        }
+        SecurityProfileType::Restricted => (
+            PodSecurityContext {
+                run_as_non_root: Some(true),
+                run_as_user: Some(profile.run_as_user.unwrap_or(DEFAULT_NON_ROOT_ID)),
+                run_as_group: Some(profile.run_as_group.unwrap_or(DEFAULT_NON_ROOT_ID)),
+                fs_group: Some(profile.fs_group.unwrap_or(DEFAULT_NON_ROOT_ID)),
+                seccomp_profile: Some(seccomp_profile(
+                    profile
+                        .seccomp_profile
+                        .as_deref()
+                        .unwrap_or(SECCOMP_RUNTIME_DEFAULT),
+                )),
+                ..Default::default()
+            },
+            SecurityContext {
+                run_as_non_root: Some(true),
+                allow_privilege_escalation: Some(false),
+                read_only_root_filesystem: Some(profile.read_only_root_filesystem.unwrap_or(true)),
+                capabilities: capabilities(vec!["ALL".to_string()], &profile.add_capabilities),
+                ..Default::default()
+            },
+        ),
+        SecurityProfileType::Baseline => (
+            PodSecurityContext {
+                run_as_non_root: profile.run_as_non_root,
+                run_as_user: profile.run_as_user,
+                run_as_group: profile.run_as_group,
+                fs_group: profile.fs_group,
+                seccomp_profile: Some(seccomp_profile(
+                    profile
+                        .seccomp_profile
+                        .as_deref()
+                        .unwrap_or(SECCOMP_RUNTIME_DEFAULT),
+                )),
+                ..Default::default()
+            },
+            SecurityContext {
+                allow_privilege_escalation: Some(
+                    profile.allow_privilege_escalation.unwrap_or(false),
+                ),
+                read_only_root_filesystem: profile.read_only_root_filesystem,
+                capabilities: capabilities(
+                    if profile.drop_capabilities.is_empty() {
+                        vec!["NET_RAW".to_string()]
+                    } else {
+                        profile.drop_capabilities.clone()
+                    },
+                    &profile.add_capabilities,
+                ),
+                ..Default::default()
+            },
+        ),
+        SecurityProfileType::Custom => (
+            PodSecurityContext {
+                run_as_non_root: profile.run_as_non_root,
+                run_as_user: profile.run_as_user,
+                run_as_group: profile.run_as_group,
+                fs_group: profile.fs_group,
+                seccomp_profile: profile.seccomp_profile.as_deref().map(seccomp_profile),
+                ..Default::default()
+            },
+            SecurityContext {
+                allow_privilege_escalation: profile.allow_privilege_escalation,
+                read_only_root_filesystem: profile.read_only_root_filesystem,
+                capabilities: capabilities(
+                    profile.drop_capabilities.clone(),
+                    &profile.add_capabilities,
+                ),
+                ..Default::default()
+            },
+        ),
+    };
+
+    let tmp_volume = (container.read_only_root_filesystem == Some(true)).then(|| {
+        (
+            Volume {
+                name: VOLUME_NAME_TMP.to_string(),
+                empty_dir: Some(EmptyDirVolumeSource::default()),
+                ..Default::default()
+            },
+            VolumeMount {
+                name: VOLUME_NAME_TMP.to_string(),
+                mount_path: TMP_MOUNT_PATH.to_string(),
+                ..Default::default()
+            },
+        )
+    });
+    SessionSecurity {
+        pod: Some(pod),
+        container: Some(container),
+        runtime_class_name,
+        tmp_volume,
+        hardened: true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile(r#type: SecurityProfileType) -> SecurityProfile {
+        SecurityProfile {
+            r#type: r#type.into(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_build_restricted() {
+        let security = build_security(Some(&SecurityProfile {
+            runtime_class_name: Some("gvisor".to_string()),
+            ..profile(SecurityProfileType::Restricted)
+        }));
+        let pod = security.pod.unwrap();
+        assert_eq!(pod.run_as_user, Some(DEFAULT_NON_ROOT_ID));
+        assert_eq!(pod.seccomp_profile.unwrap().type_, "RuntimeDefault");
+        let container = security.container.unwrap();
+        assert_eq!(container.read_only_root_filesystem, Some(true));
+        assert_eq!(container.allow_privilege_escalation, Some(false));
+        assert_eq!(
+            container.capabilities.unwrap().drop,
+            Some(vec!["ALL".to_string()])
+        );
+        assert_eq!(security.tmp_volume.unwrap().1.mount_path, "/tmp");
+        assert_eq!(security.runtime_class_name.as_deref(), Some("gvisor"));
+        assert!(security.hardened);
+
+        let none = build_security(Some(&profile(SecurityProfileType::None)));
+        assert!(none.pod.is_none() && none.container.is_none() && !none.hardened);
+    }
+
+    #[test]
+    fn test_resolve_security() {
+        let restricted = profile(SecurityProfileType::Restricted);
+        let baseline = profile(SecurityProfileType::Baseline);
+        // resource limit 이 없으면 템플릿 프로필을 그대로 사용
+        assert!(resolve_security(Some(&profile(SecurityProfileType::None)), None).is_ok());
+        assert!(resolve_security(None, Some(&restricted)).unwrap().hardened);
+        assert!(resolve_security(Some(&restricted), Some(&baseline)).is_ok());
+        assert!(
+            resolve_security(
+                Some(&SecurityProfile {
+                    run_as_user: Some(1000),
+                    ..restricted.clone()
+                }),
+                Some(&restricted)
+            )
+            .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_resolve_security_weakened() {
+        let restricted = profile(SecurityProfileType::Restricted);
+        let baseline = profile(SecurityProfileType::Baseline);
+        assert!(
+            resolve_security(Some(&profile(SecurityProfileType::None)), Some(&restricted)).is_err()
+        );
+        assert!(resolve_security(Some(&baseline), Some(&restricted)).is_err());
+        assert!(
+            resolve_security(
+                Some(&SecurityProfile {
+                    read_only_root_filesystem: Some(false),
+                    ..restricted.clone()
+                }),
+                Some(&restricted)
+            )
+            .is_err()
+        );
+        assert!(
+            resolve_security(
+                Some(&SecurityProfile {
+                    allow_privilege_escalation: Some(true),
+                    ..profile(SecurityProfileType::Custom)
+                }),
+                Some(&baseline)
+            )
+            .is_err()
+        );
+        assert!(
+            resolve_security(
+                Some(&SecurityProfile {
+                    add_capabilities: vec!["SYS_ADMIN".to_string()],
+                    ..baseline.clone()
+                }),
+                Some(&baseline)
+            )
+            .is_err()
+        );
+        assert!(
+            resolve_security(
+                Some(&restricted),
+                Some(&SecurityProfile {
+                    runtime_class_name: Some("gvisor".to_string()),
+                    ..baseline.clone()
+                })
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_validate_security_profile() {
+        assert!(validate_security_profile(&profile(SecurityProfileType::Restricted)).is_ok());
+        assert!(
+            validate_security_profile(&SecurityProfile {
+                run_as_user: Some(0),
+                ..profile(SecurityProfileType::Restricted)
+            })
+            .is_err()
+        );
+        assert!(
+            validate_security_profile(&SecurityProfile {
+                add_capabilities: vec!["SYS_ADMIN".to_string()],
+                ..profile(SecurityProfileType::Restricted)
+            })
+            .is_err()
+        );
+        assert!(
+            validate_security_profile(&SecurityProfile {
+                add_capabilities: vec!["SYS_ADMIN".to_string()],
+                seccomp_profile: Some("Localhost/profiles/mcp.json".to_string()),
+                ..profile(SecurityProfileType::Custom)
+            })
+            .is_ok()
+        );
+        assert!(
+            validate_security_profile(&SecurityProfile {
+                seccomp_profile: Some("localhost".to_string()),
+                ..profile(SecurityProfileType::Custom)
+            })
+            .is_err()
+        );
+    }
+}
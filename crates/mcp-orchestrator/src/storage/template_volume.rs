@@ -0,0 +1,460 @@
+use std::collections::{HashMap, HashSet};
+
+use k8s_openapi::api::core::v1::{
+    EmptyDirVolumeSource, EphemeralVolumeSource, PersistentVolumeClaimSpec,
+    PersistentVolumeClaimTemplate, PersistentVolumeClaimVolumeSource, SecretVolumeSource, Volume,
+    VolumeMount, VolumeResourceRequirements,
+};
+use k8s_openapi::apimachinery::pkg::api::resource::Quantity;
+use proto::mcp::orchestrator::v1::{self, VolumeLimit};
+
+use crate::error::AppError;
+
+const VOLUME_PREFIX_SECRET: &str = "secret-mount";
+const VOLUME_PREFIX_VOLUME: &str = "volume-mount";
+const VOLUME_NAME_WORKSPACE: &str = "workspace";
+
+fn pattern_to_regex(pattern: &str) -> regex::Regex {
+    let mut expr = String::with_capacity(pattern.len() + 2);
+    expr.push('^');
+    for c in pattern.chars() {
+        match c {
+            '*' => expr.push_str(".*"),
+            '?' => expr.push('.'),
+            c => expr.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    expr.push('$');
+    regex::Regex::new(&expr).unwrap()
+}
+
+pub fn resolve_volume_pattern<'a>(
+    pattern: &str,
+    volumes: &'a HashMap<String, VolumeLimit>,
+) -> Result<(&'a String, &'a VolumeLimit), AppError> {
+    if let Some(found) = volumes.get_key_value(pattern) {
+        return Ok(found);
+    }
+    let regex = pattern_to_regex(pattern);
+    let mut matched = volumes
+        .iter()
+        .filter(|(name, _)| regex.is_match(name))
+        .collect::<Vec<_>>();
+    match matched.len() {
+        0 => Err(AppError::InvalidInput(format!(
+            "Volume pattern '{}' does not match any volume in ResourceLimit",
+            pattern
+        ))),
+        1 => Ok(matched.remove(0)),
+        _ => {
+            let mut names = matched
+                .iter()
+                .map(|(name, _)| name.as_str())
+                .collect::<Vec<_>>();
+            names.sort();
+            Err(AppError::InvalidInput(format!(
+                "Volume pattern '{}' is ambiguous, it matches {:?}",
+                pattern, names
+            )))
+        }
+    }
+}
+
+fn validate_mount_path(mount_path: &str) -> Result<(), AppError> {
+    if !mount_path.starts_with('/') {
+        return Err(AppError::InvalidInput(format!(
+            "Mount path '{}' must be an absolute path",
+            mount_path
+        )));
+    }
+    if mount_path.contains(':') || mount_path.split('/').any(|seg| seg == "..") {
+        return Err(AppError::InvalidInput(format!(
+            "Mount path '{}' must not contain ':' or '..'",
+            mount_path
+        )));
+    }
+    Ok(())
+}
+
+pub fn validate_mounts(
+    volume_mounts: &[v1::VolumeMount],
+    secret_mounts: &[v1::SecretMount],
+    volumes: &HashMap<String, VolumeLimit>,
+) -> Result<(), AppError> {
+    let mut mount_paths = HashSet::new();
+    let mut volume_names = HashSet::new();
+    for mount in volume_mounts {
+        validate_mount_path(&mount.mount_path)?;
+        if !mount_paths.insert(mount.mount_path.as_str()) {
+            return Err(AppError::InvalidInput(format!(
+                "Mount path '{}' is used more than once",
+                mount.mount_path
+            )));
+        }
+        let (name, _) = resolve_volume_pattern(&mount.pattern, volumes)?;
+        if !volume_names.insert(name.as_str()) {
+            return Err(AppError::InvalidInput(format!(
+                "Volume '{}' is mounted more than once",
+                name
+            )));
+        }
+    }
+    for mount in secret_mounts {
+        if mount.name.is_empty() {
+            return Err(AppError::InvalidInput(
+                "Secret mount requires a secret name".to_string(),
+            ));
+        }
+        validate_mount_path(&mount.mount_path)?;
+        if !mount_paths.insert(mount.mount_path.as_str()) {
+            return Err(AppError::InvalidInput(format!(
+                "Mount path '{}' is used more than once",
+                mount.mount_path
+            )));
+        }
+    }
+    Ok(())
+}
+
+pub fn validate_workspace(
+    workspace: &v1::WorkspaceConfig,
+    volume_mounts: &[v1::VolumeMount],
+    secret_mounts: &[v1::SecretMount],
+    volumes: &HashMap<String, VolumeLimit>,
+    anonymous_authorization: Option<&str>,
+) -> Result<(), AppError> {
+    if let Some(anonymous) = anonymous_authorization {
+        return Err(AppError::InvalidInput(format!(
+            "Workspace requires an authenticated subject, Authorization {} is anonymous",
+            anonymous
+        )));
+    }
+    validate_mount_path(&workspace.mount_path)?;
+    let (name, _) = resolve_volume_pattern(&workspace.volume, volumes)?;
+    if volume_mounts
+        .iter()
+        .any(|mount| mount.mount_path == workspace.mount_path)
+        || secret_mounts
+            .iter()
+            .any(|mount| mount.mount_path == workspace.mount_path)
+    {
+        return Err(AppError::InvalidInput(format!(
+            "Mount path '{}' is used more than once",
+            workspace.mount_path
+        )));
+    }
+    for mount in volume_mounts {
+        let (mounted, _) = resolve_volume_pattern(&mount.pattern, volumes)?;
+        if mounted == name {
+            return Err(AppError::InvalidInput(format!(
+                "Volume '{}' is mounted more than once",
+                name
+            )));
+        }
+    }
+    if workspace
+        .idle_ttl
+        .as_ref()
+        .is_some_and(|ttl| ttl.seconds <= 0)
+    {
+        return Err(AppError::InvalidInput(
+            "Workspace idle_ttl must be positive".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Pod volume name of the template volume mount declared with `pattern`.
+pub fn volume_mount_name(volume_mounts: &[v1::VolumeMount], pattern: &str) -> Option<String> {
+    volume_mounts
+        .iter()
+        .position(|mount| mount.pattern == pattern)
+        .map(|index| format!("{}-{}", VOLUME_PREFIX_VOLUME, index))
+}
+
+pub fn workspace_volume(claim_name: &str, mount_path: &str) -> (Volume, VolumeMount) {
+    (
+        Volume {
+            name: VOLUME_NAME_WORKSPACE.to_string(),
+            persistent_volume_claim: Some(PersistentVolumeClaimVolumeSource {
+                claim_name: claim_name.to_string(),
+                read_only: Some(false),
+            }),
+            ..Default::default()
+        },
+        VolumeMount {
+            name: VOLUME_NAME_WORKSPACE.to_string(),
+            mount_path: mount_path.to_string(),
+            ..Default::default()
+        },
+    )
+}
+
+fn volume_limit_source(volume: &mut Volume, limit: &VolumeLimit) {
+    match &limit.storage_class {
+        Some(storage_class) => {
+            volume.ephemeral = Some(EphemeralVolumeSource {
+                volume_claim_template: Some(PersistentVolumeClaimTemplate {
+                    metadata: None,
+                    spec: PersistentVolumeClaimSpec {
+                        access_modes: Some(vec!["ReadWriteOnce".to_string()]),
+                        storage_class_name: Some(storage_class.clone()),
+                        resources: Some(VolumeResourceRequirements {
+                            requests: Some(
+                                [("storage".to_string(), Quantity(limit.size.clone()))]
+                                    .into_iter()
+                                    .collect(),
+                            ),
+                            ..Default::default()
+                        }),
+                        ..Default::default()
+                    },
+                }),
+            });
+        }
+        None => {
+            volume.empty_dir = Some(EmptyDirVolumeSource {
+                size_limit: Some(Quantity(limit.size.clone())),
+                ..Default::default()
+            });
+        }
+    }
+}
+
+/// `secret_name` maps the secret reference of a mount to the Kubernetes Secret of the session.
+pub fn build_volumes(
+    volume_mounts: &[v1::VolumeMount],
+    secret_mounts: &[v1::SecretMount],
+    volumes: &HashMap<String, VolumeLimit>,
+    secret_name: impl Fn(&str) -> String,
+) -> Result<(Vec<Volume>, Vec<VolumeMount>), AppError> {
+    validate_mounts(volume_mounts, secret_mounts, volumes)?;
+
+    let mut pod_volumes = Vec::new();
+    let mut container_mounts = Vec::new();
+    for (index, mount) in volume_mounts.iter().enumerate() {
+        let (_, limit) = resolve_volume_pattern(&mount.pattern, volumes)?;
+        let name = format!("{}-{}", VOLUME_PREFIX_VOLUME, index);
+        let mut volume = Volume {
+            name: name.clone(),
+            ..Default::default()
+        };
+        volume_limit_source(&mut volume, limit);
+        pod_volumes.push(volume);
+        container_mounts.push(VolumeMount {
+            name,
+            mount_path: mount.mount_path.clone(),
+            ..Default::default()
+        });
+    }
+    for (index, mount) in secret_mounts.iter().enumerate() {
+        let name = format!("{}-{}", VOLUME_PREFIX_SECRET, index);
+        pod_volumes.push(Volume {
+            name: name.clone(),
+            secret: Some(SecretVolumeSource {
+                secret_name: Some(secret_name(&mount.name)),
+                ..Default::default()
+            }),
+            ..Default::default()
+        });
+        container_mounts.push(VolumeMount {
+            name,
+            mount_path: mount.mount_path.clone(),
+            read_only: Some(true),
+            ..Default::default()
+        });
+    }
+    Ok((pod_volumes, container_mounts))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::{resource_type::RESOURCE_TYPE_PREFIX_SECRET, util_name::encode_k8sname};
+
+    fn volumes() -> HashMap<String, VolumeLimit> {
+        [
+            (
+                "cache".to_string(),
+                VolumeLimit {
+                    size: "1Gi".to_string(),
+                    storage_class: None,
+                },
+            ),
+            (
+                "data-ssd".to_string(),
+                VolumeLimit {
+                    size: "10Gi".to_string(),
+                    storage_class: Some("fast".to_string()),
+                },
+            ),
+            (
+                "data-hdd".to_string(),
+                VolumeLimit {
+                    size: "100Gi".to_string(),
+                    storage_class: Some("slow".to_string()),
+                },
+            ),
+        ]
+        .into_iter()
+        .collect()
+    }
+
+    fn volume_mount(pattern: &str, mount_path: &str) -> v1::VolumeMount {
+        v1::VolumeMount {
+            pattern: pattern.to_string(),
+            mount_path: mount_path.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_resolve_volume_pattern_exact() {
+        let volumes = volumes();
+        let (name, _) = resolve_volume_pattern("cache", &volumes).unwrap();
+        assert_eq!(name, "cache");
+    }
+
+    #[test]
+    fn test_resolve_volume_pattern_glob() {
+        let volumes = volumes();
+        let (name, _) = resolve_volume_pattern("*-ssd", &volumes).unwrap();
+        assert_eq!(name, "data-ssd");
+    }
+
+    #[test]
+    fn test_resolve_volume_pattern_invalid() {
+        let volumes = volumes();
+        assert!(resolve_volume_pattern("data-*", &volumes).is_err());
+        assert!(resolve_volume_pattern("missing", &volumes).is_err());
+    }
+
+    #[test]
+    fn test_validate_mounts_invalid() {
+        let volumes = volumes();
+        assert!(validate_mounts(&[volume_mount("cache", "relative")], &[], &volumes).is_err());
+        assert!(
+            validate_mounts(
+                &[
+                    volume_mount("cache", "/data"),
+                    volume_mount("*-ssd", "/data")
+                ],
+                &[],
+                &volumes
+            )
+            .is_err()
+        );
+        assert!(
+            validate_mounts(
+                &[volume_mount("cache", "/a"), volume_mount("cach?", "/b")],
+                &[],
+                &volumes
+            )
+            .is_err()
+        );
+    }
+
+    fn workspace(volume: &str, mount_path: &str) -> v1::WorkspaceConfig {
+        v1::WorkspaceConfig {
+            volume: volume.to_string(),
+            mount_path: mount_path.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_validate_workspace() {
+        let volumes = volumes();
+        assert!(
+            validate_workspace(&workspace("*-ssd", "/workspace"), &[], &[], &volumes, None).is_ok()
+        );
+        assert!(
+            validate_workspace(
+                &workspace("*-ssd", "/workspace"),
+                &[volume_mount("cache", "/cache")],
+                &[],
+                &volumes,
+                None
+            )
+            .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_validate_workspace_invalid() {
+        let volumes = volumes();
+        // anonymous 는 subject 가 없어 workspace 를 특정할 수 없음
+        assert!(
+            validate_workspace(
+                &workspace("cache", "/workspace"),
+                &[],
+                &[],
+                &volumes,
+                Some("anonymous")
+            )
+            .is_err()
+        );
+        assert!(
+            validate_workspace(&workspace("cache", "relative"), &[], &[], &volumes, None).is_err()
+        );
+        assert!(
+            validate_workspace(&workspace("data-*", "/workspace"), &[], &[], &volumes, None)
+                .is_err()
+        );
+        assert!(
+            validate_workspace(
+                &workspace("cache", "/workspace"),
+                &[volume_mount("cach?", "/cache")],
+                &[],
+                &volumes,
+                None
+            )
+            .is_err()
+        );
+        assert!(
+            validate_workspace(
+                &workspace("cache", "/data"),
+                &[volume_mount("*-ssd", "/data")],
+                &[],
+                &volumes,
+                None
+            )
+            .is_err()
+        );
+        let mut zero_ttl = workspace("cache", "/workspace");
+        zero_ttl.idle_ttl = Some(prost_wkt_types::Duration {
+            seconds: 0,
+            nanos: 0,
+        });
+        assert!(validate_workspace(&zero_ttl, &[], &[], &volumes, None).is_err());
+    }
+
+    #[test]
+    fn test_build_volumes() {
+        let volumes = volumes();
+        let (pod_volumes, mounts) = build_volumes(
+            &[
+                volume_mount("cache", "/cache"),
+                volume_mount("*-ssd", "/data"),
+            ],
+            &[v1::SecretMount {
+                name: "github".to_string(),
+                mount_path: "/etc/github".to_string(),
+            }],
+            &volumes,
+            |name| encode_k8sname(RESOURCE_TYPE_PREFIX_SECRET, name),
+        )
+        .unwrap();
+        assert_eq!(pod_volumes.len(), 3);
+        assert_eq!(mounts.len(), 3);
+        assert!(pod_volumes[0].empty_dir.is_some());
+        assert!(pod_volumes[1].ephemeral.is_some());
+        assert_eq!(
+            pod_volumes[2]
+                .secret
+                .as_ref()
+                .and_then(|s| s.secret_name.as_deref()),
+            Some("sc-github")
+        );
+        assert_eq!(mounts[2].read_only, Some(true));
+    }
+}
@@ -0,0 +1,188 @@
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+use proto::mcp::orchestrator::v1::{UsageGroupBy, UsageReportRow};
+use serde::{Deserialize, Serialize};
+
+/// Lifecycle of one session as recorded by the metering loop.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UsageRecord {
+    pub namespace: String,
+    pub session_id: String,
+    pub template: String,
+    pub subject: Option<String>,
+    /// Requests of the session pod in cores and bytes.
+    pub cpu: f64,
+    pub memory: f64,
+    pub started_at: DateTime<Utc>,
+    pub last_seen_at: DateTime<Utc>,
+    pub ended_at: Option<DateTime<Utc>>,
+    pub used_cpu_seconds: Option<f64>,
+    pub used_memory_byte_seconds: Option<f64>,
+}
+
+impl UsageRecord {
+    /// End of the session, the last time it was metered while it is still running.
+    pub fn end(&self) -> DateTime<Utc> {
+        self.ended_at.unwrap_or(self.last_seen_at)
+    }
+
+    fn overlap_seconds(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> f64 {
+        let from = self.started_at.max(start);
+        let to = self.end().min(end);
+        ((to - from).num_milliseconds() as f64 / 1000.0).max(0.0)
+    }
+
+    fn lifetime_seconds(&self) -> f64 {
+        (self.end() - self.started_at).num_milliseconds() as f64 / 1000.0
+    }
+
+    fn group_key(&self, group_by: UsageGroupBy) -> String {
+        match group_by {
+            UsageGroupBy::Namespace => self.namespace.clone(),
+            UsageGroupBy::Template => format!("{}/{}", self.namespace, self.template),
+            UsageGroupBy::Subject => self.subject.clone().unwrap_or_default(),
+        }
+    }
+}
+
+fn add_optional(total: &mut Option<f64>, value: Option<f64>) {
+    if let Some(value) = value {
+        *total = Some(total.unwrap_or_default() + value);
+    }
+}
+
+/// Aggregates the records overlapping `[start, end)`.
+/// Sampled usage is prorated by the part of the session inside the range.
+pub fn build_report(
+    records: &[UsageRecord],
+    group_by: UsageGroupBy,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+) -> Vec<UsageReportRow> {
+    let mut rows = BTreeMap::<String, UsageReportRow>::new();
+    for record in records {
+        let seconds = record.overlap_seconds(start, end);
+        if seconds <= 0.0 {
+            continue;
+        }
+        let lifetime = record.lifetime_seconds();
+        let ratio = if lifetime > 0.0 {
+            seconds / lifetime
+        } else {
+            1.0
+        };
+        let key = record.group_key(group_by);
+        let row = rows.entry(key.clone()).or_insert_with(|| UsageReportRow {
+            key,
+            ..Default::default()
+        });
+        row.sessions += 1;
+        row.session_seconds += seconds;
+        row.cpu_seconds += record.cpu * seconds;
+        row.memory_byte_seconds += record.memory * seconds;
+        add_optional(
+            &mut row.used_cpu_seconds,
+            record.used_cpu_seconds.map(|x| x * ratio),
+        );
+        add_optional(
+            &mut row.used_memory_byte_seconds,
+            record.used_memory_byte_seconds.map(|x| x * ratio),
+        );
+    }
+    rows.into_values().collect()
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+pub fn render_csv(group_by: UsageGroupBy, rows: &[UsageReportRow]) -> String {
+    let key = match group_by {
+        UsageGroupBy::Namespace => "namespace",
+        UsageGroupBy::Template => "template",
+        UsageGroupBy::Subject => "subject",
+    };
+    let optional = |x: Option<f64>| x.map(|x| format!("{:.3}", x)).unwrap_or_default();
+    let mut csv = format!(
+        "{},sessions,session_seconds,cpu_seconds,memory_byte_seconds,used_cpu_seconds,used_memory_byte_seconds\n",
+        key
+    );
+    for row in rows {
+        csv.push_str(&format!(
+            "{},{},{:.3},{:.3},{:.3},{},{}\n",
+            csv_field(&row.key),
+            row.sessions,
+            row.session_seconds,
+            row.cpu_seconds,
+            row.memory_byte_seconds,
+            optional(row.used_cpu_seconds),
+            optional(row.used_memory_byte_seconds),
+        ));
+    }
+    csv
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    fn record(
+        template: &str,
+        subject: Option<&str>,
+        start_hour: u32,
+        end_hour: u32,
+    ) -> UsageRecord {
+        let at = |hour| Utc.with_ymd_and_hms(2025, 1, 1, hour, 0, 0).unwrap();
+        UsageRecord {
+            namespace: "team-a".to_string(),
+            session_id: format!("{}-{}", template, start_hour),
+            template: template.to_string(),
+            subject: subject.map(str::to_string),
+            cpu: 0.5,
+            memory: 1024.0,
+            started_at: at(start_hour),
+            last_seen_at: at(end_hour),
+            ended_at: Some(at(end_hour)),
+            used_cpu_seconds: Some(3600.0),
+            used_memory_byte_seconds: None,
+        }
+    }
+
+    #[test]
+    fn test_build_report() {
+        let records = vec![
+            record("github", Some("alice"), 0, 2),
+            record("github", None, 1, 3),
+            record("slack", Some("alice"), 5, 6),
+        ];
+        let start = Utc.with_ymd_and_hms(2025, 1, 1, 1, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2025, 1, 1, 4, 0, 0).unwrap();
+
+        let rows = build_report(&records, UsageGroupBy::Template, start, end);
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].key, "team-a/github");
+        assert_eq!(rows[0].sessions, 2);
+        assert_eq!(rows[0].session_seconds, 3.0 * 3600.0);
+        assert_eq!(rows[0].cpu_seconds, 0.5 * 3.0 * 3600.0);
+        // 범위 안에 든 비율만큼 실사용량을 나눔
+        assert_eq!(rows[0].used_cpu_seconds, Some(1800.0 + 3600.0));
+        assert_eq!(rows[0].used_memory_byte_seconds, None);
+
+        let rows = build_report(&records, UsageGroupBy::Subject, start, end);
+        assert_eq!(
+            rows.iter().map(|x| x.key.as_str()).collect::<Vec<_>>(),
+            vec!["", "alice"]
+        );
+
+        let csv = render_csv(UsageGroupBy::Subject, &rows);
+        assert!(csv.starts_with("subject,sessions,"));
+        assert_eq!(csv_field("a,\"b\""), "\"a,\"\"b\"\"\"");
+    }
+}
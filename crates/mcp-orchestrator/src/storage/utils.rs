@@ -190,6 +190,16 @@ pub fn parse_data_elem<D: DeserializeOwned>(
     serde_json::from_str::<D>(value).map_err(AppError::SerializationError)
 }
 
+pub fn parse_data_elem_or_default<D: DeserializeOwned + Default>(
+    data: &Option<BTreeMap<String, String>>,
+    key: &str,
+) -> Result<D, AppError> {
+    match data.as_ref().and_then(|map| map.get(key)) {
+        Some(value) => serde_json::from_str::<D>(value).map_err(AppError::SerializationError),
+        None => Ok(D::default()),
+    }
+}
+
 pub fn parse_secret_elem<D: DeserializeOwned>(
     data: &Option<BTreeMap<String, ByteString>>,
     key: &str,
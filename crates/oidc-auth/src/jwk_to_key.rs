@@ -11,8 +11,12 @@ pub fn jwk_to_encoding_key(jwk: &LocalJwk) -> Result<EncodingKey, String> {
         LocalAlgorithmParameters::OctetKeyPair(okp) => okp_to_encoding_key(okp),
         LocalAlgorithmParameters::Rsa(rsa) => rsa_to_encoding_key(rsa),
         LocalAlgorithmParameters::EllipticCurve(ec) => ec_to_encoding_key(ec),
-        LocalAlgorithmParameters::OctetKey(oct) => EncodingKey::from_base64_secret(&oct.value)
-            .map_err(|e| format!("Failed to create symmetric key: {}", e)),
+        LocalAlgorithmParameters::OctetKey(oct) => {
+            let secret = general_purpose::URL_SAFE_NO_PAD
+                .decode(&oct.value)
+                .map_err(|e| format!("Failed to create symmetric key: {}", e))?;
+            Ok(EncodingKey::from_secret(secret.as_slice()))
+        }
     }
 }
 
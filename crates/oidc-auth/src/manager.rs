@@ -6,6 +6,7 @@ use std::{
 };
 
 use axum::{extract::Request, response::Response};
+use base64::Engine;
 use http::{HeaderMap, HeaderValue};
 use jsonwebtoken::{DecodingKey, EncodingKey, Validation, jwk::JwkSet};
 use k8s_openapi::api::core::v1::Secret;
@@ -210,13 +211,20 @@ impl AuthManager {
             None => self.remote_decoding_key.values().next(),
         }
     }
+    /// `iss` of the tokens of the provider.
+    pub fn issuer(&self) -> String {
+        let iss = self.metadata.issuer().url().to_string();
+        if self.config.runtime.iss_remove_postfix_slash && iss.ends_with('/') {
+            iss.trim_end_matches('/').to_string()
+        } else {
+            iss
+        }
+    }
+
     pub fn validator(&self, header: jsonwebtoken::Header) -> Validation {
         let mut validation = Validation::new(header.alg);
-        let mut iss = self.metadata.issuer().url().to_string();
+        let iss = self.issuer();
         let aud = self.config.client.id.clone();
-        if self.config.runtime.iss_remove_postfix_slash && iss.ends_with('/') {
-            iss = iss.trim_end_matches('/').to_string();
-        }
         validation.set_audience(&[aud]);
         validation.set_issuer(&[iss]);
         tracing::debug!(
@@ -226,6 +234,42 @@ impl AuthManager {
         );
         validation
     }
+
+    /// Whether the token claims to be issued by the provider, its signature is not checked.
+    pub fn is_provider_token(&self, token: &str) -> bool {
+        unverified_issuer(token).is_some_and(|x| x == self.issuer())
+    }
+
+    /// Validates a token of the provider, non-empty `audiences` and `issuers` replace the defaults.
+    pub fn decode_claims(
+        &self,
+        token: &str,
+        audiences: &[String],
+        issuers: &[String],
+    ) -> Result<serde_json::Map<String, serde_json::Value>, AuthError> {
+        let header =
+            jsonwebtoken::decode_header(token.as_bytes()).map_err(AuthError::InvalidToken)?;
+        let decoding_key = self
+            .get_decoding_key(&header.kid)
+            .ok_or(AuthError::UnknownKeyId)?;
+        let mut validation = self.validator(header);
+        if !audiences.is_empty() {
+            validation.set_audience(audiences);
+        }
+        if !issuers.is_empty() {
+            validation.set_issuer(issuers);
+        }
+        jsonwebtoken::decode::<serde_json::Map<String, serde_json::Value>>(
+            token,
+            decoding_key,
+            &validation,
+        )
+        .map(|x| x.claims)
+        .map_err(|err| match err.kind() {
+            jsonwebtoken::errors::ErrorKind::ExpiredSignature => AuthError::TokenExpired,
+            _ => AuthError::InvalidToken(err),
+        })
+    }
 }
 
 impl<S> Layer<S> for AuthManager {
@@ -261,3 +305,36 @@ where
         })
     }
 }
+
+/// `iss` of a JWT without validating it, only to decide where the token is checked.
+pub fn unverified_issuer(token: &str) -> Option<String> {
+    let mut parts = token.split('.');
+    let (_, payload, _) = (parts.next()?, parts.next()?, parts.next()?);
+    if parts.next().is_some() {
+        return None;
+    }
+    let payload = base64::engine::general_purpose::URL_SAFE_NO_PAD
+        .decode(payload.trim_end_matches('='))
+        .ok()?;
+    let claims: serde_json::Value = serde_json::from_slice(&payload).ok()?;
+    claims.get("iss")?.as_str().map(|x| x.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unverified_issuer() {
+        let payload = base64::engine::general_purpose::URL_SAFE_NO_PAD
+            .encode(r#"{"iss":"https://auth.example.com","sub":"alice"}"#);
+        let token = format!("eyJhbGciOiJSUzI1NiJ9.{}.signature", payload);
+        assert_eq!(
+            unverified_issuer(&token).as_deref(),
+            Some("https://auth.example.com")
+        );
+        assert_eq!(unverified_issuer("mcpk_abcdefghijkl_secret"), None);
+        assert_eq!(unverified_issuer("a.b.c.d"), None);
+        assert_eq!(unverified_issuer("a.!!.c"), None);
+    }
+}
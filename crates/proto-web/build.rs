@@ -21,6 +21,9 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             "../../protobuf/secret.proto",
             "../../protobuf/resource_limit.proto",
             "../../protobuf/authorization.proto",
+            "../../protobuf/workspace.proto",
+            "../../protobuf/bundle.proto",
+            "../../protobuf/usage.proto",
         ],
         &["../../protobuf"],
     )?;
@@ -14,6 +14,9 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         .extern_path(".google.protobuf", "::prost_wkt_types")
         .file_descriptor_set_path(&descriptor_file)
         .type_attribute(".", "#[derive(serde::Serialize, serde::Deserialize)]")
+        // 번들에 저장된 예전 ResourceLimit 에 없는 필드는 기본값으로
+        .type_attribute(".mcp.orchestrator.v1.ResourceLimit", "#[serde(default)]")
+        .type_attribute(".mcp.orchestrator.v1.WorkspaceConfig", "#[serde(default)]")
         .compile_protos(&["../../protobuf/service.proto"], &["../../protobuf"])?;
 
     let descriptor_bytes = std::fs::read(descriptor_file).unwrap();